@@ -51,7 +51,7 @@ export class PrizmClient {
   createWebSocketClientFromConfig(config: PrizmConfig): PrizmWebSocketClient {
     const wsConfig: WebSocketConfig = {
       host: config.server.host,
-      port: parseInt(config.server.port, 10),
+      port: config.server.port,
       apiKey: config.api_key
     }
     return new PrizmWebSocketClient(wsConfig)
@@ -7,7 +7,7 @@ import type { PrizmConfig, NotificationPayload, EventPushPayload } from './types
 import { DATA_SYNC_EVENTS, ONLINE_SCOPE } from './types'
 import { PrizmClient } from './http/client'
 import { PrizmWebSocketClient } from './websocket/connection'
-import { buildServerUrl, formatEventToNotification } from './utils'
+import { buildServerUrlFromConfig, formatEventToNotification } from './utils'
 
 export interface PrizmClientManagerOptions {
   config: PrizmConfig
@@ -74,7 +74,7 @@ export class PrizmClientManager {
    * 获取服务器 URL
    */
   getServerUrl(): string {
-    return buildServerUrl(this.config.server.host, this.config.server.port)
+    return buildServerUrlFromConfig(this.config.server, 'http', this.config.network?.require_tls)
   }
 
   /**
@@ -111,7 +111,7 @@ export class PrizmClientManager {
 
     const wsClient = new PrizmWebSocketClient({
       host: this.config.server.host,
-      port: parseInt(this.config.server.port, 10),
+      port: this.config.server.port,
       apiKey: this.config.api_key,
       subscribeEvents: this.subscribeEventsOption
     })
@@ -76,28 +76,165 @@ export type {
 /** 客户端连接用：服务器 host/port */
 export interface ServerConnectionConfig {
   host: string
-  port: string
+  port: number
+  /** 协议，默认 http；ws/wss 与 http/https 等价 */
+  scheme?: 'http' | 'https' | 'ws' | 'wss'
+  /** 是否校验 TLS 证书，默认 true */
+  verify_tls?: boolean
+  /** 反向代理子路径前缀，如 /prizm */
+  base_path?: string
+  /** 本机服务端的 Unix 域套接字或 Windows 命名管道（仅 Electron 主进程的 HTTP 请求使用） */
+  socket_path?: string
+  /** 上次健康检查成功的路径 */
+  health_path?: string
 }
 
 export interface ClientConfig {
+  /** 注册前为要使用的名称，注册后为服务端分配的 clientId */
   name: string
-  auto_register: string
+  /** 注册时用户填写的名称，重新注册与配对时沿用 */
+  registration_name?: string
+  auto_register: boolean
   requested_scopes: string[]
+  /** 注册时服务端实际授予的 scope */
+  granted_scopes?: string[]
+  /** 预设的 scope 组合，key 为预设名 */
+  scope_presets?: Record<string, string[]>
 }
 
+/** 托盘图标的点击动作 */
+export type TrayClickAction = 'toggle_window' | 'menu' | 'dashboard' | 'none'
+
 export interface TrayConfig {
-  enabled: string
-  minimize_to_tray: string
-  show_notification: string
+  enabled: boolean
+  minimize_to_tray: boolean
+  show_notification: boolean
+  /** 启动时直接进入托盘，不显示主窗口 */
+  start_minimized?: boolean
+  /** 显示/隐藏主窗口的全局快捷键，空字符串表示不注册 */
+  global_hotkey?: string
+  /** 单击，默认 toggle_window（显示或隐藏主窗口） */
+  left_click?: TrayClickAction
+  /** 右键，默认 menu */
+  right_click?: TrayClickAction
+  /** 双击，默认 dashboard（打开管理面板） */
+  double_click?: TrayClickAction
+}
+
+/** 通知分类：connection 连接状态；server_alerts 服务端通知；sync 数据同步；updates 应用更新 */
+export type NotificationCategory = 'connection' | 'server_alerts' | 'sync' | 'updates'
+
+/** 系统通知偏好；tray.show_notification 关闭时全部不弹出 */
+export interface NotificationPreferences {
+  categories: Record<NotificationCategory, boolean>
+  /** 低于该级别的通知不弹出 */
+  min_severity: import('@prizm/shared').NotificationSeverity
+  /** 免打扰时段（本机时间，HH:MM）：期间通知只记入通知中心，不弹出 */
+  quiet_hours: {
+    enabled: boolean
+    start: string
+    end: string
+    /** 周六、周日全天免打扰 */
+    weekends: boolean
+  }
+  /** 各分类的提示音：default 系统默认，none 静音，或音频文件的绝对路径 */
+  sounds: Record<NotificationCategory, string>
+  /** 与服务器断开超过该时长（毫秒）才提示服务器无法连接 */
+  server_down_grace_ms: number
+}
+
+/** 网络设置（毫秒） */
+export interface NetworkConfig {
+  connect_timeout_ms: number
+  read_timeout_ms: number
+  health_timeout_ms: number
+  /** 幂等请求的重试策略 */
+  retry?: {
+    attempts: number
+    base_delay_ms: number
+    max_delay_ms: number
+    jitter: boolean
+  }
+  /** 代理：system 跟随系统，manual 使用 url，none 直连 */
+  proxy?: {
+    mode: 'system' | 'manual' | 'none'
+    url: string
+    bypass: string
+  }
+  /** 额外信任的 CA 证书 PEM 文件路径 */
+  extra_ca_certs?: string[]
+  /** 只允许加密连接（http 自动升级为 https） */
+  require_tls?: boolean
+  /** 每个请求附带的额外静态请求头 */
+  extra_headers?: Record<string, string>
+  /** 主机名 → IP 的静态解析，修改后需重启应用 */
+  host_overrides?: Record<string, string>
+  /** 空闲连接保留时间上限 */
+  pool_idle_timeout_ms?: number
+  /** 每个主机保留的空闲连接数，0 表示不复用连接 */
+  pool_max_idle_per_host?: number
+  /** 是否允许 HTTP/2，修改后需重启应用 */
+  http2?: boolean
+  /** 对需鉴权的请求做 HMAC 签名，不再发送原始 API Key（需服务端支持） */
+  sign_requests?: boolean
+  /** 按服务器熔断：连续失败 failure_threshold 次（0 关闭）后 cooldown_ms 内直接失败 */
+  circuit_breaker?: {
+    failure_threshold: number
+    cooldown_ms: number
+  }
+  /** 健康检查依次尝试的路径，404 时尝试下一个 */
+  health_paths?: string[]
+  /** 实时连接使用 permessage-deflate 压缩（默认开启），低性能设备可关闭 */
+  realtime_compression?: boolean
+}
+
+/** 服务器档案：切换档案时替换 server / api_key / requested_scopes */
+export interface ServerProfile {
+  server: ServerConnectionConfig
+  api_key: string
+  admin_key?: string
+  requested_scopes: string[]
+  granted_scopes?: string[]
+}
+
+/** 同一服务器上的客户端身份：切换身份时替换 client.name / api_key / scope */
+export interface ClientIdentity {
+  /** 所属服务器，host:port */
+  server: string
+  client_name: string
+  registration_name?: string
+  api_key: string
+  requested_scopes: string[]
+  granted_scopes: string[]
 }
 
 export interface PrizmConfig {
+  /** 配置结构版本（由 Electron 主进程维护） */
+  version?: number
   server: ServerConnectionConfig
   client: ClientConfig
   api_key: string
+  /** api_key 过期时间（毫秒时间戳），永久 Key 为空；过期前由主进程用 refresh_token 续期 */
+  api_key_expires_at?: number
+  refresh_token?: string
+  /** 当前服务器的管理员 Key，管理类接口（审批客户端、吊销 Key）优先使用 */
+  admin_key?: string
   tray: TrayConfig
   /** 需要弹出通知的事件类型 */
   notify_events?: import('@prizm/shared').EventType[]
+  /** 按分类与级别过滤系统通知 */
+  notifications?: NotificationPreferences
+  /** 已保存的服务器档案，key 为档案名 */
+  profiles?: Record<string, ServerProfile>
+  /** 当前使用的档案名 */
+  active_profile?: string
+  /** 已注册的客户端身份，key 为身份名 */
+  identities?: Record<string, ClientIdentity>
+  /** 当前使用的身份名 */
+  active_identity?: string
+  /** API Key 存储位置：keyring 为系统凭据存储，file 为加密写入配置文件（便携安装） */
+  credential_store?: 'keyring' | 'file'
+  network?: NetworkConfig
 }
 
 // ============ WebSocket 配置与消息（仅 client-core） ============
@@ -140,6 +277,9 @@ export interface NotifyWindowPayload {
   title?: string
   body?: string
   source?: string
+  /** 通知分类，缺省时按 eventType 推断 */
+  category?: NotificationCategory
+  severity?: import('@prizm/shared').NotificationSeverity
 }
 
 export type WebSocketEventType = 'connected' | 'disconnected' | 'error' | 'notification' | 'event'
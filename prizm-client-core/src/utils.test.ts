@@ -0,0 +1,21 @@
+import { describe, it, expect } from 'vitest'
+import { buildServerUrl, buildServerUrlFromConfig, formatUrlHost } from './utils'
+
+describe('formatUrlHost', () => {
+  it('brackets IPv6 literals and encodes zone IDs', () => {
+    expect(formatUrlHost('127.0.0.1')).toBe('127.0.0.1')
+    expect(formatUrlHost('::1')).toBe('[::1]')
+    expect(formatUrlHost('[::1]')).toBe('[::1]')
+    expect(formatUrlHost('fe80::1%eth0')).toBe('[fe80::1%25eth0]')
+    expect(formatUrlHost('[fe80::1%25eth0]')).toBe('[fe80::1%25eth0]')
+  })
+})
+
+describe('buildServerUrl', () => {
+  it('builds urls for IPv6 hosts', () => {
+    expect(buildServerUrl('::1', 4127)).toBe('http://[::1]:4127')
+    expect(
+      buildServerUrlFromConfig({ host: 'fe80::1%eth0', port: 4127, scheme: 'https' }, 'ws')
+    ).toBe('wss://[fe80::1%25eth0]:4127')
+  })
+})
@@ -44,14 +44,41 @@ export const EVENT_LABELS_UI: Record<string, string> = {
   'document:deleted': '文档删除'
 }
 
+/**
+ * URL 中的主机部分：IPv6 字面量加方括号，zone ID 中的 % 编码为 %25（RFC 6874）
+ */
+export function formatUrlHost(host: string): string {
+  if (!host.includes(':')) return host
+  const bare = host.replace(/^\[|\]$/g, '')
+  const zoneIdx = bare.indexOf('%')
+  if (zoneIdx === -1) return `[${bare}]`
+  return `[${bare.slice(0, zoneIdx)}%25${bare.slice(zoneIdx + 1).replace(/^25/, '')}]`
+}
+
 /**
  * 构建服务器 URL
  */
-export function buildServerUrl(host: string, port: string): string {
+export function buildServerUrl(host: string, port: string | number): string {
   if (host.startsWith('http://') || host.startsWith('https://')) {
     return host.includes(':') ? host : `${host}:${port}`
   }
-  return `http://${host}:${port}`
+  return `http://${formatUrlHost(host)}:${port}`
+}
+
+/**
+ * 由服务器连接配置构建 URL，尊重 scheme 与 base_path；kind 为 ws 时映射为 ws/wss。
+ * requireTls（network.require_tls）为 true 时始终使用 https/wss
+ */
+export function buildServerUrlFromConfig(
+  server: import('./types').ServerConnectionConfig,
+  kind: 'http' | 'ws' = 'http',
+  requireTls = false
+): string {
+  const secure = requireTls || server.scheme === 'https' || server.scheme === 'wss'
+  const scheme = kind === 'ws' ? (secure ? 'wss' : 'ws') : secure ? 'https' : 'http'
+  const host = formatUrlHost(server.host)
+  const basePath = (server.base_path ?? '').replace(/^\/+|\/+$/g, '')
+  return `${scheme}://${host}:${server.port}${basePath ? `/${basePath}` : ''}`
 }
 
 /** 截断长文本用于通知展示 */
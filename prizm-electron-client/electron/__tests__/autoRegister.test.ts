@@ -0,0 +1,115 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+interface TestConfig {
+  server: { host: string; port: number }
+  client: {
+    name: string
+    registration_name?: string
+    auto_register: boolean
+    requested_scopes: string[]
+  }
+  api_key: string
+}
+
+const { state, startMock, resumeMock, sendMock } = vi.hoisted(() => ({
+  state: { config: {} as TestConfig },
+  startMock: vi.fn(),
+  resumeMock: vi.fn(),
+  sendMock: vi.fn()
+}))
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  loadConfigFromDisk: async () => JSON.parse(JSON.stringify(state.config)),
+  updateConfig: vi.fn(),
+  sharedState: { mainWindow: { isDestroyed: () => false, webContents: { send: sendMock } } }
+}))
+
+vi.mock('../registration', () => ({
+  startRegistration: startMock,
+  resumeRegistration: resumeMock
+}))
+
+import { PrizmError } from '../errors'
+import { autoRegisterOnStartup } from '../autoRegister'
+
+describe('autoRegisterOnStartup', () => {
+  beforeEach(() => {
+    startMock.mockReset()
+    resumeMock.mockReset().mockResolvedValue(null)
+    sendMock.mockReset()
+    state.config = {
+      server: { host: '127.0.0.1', port: 4127 },
+      client: { name: 'kiosk', auto_register: true, requested_scopes: ['default'] },
+      api_key: ''
+    }
+  })
+
+  it('registers in the background and reports success', async () => {
+    startMock.mockResolvedValue({ clientId: 'kiosk', apiKey: 'k', grantedScopes: ['default'] })
+    await expect(autoRegisterOnStartup()).resolves.toBe(true)
+    expect(startMock).toHaveBeenCalledWith({
+      serverUrl: 'http://127.0.0.1:4127',
+      name: 'kiosk',
+      requestedScopes: ['default'],
+      unattended: true
+    })
+    expect(sendMock).toHaveBeenCalledWith('auth://auto-register', {
+      status: 'registered',
+      clientId: 'kiosk',
+      serverUrl: 'http://127.0.0.1:4127'
+    })
+  })
+
+  it('registers under the name the user chose, not a previously issued client id', async () => {
+    state.config.client.name = '3f9c0e'
+    state.config.client.registration_name = 'kiosk'
+    startMock.mockResolvedValue({ clientId: '3f9c0e', apiKey: 'k', grantedScopes: ['default'] })
+    await autoRegisterOnStartup()
+    expect(startMock).toHaveBeenCalledWith(expect.objectContaining({ name: 'kiosk' }))
+  })
+
+  it('continues an unfinished registration instead of starting over', async () => {
+    resumeMock.mockResolvedValue({ clientId: 'kiosk-2', apiKey: 'k', grantedScopes: [] })
+    await autoRegisterOnStartup()
+    expect(startMock).not.toHaveBeenCalled()
+    expect(sendMock).toHaveBeenCalledWith(
+      'auth://auto-register',
+      expect.objectContaining({ status: 'registered', clientId: 'kiosk-2' })
+    )
+  })
+
+  it('reports failure without throwing', async () => {
+    startMock.mockRejectedValue(PrizmError.auth('denied'))
+    await expect(autoRegisterOnStartup()).resolves.toBe(true)
+    expect(sendMock).toHaveBeenCalledWith('auth://auto-register', {
+      status: 'failed',
+      serverUrl: 'http://127.0.0.1:4127',
+      message: 'denied'
+    })
+  })
+
+  it('does not register on startup after the client was deregistered', async () => {
+    // 注销会清空 client.name；旧版注销不关闭 auto_register，也不能再用空名称注册
+    state.config.client.name = ''
+    state.config.client.registration_name = 'kiosk'
+    await expect(autoRegisterOnStartup()).resolves.toBe(false)
+    expect(resumeMock).not.toHaveBeenCalled()
+    expect(startMock).not.toHaveBeenCalled()
+    expect(sendMock).not.toHaveBeenCalled()
+  })
+
+  it('does nothing when a key is stored or auto-register is off', async () => {
+    state.config.api_key = 'existing'
+    await expect(autoRegisterOnStartup()).resolves.toBe(false)
+    state.config.api_key = ''
+    state.config.client.auto_register = false
+    await expect(autoRegisterOnStartup()).resolves.toBe(false)
+    expect(resumeMock).not.toHaveBeenCalled()
+    expect(startMock).not.toHaveBeenCalled()
+    expect(sendMock).not.toHaveBeenCalled()
+  })
+})
@@ -0,0 +1,19 @@
+import { describe, it, expect } from 'vitest'
+import {
+  BINARY_FRAME_HEADER_SIZE,
+  decodeBinaryFrame,
+  encodeBinaryFrame,
+  splitBinaryFrames
+} from '../binaryFrames'
+
+describe('binaryFrames', () => {
+  it('writes a big-endian header the server can read', () => {
+    const bytes = encodeBinaryFrame({ channel: 258, index: 1, last: true, data: Buffer.from('x') })
+    expect([...bytes.subarray(0, BINARY_FRAME_HEADER_SIZE)]).toEqual([1, 1, 0, 0, 1, 2, 0, 0, 0, 1])
+    expect(decodeBinaryFrame(bytes)?.data.toString()).toBe('x')
+  })
+
+  it('produces a single last frame for empty payloads', () => {
+    expect(splitBinaryFrames(1, Buffer.alloc(0), 16)).toMatchObject([{ index: 0, last: true }])
+  })
+})
@@ -0,0 +1,64 @@
+import { describe, it, expect } from 'vitest'
+import { CircuitBreaker } from '../circuitBreaker'
+import type { CircuitStatus } from '../circuitBreaker'
+
+const ORIGIN = 'http://127.0.0.1:4127'
+
+function createBreaker() {
+  const clock = { now: 1_000 }
+  const events: CircuitStatus[] = []
+  const breaker = new CircuitBreaker(
+    { failure_threshold: 2, cooldown_ms: 5_000 },
+    (status) => events.push(status),
+    () => clock.now
+  )
+  return { breaker, clock, events }
+}
+
+describe('CircuitBreaker', () => {
+  it('opens after consecutive failures and fails fast during cooldown', () => {
+    const { breaker, events } = createBreaker()
+    breaker.recordFailure(ORIGIN)
+    expect(() => breaker.check(ORIGIN)).not.toThrow()
+    breaker.recordFailure(ORIGIN)
+    expect(events).toEqual([{ origin: ORIGIN, state: 'open', failures: 2, retry_at: 6_000 }])
+    expect(() => breaker.check(ORIGIN)).toThrow(/Circuit open/)
+    expect(breaker.list()).toHaveLength(1)
+  })
+
+  it('lets a single probe through after cooldown and closes on success', () => {
+    const { breaker, clock, events } = createBreaker()
+    breaker.recordFailure(ORIGIN)
+    breaker.recordFailure(ORIGIN)
+    clock.now += 5_000
+    breaker.check(ORIGIN)
+    expect(breaker.status(ORIGIN).state).toBe('half_open')
+    expect(() => breaker.check(ORIGIN)).toThrow(/Circuit open/)
+    breaker.recordSuccess(ORIGIN)
+    expect(events.map((e) => e.state)).toEqual(['open', 'half_open', 'closed'])
+    expect(() => breaker.check(ORIGIN)).not.toThrow()
+  })
+
+  it('reopens when the probe fails', () => {
+    const { breaker, clock } = createBreaker()
+    breaker.recordFailure(ORIGIN)
+    breaker.recordFailure(ORIGIN)
+    clock.now += 5_000
+    breaker.check(ORIGIN)
+    breaker.recordFailure(ORIGIN)
+    expect(breaker.status(ORIGIN)).toMatchObject({ state: 'open', retry_at: 11_000 })
+  })
+
+  it('resets the failure count on success and can be disabled', () => {
+    const { breaker } = createBreaker()
+    breaker.recordFailure(ORIGIN)
+    breaker.recordSuccess(ORIGIN)
+    breaker.recordFailure(ORIGIN)
+    expect(breaker.status(ORIGIN).state).toBe('closed')
+
+    breaker.configure({ failure_threshold: 0, cooldown_ms: 5_000 })
+    breaker.recordFailure(ORIGIN)
+    breaker.recordFailure(ORIGIN)
+    expect(() => breaker.check(ORIGIN)).not.toThrow()
+  })
+})
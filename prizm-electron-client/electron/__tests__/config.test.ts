@@ -0,0 +1,291 @@
+import { describe, it, expect, vi } from 'vitest'
+import * as path from 'path'
+
+vi.mock('electron', () => {
+  const electronMock = {
+    app: {
+      getPath: vi.fn().mockReturnValue('/mock/app/data')
+    }
+  }
+  return {
+    ...electronMock,
+    default: electronMock
+  }
+})
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+import {
+  CURRENT_CONFIG_VERSION,
+  createDefaultConfig,
+  deepMergeConfig,
+  migrateConfig,
+  normalizeConfig,
+  normalizeNetworkConfig,
+  normalizeNotificationPreferences,
+  normalizeThemeMode,
+  normalizeTrayClickBindings,
+  shouldStartMinimized
+} from '../config'
+
+describe('normalizeConfig', () => {
+  it('converts legacy string booleans and port', () => {
+    const { config, migrated } = normalizeConfig({
+      server: { host: '10.0.0.2', port: '5000', is_dev: 'false' },
+      client: { name: 'legacy', auto_register: 'false', requested_scopes: ['default'] },
+      api_key: 'k',
+      tray: { enabled: 'true', minimize_to_tray: 'false', show_notification: 'true' }
+    })
+
+    expect(migrated).toBe(true)
+    expect(config.version).toBe(CURRENT_CONFIG_VERSION)
+    expect(config.server).toEqual({
+      host: '10.0.0.2',
+      port: 5000,
+      is_dev: false,
+      scheme: 'http',
+      verify_tls: true,
+      base_path: ''
+    })
+    expect(config.client.auto_register).toBe(false)
+    expect(config.tray).toEqual({
+      enabled: true,
+      minimize_to_tray: false,
+      show_notification: true,
+      start_minimized: false,
+      global_hotkey: 'CommandOrControl+Shift+P',
+      left_click: 'toggle_window',
+      right_click: 'menu',
+      double_click: 'dashboard'
+    })
+  })
+
+  it('leaves typed configs untouched', () => {
+    const { config, migrated } = normalizeConfig(createDefaultConfig())
+    expect(migrated).toBe(false)
+    expect(config).toEqual(createDefaultConfig())
+  })
+
+  it('falls back to defaults for invalid values', () => {
+    const { config } = normalizeConfig({
+      server: { host: 'h', port: 'abc' },
+      tray: { enabled: 'maybe' }
+    })
+    expect(config.server.port).toBe(4127)
+    expect(config.tray.enabled).toBe(true)
+  })
+
+  it('keeps only valid loopback tunnels, disabled by default', () => {
+    const { config } = normalizeConfig({
+      tunnels: [
+        { name: 'grafana', local_port: 3000 },
+        { name: 'grafana', local_port: 3001 },
+        { name: 'Bad Name', local_port: 3002 },
+        { name: 'lan', local_port: 3003, local_host: '192.168.1.2' },
+        { name: 'db', local_port: '5432', local_host: '::1', scope: 'online', enabled: 'true' }
+      ]
+    })
+    expect(config.tunnels).toEqual([
+      {
+        name: 'grafana',
+        local_port: 3000,
+        local_host: '127.0.0.1',
+        scope: 'default',
+        enabled: false
+      },
+      { name: 'db', local_port: 5432, local_host: '::1', scope: 'online', enabled: true }
+    ])
+  }
+
+  it('drops malformed window layouts and enforces the minimum size', () => {
+    const { config } = normalizeConfig({
+      window: {
+        layouts: {
+          a: { x: '10', y: -20.4, width: 200, height: 900, maximized: 'true' },
+          b: { x: 'left', y: 0, width: 800, height: 600 },
+          c: null
+        }
+      }
+    })
+    expect(config.window).toEqual({
+      layouts: { a: { x: 10, y: -20, width: 400, height: 900, maximized: true } }
+    })
+  })
+})
+
+describe('shouldStartMinimized', () => {
+  it('starts in the tray from the setting or the --minimized flag', () => {
+    const config = createDefaultConfig()
+    expect(shouldStartMinimized(config, ['prizm'])).toBe(false)
+    expect(shouldStartMinimized(config, ['prizm', '--minimized'])).toBe(true)
+    config.tray.start_minimized = true
+    expect(shouldStartMinimized(config, ['prizm'])).toBe(true)
+    config.tray.enabled = false
+    expect(shouldStartMinimized(config, ['prizm', '--minimized'])).toBe(false)
+  })
+})
+
+describe('normalizeThemeMode', () => {
+  it('treats system and unknown values as auto', () => {
+    expect(normalizeThemeMode('dark')).toBe('dark')
+    expect(normalizeThemeMode('system')).toBe('auto')
+    expect(normalizeThemeMode(undefined)).toBe('auto')
+  })
+})
+
+describe('normalizeTrayClickBindings', () => {
+  it('falls back to defaults for unknown actions', () => {
+    expect(normalizeTrayClickBindings({ left_click: 'menu', double_click: 'explode' })).toEqual({
+      left_click: 'menu',
+      right_click: 'menu',
+      double_click: 'dashboard'
+    })
+  })
+
+  it('keeps the tray menu reachable', () => {
+    const bindings = normalizeTrayClickBindings({
+      left_click: 'none',
+      right_click: 'dashboard',
+      double_click: 'none'
+    })
+    expect(bindings).toEqual({ left_click: 'none', right_click: 'menu', double_click: 'none' })
+  })
+})
+
+describe('normalizeNotificationPreferences', () => {
+  it('defaults missing categories on and invalid severity to info', () => {
+    const prefs = normalizeNotificationPreferences({
+      categories: { updates: 'false', unknown: false },
+      min_severity: 'x'
+    })
+    expect(prefs).toEqual({
+      categories: { connection: true, server_alerts: true, sync: true, updates: false },
+      min_severity: 'info',
+      quiet_hours: { enabled: false, start: '22:00', end: '08:00', weekends: false },
+      sounds: {
+        connection: 'default',
+        server_alerts: 'default',
+        sync: 'default',
+        updates: 'default'
+      },
+      server_down_grace_ms: 30_000
+    })
+  })
+
+  it('normalizes quiet hours clock times', () => {
+    const { quiet_hours } = normalizeNotificationPreferences({
+      quiet_hours: { enabled: 'true', start: '7:05', end: '25:00', weekends: true }
+    })
+    expect(quiet_hours).toEqual({ enabled: true, start: '07:05', end: '08:00', weekends: true })
+  })
+
+  it('keeps silence and absolute sound files, falling back to the default sound', () => {
+    const file = path.resolve('alarm.wav')
+    const { sounds } = normalizeNotificationPreferences({
+      sounds: { connection: file, server_alerts: 'none', sync: 'relative.wav', updates: 3 }
+    })
+    expect(sounds).toEqual({
+      connection: file,
+      server_alerts: 'none',
+      sync: 'default',
+      updates: 'default'
+    })
+  })
+})
+
+describe('normalizeNetworkConfig', () => {
+  it('drops reserved and malformed extra headers', () => {
+    const network = normalizeNetworkConfig({
+      extra_headers: {
+        'X-Team': 'ops',
+        Authorization: 'Bearer x',
+        'Bad Header': 'v',
+        'X-Injected': 'a\r\nb'
+      }
+    })
+    expect(network.extra_headers).toEqual({ 'X-Team': 'ops' })
+  })
+
+  it('keeps only hostname to IP host overrides', () => {
+    const network = normalizeNetworkConfig({
+      host_overrides: {
+        'Prizm.Corp': '10.0.0.2',
+        'v6.corp': '[fd00::2]',
+        'bad host': '10.0.0.3',
+        'name.corp': 'other.corp'
+      }
+    })
+    expect(network.host_overrides).toEqual({ 'prizm.corp': '10.0.0.2', 'v6.corp': 'fd00::2' })
+  })
+
+  it('normalizes health check paths', () => {
+    const network = normalizeNetworkConfig({ health_paths: ['healthz', '/healthz', 1] })
+    expect(network.health_paths).toEqual(['/healthz'])
+    expect(normalizeNetworkConfig({ health_paths: [] }).health_paths).toEqual([
+      '/health',
+      '/healthz'
+    ])
+  })
+
+  it('accepts zero for pool settings and legacy http2 strings', () => {
+    const network = normalizeNetworkConfig({
+      pool_idle_timeout_ms: 0,
+      pool_max_idle_per_host: -1,
+      http2: 'false'
+    })
+    expect(network.pool_idle_timeout_ms).toBe(0)
+    expect(network.pool_max_idle_per_host).toBe(6)
+    expect(network.http2).toBe(false)
+  })
+
+  it('defaults heartbeat settings and allows turning heartbeats off', () => {
+    expect(normalizeNetworkConfig({}).heartbeat).toEqual({ interval_ms: 25_000, max_missed: 2 })
+    expect(
+      normalizeNetworkConfig({ heartbeat: { interval_ms: 0, max_missed: 0 } }).heartbeat
+    ).toEqual({ interval_ms: 0, max_missed: 2 })
+  })
+
+  it('enables realtime compression unless turned off', () => {
+    expect(normalizeNetworkConfig({}).realtime_compression).toBe(true)
+    expect(normalizeNetworkConfig({ realtime_compression: false }).realtime_compression).toBe(false)
+  })
+})
+
+describe('migrateConfig', () => {
+  it('upgrades unversioned configs step by step', () => {
+    const { raw, fromVersion } = migrateConfig({
+      server: { host: 'h', port: '4127' },
+      client: { name: 'c', auto_register: 'true', requested_scopes: [] },
+      api_key: '',
+      tray: { enabled: 'false', minimize_to_tray: 'true', show_notification: 'false' }
+    })
+    expect(fromVersion).toBe(1)
+    expect(raw.version).toBe(CURRENT_CONFIG_VERSION)
+    expect(raw.server.port).toBe(4127)
+    expect(raw.client.auto_register).toBe(true)
+    expect(raw.tray).toEqual({ enabled: false, minimize_to_tray: true, show_notification: false })
+  })
+
+  it('does not touch configs at the current version', () => {
+    const current = createDefaultConfig()
+    const { raw, fromVersion } = migrateConfig(current)
+    expect(fromVersion).toBe(CURRENT_CONFIG_VERSION)
+    expect(raw).toEqual(current)
+  })
+})
+
+describe('deepMergeConfig', () => {
+  it('merges nested objects, replaces arrays and deletes null fields', () => {
+    const merged = deepMergeConfig(createDefaultConfig(), {
+      server: { port: 5000 },
+      client: { requested_scopes: ['online'] },
+      notify_events: null
+    })
+    expect(merged.server).toMatchObject({ host: '127.0.0.1', port: 5000, is_dev: true })
+    expect(merged.client.requested_scopes).toEqual(['online'])
+    expect(merged.client.name).toBe('Prizm Electron Client')
+    expect(merged).not.toHaveProperty('notify_events')
+  })
+})
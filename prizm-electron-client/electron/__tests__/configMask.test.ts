@@ -0,0 +1,37 @@
+import { describe, it, expect } from 'vitest'
+import { MASKED_SECRET, maskSecrets, restoreMaskedSecrets } from '../configMask'
+import type { PrizmConfig } from '../config'
+
+function config(): PrizmConfig {
+  return {
+    api_key: 'key-1',
+    refresh_token: 'r-1',
+    client: { name: 'c-1' },
+    profiles: {
+      home: { server: { host: 'a', port: 1 }, api_key: 'key-2', admin_key: 'admin-2' }
+    },
+    identities: {
+      work: { server: 'home', client_name: 'c-2', api_key: '', granted_scopes: [] }
+    }
+  } as unknown as PrizmConfig
+}
+
+describe('maskSecrets', () => {
+  it('replaces every stored secret and keeps empty ones empty', () => {
+    const masked = maskSecrets(config())
+    expect(JSON.stringify(masked)).not.toMatch(/key-1|key-2|admin-2|r-1/)
+    expect(masked.api_key).toBe(MASKED_SECRET)
+    expect(masked.admin_key).toBeUndefined()
+    expect(masked.identities?.work.api_key).toBe('')
+  })
+
+  it('round-trips through restoreMaskedSecrets', () => {
+    const current = config()
+    expect(restoreMaskedSecrets(maskSecrets(current), current)).toEqual(current)
+  })
+
+  it('applies secrets the renderer actually changed', () => {
+    const next = { ...maskSecrets(config()), api_key: '' }
+    expect(restoreMaskedSecrets(next, config()).api_key).toBe('')
+  })
+})
@@ -0,0 +1,50 @@
+import { describe, it, expect, vi } from 'vitest'
+
+const { appendSwitch } = vi.hoisted(() => ({ appendSwitch: vi.fn() }))
+
+vi.mock('electron', () => {
+  const electronMock = {
+    app: { getPath: vi.fn().mockReturnValue('/mock/app/data'), commandLine: { appendSwitch } }
+  }
+  return {
+    ...electronMock,
+    default: electronMock
+  }
+})
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+import { DEFAULT_NETWORK_CONFIG } from '../config'
+import {
+  applyHttp2Setting,
+  createSocketAgent,
+  effectiveIdleTimeout,
+  http2NeedsRestart
+} from '../connectionPool'
+
+describe('connectionPool', () => {
+  it('disables keep-alive when no idle connections are allowed', () => {
+    const network = { ...DEFAULT_NETWORK_CONFIG, pool_max_idle_per_host: 0 }
+    expect(effectiveIdleTimeout(network)).toBe(0)
+    expect(effectiveIdleTimeout({ ...network, pool_max_idle_per_host: 1 })).toBe(90_000)
+  })
+
+  it('applies idle limits to the local socket agent', () => {
+    const agent = createSocketAgent({
+      ...DEFAULT_NETWORK_CONFIG,
+      pool_idle_timeout_ms: 15_000,
+      pool_max_idle_per_host: 2
+    })
+    expect(agent.maxFreeSockets).toBe(2)
+    agent.destroy()
+  })
+
+  it('disables HTTP/2 through a startup switch', () => {
+    applyHttp2Setting(false)
+    expect(appendSwitch).toHaveBeenCalledWith('disable-http2')
+    expect(http2NeedsRestart(false)).toBe(false)
+    expect(http2NeedsRestart(true)).toBe(true)
+  })
+})
@@ -0,0 +1,60 @@
+import { describe, it, expect, vi } from 'vitest'
+
+const { sendMock } = vi.hoisted(() => ({ sendMock: vi.fn() }))
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  sharedState: { mainWindow: { isDestroyed: () => false, webContents: { send: sendMock } } }
+}))
+
+import { ConnectionStateTracker, deriveConnectionState } from '../connectionState'
+
+const circuit = (origin: string, state: 'closed' | 'open' | 'half_open') => ({
+  origin,
+  state,
+  failures: 0,
+  retry_at: null
+})
+
+describe('deriveConnectionState', () => {
+  it('combines the realtime state with tripped circuits', () => {
+    expect(deriveConnectionState('connected', false)).toBe('connected')
+    expect(deriveConnectionState('connected', true)).toBe('degraded')
+    expect(deriveConnectionState('reconnecting', false)).toBe('degraded')
+    expect(deriveConnectionState('connecting', true)).toBe('connecting')
+    expect(deriveConnectionState('disconnected', true)).toBe('disconnected')
+  })
+})
+
+describe('ConnectionStateTracker', () => {
+  it('notifies only when the overall state changes', () => {
+    let now = 1_000
+    const tracker = new ConnectionStateTracker(() => now)
+    const listener = vi.fn()
+    tracker.onChange(listener)
+
+    tracker.updateRealtime('connecting')
+    now = 2_000
+    tracker.updateRealtime('connected')
+    expect(listener).toHaveBeenCalledTimes(2)
+    expect(tracker.get()).toEqual({
+      state: 'connected',
+      realtime: 'connected',
+      trippedOrigins: [],
+      changedAt: 2_000
+    })
+
+    tracker.updateCircuit(circuit('http://a', 'open'))
+    tracker.updateCircuit(circuit('http://b', 'half_open'))
+    tracker.updateCircuit(circuit('http://a', 'closed'))
+    expect(listener).toHaveBeenCalledTimes(3)
+    expect(tracker.get()).toMatchObject({ state: 'degraded', trippedOrigins: ['http://b'] })
+
+    tracker.updateCircuit(circuit('http://b', 'closed'))
+    expect(listener).toHaveBeenLastCalledWith(expect.objectContaining({ state: 'connected' }))
+    expect(sendMock).toHaveBeenLastCalledWith('connection://state', tracker.get())
+  })
+})
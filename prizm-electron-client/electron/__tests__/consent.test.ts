@@ -0,0 +1,65 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+const { sendMock, state } = vi.hoisted(() => ({
+  sendMock: vi.fn(),
+  state: { windowOpen: true }
+}))
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  sharedState: {
+    get mainWindow() {
+      return state.windowOpen
+        ? { isDestroyed: () => false, webContents: { send: sendMock } }
+        : null
+    }
+  }
+}))
+
+import { PrizmError } from '../errors'
+import { listPendingConsents, newSensitiveScopes, requestConsent, respondConsent } from '../consent'
+
+const request = { serverUrl: 'http://127.0.0.1:4127', reason: 'auto_register' as const }
+
+describe('consent', () => {
+  beforeEach(() => {
+    sendMock.mockReset()
+    state.windowOpen = true
+  })
+
+  it('only asks about sensitive scopes that are not already granted', async () => {
+    expect(newSensitiveScopes(['default', 'clipboard', 'filesystem'], ['clipboard'])).toEqual([
+      'filesystem'
+    ])
+    await expect(requestConsent({ ...request, granted: ['default', 'online'] })).resolves.toBe(
+      true
+    )
+    expect(sendMock).not.toHaveBeenCalled()
+  })
+
+  it('waits for the answer from the renderer', async () => {
+    const answer = requestConsent({ ...request, granted: ['default', 'clipboard'] })
+    const [channel, event] = sendMock.mock.calls[0]
+    expect(channel).toBe('auth://consent-request')
+    expect(event).toMatchObject({ reason: 'auto_register', scopes: ['clipboard'] })
+    expect(listPendingConsents()).toEqual([event])
+
+    respondConsent(event.id, true)
+    await expect(answer).resolves.toBe(true)
+    expect(listPendingConsents()).toEqual([])
+    expect(() => respondConsent(event.id, false)).toThrow(PrizmError)
+  })
+
+  it('denies when cancelled or when there is no window to ask', async () => {
+    const controller = new AbortController()
+    const answer = requestConsent({ ...request, granted: ['*'] }, controller.signal)
+    controller.abort()
+    await expect(answer).resolves.toBe(false)
+
+    state.windowOpen = false
+    await expect(requestConsent({ ...request, granted: ['filesystem'] })).resolves.toBe(false)
+  })
+})
@@ -0,0 +1,83 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+interface TestConfig {
+  server: { host: string; port: number }
+  client: { name: string; granted_scopes: string[] }
+  api_key: string
+  api_key_expires_at?: number
+}
+
+const { state, currentMock, verifyMock } = vi.hoisted(() => ({
+  state: { config: {} as TestConfig },
+  currentMock: vi.fn(),
+  verifyMock: vi.fn()
+}))
+
+vi.mock('../config', () => ({
+  loadConfigFromDisk: async () => JSON.parse(JSON.stringify(state.config))
+}))
+
+vi.mock('../prizmApi', () => ({
+  PrizmApi: class {
+    currentClient = currentMock
+    verifyApiKey = verifyMock
+  }
+}))
+
+import { PrizmError } from '../errors'
+import { verifyCredentials } from '../credentialHealth'
+
+describe('verifyCredentials', () => {
+  beforeEach(() => {
+    currentMock.mockReset()
+    verifyMock.mockReset()
+    state.config = {
+      server: { host: '127.0.0.1', port: 4127 },
+      client: { name: 'c-1', granted_scopes: ['default'] },
+      api_key: 'secret',
+      api_key_expires_at: 5_000
+    }
+  })
+
+  it('reports scopes and last use of a valid key', async () => {
+    currentMock.mockResolvedValue({
+      clientId: 'c-1',
+      name: 'laptop',
+      allowedScopes: ['default', 'online'],
+      createdAt: 1,
+      lastSeenAt: 1_000
+    })
+    await expect(verifyCredentials()).resolves.toEqual({
+      serverUrl: 'http://127.0.0.1:4127',
+      valid: true,
+      clientId: 'c-1',
+      name: 'laptop',
+      scopes: ['default', 'online'],
+      lastUsed: 1_000,
+      expiresAt: 5_000
+    })
+    expect(currentMock).toHaveBeenCalledWith('secret', undefined)
+  })
+
+  it('reports a rejected key', async () => {
+    currentMock.mockResolvedValue(null)
+    await expect(verifyCredentials()).resolves.toMatchObject({ valid: false, reason: 'rejected' })
+  })
+
+  it('does not contact the server without a key', async () => {
+    state.config.api_key = ''
+    await expect(verifyCredentials()).resolves.toMatchObject({ valid: false, reason: 'missing' })
+    expect(currentMock).not.toHaveBeenCalled()
+  })
+
+  it('falls back to a plain key check on older servers', async () => {
+    currentMock.mockRejectedValue(PrizmError.badStatus(404, ''))
+    verifyMock.mockResolvedValue(true)
+    await expect(verifyCredentials()).resolves.toMatchObject({
+      valid: true,
+      clientId: 'c-1',
+      scopes: ['default'],
+      lastUsed: null
+    })
+  })
+})
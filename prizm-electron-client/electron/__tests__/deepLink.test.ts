@@ -0,0 +1,107 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+const { app, dialog, pairMock, notifyMock, navigateMock, openDashboardMock } = vi.hoisted(() => ({
+  app: {
+    setAsDefaultProtocolClient: vi.fn(() => true),
+    on: vi.fn()
+  },
+  dialog: { showMessageBox: vi.fn() },
+  pairMock: vi.fn(),
+  notifyMock: vi.fn(),
+  navigateMock: vi.fn(),
+  openDashboardMock: vi.fn(async () => undefined)
+}))
+
+vi.mock('electron', () => {
+  const m = { app, dialog }
+  return { ...m, default: m }
+})
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+vi.mock('../config', () => ({
+  loadConfigFromDisk: async () => ({
+    server: { host: '127.0.0.1', port: 4127 },
+    // 注册后 client.name 已是 clientId，配对沿用注册时的名称
+    client: { name: '3f9c0e', registration_name: 'desktop' }
+  })
+}))
+vi.mock('../registration', () => ({ saveRegistration: vi.fn() }))
+vi.mock('../prizmApi', () => ({
+  PrizmApi: class {
+    pair = pairMock
+  },
+  grantedScopesOf: (_register: unknown, requested: string[]) => requested
+}))
+vi.mock('../serverNotifications', () => ({
+  navigateMainWindow: navigateMock,
+  showLocalNotification: notifyMock
+}))
+vi.mock('../windowManager', () => ({
+  openDashboard: openDashboardMock,
+  showMainWindow: vi.fn(() => ({}))
+}))
+
+import { flushDeepLinks, handleDeepLink, parseDeepLink, watchDeepLinks } from '../deepLink'
+
+beforeEach(() => {
+  vi.clearAllMocks()
+})
+
+describe('parseDeepLink', () => {
+  it('parses registration links and pairing QR payloads', () => {
+    const link = 'prizm://register?server=http%3A%2F%2F10.0.0.2%3A4127&token=t1'
+    expect(parseDeepLink(link)).toEqual({
+      type: 'register',
+      serverUrl: 'http://10.0.0.2:4127',
+      token: 't1'
+    })
+    expect(parseDeepLink('prizm://pair?server=10.0.0.2&token=t1')?.type).toBe('register')
+    expect(parseDeepLink('prizm://register?server=10.0.0.2')).toBeNull()
+  })
+
+  it('parses navigation links', () => {
+    expect(parseDeepLink('prizm://open')).toEqual({ type: 'open', target: 'window' })
+    expect(parseDeepLink('prizm://open/dashboard/')).toEqual({ type: 'open', target: 'dashboard' })
+    expect(parseDeepLink('prizm://open/settings/devices')).toEqual({
+      type: 'open',
+      target: 'settings',
+      section: 'devices'
+    })
+    expect(parseDeepLink('prizm://open/settings/unknown')).toBeNull()
+    expect(parseDeepLink('https://open/dashboard')).toBeNull()
+  })
+})
+
+describe('handleDeepLink', () => {
+  const link = 'prizm://register?server=http%3A%2F%2F10.0.0.2%3A4127&token=t1'
+
+  it('registers only after the user confirms', async () => {
+    dialog.showMessageBox.mockResolvedValueOnce({ response: 1 })
+    await handleDeepLink(link)
+    expect(pairMock).not.toHaveBeenCalled()
+
+    dialog.showMessageBox.mockResolvedValueOnce({ response: 0 })
+    pairMock.mockResolvedValueOnce({ clientId: 'desktop', apiKey: 'k' })
+    await handleDeepLink(link)
+    expect(pairMock).toHaveBeenCalledWith('t1', 'desktop', undefined)
+    expect(notifyMock).toHaveBeenCalledWith(expect.objectContaining({ title: '配对成功' }))
+  })
+
+  it('opens the dashboard of the configured server', async () => {
+    await handleDeepLink('prizm://open/dashboard')
+    expect(openDashboardMock).toHaveBeenCalledWith('http://127.0.0.1:4127/dashboard/')
+  })
+})
+
+describe('watchDeepLinks', () => {
+  it('holds the launch link until the app is ready', async () => {
+    watchDeepLinks(['prizm.exe', 'prizm://open/settings'])
+    expect(app.setAsDefaultProtocolClient).toHaveBeenCalled()
+    await Promise.resolve()
+    expect(navigateMock).not.toHaveBeenCalled()
+    flushDeepLinks()
+    await Promise.resolve()
+    expect(navigateMock).toHaveBeenCalledWith({ page: 'settings', section: 'connection' })
+  })
+})
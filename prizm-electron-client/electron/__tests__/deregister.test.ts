@@ -0,0 +1,74 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+interface TestConfig {
+  server: { host: string; port: number }
+  client: { name: string; auto_register?: boolean }
+  api_key: string
+}
+
+const { state, revokeMock } = vi.hoisted(() => ({
+  state: { config: {} as TestConfig },
+  revokeMock: vi.fn()
+}))
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  loadConfigFromDisk: async () => JSON.parse(JSON.stringify(state.config)),
+  updateConfig: async (mutate: (config: TestConfig) => void) => {
+    const next = JSON.parse(JSON.stringify(state.config))
+    mutate(next)
+    state.config = next
+    return next
+  },
+  sharedState: { mainWindow: null }
+}))
+
+vi.mock('../prizmApi', () => ({
+  PrizmApi: class {
+    revokeClient = revokeMock
+  }
+}))
+
+import { PrizmError } from '../errors'
+import { deregisterClient } from '../deregister'
+
+describe('deregisterClient', () => {
+  beforeEach(() => {
+    revokeMock.mockReset()
+    state.config = {
+      server: { host: '127.0.0.1', port: 4127 },
+      client: { name: 'client-1', auto_register: true },
+      api_key: 'secret'
+    }
+  })
+
+  it('revokes on the server and clears local credentials', async () => {
+    revokeMock.mockResolvedValue(undefined)
+    await expect(deregisterClient()).resolves.toEqual({
+      revoked: true,
+      serverUrl: 'http://127.0.0.1:4127'
+    })
+    expect(revokeMock).toHaveBeenCalledWith('client-1')
+    expect(state.config.api_key).toBe('')
+    expect(state.config.client.name).toBe('')
+    // 注销后下次启动不再自动注册
+    expect(state.config.client.auto_register).toBe(false)
+  })
+
+  it('treats an already revoked client as success', async () => {
+    revokeMock.mockRejectedValue(PrizmError.badStatus(404, '{"error":"Client not found"}'))
+    await expect(deregisterClient()).resolves.toMatchObject({ revoked: true })
+  })
+
+  it('keeps credentials when the server is unreachable unless forced', async () => {
+    revokeMock.mockRejectedValue(PrizmError.network('net::ERR_CONNECTION_REFUSED'))
+    await expect(deregisterClient()).rejects.toMatchObject({ kind: 'network' })
+    expect(state.config.api_key).toBe('secret')
+
+    await expect(deregisterClient(true)).resolves.toMatchObject({ revoked: false })
+    expect(state.config.api_key).toBe('')
+  })
+})
@@ -0,0 +1,84 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+const { codeMock, pollMock, saveMock } = vi.hoisted(() => ({
+  codeMock: vi.fn(),
+  pollMock: vi.fn(),
+  saveMock: vi.fn()
+}))
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  sharedState: { mainWindow: null }
+}))
+
+vi.mock('../httpClient', () => ({
+  sleep: async () => {}
+}))
+
+vi.mock('../registration', () => ({
+  saveRegistration: saveMock
+}))
+
+vi.mock('../prizmApi', () => ({
+  PrizmApi: class {
+    requestDeviceCode = codeMock
+    pollDeviceToken = pollMock
+  }
+}))
+
+import { PrizmError } from '../errors'
+import { registerWithDeviceCode } from '../deviceAuth'
+
+const request = {
+  serverUrl: 'http://127.0.0.1:4127',
+  name: 'desktop',
+  requestedScopes: ['default']
+}
+
+describe('registerWithDeviceCode', () => {
+  beforeEach(() => {
+    codeMock.mockReset()
+    pollMock.mockReset()
+    saveMock.mockReset()
+    codeMock.mockResolvedValue({
+      device_code: 'dev-1',
+      user_code: 'ABCD-EFGH',
+      verification_uri: 'http://127.0.0.1:4127/device',
+      expires_in: 600,
+      interval: 1
+    })
+  })
+
+  it('polls until the user approves and saves the token', async () => {
+    pollMock
+      .mockResolvedValueOnce({ status: 'authorization_pending' })
+      .mockResolvedValueOnce({ status: 'slow_down' })
+      .mockResolvedValueOnce({
+        status: 'granted',
+        token: {
+          access_token: 'tok',
+          token_type: 'Bearer',
+          scope: 'default online',
+          client_id: 'c1'
+        }
+      })
+
+    await expect(registerWithDeviceCode(request)).resolves.toEqual({
+      clientId: 'c1',
+      apiKey: 'tok',
+      grantedScopes: ['default', 'online']
+    })
+    expect(pollMock).toHaveBeenCalledTimes(3)
+    expect(pollMock).toHaveBeenCalledWith('dev-1', undefined)
+    expect(saveMock).toHaveBeenCalledWith(request, expect.objectContaining({ apiKey: 'tok' }))
+  })
+
+  it('stops when the user denies the request', async () => {
+    pollMock.mockRejectedValue(PrizmError.auth('Device authorization was denied'))
+    await expect(registerWithDeviceCode(request)).rejects.toMatchObject({ kind: 'auth' })
+    expect(saveMock).not.toHaveBeenCalled()
+  })
+})
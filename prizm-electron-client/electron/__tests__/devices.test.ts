@@ -0,0 +1,95 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+interface TestConfig {
+  server: { host: string; port: number }
+  client: { name: string }
+  api_key: string
+}
+
+const { state, revokeMock, resolveMock, deregisterMock } = vi.hoisted(() => ({
+  state: { config: {} as TestConfig },
+  revokeMock: vi.fn(),
+  resolveMock: vi.fn(),
+  deregisterMock: vi.fn()
+}))
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  loadConfigFromDisk: async () => JSON.parse(JSON.stringify(state.config))
+}))
+
+vi.mock('../deregister', () => ({
+  deregisterClient: deregisterMock
+}))
+
+vi.mock('../prizmApi', () => ({
+  PrizmApi: class {
+    revokeClient = revokeMock
+    resolveRegistrationRequest = resolveMock
+  }
+}))
+
+import { PrizmError } from '../errors'
+import { resolveRegistrationRequest, revokeClient, toClientInfo } from '../devices'
+
+describe('revokeClient', () => {
+  beforeEach(() => {
+    revokeMock.mockReset()
+    deregisterMock.mockReset()
+    state.config = {
+      server: { host: '127.0.0.1', port: 4127 },
+      client: { name: 'client-1' },
+      api_key: 'secret'
+    }
+  })
+
+  it('revokes another client on the server', async () => {
+    await revokeClient(' client-2 ')
+    expect(revokeMock).toHaveBeenCalledWith('client-2')
+    expect(deregisterMock).not.toHaveBeenCalled()
+  })
+
+  it('deregisters when revoking this client', async () => {
+    await revokeClient('client-1')
+    expect(deregisterMock).toHaveBeenCalled()
+    expect(revokeMock).not.toHaveBeenCalled()
+  })
+
+  it('rejects an empty client id', async () => {
+    await expect(revokeClient('  ')).rejects.toThrow(PrizmError)
+  })
+})
+
+describe('resolveRegistrationRequest', () => {
+  it('approves or denies a pending registration on the server', async () => {
+    state.config = {
+      server: { host: '127.0.0.1', port: 4127 },
+      client: { name: 'client-1' },
+      api_key: 'secret'
+    }
+    await resolveRegistrationRequest(' req-1 ', 'approve')
+    expect(resolveMock).toHaveBeenCalledWith('req-1', 'approve')
+    await expect(resolveRegistrationRequest('', 'deny')).rejects.toThrow(PrizmError)
+  })
+})
+
+describe('toClientInfo', () => {
+  it('maps the server record and marks this client', () => {
+    expect(
+      toClientInfo(
+        { clientId: 'client-1', name: 'laptop', allowedScopes: ['default'], createdAt: 1 },
+        'client-1'
+      )
+    ).toEqual({
+      id: 'client-1',
+      name: 'laptop',
+      scopes: ['default'],
+      lastSeen: null,
+      createdAt: 1,
+      current: true
+    })
+  })
+})
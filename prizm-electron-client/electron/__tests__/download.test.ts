@@ -0,0 +1,78 @@
+import { describe, it, expect, vi } from 'vitest'
+import * as fs from 'fs'
+import * as os from 'os'
+import * as path from 'path'
+
+const { getMock } = vi.hoisted(() => ({ getMock: vi.fn() }))
+
+vi.mock('../httpClient', () => ({
+  httpClient: {
+    get: getMock,
+    getAuth: getMock,
+    acquireConnection: () => () => undefined,
+    getNetworkConfig: () => ({ read_timeout_ms: 1000 })
+  }
+}))
+
+import { downloadFile } from '../download'
+import type { DownloadProgress } from '../download'
+
+function tmpDest(): string {
+  const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'prizm-download-'))
+  return path.join(dir, 'nested', 'artifact.bin')
+}
+
+describe('downloadFile', () => {
+  it('streams the body to disk and reports progress', async () => {
+    const chunks = [new Uint8Array([1, 2, 3]), new Uint8Array([4, 5])]
+    const body = new ReadableStream<Uint8Array>({
+      start(controller) {
+        chunks.forEach((chunk) => controller.enqueue(chunk))
+        controller.close()
+      }
+    })
+    getMock.mockResolvedValueOnce(
+      new Response(body, { status: 200, headers: { 'Content-Length': '5' } })
+    )
+    const dest = tmpDest()
+    const events: DownloadProgress[] = []
+
+    await expect(
+      downloadFile('http://h:1/file', dest, { onProgress: (p) => events.push(p) })
+    ).resolves.toEqual({ path: dest, bytes: 5 })
+    expect([...fs.readFileSync(dest)]).toEqual([1, 2, 3, 4, 5])
+    expect(fs.existsSync(`${dest}.part`)).toBe(false)
+    expect(events.at(-1)).toMatchObject({ bytes: 5, total: 5, done: true })
+    expect(getMock.mock.calls[0][1].timeoutMs).toBe(Number.POSITIVE_INFINITY)
+  })
+
+  it('removes the partial file when cancelled', async () => {
+    const body = new ReadableStream<Uint8Array>({
+      start(controller) {
+        controller.enqueue(new Uint8Array([1]))
+      }
+    })
+    getMock.mockResolvedValueOnce(new Response(body, { status: 200 }))
+    const dest = tmpDest()
+    const controller = new AbortController()
+
+    const pending = downloadFile('http://h:1/file', dest, {
+      signal: controller.signal,
+      onProgress: () => controller.abort(new Error('cancelled'))
+    })
+    await expect(pending).rejects.toThrow('cancelled')
+    expect(fs.existsSync(`${dest}.part`)).toBe(false)
+    expect(fs.existsSync(dest)).toBe(false)
+  })
+
+  it('rejects relative destinations and error statuses', async () => {
+    await expect(downloadFile('http://h:1/file', 'relative.bin')).rejects.toMatchObject({
+      kind: 'invalid_input'
+    })
+    getMock.mockResolvedValueOnce(new Response('missing', { status: 404 }))
+    await expect(downloadFile('http://h:1/file', tmpDest())).rejects.toMatchObject({
+      kind: 'bad_status',
+      status: 404
+    })
+  })
+})
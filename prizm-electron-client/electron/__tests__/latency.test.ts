@@ -0,0 +1,44 @@
+import { describe, it, expect, vi } from 'vitest'
+import * as net from 'net'
+
+vi.mock('electron', () => {
+  const electronMock = {
+    app: { getPath: vi.fn().mockReturnValue('/mock/app/data') },
+    session: { fromPartition: vi.fn() }
+  }
+  return {
+    ...electronMock,
+    default: electronMock
+  }
+})
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+import { measureConnectionPhases, summarizeLatencies } from '../latency'
+
+describe('summarizeLatencies', () => {
+  it('computes min, average and nearest-rank p95', () => {
+    const samples = Array.from({ length: 20 }, (_, i) => i + 1)
+    expect(summarizeLatencies(samples)).toEqual({ min_ms: 1, avg_ms: 10.5, p95_ms: 19 })
+    expect(summarizeLatencies([7])).toEqual({ min_ms: 7, avg_ms: 7, p95_ms: 7 })
+    expect(summarizeLatencies([])).toEqual({ min_ms: null, avg_ms: null, p95_ms: null })
+  })
+})
+
+describe('measureConnectionPhases', () => {
+  it('times DNS and TCP for plain http servers', async () => {
+    const server = net.createServer((socket) => socket.end())
+    await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve))
+    const { port } = server.address() as net.AddressInfo
+    try {
+      const phases = await measureConnectionPhases(`http://127.0.0.1:${port}`, 1000)
+      expect(phases.address).toBe('127.0.0.1')
+      expect(phases.tcp_ms).toBeGreaterThanOrEqual(0)
+      expect(phases.tls_ms).toBeNull()
+    } finally {
+      server.close()
+    }
+  })
+})
@@ -0,0 +1,28 @@
+import { describe, it, expect, vi } from 'vitest'
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+import { redactBody, redactHeaders, redactUrl } from '../networkLog'
+
+describe('network log redaction', () => {
+  it('masks auth headers', () => {
+    expect(
+      redactHeaders({ Authorization: 'Bearer secret', 'X-Prizm-Api-Key': 'k', Accept: 'json' })
+    ).toEqual({ Authorization: '***', 'X-Prizm-Api-Key': '***', Accept: 'json' })
+  })
+
+  it('masks api keys in query strings', () => {
+    expect(redactUrl('http://127.0.0.1:4127/ws?apiKey=secret&scope=default')).toBe(
+      'http://127.0.0.1:4127/ws?apiKey=***&scope=default'
+    )
+  })
+
+  it('masks nested secret fields and truncates long bodies', () => {
+    expect(redactBody('{"clientId":"c1","apiKey":"secret","nested":{"api_key":"x"}}')).toBe(
+      '{"clientId":"c1","apiKey":"***","nested":{"api_key":"***"}}'
+    )
+    expect(redactBody('x'.repeat(600))).toHaveLength(501)
+  })
+})
@@ -0,0 +1,43 @@
+import { describe, it, expect } from 'vitest'
+import { NetworkStats, countResponseBytes, endpointKey, headersByteLength } from '../networkStats'
+
+describe('endpointKey', () => {
+  it('collapses id segments and drops the query', () => {
+    expect(endpointKey('get', 'http://h:1/files/42?x=1')).toBe('GET http://h:1/files/:id')
+    expect(endpointKey('DELETE', 'http://h:1/todo/550e8400-e29b-41d4-a716-446655440000')).toBe(
+      'DELETE http://h:1/todo/:id'
+    )
+    expect(endpointKey('GET', 'http://h:1/health')).toBe('GET http://h:1/health')
+  })
+})
+
+describe('NetworkStats', () => {
+  it('accumulates per endpoint and totals', async () => {
+    const stats = new NetworkStats()
+    const entry = stats.recordRequest('POST', 'http://h:1/notes/1', 10)
+    stats.recordRequest('POST', 'http://h:1/notes/2', 5).errors++
+    const resp = countResponseBytes(new Response('hello'), (n) => {
+      entry.bytes_received += n
+    })
+    expect(await resp.text()).toBe('hello')
+
+    const snapshot = stats.snapshot()
+    expect(snapshot.endpoints).toEqual([
+      {
+        endpoint: 'POST http://h:1/notes/:id',
+        requests: 2,
+        errors: 1,
+        bytes_sent: 15,
+        bytes_received: 5
+      }
+    ])
+    expect(snapshot.totals).toEqual({ requests: 2, errors: 1, bytes_sent: 15, bytes_received: 5 })
+
+    stats.reset()
+    expect(stats.snapshot().endpoints).toEqual([])
+  })
+
+  it('estimates header bytes', () => {
+    expect(headersByteLength({ Accept: 'application/json' })).toBe(6 + 16 + 4)
+  })
+})
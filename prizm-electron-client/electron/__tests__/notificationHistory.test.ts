@@ -0,0 +1,60 @@
+import { describe, it, expect, vi } from 'vitest'
+import * as fs from 'fs'
+import * as os from 'os'
+import * as path from 'path'
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({ sharedState: { mainWindow: null } }))
+
+import { MAX_HISTORY_ENTRIES, NotificationHistory } from '../notificationHistory'
+
+function tempHistory() {
+  const file = path.join(fs.mkdtempSync(path.join(os.tmpdir(), 'prizm-notify-')), 'history.json')
+  let now = 1_000
+  const history = new NotificationHistory(() => file, () => now++)
+  return { file, history }
+}
+
+describe('NotificationHistory', () => {
+  it('records newest first and replaces entries with the same updateId', () => {
+    const { history } = tempHistory()
+    const listener = vi.fn()
+    history.onChange(listener)
+    history.record({ title: 'todo 1/3', body: '', source: 'server', updateId: 'todo:a' })
+    history.record({ title: 'build', body: 'ok', source: 'local' })
+    history.record({ title: 'todo 2/3', body: '', source: 'server', updateId: 'todo:a' })
+
+    expect(history.list().map((e) => e.title)).toEqual(['todo 2/3', 'build'])
+    expect(history.list({ limit: 1 })).toHaveLength(1)
+    expect(listener).toHaveBeenLastCalledWith(2)
+  })
+
+  it('marks entries read and clears them', () => {
+    const { history } = tempHistory()
+    const first = history.record({ title: 'a', body: '', source: 'server' })
+    history.record({ title: 'b', body: '', source: 'server' })
+
+    expect(history.markRead([first.id])).toBe(1)
+    expect(history.list({ unreadOnly: true }).map((e) => e.title)).toEqual(['b'])
+    expect(history.markRead()).toBe(1)
+    expect(history.unreadCount()).toBe(0)
+    expect(history.clear()).toBe(2)
+    expect(history.list()).toEqual([])
+  })
+
+  it('persists across instances and caps the history', async () => {
+    const { file, history } = tempHistory()
+    for (let i = 0; i < MAX_HISTORY_ENTRIES + 5; i++) {
+      history.record({ title: `n${i}`, body: '', source: 'server' })
+    }
+    await history.flush()
+
+    const reloaded = new NotificationHistory(() => file)
+    const entries = reloaded.list()
+    expect(entries).toHaveLength(MAX_HISTORY_ENTRIES)
+    expect(entries[0].title).toBe(`n${MAX_HISTORY_ENTRIES + 4}`)
+  })
+})
@@ -0,0 +1,63 @@
+import { describe, it, expect, vi } from 'vitest'
+
+vi.mock('electron', () => {
+  const electronMock = { app: { getPath: vi.fn().mockReturnValue('/mock/app/data') } }
+  return { ...electronMock, default: electronMock }
+})
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+import { DEFAULT_NOTIFICATION_PREFERENCES, normalizeNotificationPreferences } from '../config'
+import {
+  isNotificationAllowed,
+  isQuietTime,
+  notificationCategoryOf,
+  toNotificationSeverity
+} from '../notificationPolicy'
+
+describe('notificationCategoryOf', () => {
+  it('prefers a valid explicit category and falls back by event type', () => {
+    expect(notificationCategoryOf('notification', 'connection')).toBe('connection')
+    expect(notificationCategoryOf('notification', 'bogus')).toBe('server_alerts')
+    expect(notificationCategoryOf(undefined)).toBe('server_alerts')
+    expect(notificationCategoryOf('todo_list:updated')).toBe('sync')
+  })
+})
+
+describe('isNotificationAllowed', () => {
+  it('checks the category toggle and minimum severity', () => {
+    const prefs = normalizeNotificationPreferences({
+      categories: { sync: false },
+      min_severity: 'warning'
+    })
+    expect(isNotificationAllowed('sync', 'error', prefs)).toBe(false)
+    expect(isNotificationAllowed('server_alerts', 'info', prefs)).toBe(false)
+    const severity = toNotificationSeverity('error')
+    expect(isNotificationAllowed('server_alerts', severity, prefs)).toBe(true)
+    expect(isNotificationAllowed('updates', 'info', DEFAULT_NOTIFICATION_PREFERENCES)).toBe(true)
+  })
+})
+
+describe('isQuietTime', () => {
+  const quiet = { enabled: true, start: '22:00', end: '08:00', weekends: false }
+  // 2026-10-14 为周三，2026-10-17 为周六
+  const at = (day: number, hours: number, minutes = 0) => new Date(2026, 9, day, hours, minutes)
+
+  it('handles windows that cross midnight', () => {
+    expect(isQuietTime(quiet, at(14, 23))).toBe(true)
+    expect(isQuietTime(quiet, at(14, 7, 59))).toBe(true)
+    expect(isQuietTime(quiet, at(14, 8))).toBe(false)
+    expect(isQuietTime(quiet, at(14, 12))).toBe(false)
+  })
+
+  it('handles same-day windows, weekends and disabled schedules', () => {
+    const lunch = { ...quiet, start: '12:00', end: '13:30' }
+    expect(isQuietTime(lunch, at(14, 12, 45))).toBe(true)
+    expect(isQuietTime(lunch, at(14, 13, 30))).toBe(false)
+    expect(isQuietTime({ ...lunch, weekends: true }, at(17, 10))).toBe(true)
+    expect(isQuietTime({ ...quiet, start: '08:00' }, at(14, 8))).toBe(false)
+    expect(isQuietTime({ ...quiet, enabled: false }, at(14, 23))).toBe(false)
+  })
+})
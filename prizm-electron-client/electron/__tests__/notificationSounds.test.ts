@@ -0,0 +1,33 @@
+import { describe, it, expect, vi } from 'vitest'
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  sharedState: {
+    notificationPreferences: {
+      sounds: { connection: '/sounds/down.wav', server_alerts: 'none', sync: 'default' }
+    }
+  }
+}))
+
+import { soundCommand, soundFileOf, usesCustomSound } from '../notificationSounds'
+
+describe('notification sounds', () => {
+  it('resolves sound files and silences the system sound when customized', () => {
+    expect(soundFileOf('connection')).toBe('/sounds/down.wav')
+    expect(soundFileOf('server_alerts')).toBeNull()
+    expect(soundFileOf('sync')).toBeNull()
+    expect(usesCustomSound('server_alerts')).toBe(true)
+    expect(usesCustomSound('sync')).toBe(false)
+  })
+
+  it('builds a player command for each platform', () => {
+    expect(soundCommand('/a.wav', 'darwin')).toEqual(['afplay', ['/a.wav']])
+    expect(soundCommand('/a.wav', 'linux')).toEqual(['paplay', ['/a.wav']])
+    const [command, args] = soundCommand("C:\\it's.wav", 'win32')
+    expect(command).toBe('powershell')
+    expect(args[args.length - 1]).toBe("(New-Object Media.SoundPlayer 'C:\\it''s.wav').PlaySync()")
+  })
+})
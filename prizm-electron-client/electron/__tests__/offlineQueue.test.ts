@@ -0,0 +1,74 @@
+import { describe, it, expect, vi } from 'vitest'
+import * as fs from 'fs'
+import * as os from 'os'
+import * as path from 'path'
+
+const { state, requestMock } = vi.hoisted(() => ({
+  state: { configDir: '' },
+  requestMock: vi.fn()
+}))
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  getConfigPath: () => ({ configDir: state.configDir }),
+  sharedState: { mainWindow: null }
+}))
+
+vi.mock('../httpClient', () => ({
+  httpClient: {
+    request: requestMock,
+    requestAuth: requestMock,
+    isServerUrl: (url: string) => new URL(url).origin === 'http://h:1'
+  }
+}))
+
+import { PrizmError } from '../errors'
+import { getOfflineQueueStatus, replayOfflineQueue, sendOrQueue } from '../offlineQueue'
+
+describe('offline queue', () => {
+  it('queues unreachable requests and replays them in order', async () => {
+    state.configDir = fs.mkdtempSync(path.join(os.tmpdir(), 'prizm-queue-'))
+    requestMock.mockRejectedValue(PrizmError.network('net::ERR_CONNECTION_REFUSED'))
+
+    const entry = { method: 'POST', url: 'http://h:1/settings', auth: true }
+    await expect(sendOrQueue({ ...entry, body: { n: 1 } })).resolves.toMatchObject({
+      queued: true
+    })
+    await sendOrQueue({ ...entry, body: { n: 2 } })
+    expect(getOfflineQueueStatus().pending).toBe(2)
+    const queueFile = path.join(state.configDir, 'offline-queue.json')
+    expect(JSON.parse(fs.readFileSync(queueFile, 'utf-8'))).toHaveLength(2)
+
+    requestMock.mockReset()
+    requestMock.mockImplementation(async () => new Response('', { status: 200 }))
+    const status = await replayOfflineQueue()
+    expect(status).toMatchObject({ pending: 0, replaying: false, replayed: 2, dropped: 0 })
+    expect(requestMock.mock.calls.map((call) => call[2].body)).toEqual([{ n: 1 }, { n: 2 }])
+
+    fs.rmSync(state.configDir, { recursive: true, force: true })
+  })
+
+  it('refuses requests for other origins and drops them on replay', async () => {
+    state.configDir = fs.mkdtempSync(path.join(os.tmpdir(), 'prizm-queue-'))
+    requestMock.mockReset()
+    await expect(
+      sendOrQueue({ method: 'POST', url: 'https://evil.example/x', auth: true })
+    ).rejects.toMatchObject({ kind: 'invalid_input' })
+
+    // 队列文件被改写成指向其他主机的请求
+    const queueFile = path.join(state.configDir, 'offline-queue.json')
+    fs.writeFileSync(
+      queueFile,
+      JSON.stringify([{ id: '1', method: 'POST', url: 'https://evil.example/x', auth: true }])
+    )
+    vi.resetModules()
+    const fresh = await import('../offlineQueue')
+    await expect(fresh.replayOfflineQueue()).resolves.toMatchObject({ pending: 0, dropped: 1 })
+    expect(requestMock).not.toHaveBeenCalled()
+
+    fs.rmSync(state.configDir, { recursive: true, force: true })
+  })
+})
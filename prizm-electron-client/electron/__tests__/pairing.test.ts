@@ -0,0 +1,112 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+const { pairMock, pairCodeMock, saveMock } = vi.hoisted(() => ({
+  pairMock: vi.fn(),
+  pairCodeMock: vi.fn(),
+  saveMock: vi.fn()
+}))
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  loadConfigFromDisk: async () => ({
+    server: { host: '127.0.0.1', port: 4127 },
+    client: { name: 'desktop' }
+  })
+}))
+
+vi.mock('../registration', () => ({
+  saveRegistration: saveMock
+}))
+
+vi.mock('../prizmApi', () => ({
+  PrizmApi: class {
+    pair = pairMock
+    pairWithCode = pairCodeMock
+  },
+  grantedScopesOf: (register: { grantedScopes?: string[] }, requested: string[]) =>
+    register.grantedScopes ?? requested
+}))
+
+import { PrizmError } from '../errors'
+import {
+  encodePairingPayload,
+  normalizePairingCode,
+  pairFromQr,
+  pairWithCode,
+  parsePairingPayload
+} from '../pairing'
+
+describe('pairing payload', () => {
+  it('round-trips server url and token', () => {
+    const payload = encodePairingPayload({
+      serverUrl: 'https://prizm.example.com:443/api',
+      token: 'tok-1'
+    })
+    expect(payload.startsWith('prizm://pair?')).toBe(true)
+    expect(parsePairingPayload(payload)).toEqual({
+      serverUrl: 'https://prizm.example.com:443/api',
+      token: 'tok-1'
+    })
+  })
+
+  it('rejects payloads that are not pairing links', () => {
+    for (const payload of ['not a url', 'https://pair?server=a&token=b', 'prizm://pair?server=x']) {
+      expect(() => parsePairingPayload(payload)).toThrow(PrizmError)
+    }
+  })
+})
+
+describe('pairFromQr', () => {
+  beforeEach(() => {
+    pairMock.mockReset()
+    saveMock.mockReset()
+  })
+
+  it('registers with the scanned server and saves the granted scopes', async () => {
+    pairMock.mockResolvedValue({ clientId: 'c-1', apiKey: 'key-1', grantedScopes: ['default'] })
+    const payload = encodePairingPayload({ serverUrl: 'http://10.0.0.2:4127', token: 'tok-1' })
+
+    const result = await pairFromQr(payload, 'phone')
+
+    expect(pairMock).toHaveBeenCalledWith('tok-1', 'phone', undefined)
+    expect(result).toEqual({ clientId: 'c-1', apiKey: 'key-1', grantedScopes: ['default'] })
+    expect(saveMock).toHaveBeenCalledWith(
+      { serverUrl: 'http://10.0.0.2:4127', name: 'phone', requestedScopes: ['default'] },
+      result
+    )
+  })
+})
+
+describe('pairWithCode', () => {
+  beforeEach(() => {
+    pairCodeMock.mockReset()
+    saveMock.mockReset()
+  })
+
+  it('accepts codes with separators', () => {
+    expect(normalizePairingCode(' 123-456 ')).toBe('123456')
+    expect(() => normalizePairingCode('12345')).toThrow(PrizmError)
+    expect(() => normalizePairingCode('abcdef')).toThrow(PrizmError)
+  })
+
+  it('pairs with the configured server', async () => {
+    pairCodeMock.mockResolvedValue({ clientId: 'c-2', apiKey: 'key-2', grantedScopes: ['notes'] })
+
+    const result = await pairWithCode('123 456', 'laptop')
+
+    expect(pairCodeMock).toHaveBeenCalledWith('123456', 'laptop', undefined)
+    expect(result.apiKey).toBe('key-2')
+    expect(saveMock).toHaveBeenCalledWith(
+      { serverUrl: 'http://127.0.0.1:4127', name: 'laptop', requestedScopes: ['notes'] },
+      result
+    )
+  })
+
+  it('does not contact the server for malformed codes', async () => {
+    await expect(pairWithCode('12', 'laptop')).rejects.toThrow(PrizmError)
+    expect(pairCodeMock).not.toHaveBeenCalled()
+  })
+})
@@ -0,0 +1,80 @@
+import { describe, it, expect, vi } from 'vitest'
+
+vi.mock('electron', () => {
+  const electronMock = { powerMonitor: { getSystemIdleTime: vi.fn(), getSystemIdleState: vi.fn() } }
+  return { ...electronMock, default: electronMock }
+})
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../realtime', () => ({ realtimeConnection: {} }))
+
+import type { PrizmConfig } from '../config'
+import type { MessageListener, RealtimeConnection } from '../realtime'
+import {
+  AWAY_AFTER_SECONDS,
+  IDLE_AFTER_SECONDS,
+  PresenceReporter,
+  presenceFromIdle
+} from '../presence'
+
+function fakeConnection(state: string) {
+  const listeners = new Set<MessageListener>()
+  const connection = {
+    state,
+    sendPresence: vi.fn(() => true),
+    status: () => ({ state: connection.state }),
+    onMessage: (listener: MessageListener) => {
+      listeners.add(listener)
+      return () => listeners.delete(listener)
+    },
+    emit: (message: { type: string }) => listeners.forEach((l) => l(message)),
+    listeners
+  }
+  return connection
+}
+
+function config(reportPresence?: boolean): PrizmConfig {
+  return { client: { report_presence: reportPresence } } as unknown as PrizmConfig
+}
+
+describe('presenceFromIdle', () => {
+  it('maps idle time and screen lock to a state', () => {
+    expect(presenceFromIdle(0)).toBe('online')
+    expect(presenceFromIdle(IDLE_AFTER_SECONDS)).toBe('idle')
+    expect(presenceFromIdle(AWAY_AFTER_SECONDS)).toBe('away')
+    expect(presenceFromIdle(0, true)).toBe('away')
+  })
+})
+
+describe('PresenceReporter', () => {
+  const probe = { idleSeconds: () => 12, locked: () => false }
+
+  it('reports when the connection comes up', () => {
+    const connection = fakeConnection('connecting')
+    const reporter = new PresenceReporter(connection as unknown as RealtimeConnection, probe)
+    reporter.schedule(config())
+    expect(connection.sendPresence).not.toHaveBeenCalled()
+
+    connection.state = 'connected'
+    connection.emit({ type: 'connected' })
+    expect(connection.sendPresence).toHaveBeenCalledWith('online', 12)
+    reporter.stop()
+    expect(connection.listeners.size).toBe(0)
+  })
+
+  it('stops reporting when opted out', () => {
+    const connection = fakeConnection('connected')
+    const reporter = new PresenceReporter(connection as unknown as RealtimeConnection, probe)
+    reporter.schedule(config(true))
+    expect(connection.sendPresence).toHaveBeenCalledTimes(1)
+
+    reporter.schedule(config(false))
+    connection.emit({ type: 'connected' })
+    reporter.report()
+    expect(connection.sendPresence).toHaveBeenCalledTimes(1)
+    expect(connection.listeners.size).toBe(0)
+  })
+})
@@ -0,0 +1,127 @@
+import { describe, it, expect, vi } from 'vitest'
+
+vi.mock('electron', () => {
+  const electronMock = {
+    app: { getPath: vi.fn().mockReturnValue('/mock/app/data') },
+    session: { fromPartition: vi.fn() }
+  }
+  return {
+    ...electronMock,
+    default: electronMock
+  }
+})
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+import { DEFAULT_NETWORK_CONFIG } from '../config'
+import type { HttpClient } from '../httpClient'
+import { PrizmApi, grantedScopesOf } from '../prizmApi'
+
+function fakeClient(resp: Response) {
+  const client = {
+    getNetworkConfig: () => DEFAULT_NETWORK_CONFIG,
+    healthGet: vi.fn().mockResolvedValue(resp),
+    get: vi.fn().mockResolvedValue(resp),
+    post: vi.fn().mockResolvedValue(resp)
+  }
+  return client as typeof client & HttpClient
+}
+
+describe('PrizmApi', () => {
+  it('builds endpoint urls from the base url', async () => {
+    const client = fakeClient(new Response('{"clientId":"c1","apiKey":"k1"}', { status: 201 }))
+    const api = new PrizmApi('http://127.0.0.1:4127/', client)
+    await expect(api.register('desktop', ['default'])).resolves.toEqual({
+      clientId: 'c1',
+      apiKey: 'k1'
+    })
+    expect(client.post).toHaveBeenCalledWith(
+      'http://127.0.0.1:4127/auth/register',
+      { name: 'desktop', requestedScopes: ['default'] },
+      { signal: undefined }
+    )
+  })
+
+  it('sends declared capabilities with the registration', async () => {
+    const client = fakeClient(new Response('{"clientId":"c1","apiKey":"k1"}', { status: 201 }))
+    const capabilities = { platform: 'linux-x64', appVersion: '1.0.0', features: ['ws'] }
+    await new PrizmApi('http://127.0.0.1:4127', client).register(
+      'desktop',
+      [],
+      undefined,
+      capabilities
+    )
+    expect(client.post).toHaveBeenCalledWith(
+      'http://127.0.0.1:4127/auth/register',
+      { name: 'desktop', requestedScopes: undefined, capabilities },
+      { signal: undefined }
+    )
+  })
+
+  it('uses granted scopes from the server, falling back to the requested ones', () => {
+    const register = { clientId: 'c1', apiKey: 'k1' }
+    expect(grantedScopesOf({ ...register, grantedScopes: ['online'] }, ['default'])).toEqual([
+      'online'
+    ])
+    expect(grantedScopesOf(register, ['default', 'online'])).toEqual(['default', 'online'])
+  })
+
+  it('maps device token errors to poll results', async () => {
+    const pending = new Response('{"error":"authorization_pending"}', { status: 400 })
+    const api = new PrizmApi('http://h:1', fakeClient(pending))
+    await expect(api.pollDeviceToken('dev-1')).resolves.toEqual({
+      status: 'authorization_pending'
+    })
+
+    const denied = new Response('{"error":"access_denied"}', { status: 400 })
+    const deniedApi = new PrizmApi('http://h:1', fakeClient(denied))
+    await expect(deniedApi.pollDeviceToken('dev-1')).rejects.toMatchObject({ kind: 'auth' })
+
+    const unsupported = new PrizmApi('http://h:1', fakeClient(new Response('', { status: 404 })))
+    await expect(unsupported.requestDeviceCode('desktop', ['default'])).rejects.toMatchObject({
+      kind: 'config'
+    })
+  })
+
+  it('throws typed errors for bad status and invalid JSON', async () => {
+    const failing = new PrizmApi('http://h:1', fakeClient(new Response('nope', { status: 500 })))
+    await expect(failing.health()).rejects.toMatchObject({ kind: 'bad_status', status: 500 })
+
+    const garbled = new PrizmApi('http://h:1', fakeClient(new Response('<html>', { status: 200 })))
+    await expect(garbled.scopes()).rejects.toMatchObject({ kind: 'parse' })
+  })
+
+  it('falls back to the next health path on 404 and remembers it', async () => {
+    const client = fakeClient(new Response('', { status: 404 }))
+    client.healthGet
+      .mockResolvedValueOnce(new Response('', { status: 404 }))
+      .mockResolvedValueOnce(new Response('ok', { headers: { 'Content-Type': 'text/plain' } }))
+    const api = new PrizmApi('http://h:1/prizm', client)
+    await expect(api.health()).resolves.toEqual({ status: 'ok' })
+    expect(client.healthGet.mock.calls.map((call) => call[0])).toEqual([
+      'http://h:1/prizm/health',
+      'http://h:1/prizm/healthz'
+    ])
+    expect(api.healthPath).toBe('/healthz')
+  })
+
+  it('tries the remembered health path first', async () => {
+    const client = fakeClient(new Response('{"status":"ok"}'))
+    await new PrizmApi('http://h:1', client, '/api/health').health()
+    expect(client.healthGet.mock.calls[0][0]).toBe('http://h:1/api/health')
+  })
+
+  it('does not treat an HTML fallback page as healthy', async () => {
+    const page = new Response('<html>', { headers: { 'Content-Type': 'text/html' } })
+    await expect(new PrizmApi('http://h:1', fakeClient(page)).health()).rejects.toMatchObject({
+      kind: 'parse'
+    })
+  })
+
+  it('reports health without throwing', async () => {
+    const api = new PrizmApi('http://h:1', fakeClient(new Response('{"status":"ok"}')))
+    await expect(api.isHealthy()).resolves.toBe(true)
+  })
+})
@@ -0,0 +1,45 @@
+import { describe, it, expect, vi } from 'vitest'
+
+vi.mock('electron', () => {
+  const electronMock = { session: { fromPartition: vi.fn() } }
+  return {
+    ...electronMock,
+    default: electronMock
+  }
+})
+
+import { toElectronProxyConfig } from '../proxy'
+
+describe('toElectronProxyConfig', () => {
+  it('maps system and none modes', () => {
+    expect(toElectronProxyConfig({ mode: 'system', url: '', bypass: '' })).toEqual({
+      mode: 'system'
+    })
+    expect(toElectronProxyConfig({ mode: 'none', url: '', bypass: '' })).toEqual({
+      mode: 'direct'
+    })
+  })
+
+  it('builds fixed proxy rules for manual mode', () => {
+    expect(
+      toElectronProxyConfig({
+        mode: 'manual',
+        url: 'http://proxy.corp:8080/',
+        bypass: 'localhost,.corp'
+      })
+    ).toEqual({
+      mode: 'fixed_servers',
+      proxyRules: 'http://proxy.corp:8080',
+      proxyBypassRules: 'localhost,.corp'
+    })
+  })
+
+  it('rejects invalid manual proxy urls', () => {
+    expect(() => toElectronProxyConfig({ mode: 'manual', url: '', bypass: '' })).toThrow(
+      'Invalid proxy url'
+    )
+    expect(() =>
+      toElectronProxyConfig({ mode: 'manual', url: 'ftp://proxy:21', bypass: '' })
+    ).toThrow('Unsupported proxy scheme')
+  })
+})
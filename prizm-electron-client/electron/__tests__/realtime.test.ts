@@ -0,0 +1,390 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+import { EventEmitter } from 'events'
+
+interface TestConfig {
+  server: { host: string; port: number; scheme?: 'http' | 'https'; verify_tls?: boolean }
+  network: {
+    heartbeat: { interval_ms: number; max_missed: number }
+    realtime_compression?: boolean
+  }
+  api_key: string
+}
+
+const { state, sendMock } = vi.hoisted(() => ({
+  state: { config: {} as TestConfig },
+  sendMock: vi.fn()
+}))
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  loadConfigFromDisk: async () => JSON.parse(JSON.stringify(state.config)),
+  sharedState: { mainWindow: { isDestroyed: () => false, webContents: { send: sendMock } } }
+}))
+
+import { decodeBinaryFrame, encodeBinaryFrame } from '../binaryFrames'
+import type { PrizmConfig } from '../config'
+import type { EventCursor } from '../eventCursor'
+import {
+  MAX_RECONNECT_ATTEMPTS,
+  RealtimeConnection,
+  reconnectDelay,
+  realtimeUrl
+} from '../realtime'
+import type { SocketOptions } from '../realtime'
+
+class FakeSocket extends EventEmitter {
+  constructor(readonly url: string, readonly options?: SocketOptions) {
+    super()
+  }
+  send = vi.fn()
+  ping = vi.fn()
+  close = vi.fn((code = 1000, reason = '') => this.emit('close', code, Buffer.from(reason)))
+  terminate = vi.fn(() => this.emit('close', 1006, Buffer.from('')))
+  receive(message: object): void {
+    this.emit('message', Buffer.from(JSON.stringify(message)))
+  }
+}
+
+function setup(cursor: EventCursor | null = null) {
+  const sockets: FakeSocket[] = []
+  const cursorStore = {
+    cursor,
+    load: () => cursorStore.cursor,
+    save: (next: EventCursor) => {
+      cursorStore.cursor = next
+    }
+  }
+  const connection = new RealtimeConnection(
+    (url, options) => {
+      const socket = new FakeSocket(url, options)
+      sockets.push(socket)
+      return socket
+    },
+    () => 1_000,
+    () => 0,
+    cursorStore
+  )
+  return { connection, sockets, cursorStore }
+}
+
+/** 等待 connect() 读完配置并创建 socket */
+const tick = () => new Promise((resolve) => setTimeout(resolve, 0))
+
+describe('RealtimeConnection', () => {
+  beforeEach(() => {
+    sendMock.mockReset()
+    state.config = {
+      server: { host: '127.0.0.1', port: 4127 },
+      network: { heartbeat: { interval_ms: 0, max_missed: 2 } },
+      api_key: 'secret key'
+    }
+  })
+
+  it('derives ws and wss urls from the server config', () => {
+    const config = state.config as unknown as PrizmConfig
+    expect(realtimeUrl(config)).toBe('ws://127.0.0.1:4127/ws')
+    config.server.scheme = 'https'
+    expect(realtimeUrl(config)).toBe('wss://127.0.0.1:4127/ws')
+  })
+
+  it('connects with the api key and reports the confirmed client', async () => {
+    const { connection, sockets } = setup()
+    const connected = connection.connect()
+    await tick()
+    expect(sockets[0].url).toBe('ws://127.0.0.1:4127/ws?apiKey=secret%20key')
+    expect(connection.status().state).toBe('connecting')
+
+    sockets[0].receive({ type: 'connected', clientId: 'c-1', serverTime: 1 })
+    await expect(connected).resolves.toEqual({
+      state: 'connected',
+      url: 'ws://127.0.0.1:4127/ws',
+      clientId: 'c-1',
+      connectedAt: 1_000,
+      attempt: 0,
+      nextRetryAt: null,
+      missedEvents: 0
+    })
+    expect(sendMock).toHaveBeenLastCalledWith('ws://state', connection.status())
+  })
+
+  it('rejects with an auth error when the server refuses the key', async () => {
+    const { connection, sockets } = setup()
+    const connected = connection.connect()
+    await tick()
+    sockets[0].emit('close', 4003, Buffer.from('Invalid API key'))
+    await expect(connected).rejects.toMatchObject({ kind: 'auth' })
+    expect(connection.status()).toMatchObject({
+      state: 'disconnected',
+      lastError: 'WebSocket rejected: Invalid API key'
+    })
+  })
+
+  it('records unexpected drops but not manual disconnects', async () => {
+    const { connection, sockets } = setup()
+    const connected = connection.connect()
+    await tick()
+    sockets[0].receive({ type: 'connected', clientId: 'c-1' })
+    await connected
+    sockets[0].emit('close', 1006, Buffer.from(''))
+    expect(connection.status().lastError).toBe('WebSocket closed with code 1006')
+
+    const again = connection.connect()
+    await tick()
+    sockets[1].receive({ type: 'connected', clientId: 'c-1' })
+    await again
+    expect(connection.disconnect()).toMatchObject({ state: 'disconnected', lastError: undefined })
+    expect(sockets[1].close).toHaveBeenCalled()
+  })
+
+  it('backs off exponentially with jitter up to a cap', () => {
+    expect(reconnectDelay(1, () => 0)).toBe(500)
+    expect(reconnectDelay(1, () => 1)).toBe(1_000)
+    expect(reconnectDelay(3, () => 1)).toBe(4_000)
+    expect(reconnectDelay(20, () => 1)).toBe(30_000)
+  })
+
+  it('reconnects after a drop and restores subscriptions', async () => {
+    vi.useFakeTimers()
+    try {
+      const { connection, sockets } = setup()
+      connection.subscribe('notification')
+      const connected = connection.connect()
+      await vi.advanceTimersByTimeAsync(0)
+      sockets[0].receive({ type: 'connected', clientId: 'c-1' })
+      await connected
+      expect(sockets[0].send).toHaveBeenCalledWith('{"type":"register","eventType":"notification"}')
+
+      sockets[0].emit('close', 1012, Buffer.from('Service restart'))
+      expect(connection.status()).toMatchObject({
+        state: 'reconnecting',
+        attempt: 1,
+        nextRetryAt: 1_500
+      })
+      await vi.advanceTimersByTimeAsync(500)
+      sockets[1].receive({ type: 'connected', clientId: 'c-1' })
+      expect(connection.status()).toMatchObject({ state: 'connected', attempt: 0 })
+      expect(sockets[1].send).toHaveBeenCalledWith('{"type":"register","eventType":"notification"}')
+    } finally {
+      vi.useRealTimers()
+    }
+  })
+
+  it('keeps subscriptions across disconnects and rejects blank topics', async () => {
+    const { connection, sockets } = setup()
+    expect(connection.subscribe(' document:updated ')).toEqual(['document:updated'])
+    expect(() => connection.subscribe('  ')).toThrow(/non-empty/)
+
+    const connected = connection.connect()
+    await tick()
+    sockets[0].receive({ type: 'connected', clientId: 'c-1' })
+    await connected
+    expect(connection.subscribe('notification')).toEqual(['document:updated', 'notification'])
+    expect(connection.unsubscribe('document:updated')).toEqual(['notification'])
+    expect(sockets[0].send.mock.calls.map(([data]) => JSON.parse(data).type)).toEqual([
+      'register',
+      'register',
+      'unregister'
+    ])
+
+    connection.disconnect()
+    const reconnected = connection.connect()
+    await tick()
+    sockets[1].receive({ type: 'connected', clientId: 'c-1' })
+    await reconnected
+    expect(sockets[1].send).toHaveBeenCalledTimes(1)
+    expect(sockets[1].send).toHaveBeenCalledWith('{"type":"register","eventType":"notification"}')
+  })
+
+  it('streams binary frames in chunks and dispatches incoming ones', async () => {
+    const { connection, sockets } = setup()
+    expect(() => connection.sendBinary(1, Buffer.alloc(3))).toThrow(/not connected/)
+    const connected = connection.connect()
+    await tick()
+    sockets[0].receive({ type: 'connected', clientId: 'c-1' })
+    await connected
+
+    expect(connection.sendBinary(5, Buffer.from('abcdefghij'), 4)).toBe(3)
+    const sent = sockets[0].send.mock.calls.map(([data]) => decodeBinaryFrame(data))
+    expect(sent.map((f) => [f?.channel, f?.index, f?.last, f?.data.toString()])).toEqual([
+      [5, 0, false, 'abcd'],
+      [5, 1, false, 'efgh'],
+      [5, 2, true, 'ij']
+    ])
+
+    const received = vi.fn()
+    const messages = vi.fn()
+    connection.onBinary(received)
+    connection.onMessage(messages)
+    const frame = { channel: 9, index: 0, last: true, data: Buffer.from('png') }
+    sockets[0].emit('message', encodeBinaryFrame(frame), true)
+    sockets[0].emit('message', Buffer.from([1, 2]), true)
+    expect(received).toHaveBeenCalledTimes(1)
+    expect(received.mock.calls[0][0]).toMatchObject({ channel: 9, last: true })
+    expect(messages).not.toHaveBeenCalled()
+  })
+
+  it('matches rpc responses by id and fails pending requests on drop', async () => {
+    const { connection, sockets } = setup()
+    await expect(connection.request('GET', '/auth/clients')).rejects.toThrow(/not connected/)
+    const connected = connection.connect()
+    await tick()
+    sockets[0].receive({ type: 'connected', clientId: 'c-1' })
+    await connected
+    expect(connection.servesBaseUrl('http://127.0.0.1:4127/')).toBe(true)
+    expect(connection.servesBaseUrl('http://127.0.0.1:4128')).toBe(false)
+
+    const pending = connection.request('POST', '/auth/pairing', { scopes: ['default'] })
+    const request = JSON.parse(sockets[0].send.mock.calls.at(-1)?.[0])
+    expect(request).toMatchObject({ type: 'request', method: 'POST', path: '/auth/pairing' })
+    sockets[0].receive({ type: 'response', id: request.id, status: 201, body: { code: 'x' } })
+    const response = await pending
+    expect(response.status).toBe(201)
+    await expect(response.json()).resolves.toEqual({ code: 'x' })
+
+    const dropped = connection.request('GET', '/auth/clients')
+    sockets[0].emit('close', 1006, Buffer.from(''))
+    await expect(dropped).rejects.toThrow(/Connection lost/)
+  })
+
+  it('enables flow control and returns channel credit after processing', async () => {
+    const { connection, sockets } = setup()
+    const connected = connection.connect()
+    await tick()
+    sockets[0].receive({ type: 'connected', clientId: 'c-1', channels: ['control', 'data-sync'] })
+    await connected
+    const sent = () => sockets[0].send.mock.calls.map(([data]) => JSON.parse(data))
+    expect(sent()).toEqual([{ type: 'flow_control', window: 1024 * 1024 }])
+
+    const sync = { type: 'event', eventType: 'document:updated', payload: 'x'.repeat(300 * 1024) }
+    sockets[0].receive({ type: 'event', eventType: 'notification', channel: 'notifications' })
+    sockets[0].receive({ ...sync, channel: 'data-sync' })
+    expect(sent()).toHaveLength(1)
+    sockets[0].receive({ ...sync, channel: 'data-sync' })
+    expect(sent()[1]).toMatchObject({ type: 'channel_window', channel: 'data-sync' })
+    expect(sent()[1].credit).toBeGreaterThan(600 * 1024)
+  })
+
+  it('negotiates compression per config and reconnects when it changes', async () => {
+    const { connection, sockets } = setup()
+    state.config.network.realtime_compression = true
+    const connected = connection.connect()
+    await tick()
+    sockets[0].receive({ type: 'connected', clientId: 'c-1' })
+    await connected
+    expect(sockets[0].options).toEqual({ compress: true, verifyTls: true })
+
+    state.config.network.realtime_compression = false
+    connection.reconfigure(state.config as unknown as PrizmConfig)
+    await tick()
+    expect(sockets[0].close).toHaveBeenCalled()
+    expect(sockets[1].options).toEqual({ compress: false, verifyTls: true })
+  })
+
+  it('skips certificate checks when server.verify_tls is off', async () => {
+    const { connection, sockets } = setup()
+    state.config.server = { host: 'prizm.local', port: 443, scheme: 'https', verify_tls: true }
+    const connected = connection.connect()
+    await tick()
+    sockets[0].receive({ type: 'connected', clientId: 'c-1' })
+    await connected
+    expect(sockets[0].options?.verifyTls).toBe(true)
+
+    state.config.server = { ...state.config.server, verify_tls: false }
+    connection.reconfigure(state.config as unknown as PrizmConfig)
+    await tick()
+    expect(sockets[0].close).toHaveBeenCalled()
+    expect(sockets[1].url).toMatch(/^wss:\/\/prizm\.local:443\//)
+    expect(sockets[1].options?.verifyTls).toBe(false)
+  })
+
+  it('resumes from the saved cursor and counts down the replay', async () => {
+    const url = 'ws://127.0.0.1:4127/ws'
+    const { connection, sockets, cursorStore } = setup({ url, streamId: 's-1', lastEventId: 3 })
+    connection.subscribe('notification')
+    const connected = connection.connect()
+    await tick()
+    sockets[0].receive({ type: 'connected', clientId: 'c-1', streamId: 's-1', lastEventId: 5 })
+    await connected
+    expect(sockets[0].send.mock.calls.map(([data]) => JSON.parse(data))).toEqual([
+      { type: 'register', eventType: 'notification' },
+      { type: 'resume', lastEventId: 3 }
+    ])
+
+    sockets[0].receive({ type: 'replay', count: 2, untilId: 5, truncated: false })
+    expect(connection.getMissedEventCount()).toBe(2)
+    expect(connection.status().missedEvents).toBe(2)
+    sockets[0].receive({ type: 'event', id: 4, eventType: 'notification', payload: {} })
+    sockets[0].receive({ type: 'event', id: 5, eventType: 'notification', payload: {} })
+    expect(connection.getMissedEventCount()).toBe(0)
+    expect(connection.status().missedEvents).toBe(0)
+    expect(cursorStore.cursor).toEqual({ url, streamId: 's-1', lastEventId: 5 })
+  })
+
+  it('starts a fresh cursor when the server restarted', async () => {
+    const url = 'ws://127.0.0.1:4127/ws'
+    const { connection, sockets, cursorStore } = setup({ url, streamId: 'old', lastEventId: 40 })
+    const connected = connection.connect()
+    await tick()
+    sockets[0].receive({ type: 'connected', clientId: 'c-1', streamId: 'new', lastEventId: 2 })
+    await connected
+    expect(sockets[0].send).not.toHaveBeenCalled()
+    expect(cursorStore.cursor).toEqual({ url, streamId: 'new', lastEventId: 2 })
+  })
+
+  it('gives up after the capped number of attempts', async () => {
+    vi.useFakeTimers()
+    try {
+      const { connection, sockets } = setup()
+      void connection.connect().catch(() => undefined)
+      await vi.advanceTimersByTimeAsync(0)
+      for (let attempt = 1; attempt <= MAX_RECONNECT_ATTEMPTS; attempt++) {
+        sockets[attempt - 1].emit('close', 1006, Buffer.from(''))
+        await vi.advanceTimersByTimeAsync(reconnectDelay(attempt, () => 0))
+      }
+      sockets[MAX_RECONNECT_ATTEMPTS].emit('close', 1006, Buffer.from(''))
+      expect(sockets).toHaveLength(MAX_RECONNECT_ATTEMPTS + 1)
+      expect(connection.status()).toMatchObject({ state: 'disconnected', attempt: 0 })
+      await vi.advanceTimersByTimeAsync(60_000)
+      expect(sockets).toHaveLength(MAX_RECONNECT_ATTEMPTS + 1)
+    } finally {
+      vi.useRealTimers()
+    }
+  })
+
+  it('drops a connection that stops answering heartbeats', async () => {
+    vi.useFakeTimers()
+    try {
+      state.config.network.heartbeat = { interval_ms: 1_000, max_missed: 2 }
+      const { connection, sockets } = setup()
+      const connected = connection.connect()
+      await vi.advanceTimersByTimeAsync(0)
+      sockets[0].receive({ type: 'connected', clientId: 'c-1' })
+      await connected
+
+      await vi.advanceTimersByTimeAsync(2_000)
+      sockets[0].emit('pong')
+      await vi.advanceTimersByTimeAsync(2_000)
+      expect(connection.status().state).toBe('connected')
+      expect(sockets[0].ping).toHaveBeenCalledTimes(4)
+
+      await vi.advanceTimersByTimeAsync(1_000)
+      expect(sockets[0].terminate).toHaveBeenCalled()
+      expect(connection.status()).toMatchObject({
+        state: 'reconnecting',
+        lastError: 'Server stopped answering heartbeats'
+      })
+    } finally {
+      vi.useRealTimers()
+    }
+  })
+
+  it('refuses to connect without an api key', async () => {
+    state.config.api_key = ''
+    const { connection, sockets } = setup()
+    await expect(connection.connect()).rejects.toMatchObject({ kind: 'config' })
+    expect(sockets).toHaveLength(0)
+  })
+})
@@ -0,0 +1,232 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+import * as fs from 'fs'
+import * as os from 'os'
+import * as path from 'path'
+
+interface TestConfig {
+  server: { host: string; port: number }
+  client: { name: string; requested_scopes: string[]; granted_scopes: string[] }
+  api_key: string
+}
+
+const { state, registerMock, verifyMock, pollMock, updateMock, sendMock, notifyMock } = vi.hoisted(
+  () => ({
+    state: { configDir: '', config: {} as TestConfig },
+    registerMock: vi.fn(),
+    verifyMock: vi.fn(),
+    pollMock: vi.fn(),
+    updateMock: vi.fn(),
+    sendMock: vi.fn(),
+    notifyMock: vi.fn()
+  })
+)
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  getConfigPath: () => ({ configDir: state.configDir }),
+  updateConfig: async (mutate: (config: TestConfig) => void) => {
+    updateMock()
+    const next = JSON.parse(JSON.stringify(state.config))
+    mutate(next)
+    state.config = next
+    return next
+  },
+  sharedState: {
+    mainWindow: { isDestroyed: () => false, webContents: { send: sendMock } }
+  }
+}))
+
+vi.mock('../httpClient', async (importOriginal) => ({
+  ...(await importOriginal<typeof import('../httpClient')>()),
+  sleep: async () => {}
+}))
+
+vi.mock('../serverHealth', () => ({
+  probeHealth: async () => ({ status: 'ok' })
+}))
+
+vi.mock('../profiles', () => ({
+  upsertActiveProfile: vi.fn()
+}))
+
+vi.mock('../clientCapabilities', () => ({
+  detectCapabilities: () => ({ platform: 'test', appVersion: '0.0.0', features: [] })
+}))
+
+vi.mock('../serverNotifications', () => ({
+  showLocalNotification: notifyMock
+}))
+
+vi.mock('../prizmApi', async (importOriginal) => ({
+  ...(await importOriginal<typeof import('../prizmApi')>()),
+  PrizmApi: class {
+    register = registerMock
+    verifyApiKey = verifyMock
+    getRegistrationRequest = pollMock
+  }
+}))
+
+import { PrizmError } from '../errors'
+import { getPendingRegistration, resumeRegistration, startRegistration } from '../registration'
+
+const request = {
+  serverUrl: 'http://127.0.0.1:4127',
+  name: 'desktop',
+  requestedScopes: ['default']
+}
+
+function pendingFile(): string {
+  return path.join(state.configDir, 'pending-registration.json')
+}
+
+describe('registration', () => {
+  beforeEach(() => {
+    state.configDir = fs.mkdtempSync(path.join(os.tmpdir(), 'prizm-register-'))
+    state.config = {
+      server: { host: '', port: 0 },
+      client: { name: '', requested_scopes: [], granted_scopes: [] },
+      api_key: ''
+    }
+    registerMock.mockReset()
+    verifyMock.mockReset()
+    pollMock.mockReset()
+    updateMock.mockReset()
+    sendMock.mockReset()
+    notifyMock.mockReset()
+    registerMock.mockResolvedValue({ clientId: 'c1', apiKey: 'k1', grantedScopes: ['default'] })
+    verifyMock.mockResolvedValue(true)
+  })
+
+  it('registers, verifies and saves in one go', async () => {
+    await expect(startRegistration(request)).resolves.toEqual({
+      clientId: 'c1',
+      apiKey: 'k1',
+      grantedScopes: ['default']
+    })
+    expect(verifyMock).toHaveBeenCalledWith('k1', undefined)
+    expect(state.config.api_key).toBe('k1')
+    expect(state.config.client.name).toBe('c1')
+    expect(state.config.client.registration_name).toBe('desktop')
+    expect(state.config.server).toMatchObject({ host: '127.0.0.1', port: 4127 })
+    expect(fs.existsSync(pendingFile())).toBe(false)
+    await expect(resumeRegistration()).resolves.toBeNull()
+  })
+
+  it('announces completion with an event and a system notification', async () => {
+    await startRegistration(request)
+    expect(sendMock).toHaveBeenCalledWith('registration://complete', {
+      clientId: 'c1',
+      serverUrl: 'http://127.0.0.1:4127',
+      name: 'desktop'
+    })
+    expect(notifyMock).toHaveBeenCalledWith(
+      expect.objectContaining({ category: 'connection', body: expect.stringContaining('c1') })
+    )
+  })
+
+  it('resumes after the server created the client without registering again', async () => {
+    verifyMock.mockRejectedValueOnce(new Error('connection reset'))
+    await expect(startRegistration(request)).rejects.toThrow('connection reset')
+    await expect(getPendingRegistration()).resolves.toMatchObject({
+      step: 'created',
+      clientId: 'c1'
+    })
+    // 进度文件中不保存明文 Key
+    expect(fs.readFileSync(pendingFile(), 'utf-8')).not.toContain('"k1"')
+    expect(state.config.api_key).toBe('')
+
+    await expect(resumeRegistration()).resolves.toMatchObject({ apiKey: 'k1' })
+    expect(registerMock).toHaveBeenCalledTimes(1)
+    expect(state.config.api_key).toBe('k1')
+    expect(fs.existsSync(pendingFile())).toBe(false)
+  })
+
+  it('resumes a verified registration by saving the config only', async () => {
+    updateMock.mockImplementationOnce(() => {
+      throw new Error('disk full')
+    })
+    await expect(startRegistration(request)).rejects.toThrow('disk full')
+    await expect(getPendingRegistration()).resolves.toMatchObject({ step: 'verified' })
+
+    await resumeRegistration()
+    expect(registerMock).toHaveBeenCalledTimes(1)
+    expect(verifyMock).toHaveBeenCalledTimes(1)
+    expect(state.config.api_key).toBe('k1')
+  })
+
+  it('starts over when the created key is rejected', async () => {
+    verifyMock.mockResolvedValueOnce(false)
+    await expect(startRegistration(request)).rejects.toMatchObject({ kind: 'auth' })
+    await expect(getPendingRegistration()).resolves.toMatchObject({ step: 'requested' })
+
+    registerMock.mockResolvedValueOnce({ clientId: 'c1', apiKey: 'k2' })
+    await expect(resumeRegistration()).resolves.toMatchObject({ apiKey: 'k2' })
+    expect(registerMock).toHaveBeenCalledTimes(2)
+    expect(state.config.api_key).toBe('k2')
+  })
+
+  it('waits for approval when the server requires it', async () => {
+    const expiresAt = Date.now() + 60_000
+    registerMock.mockResolvedValue({ requestId: 'req-1', status: 'pending', expiresAt })
+    pollMock
+      .mockResolvedValueOnce({ requestId: 'req-1', status: 'pending', expiresAt })
+      .mockResolvedValueOnce({
+        requestId: 'req-1',
+        status: 'approved',
+        expiresAt,
+        result: { clientId: 'c1', apiKey: 'k1', grantedScopes: ['default'] }
+      })
+
+    await expect(startRegistration(request)).resolves.toMatchObject({ apiKey: 'k1' })
+    expect(pollMock).toHaveBeenCalledWith('req-1', undefined)
+    const statuses = sendMock.mock.calls
+      .filter(([channel]) => channel === 'auth://registration-status')
+      .map(([, event]) => event.status)
+    expect(statuses).toEqual(['pending', 'approved'])
+    expect(state.config.api_key).toBe('k1')
+  })
+
+  it('resumes waiting for approval without registering again', async () => {
+    registerMock.mockResolvedValue({ requestId: 'req-1', status: 'pending' })
+    pollMock.mockRejectedValueOnce(new Error('connection reset'))
+    await expect(startRegistration(request)).rejects.toThrow('connection reset')
+    await expect(getPendingRegistration()).resolves.toMatchObject({ step: 'awaiting_approval' })
+    // 申请 ID 可用于领取 Key，不以明文落盘
+    expect(fs.readFileSync(pendingFile(), 'utf-8')).not.toContain('req-1')
+
+    pollMock.mockResolvedValueOnce({
+      requestId: 'req-1',
+      status: 'approved',
+      result: { clientId: 'c1', apiKey: 'k1' }
+    })
+    await expect(resumeRegistration()).resolves.toMatchObject({ apiKey: 'k1' })
+    expect(registerMock).toHaveBeenCalledTimes(1)
+  })
+
+  it('starts over when the registration is denied', async () => {
+    registerMock.mockResolvedValue({ requestId: 'req-1', status: 'pending' })
+    pollMock.mockResolvedValueOnce({ requestId: 'req-1', status: 'denied' })
+    await expect(startRegistration(request)).rejects.toMatchObject({ kind: 'auth' })
+    expect(sendMock).toHaveBeenLastCalledWith(
+      'auth://registration-status',
+      expect.objectContaining({ status: 'denied' })
+    )
+    await expect(getPendingRegistration()).resolves.toMatchObject({ step: 'requested' })
+  })
+
+  it('treats a vanished approval request as expired', async () => {
+    registerMock.mockResolvedValue({ requestId: 'req-1', status: 'pending' })
+    pollMock.mockRejectedValueOnce(PrizmError.badStatus(404, ''))
+    await expect(startRegistration(request)).rejects.toThrow('expired')
+  })
+
+  it('rejects invalid server urls before recording anything', async () => {
+    await expect(startRegistration({ ...request, serverUrl: '' })).rejects.toMatchObject({
+      kind: 'invalid_input'
+    })
+    expect(fs.existsSync(pendingFile())).toBe(false)
+  })
+})
@@ -0,0 +1,51 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+import type { PrizmConfig } from '../config'
+
+vi.mock('electron', () => {
+  const m = { session: { defaultSession: null } }
+  return { ...m, default: m }
+})
+
+import { applyRendererAuth, withRendererCredentials } from '../rendererAuth'
+
+function configWith(apiKey: string, host = '127.0.0.1'): PrizmConfig {
+  return { api_key: apiKey, server: { host, port: 4127 } } as unknown as PrizmConfig
+}
+
+beforeEach(() => {
+  applyRendererAuth(configWith('secret'))
+})
+
+describe('withRendererCredentials', () => {
+  it('adds a bearer token to renderer requests for the server', () => {
+    expect(withRendererCredentials('http://127.0.0.1:4127/api/v1/notes', {}, 1)).toEqual({
+      Authorization: 'Bearer secret'
+    })
+    expect(withRendererCredentials('ws://127.0.0.1:4127/ws?apiKey=', {}, 1)).toEqual({
+      Authorization: 'Bearer secret'
+    })
+  })
+
+  it('leaves requests for other origins untouched', () => {
+    expect(withRendererCredentials('http://127.0.0.1:8080/', {}, 1)).toEqual({})
+    expect(withRendererCredentials('https://example.com/', {}, 1)).toEqual({})
+    expect(withRendererCredentials('not a url', {}, 1)).toEqual({})
+  })
+
+  it('skips main process requests and requests that carry credentials', () => {
+    expect(withRendererCredentials('http://127.0.0.1:4127/health', {}, undefined)).toEqual({})
+    const headers = { 'X-Prizm-Api-Key': 'other' }
+    expect(withRendererCredentials('http://127.0.0.1:4127/health', headers, 1)).toBe(headers)
+  })
+
+  it('follows config changes and stops once the key is cleared', () => {
+    applyRendererAuth(configWith('next', 'prizm.local'))
+    expect(withRendererCredentials('http://prizm.local:4127/', {}, 1)).toEqual({
+      Authorization: 'Bearer next'
+    })
+    expect(withRendererCredentials('http://127.0.0.1:4127/', {}, 1)).toEqual({})
+
+    applyRendererAuth(configWith(''))
+    expect(withRendererCredentials('http://127.0.0.1:4127/', {}, 1)).toEqual({})
+  })
+})
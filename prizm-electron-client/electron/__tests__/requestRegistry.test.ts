@@ -0,0 +1,38 @@
+import { describe, it, expect } from 'vitest'
+import { toPrizmError } from '../errors'
+import { cancelRequest, runCancellable } from '../requestRegistry'
+
+function waitForAbort(signal?: AbortSignal): Promise<never> {
+  return new Promise((_resolve, reject) => {
+    signal?.addEventListener('abort', () => reject(signal.reason), { once: true })
+  })
+}
+
+describe('requestRegistry', () => {
+  it('aborts the registered operation with a cancelled error', async () => {
+    const pending = runCancellable('reg-1', waitForAbort)
+    expect(cancelRequest('reg-1')).toBe(true)
+    const err = await pending.catch((e) => e)
+    expect(toPrizmError(err).kind).toBe('cancelled')
+    expect(cancelRequest('reg-1')).toBe(false)
+  })
+
+  it('releases the id once the operation settles', async () => {
+    await expect(runCancellable('reg-2', async () => 42)).resolves.toBe(42)
+    expect(cancelRequest('reg-2')).toBe(false)
+    await expect(runCancellable('reg-2', async () => 'again')).resolves.toBe('again')
+  })
+
+  it('rejects duplicate ids while the first is in flight', async () => {
+    const first = runCancellable('reg-3', waitForAbort)
+    await expect(runCancellable('reg-3', async () => 1)).rejects.toMatchObject({
+      kind: 'invalid_input'
+    })
+    cancelRequest('reg-3')
+    await first.catch(() => undefined)
+  })
+
+  it('runs without registration when no id is given', async () => {
+    await expect(runCancellable(undefined, async (signal) => signal)).resolves.toBeUndefined()
+  })
+})
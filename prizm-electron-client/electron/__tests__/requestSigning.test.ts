@@ -0,0 +1,58 @@
+import * as crypto from 'crypto'
+import { describe, it, expect } from 'vitest'
+import {
+  NONCE_HEADER,
+  SIGNATURE_HEADER,
+  TIMESTAMP_HEADER,
+  canonicalRequest,
+  deriveSigningSecret,
+  signRequest,
+  signatureHeaders
+} from '../requestSigning'
+
+describe('deriveSigningSecret', () => {
+  it('derives the secret with HKDF instead of using the stored key hash', () => {
+    const expected = Buffer.from(
+      crypto.hkdfSync('sha256', 'prizm_key', 'client-1', 'prizm-request-signing', 32)
+    ).toString('hex')
+    expect(deriveSigningSecret('prizm_key', 'client-1')).toBe(expected)
+    expect(expected).not.toBe(crypto.createHash('sha256').update('prizm_key').digest('hex'))
+  })
+})
+
+describe('signRequest', () => {
+  it('uses the signing secret as HMAC key', () => {
+    const req = { method: 'GET', path: '/notes', body: '', timestamp: 1, nonce: 'n' }
+    const secret = deriveSigningSecret('prizm_key', 'client-1')
+    const expected = crypto.createHmac('sha256', secret).update(canonicalRequest(req)).digest('hex')
+    expect(signRequest(secret, req)).toBe(expected)
+  })
+})
+
+describe('signatureHeaders', () => {
+  it('carries timestamp, nonce and a matching signature', () => {
+    const headers = signatureHeaders(
+      'prizm_key',
+      { method: 'POST', path: '/notes', body: '{}' },
+      42
+    )
+    expect(headers[TIMESTAMP_HEADER]).toBe('42')
+    expect(headers[NONCE_HEADER]).toBeTruthy()
+    expect(headers[SIGNATURE_HEADER]).toBe(
+      signRequest('prizm_key', {
+        method: 'POST',
+        path: '/notes',
+        body: '{}',
+        timestamp: 42,
+        nonce: headers[NONCE_HEADER]
+      })
+    )
+  })
+
+  it('uses a fresh nonce for every request', () => {
+    const req = { method: 'GET', path: '/notes', body: '' }
+    expect(signatureHeaders('k', req, 1)[NONCE_HEADER]).not.toBe(
+      signatureHeaders('k', req, 1)[NONCE_HEADER]
+    )
+  })
+})
@@ -0,0 +1,98 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+interface TestConfig {
+  server: { host: string; port: number }
+  client: { name: string; requested_scopes: string[]; granted_scopes: string[] }
+  api_key: string
+}
+
+const { state, requestMock, pollMock, sendMock } = vi.hoisted(() => ({
+  state: { config: {} as TestConfig },
+  requestMock: vi.fn(),
+  pollMock: vi.fn(),
+  sendMock: vi.fn()
+}))
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  loadConfigFromDisk: async () => JSON.parse(JSON.stringify(state.config)),
+  updateConfig: async (mutate: (config: TestConfig) => void) => {
+    const next = JSON.parse(JSON.stringify(state.config))
+    mutate(next)
+    state.config = next
+    return next
+  },
+  sharedState: {
+    mainWindow: { isDestroyed: () => false, webContents: { send: sendMock } }
+  }
+}))
+
+vi.mock('../httpClient', () => ({
+  sleep: async () => {}
+}))
+
+vi.mock('../prizmApi', () => ({
+  PrizmApi: class {
+    requestScopes = requestMock
+    getScopeRequest = pollMock
+  }
+}))
+
+import { PrizmError } from '../errors'
+import { requestAdditionalScopes } from '../scopeElevation'
+
+describe('requestAdditionalScopes', () => {
+  beforeEach(() => {
+    requestMock.mockReset()
+    pollMock.mockReset()
+    sendMock.mockReset()
+    state.config = {
+      server: { host: '127.0.0.1', port: 4127 },
+      client: { name: 'c-1', requested_scopes: ['default'], granted_scopes: ['default', 'online'] },
+      api_key: 'secret'
+    }
+  })
+
+  it('polls until approved and stores the granted scopes', async () => {
+    requestMock.mockResolvedValue({
+      requestId: 'r-1',
+      status: 'pending',
+      expiresAt: Date.now() + 60_000
+    })
+    pollMock
+      .mockResolvedValueOnce({ requestId: 'r-1', status: 'pending' })
+      .mockResolvedValueOnce({
+        requestId: 'r-1',
+        status: 'approved',
+        grantedScopes: ['default', 'online', 'notes']
+      })
+
+    await expect(
+      requestAdditionalScopes([' notes ', 'notes'], { requestId: 'ui-1' })
+    ).resolves.toEqual(['default', 'online', 'notes'])
+    expect(requestMock).toHaveBeenCalledWith(['notes'], undefined)
+    expect(pollMock).toHaveBeenCalledTimes(2)
+    expect(state.config.client.granted_scopes).toEqual(['default', 'online', 'notes'])
+    expect(state.config.client.requested_scopes).toEqual(['default', 'notes'])
+    expect(sendMock).toHaveBeenLastCalledWith('auth://scope-request', {
+      requestId: 'ui-1',
+      status: 'approved',
+      scopes: ['notes']
+    })
+  })
+
+  it('fails without touching the config when denied', async () => {
+    requestMock.mockResolvedValue({ requestId: 'r-1', status: 'pending' })
+    pollMock.mockResolvedValue({ requestId: 'r-1', status: 'denied' })
+    await expect(requestAdditionalScopes(['notes'])).rejects.toMatchObject({ kind: 'auth' })
+    expect(state.config.client.granted_scopes).toEqual(['default', 'online'])
+  })
+
+  it('rejects empty requests', async () => {
+    await expect(requestAdditionalScopes(['  '])).rejects.toBeInstanceOf(PrizmError)
+    expect(requestMock).not.toHaveBeenCalled()
+  })
+})
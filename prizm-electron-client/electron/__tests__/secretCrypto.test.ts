@@ -0,0 +1,22 @@
+import { describe, it, expect } from 'vitest'
+import { decryptSecret, encryptSecret, isEncryptedSecret } from '../secretCrypto'
+
+describe('secretCrypto', () => {
+  it('round-trips a secret without exposing plaintext', () => {
+    const encrypted = encryptSecret('prizm_secret_key')
+    expect(isEncryptedSecret(encrypted)).toBe(true)
+    expect(encrypted).not.toContain('prizm_secret_key')
+    expect(decryptSecret(encrypted)).toBe('prizm_secret_key')
+  })
+
+  it('passes through empty and plaintext values', () => {
+    expect(encryptSecret('')).toBe('')
+    expect(decryptSecret('plain')).toBe('plain')
+  })
+
+  it('rejects tampered ciphertext', () => {
+    const encrypted = encryptSecret('value')
+    const tampered = encrypted.slice(0, -4) + (encrypted.endsWith('AAAA') ? 'BBBB' : 'AAAA')
+    expect(() => decryptSecret(tampered)).toThrow()
+  })
+})
@@ -0,0 +1,59 @@
+import { describe, it, expect, vi } from 'vitest'
+
+const { sendMock } = vi.hoisted(() => ({ sendMock: vi.fn() }))
+
+vi.mock('../config', () => ({
+  sharedState: { mainWindow: { isDestroyed: () => false, webContents: { send: sendMock } } }
+}))
+
+import type { MessageListener, RealtimeConnection } from '../realtime'
+import { bridgeServerEvents, toServerEvent } from '../serverEvents'
+
+function event(eventType: string) {
+  return { type: 'event', eventType, payload: { id: 'x' }, scope: 'default', timestamp: 5 }
+}
+
+describe('toServerEvent', () => {
+  it('classifies notifications, data changes and status updates', () => {
+    expect(toServerEvent(event('notification'))).toMatchObject({ kind: 'notification' })
+    expect(toServerEvent(event('document:memory.updated'))).toMatchObject({
+      kind: 'data_changed',
+      entity: 'document',
+      action: 'memory.updated'
+    })
+    expect(toServerEvent(event('clipboard:itemAdded'))).toMatchObject({
+      kind: 'data_changed',
+      entity: 'clipboard'
+    })
+    expect(toServerEvent(event('task:completed'))).toMatchObject({ kind: 'status' })
+    expect(toServerEvent(event('agent:session.chatStatusChanged'))).toMatchObject({
+      kind: 'status'
+    })
+  })
+
+  it('ignores protocol messages', () => {
+    expect(toServerEvent({ type: 'connected', clientId: 'c-1' })).toBeNull()
+    expect(toServerEvent({ type: 'event' })).toBeNull()
+  })
+})
+
+describe('bridgeServerEvents', () => {
+  it('re-emits events on their namespaced channel', () => {
+    let listener: MessageListener = () => undefined
+    const connection = {
+      onMessage: (l: MessageListener) => {
+        listener = l
+        return () => undefined
+      }
+    } as unknown as RealtimeConnection
+    bridgeServerEvents(connection)
+
+    listener(event('todo_item:deleted'))
+    listener({ type: 'registered', eventType: 'notification' })
+    expect(sendMock).toHaveBeenCalledTimes(1)
+    expect(sendMock).toHaveBeenCalledWith(
+      'server://data-changed',
+      expect.objectContaining({ eventType: 'todo_item:deleted', scope: 'default', timestamp: 5 })
+    )
+  })
+})
@@ -0,0 +1,48 @@
+import { describe, it, expect, vi } from 'vitest'
+
+vi.mock('electron', () => {
+  const electronMock = {
+    app: { getPath: vi.fn().mockReturnValue('/mock/app/data') },
+    session: { fromPartition: vi.fn() }
+  }
+  return {
+    ...electronMock,
+    default: electronMock
+  }
+})
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+import { createDefaultConfig } from '../config'
+import { healthTargets, mapWithConcurrency } from '../serverHealth'
+
+describe('mapWithConcurrency', () => {
+  it('bounds concurrency and keeps result order', async () => {
+    let running = 0
+    let peak = 0
+    const results = await mapWithConcurrency([30, 10, 20, 5, 15], 2, async (ms) => {
+      running++
+      peak = Math.max(peak, running)
+      await new Promise((resolve) => setTimeout(resolve, ms))
+      running--
+      return ms * 2
+    })
+    expect(results).toEqual([60, 20, 40, 10, 30])
+    expect(peak).toBe(2)
+  })
+})
+
+describe('healthTargets', () => {
+  it('lists profiles and the unsaved current server once', () => {
+    const config = createDefaultConfig()
+    config.profiles = {
+      home: { server: { host: '10.0.0.2', port: 4127 }, api_key: '', requested_scopes: [] }
+    }
+    expect(healthTargets(config)).toEqual([
+      { name: null, url: 'http://127.0.0.1:4127' },
+      { name: 'home', url: 'http://10.0.0.2:4127' }
+    ])
+  })
+})
@@ -0,0 +1,237 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+interface ShownNotification {
+  title: string
+  body: string
+  silent?: boolean
+  actions?: Array<{ text: string }>
+  close: () => void
+  emit: (event: string, ...args: unknown[]) => void
+}
+
+const { shown, sharedState, record, resolveMock, sent, playMock } = vi.hoisted(() => ({
+  record: vi.fn(),
+  playMock: vi.fn(),
+  resolveMock: vi.fn(async () => undefined),
+  sent: [] as Array<[string, unknown]>,
+  shown: [] as ShownNotification[],
+  sharedState: {
+    showNotification: true,
+    notificationsPaused: false,
+    notificationPreferences: {
+      categories: { connection: true, server_alerts: true, sync: true, updates: true },
+      min_severity: 'info',
+      quiet_hours: { enabled: false, start: '22:00', end: '08:00', weekends: false },
+      sounds: {
+        connection: 'default',
+        server_alerts: 'default',
+        sync: 'default',
+        updates: 'default'
+      }
+    },
+    mainWindow: {
+      isDestroyed: () => false,
+      isMinimized: () => false,
+      show: () => undefined,
+      focus: () => undefined,
+      webContents: { send: (channel: string, data: unknown) => sent.push([channel, data]) }
+    }
+  }
+}))
+
+vi.mock('electron', () => {
+  class Notification {
+    static isSupported = () => true
+    title: string
+    body: string
+    actions?: Array<{ text: string }>
+    silent?: boolean
+    close = vi.fn()
+    private handlers = new Map<string, (...args: unknown[]) => void>()
+    constructor(options: {
+      title: string
+      body: string
+      silent?: boolean
+      actions?: Array<{ text: string }>
+    }) {
+      this.title = options.title
+      this.body = options.body
+      this.silent = options.silent
+      this.actions = options.actions
+    }
+    on(event: string, handler: (...args: unknown[]) => void) {
+      this.handlers.set(event, handler)
+      return this
+    }
+    emit(event: string, ...args: unknown[]) {
+      this.handlers.get(event)?.(...args)
+    }
+    show() {
+      shown.push(this)
+    }
+  }
+  return { Notification, default: { Notification } }
+})
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  sharedState,
+  NOTIFICATION_CATEGORIES: ['connection', 'server_alerts', 'sync', 'updates'],
+  NOTIFICATION_SEVERITIES: ['info', 'warning', 'error']
+}))
+vi.mock('../notificationHistory', () => ({ notificationHistory: { record } }))
+vi.mock('../notificationSounds', async (importOriginal) => ({
+  ...(await importOriginal<typeof import('../notificationSounds')>()),
+  playNotificationSound: playMock
+}))
+vi.mock('../devices', () => ({ resolveRegistrationRequest: resolveMock }))
+
+import type { MessageListener, RealtimeConnection } from '../realtime'
+import { toServerEvent } from '../serverEvents'
+import type { ServerEvent } from '../serverEvents'
+import {
+  bridgeServerNotifications,
+  setNotificationsPaused,
+  toNativeNotification
+} from '../serverNotifications'
+
+function notification(payload: object) {
+  return { type: 'event', eventType: 'notification', payload, timestamp: 5 }
+}
+
+function serverEvent(payload: object): ServerEvent {
+  return toServerEvent(notification(payload)) as ServerEvent
+}
+
+function fakeConnection() {
+  let listener: MessageListener = () => undefined
+  const connection = {
+    onMessage: (l: MessageListener) => {
+      listener = l
+      return () => undefined
+    },
+    status: () => ({ clientId: 'me' })
+  } as unknown as RealtimeConnection
+  return { connection, emit: (message: object) => listener(message as never) }
+}
+
+describe('toNativeNotification', () => {
+  it('maps title and body with a fallback title', () => {
+    const mapped = toNativeNotification(serverEvent({ title: ' 构建完成 ', body: 'ok' }), 'me')
+    expect(mapped).toMatchObject({ title: '构建完成', body: 'ok', updateId: undefined })
+    expect(toNativeNotification(serverEvent({}), 'me')).toMatchObject({ title: '通知', body: '' })
+  })
+
+  it('skips notifications caused by this client and non-notification events', () => {
+    const own = serverEvent({ title: 't', sourceClientId: 'me' })
+    expect(toNativeNotification(own, 'me')).toBeNull()
+    const status = toServerEvent({ type: 'event', eventType: 'task:completed', payload: {} })
+    expect(toNativeNotification(status as ServerEvent, 'me')).toBeNull()
+  })
+
+  it('routes connection and registration request notifications to settings', () => {
+    const offline = toNativeNotification(serverEvent({ title: 'x', category: 'connection' }), 'me')
+    expect(offline?.route).toEqual({ page: 'settings', section: 'connection' })
+    const pending = toNativeNotification(
+      serverEvent({ title: 'x', action: { type: 'registration_request', requestId: 'req-1' } }),
+      'me'
+    )
+    expect(pending).toMatchObject({
+      registrationRequestId: 'req-1',
+      route: { page: 'settings', section: 'devices' }
+    })
+    expect(toNativeNotification(serverEvent({ title: 'x' }), 'me')?.route).toBeUndefined()
+  })
+})
+
+describe('bridgeServerNotifications', () => {
+  beforeEach(() => {
+    shown.length = 0
+    record.mockClear()
+    playMock.mockClear()
+    sharedState.showNotification = true
+    sharedState.notificationsPaused = false
+  })
+
+  it('respects tray.show_notification', () => {
+    const { connection, emit } = fakeConnection()
+    bridgeServerNotifications(connection)
+    sharedState.showNotification = false
+    emit(notification({ title: 'hidden' }))
+    expect(shown).toHaveLength(0)
+    sharedState.showNotification = true
+    emit(notification({ title: 'visible' }))
+    expect(shown.map((n) => n.title)).toEqual(['visible'])
+    expect(record).toHaveBeenCalledTimes(1)
+    expect(record).toHaveBeenCalledWith(
+      expect.objectContaining({ title: 'visible', source: 'server', eventType: 'notification' })
+    )
+  })
+
+  it('records but does not show notifications while paused from the tray', () => {
+    const { connection, emit } = fakeConnection()
+    bridgeServerNotifications(connection)
+    setNotificationsPaused(true)
+    emit(notification({ title: 'paused' }))
+    expect(shown).toHaveLength(0)
+    expect(record).toHaveBeenLastCalledWith(
+      expect.objectContaining({ title: 'paused', silent: true })
+    )
+    setNotificationsPaused(false)
+    emit(notification({ title: 'resumed' }))
+    expect(shown.map((n) => n.title)).toEqual(['resumed'])
+    expect(record).toHaveBeenLastCalledWith(expect.objectContaining({ silent: false }))
+  })
+
+  it('applies per-category and severity preferences', () => {
+    const { connection, emit } = fakeConnection()
+    bridgeServerNotifications(connection)
+    sharedState.notificationPreferences.min_severity = 'warning'
+    emit(notification({ title: 'info' }))
+    emit(notification({ title: 'disk full', severity: 'error' }))
+    sharedState.notificationPreferences.categories.server_alerts = false
+    emit(notification({ title: 'muted', severity: 'error' }))
+    sharedState.notificationPreferences.categories.server_alerts = true
+    sharedState.notificationPreferences.min_severity = 'info'
+    expect(shown.map((n) => n.title)).toEqual(['disk full'])
+    expect(record).toHaveBeenCalledWith(expect.objectContaining({ severity: 'error' }))
+  })
+
+  it('approves or denies registration requests from the notification buttons', () => {
+    const { connection, emit } = fakeConnection()
+    bridgeServerNotifications(connection)
+    const action = { type: 'registration_request', requestId: 'r' }
+    emit(notification({ title: 'pending', action }))
+    expect(shown[0].actions?.map((a) => a.text)).toEqual(['批准', '拒绝'])
+    shown[0].emit('action', {}, 1)
+    expect(resolveMock).toHaveBeenCalledWith('r', 'deny')
+    sent.length = 0
+    shown[0].emit('click')
+    expect(sent[0]).toEqual(['app://navigate', { page: 'settings', section: 'devices' }])
+  })
+
+  it('plays the configured sound instead of the system sound', () => {
+    const { connection, emit } = fakeConnection()
+    bridgeServerNotifications(connection)
+    sharedState.notificationPreferences.sounds.server_alerts = '/sounds/alarm.wav'
+    emit(notification({ title: 'server down' }))
+    sharedState.notificationPreferences.sounds.server_alerts = 'default'
+    emit(notification({ title: 'normal' }))
+    expect(shown.map((n) => n.silent)).toEqual([true, false])
+    expect(playMock).toHaveBeenCalledTimes(2)
+    expect(playMock).toHaveBeenCalledWith('server_alerts')
+  })
+
+  it('replaces the previous notification with the same updateId', () => {
+    const { connection, emit } = fakeConnection()
+    bridgeServerNotifications(connection)
+    emit(notification({ title: 'todo 1/3', updateId: 'todo_list:a' }))
+    emit(notification({ title: 'todo 2/3', updateId: 'todo_list:a' }))
+    expect(shown).toHaveLength(2)
+    expect(shown[0].close).toHaveBeenCalled()
+    expect(shown[1].close).not.toHaveBeenCalled()
+  })
+})
@@ -0,0 +1,94 @@
+import { describe, it, expect } from 'vitest'
+import {
+  normalizeBasePath,
+  parseServerUrl,
+  serverConfigToUrl,
+  upgradeToTls
+} from '../serverUrl'
+
+describe('serverConfigToUrl', () => {
+  it('builds http and ws urls from the same config', () => {
+    const server = {
+      host: 'prizm.example.com',
+      port: 443,
+      scheme: 'https' as const,
+      base_path: 'api/'
+    }
+    expect(serverConfigToUrl(server)).toBe('https://prizm.example.com:443/api')
+    expect(serverConfigToUrl(server, 'ws')).toBe('wss://prizm.example.com:443/api')
+  })
+
+  it('defaults to http and brackets IPv6 hosts', () => {
+    expect(serverConfigToUrl({ host: '::1', port: 4127 })).toBe('http://[::1]:4127')
+    expect(serverConfigToUrl({ host: 'fe80::1%eth0', port: 4127 })).toBe(
+      'http://[fe80::1%25eth0]:4127'
+    )
+  })
+
+  it('normalizes base paths', () => {
+    expect(normalizeBasePath('/')).toBe('')
+    expect(normalizeBasePath('//prizm//')).toBe('/prizm')
+  })
+})
+
+describe('upgradeToTls', () => {
+  it('upgrades plaintext schemes and keeps host, port and path', () => {
+    expect(upgradeToTls('http://10.0.0.2:4127/health')).toBe('https://10.0.0.2:4127/health')
+    expect(upgradeToTls('ws://[::1]:4127/ws')).toBe('wss://[::1]:4127/ws')
+    expect(upgradeToTls('https://prizm.example.com/health')).toBe(
+      'https://prizm.example.com/health'
+    )
+  })
+
+  it('refuses urls that cannot be upgraded', () => {
+    expect(() => upgradeToTls('ftp://prizm.example.com')).toThrow('require_tls')
+    expect(() => upgradeToTls('not a url')).toThrow('Invalid url')
+  })
+})
+
+describe('parseServerUrl', () => {
+  it('uses the Prizm default port when neither scheme nor port is given', () => {
+    expect(parseServerUrl('192.168.1.5')).toEqual({
+      host: '192.168.1.5',
+      port: 4127,
+      scheme: 'http',
+      base_path: ''
+    })
+  })
+
+  it('uses scheme default ports and keeps the path as base_path', () => {
+    expect(parseServerUrl('https://prizm.example.com/api/')).toEqual({
+      host: 'prizm.example.com',
+      port: 443,
+      scheme: 'https',
+      base_path: '/api'
+    })
+    expect(parseServerUrl('wss://prizm.example.com:8443')).toMatchObject({
+      port: 8443,
+      scheme: 'https'
+    })
+  })
+
+  it('unwraps IPv6 literals', () => {
+    expect(parseServerUrl('http://[::1]:4127')).toMatchObject({ host: '::1', port: 4127 })
+    expect(parseServerUrl('[::1]:5000')).toMatchObject({ host: '::1', port: 5000 })
+    expect(parseServerUrl('::1')).toMatchObject({ host: '::1', port: 4127 })
+  })
+
+  it('keeps IPv6 zone IDs', () => {
+    expect(parseServerUrl('http://[fe80::1%25eth0]:4127/api')).toEqual({
+      host: 'fe80::1%eth0',
+      port: 4127,
+      scheme: 'http',
+      base_path: '/api'
+    })
+    expect(parseServerUrl('fe80::1%eth0')).toMatchObject({ host: 'fe80::1%eth0', port: 4127 })
+  })
+
+  it('rejects credentials, queries and unknown schemes', () => {
+    expect(() => parseServerUrl('http://user:pw@host:4127')).toThrow('credentials')
+    expect(() => parseServerUrl('http://host:4127/?a=1')).toThrow('query')
+    expect(() => parseServerUrl('ftp://host')).toThrow('scheme')
+    expect(() => parseServerUrl('  ')).toThrow('empty')
+  })
+})
@@ -0,0 +1,86 @@
+import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest'
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+vi.mock('../config', () => ({ sharedState: {} }))
+vi.mock('../realtime', () => ({ realtimeConnection: {} }))
+vi.mock('../serverNotifications', () => ({ showLocalNotification: vi.fn() }))
+
+import type { RealtimeConnection, RealtimeState, RealtimeStatus } from '../realtime'
+import { ServerWatchdog, formatDowntime } from '../serverWatchdog'
+
+function status(state: RealtimeState, lastError?: string): RealtimeStatus {
+  return {
+    state,
+    url: 'ws://127.0.0.1:4127/ws',
+    clientId: null,
+    connectedAt: null,
+    attempt: 0,
+    nextRetryAt: null,
+    missedEvents: 0,
+    lastError
+  }
+}
+
+describe('ServerWatchdog', () => {
+  let notify: ReturnType<typeof vi.fn>
+  let watchdog: ServerWatchdog
+
+  beforeEach(() => {
+    vi.useFakeTimers()
+    notify = vi.fn()
+    watchdog = new ServerWatchdog(
+      {} as RealtimeConnection,
+      notify,
+      () => 30_000,
+      () => Date.now()
+    )
+  })
+
+  afterEach(() => {
+    vi.useRealTimers()
+  })
+
+  it('alerts once after the grace period and again on recovery', () => {
+    watchdog.update(status('connected'))
+    watchdog.update(status('reconnecting', 'closed'))
+    vi.advanceTimersByTime(29_000)
+    expect(notify).not.toHaveBeenCalled()
+    vi.advanceTimersByTime(1_000)
+    expect(notify).toHaveBeenCalledTimes(1)
+    expect(notify).toHaveBeenLastCalledWith(
+      expect.objectContaining({ title: '服务器无法连接', category: 'connection', severity: 'error' })
+    )
+
+    // 重连仍在进行，不重复提示
+    watchdog.update(status('connecting'))
+    watchdog.update(status('reconnecting', 'closed'))
+    vi.advanceTimersByTime(60_000)
+    expect(notify).toHaveBeenCalledTimes(1)
+
+    watchdog.update(status('connected'))
+    expect(notify).toHaveBeenCalledTimes(2)
+    expect(notify).toHaveBeenLastCalledWith(
+      expect.objectContaining({ title: '服务器已恢复连接', body: '连接中断了约 2 分钟' })
+    )
+  })
+
+  it('stays quiet for short outages and intentional disconnects', () => {
+    watchdog.update(status('reconnecting', 'closed'))
+    vi.advanceTimersByTime(10_000)
+    watchdog.update(status('connected'))
+    watchdog.update(status('reconnecting', 'closed'))
+    watchdog.update(status('disconnected'))
+    vi.advanceTimersByTime(60_000)
+    expect(notify).not.toHaveBeenCalled()
+  })
+})
+
+describe('formatDowntime', () => {
+  it('uses seconds, minutes or hours', () => {
+    expect(formatDowntime(45_000)).toBe('45 秒')
+    expect(formatDowntime(180_000)).toBe('3 分钟')
+    expect(formatDowntime(2 * 3600_000)).toBe('2 小时')
+  })
+})
@@ -0,0 +1,79 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+const { globalShortcut, registered, updateConfig, config } = vi.hoisted(() => {
+  const registered = new Set<string>()
+  const config = { tray: { global_hotkey: 'CommandOrControl+Shift+P' } }
+  return {
+    registered,
+    config,
+    updateConfig: vi.fn(async (mutate: (c: typeof config) => void) => mutate(config)),
+    globalShortcut: {
+      register: vi.fn((accelerator: string) => {
+        if (accelerator === 'Bogus+') throw new Error('conversion failed')
+        if (accelerator === 'Alt+Space') return false
+        registered.add(accelerator)
+        return true
+      }),
+      unregister: vi.fn((accelerator: string) => registered.delete(accelerator))
+    }
+  }
+})
+
+vi.mock('electron', () => {
+  const m = { globalShortcut, clipboard: {}, screen: {} }
+  return { ...m, default: m }
+})
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn() }
+}))
+vi.mock('../config', () => ({
+  DEFAULT_GLOBAL_HOTKEY: 'CommandOrControl+Shift+P',
+  sharedState: {},
+  updateConfig
+}))
+vi.mock('../windowManager', () => ({
+  createMainWindow: vi.fn(),
+  createQuickPanelWindow: vi.fn(),
+  showMainWindow: vi.fn()
+}))
+
+import {
+  applyGlobalHotkey,
+  getGlobalHotkey,
+  registerGlobalShortcuts,
+  setGlobalHotkey
+} from '../shortcuts'
+
+beforeEach(() => {
+  applyGlobalHotkey('')
+  registerGlobalShortcuts()
+  globalShortcut.register.mockClear()
+  updateConfig.mockClear()
+})
+
+describe('global hotkey', () => {
+  it('registers the default shortcut once', () => {
+    expect(getGlobalHotkey()).toBe('CommandOrControl+Shift+P')
+    expect(applyGlobalHotkey('CommandOrControl+Shift+P')).toBe(true)
+    expect(globalShortcut.register).not.toHaveBeenCalled()
+  })
+
+  it('switches and persists a new shortcut', async () => {
+    await expect(setGlobalHotkey(' Alt+Shift+K ')).resolves.toBe('Alt+Shift+K')
+    expect([...registered]).toEqual(['Alt+Shift+K'])
+    expect(config.tray.global_hotkey).toBe('Alt+Shift+K')
+  })
+
+  it('can be turned off', async () => {
+    await setGlobalHotkey('')
+    expect(getGlobalHotkey()).toBeNull()
+    expect(registered.size).toBe(0)
+  })
+
+  it('keeps the previous shortcut when the new one cannot be registered', async () => {
+    await expect(setGlobalHotkey('Alt+Space')).rejects.toThrow('已被其他程序占用')
+    await expect(setGlobalHotkey('Bogus+')).rejects.toThrow('无效')
+    expect(getGlobalHotkey()).toBe('CommandOrControl+Shift+P')
+    expect(updateConfig).not.toHaveBeenCalled()
+  })
+})
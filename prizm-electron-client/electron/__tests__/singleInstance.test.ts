@@ -0,0 +1,79 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+const { app, showMainWindow } = vi.hoisted(() => {
+  const listeners = new Map<string, (...args: unknown[]) => void>()
+  return {
+    showMainWindow: vi.fn(),
+    app: {
+      listeners,
+      locked: true,
+      requestSingleInstanceLock: vi.fn(() => app.locked),
+      on: vi.fn((event: string, listener: (...args: unknown[]) => void) => {
+        listeners.set(event, listener)
+      }),
+      whenReady: () => Promise.resolve()
+    }
+  }
+})
+
+vi.mock('electron', () => ({ app, default: { app } }))
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), error: vi.fn() }
+}))
+vi.mock('../windowManager', () => ({ showMainWindow }))
+
+import {
+  acquireSingleInstanceLock,
+  extractDeepLink,
+  handleSecondInstance,
+  onSecondInstance
+} from '../singleInstance'
+
+beforeEach(() => {
+  showMainWindow.mockClear()
+  app.listeners.clear()
+})
+
+describe('extractDeepLink', () => {
+  it('finds prizm:// links among the arguments', () => {
+    expect(extractDeepLink(['prizm.exe', '--minimized', 'Prizm://settings/devices'])).toBe(
+      'Prizm://settings/devices'
+    )
+    expect(extractDeepLink(['prizm.exe', 'https://example.com'])).toBeNull()
+  })
+})
+
+describe('handleSecondInstance', () => {
+  it('forwards arguments and focuses the main window', () => {
+    const listener = vi.fn()
+    const off = onSecondInstance(listener)
+    handleSecondInstance(['prizm.exe', 'prizm://settings'])
+    expect(listener).toHaveBeenCalledWith(['prizm.exe', 'prizm://settings'], 'prizm://settings')
+    expect(showMainWindow).toHaveBeenCalledTimes(1)
+    off()
+  })
+
+  it('stays in the tray when relaunched with --minimized', () => {
+    handleSecondInstance(['prizm.exe', '--minimized'])
+    expect(showMainWindow).not.toHaveBeenCalled()
+  })
+})
+
+describe('acquireSingleInstanceLock', () => {
+  it('hands off to the running instance', () => {
+    app.locked = false
+    expect(acquireSingleInstanceLock()).toBe(false)
+    expect(app.requestSingleInstanceLock).toHaveBeenCalledWith({ argv: process.argv })
+    expect(app.listeners.has('second-instance')).toBe(false)
+  })
+
+  it('prefers the argv passed along with the lock', async () => {
+    app.locked = true
+    const listener = vi.fn()
+    onSecondInstance(listener)
+    expect(acquireSingleInstanceLock()).toBe(true)
+    app.listeners.get('second-instance')?.({}, ['mangled'], '/', { argv: ['prizm.exe', 'x'] })
+    await Promise.resolve()
+    expect(listener).toHaveBeenCalledWith(['prizm.exe', 'x'], null)
+  })
+})
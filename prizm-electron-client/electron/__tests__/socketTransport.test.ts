@@ -0,0 +1,72 @@
+import { describe, it, expect, afterEach } from 'vitest'
+import * as fs from 'fs'
+import * as http from 'http'
+import * as os from 'os'
+import * as path from 'path'
+import * as zlib from 'zlib'
+import { isNamedPipePath, socketFetch } from '../socketTransport'
+
+describe('isNamedPipePath', () => {
+  it('recognizes Windows named pipes', () => {
+    expect(isNamedPipePath('\\\\.\\pipe\\prizm')).toBe(true)
+    expect(isNamedPipePath('//./pipe/prizm')).toBe(true)
+    expect(isNamedPipePath('/run/prizm.sock')).toBe(false)
+  })
+})
+
+describe.skipIf(process.platform === 'win32')('socketFetch', () => {
+  let server: http.Server | null = null
+  const socketPath = path.join(os.tmpdir(), `prizm-test-${process.pid}.sock`)
+
+  afterEach(async () => {
+    await new Promise((resolve) => (server ? server.close(resolve) : resolve(undefined)))
+    server = null
+    fs.rmSync(socketPath, { force: true })
+  })
+
+  it('sends requests over a Unix domain socket', async () => {
+    server = http.createServer((req, res) => {
+      let body = ''
+      req.on('data', (chunk) => (body += chunk))
+      req.on('end', () => {
+        res.setHeader('Content-Type', 'application/json')
+        res.end(JSON.stringify({ url: req.url, host: req.headers.host, body }))
+      })
+    })
+    await new Promise<void>((resolve) => server!.listen(socketPath, resolve))
+
+    const resp = await socketFetch(socketPath, 'http://127.0.0.1:4127/auth/register?x=1', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: '{"name":"desktop"}'
+    })
+    expect(resp.status).toBe(200)
+    expect(await resp.json()).toEqual({
+      url: '/auth/register?x=1',
+      host: '127.0.0.1:4127',
+      body: '{"name":"desktop"}'
+    })
+  })
+
+  it('decompresses gzip responses', async () => {
+    server = http.createServer((req, res) => {
+      expect(req.headers['accept-encoding']).toContain('gzip')
+      res.setHeader('Content-Encoding', 'gzip')
+      res.end(zlib.gzipSync('{"status":"ok"}'))
+    })
+    await new Promise<void>((resolve) => server!.listen(socketPath, resolve))
+
+    const resp = await socketFetch(socketPath, 'http://127.0.0.1:4127/health', {
+      method: 'GET',
+      headers: {}
+    })
+    expect(resp.headers.get('content-encoding')).toBeNull()
+    expect(await resp.json()).toEqual({ status: 'ok' })
+  })
+
+  it('reports a missing socket as a network error', async () => {
+    await expect(
+      socketFetch(socketPath, 'http://127.0.0.1:4127/health', { method: 'GET', headers: {} })
+    ).rejects.toMatchObject({ kind: 'network' })
+  })
+})
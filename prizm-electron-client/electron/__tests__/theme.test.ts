@@ -0,0 +1,72 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+type FakeWindow = { isDestroyed: () => boolean; webContents: { send: (...a: unknown[]) => void } }
+
+const { nativeTheme, windows, saveThemeMode } = vi.hoisted(() => {
+  const listeners = new Map<string, () => void>()
+  return {
+    saveThemeMode: vi.fn(async () => undefined),
+    windows: [] as FakeWindow[],
+    nativeTheme: {
+      listeners,
+      themeSource: 'system',
+      shouldUseDarkColors: false,
+      shouldUseHighContrastColors: false,
+      on: vi.fn((event: string, listener: () => void) => listeners.set(event, listener)),
+      removeListener: vi.fn()
+    }
+  }
+})
+
+vi.mock('electron', () => {
+  const m = { nativeTheme, BrowserWindow: { getAllWindows: () => windows } }
+  return { ...m, default: m }
+})
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), error: vi.fn() }
+}))
+vi.mock('../config', () => ({
+  normalizeThemeMode: (value: unknown) =>
+    value === 'light' || value === 'dark' || value === 'auto' ? value : 'auto',
+  saveThemeMode,
+  sharedState: { mainWindow: null }
+}))
+
+import { getThemeState, onThemeChange, setThemeMode, watchSystemTheme } from '../theme'
+
+const send = vi.fn()
+
+beforeEach(() => {
+  send.mockClear()
+  windows.splice(0, windows.length, { isDestroyed: () => false, webContents: { send } })
+})
+
+describe('theme', () => {
+  it('pushes theme://changed when the system appearance flips', () => {
+    watchSystemTheme()
+    const listener = vi.fn()
+    const off = onThemeChange(listener)
+    nativeTheme.listeners.get('updated')?.()
+    expect(send).not.toHaveBeenCalled()
+
+    nativeTheme.shouldUseDarkColors = true
+    nativeTheme.listeners.get('updated')?.()
+    const state = { mode: 'auto', effective: 'dark', highContrast: false }
+    expect(send).toHaveBeenCalledWith('theme://changed', state)
+    expect(listener).toHaveBeenCalledWith(state)
+    off()
+  })
+
+  it('persists the override and treats system as auto', async () => {
+    nativeTheme.shouldUseDarkColors = false
+    await setThemeMode('light')
+    expect(nativeTheme.themeSource).toBe('light')
+    expect(saveThemeMode).toHaveBeenLastCalledWith('light')
+    expect(send).toHaveBeenLastCalledWith('theme://changed', getThemeState())
+
+    await setThemeMode('system')
+    expect(nativeTheme.themeSource).toBe('system')
+    expect(saveThemeMode).toHaveBeenLastCalledWith('auto')
+    expect(getThemeState().mode).toBe('auto')
+  })
+})
@@ -0,0 +1,133 @@
+import { X509Certificate } from 'crypto'
+import { describe, it, expect, vi } from 'vitest'
+import type { Session } from 'electron'
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn() }
+}))
+
+import { applyExtraCaCertificates, isTrustedByExtraCa } from '../tlsTrust'
+
+/** Prizm Test CA：CA:TRUE */
+const CA_PEM = `-----BEGIN CERTIFICATE-----
+MIIBlzCCAT2gAwIBAgIUSjogFdMpXPDAozJqRlAJN5NxIaIwCgYIKoZIzj0EAwIw
+GDEWMBQGA1UEAwwNUHJpem0gVGVzdCBDQTAgFw0yNjEwMTYwMTEyMDdaGA8yMTI2
+MDkyMjAxMTIwN1owGDEWMBQGA1UEAwwNUHJpem0gVGVzdCBDQTBZMBMGByqGSM49
+AgEGCCqGSM49AwEHA0IABHTgNvShlZmEtzVJdBZXTmVJG5E1JPrndjnoJc/eeMKR
+ynGCydRunZdOh+vj76ADsY5RXxTnM+iNKxJHZR9PM4ujYzBhMB0GA1UdDgQWBBRZ
+O96uc8jC/s2VpHcHCATjFYjVgzAfBgNVHSMEGDAWgBRZO96uc8jC/s2VpHcHCATj
+FYjVgzAPBgNVHRMBAf8EBTADAQH/MA4GA1UdDwEB/wQEAwIBBjAKBggqhkjOPQQD
+AgNIADBFAiEA/wWgEiEGQfMWBkcqcJjJqvVJBDAgWVO2qkXAQtymPywCIGxOlbZI
+9QMmCxdXiWXnVtASrFYIwxNVLf5U2xyu2cqb
+-----END CERTIFICATE-----`
+
+/** 由 CA 签发的中间 CA（CA:TRUE） */
+const INTERMEDIATE_PEM = `-----BEGIN CERTIFICATE-----
+MIIBojCCAUegAwIBAgIUaAWwsVB/n9DG7zpoh6bOUzE2QXAwCgYIKoZIzj0EAwIw
+GDEWMBQGA1UEAwwNUHJpem0gVGVzdCBDQTAgFw0yNjEwMTYwMTEyMDdaGA8yMTI2
+MDkyMjAxMTIwN1owIjEgMB4GA1UEAwwXUHJpem0gVGVzdCBJbnRlcm1lZGlhdGUw
+WTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAAQBrx81NW8jhkKis4fRg/0RpGE/Xygn
+2PTrOs638ntE3UunR7NjT2vWcf+g57hau15xXJL99o7DRp0YqoAGV2B+o2MwYTAP
+BgNVHRMBAf8EBTADAQH/MA4GA1UdDwEB/wQEAwIBBjAdBgNVHQ4EFgQU+O8K8KKp
+k7ysdFZDTJI1jzbwk/MwHwYDVR0jBBgwFoAUWTvernPIwv7NlaR3BwgE4xWI1YMw
+CgYIKoZIzj0EAwIDSQAwRgIhAKgPomIh697mZPHlhxs+VrYtPk+ji3wTgqlHPGVg
+JBkAAiEAxLp1iaG3JfGKKqUysbqX91tsp3OYVqPhaBYYPpcvV/o=
+-----END CERTIFICATE-----`
+
+/** 由 CA 签发的 prizm.example 叶证书（CA:FALSE） */
+const LEAF_PEM = `-----BEGIN CERTIFICATE-----
+MIIBnjCCAUSgAwIBAgIUaAWwsVB/n9DG7zpoh6bOUzE2QW8wCgYIKoZIzj0EAwIw
+GDEWMBQGA1UEAwwNUHJpem0gVGVzdCBDQTAgFw0yNjEwMTYwMTEyMDdaGA8yMTI2
+MDkyMjAxMTIwN1owGDEWMBQGA1UEAwwNcHJpem0uZXhhbXBsZTBZMBMGByqGSM49
+AgEGCCqGSM49AwEHA0IABISUtfNwlClZ/MVUSzfXrpxUR1yfCJDoznoYa8mJw/7/
+Aoa7l6EkIed6Ifd1D07p1DuGgzxFjh6BQFQV+a8mXZqjajBoMAwGA1UdEwEB/wQC
+MAAwGAYDVR0RBBEwD4INcHJpem0uZXhhbXBsZTAdBgNVHQ4EFgQUbSGClyCAWB6R
+dwopvxY5SoPO3ZUwHwYDVR0jBBgwFoAUWTvernPIwv7NlaR3BwgE4xWI1YMwCgYI
+KoZIzj0EAwIDSAAwRQIhAOmHpBDWgBmpY4bFBAZFFbJaT18maEYvzA4M9QFYk7KK
+AiAYlF4caq1XjxROb3Fcly2LiniIwYKslBJZ6YQyEJS3Gw==
+-----END CERTIFICATE-----`
+
+/** 由上面的叶证书签发的 bank.example 证书 */
+const LEAF_SIGNED_PEM = `-----BEGIN CERTIFICATE-----
+MIIBnDCCAUKgAwIBAgIUbT8urMV4vAQwEKSzHvcgUn22zf0wCgYIKoZIzj0EAwIw
+GDEWMBQGA1UEAwwNcHJpem0uZXhhbXBsZTAgFw0yNjEwMTYwMTEyMDdaGA8yMTI2
+MDkyMjAxMTIwN1owFzEVMBMGA1UEAwwMYmFuay5leGFtcGxlMFkwEwYHKoZIzj0C
+AQYIKoZIzj0DAQcDQgAE4ud2Pw5WGYOEDXJUHtGAiGSapVvb4MwpOmWK5zhq9U9J
+ltJ7ORNQapZn9eWiTudTkC07f2Ohj8nVRPSy1XS8oaNpMGcwDAYDVR0TAQH/BAIw
+ADAXBgNVHREEEDAOggxiYW5rLmV4YW1wbGUwHQYDVR0OBBYEFL7qrWkYt5y8adO5
+rd/yT8wuSMMFMB8GA1UdIwQYMBaAFG0hgpcggFgekXcKKb8WOUqDzt2VMAoGCCqG
+SM49BAMCA0gAMEUCIB9jPFR4iVWpWbUAT3rZMPhC+kMzPKgfV6A9v++WuX12AiEA
+idLQVIresBaQTPzaHJYVLTeCSx4IFuxTAhGVaY7JM9I=
+-----END CERTIFICATE-----`
+
+/** 由中间 CA 签发的 bank.example 证书 */
+const INTERMEDIATE_SIGNED_PEM = `-----BEGIN CERTIFICATE-----
+MIIBpjCCAUygAwIBAgIUcqLtbQV4+RJEA49iaV+/ARQtercwCgYIKoZIzj0EAwIw
+IjEgMB4GA1UEAwwXUHJpem0gVGVzdCBJbnRlcm1lZGlhdGUwIBcNMjYxMDE2MDEx
+MjA3WhgPMjEyNjA5MjIwMTEyMDdaMBcxFTATBgNVBAMMDGJhbmsuZXhhbXBsZTBZ
+MBMGByqGSM49AgEGCCqGSM49AwEHA0IABJ0+sq4yPoYpv2SBWSyDLDu9vQYMk0tV
+B+UPY0qjlVmE595rdt2sI13yobeYC+6/j9E6QejSLb4Axj2Ktuu6HEqjaTBnMAwG
+A1UdEwEB/wQCMAAwFwYDVR0RBBAwDoIMYmFuay5leGFtcGxlMB0GA1UdDgQWBBQZ
+MmX+9XK90jdmlDGnBd62TfwrPTAfBgNVHSMEGDAWgBT47wrwoqmTvKx0VkNMkjWP
+NvCT8zAKBggqhkjOPQQDAgNIADBFAiEAmFof/B0HEHNJu3X0mk4VUKH5PxbmvI6Y
+OUhr3y1aKzkCIADWpRfKfZHxuuwV1e8nRNVL8IhgEMjKC3lv+5deWsjm
+-----END CERTIFICATE-----`
+
+type VerifyProc = (
+  request: { hostname: string; verificationResult: string; certificate: unknown },
+  callback: (result: number) => void
+) => void
+
+function fakeSession() {
+  const setCertificateVerifyProc = vi.fn<(proc: VerifyProc | null) => void>()
+  return { session: { setCertificateVerifyProc } as unknown as Session, setCertificateVerifyProc }
+}
+
+function verify(proc: VerifyProc, hostname: string, verificationResult: string): number {
+  let result = NaN
+  proc({ hostname, verificationResult, certificate: { data: '' } }, (r) => (result = r))
+  return result
+}
+
+describe('applyExtraCaCertificates', () => {
+  it('restores default verification when nothing is configured', () => {
+    const { session, setCertificateVerifyProc } = fakeSession()
+    applyExtraCaCertificates(session, [])
+    expect(setCertificateVerifyProc).toHaveBeenCalledWith(null)
+  })
+
+  it('skips verification only for hosts with verify_tls disabled', () => {
+    const { session, setCertificateVerifyProc } = fakeSession()
+    applyExtraCaCertificates(session, [], ['Prizm.Local', '[::1]'])
+    const proc = setCertificateVerifyProc.mock.calls[0][0] as VerifyProc
+    expect(verify(proc, 'prizm.local', 'net::ERR_CERT_AUTHORITY_INVALID')).toBe(0)
+    expect(verify(proc, '::1', 'net::ERR_CERT_AUTHORITY_INVALID')).toBe(0)
+    expect(verify(proc, 'example.com', 'net::ERR_CERT_AUTHORITY_INVALID')).toBe(-3)
+    expect(verify(proc, 'example.com', 'net::OK')).toBe(-3)
+  })
+})
+
+describe('isTrustedByExtraCa', () => {
+  const ca = new X509Certificate(CA_PEM)
+  const intermediate = new X509Certificate(INTERMEDIATE_PEM)
+  const leaf = new X509Certificate(LEAF_PEM)
+
+  it('accepts leaves issued by the trusted CA directly or through an intermediate CA', () => {
+    expect(isTrustedByExtraCa([leaf], 'prizm.example', [ca])).toBe(true)
+    const chain = [new X509Certificate(INTERMEDIATE_SIGNED_PEM), intermediate]
+    expect(isTrustedByExtraCa(chain, 'bank.example', [ca])).toBe(true)
+    expect(isTrustedByExtraCa([leaf], 'other.example', [ca])).toBe(false)
+  })
+
+  it('rejects certificates signed by a leaf of the trusted CA', () => {
+    const chain = [new X509Certificate(LEAF_SIGNED_PEM), leaf]
+    expect(chain[0].checkIssued(leaf) && chain[0].verify(leaf.publicKey)).toBe(true)
+    expect(isTrustedByExtraCa(chain, 'bank.example', [ca])).toBe(false)
+    // 叶证书本身被加入信任列表时也不能作为签发者
+    expect(isTrustedByExtraCa(chain.slice(0, 1), 'bank.example', [leaf])).toBe(false)
+  })
+
+  it('still accepts a pinned self-signed or leaf certificate by fingerprint', () => {
+    expect(isTrustedByExtraCa([leaf], 'prizm.example', [leaf])).toBe(true)
+  })
+})
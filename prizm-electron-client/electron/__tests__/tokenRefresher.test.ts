@@ -0,0 +1,127 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+
+interface TestConfig {
+  server: { host: string; port: number }
+  client: { name: string }
+  api_key: string
+  api_key_expires_at?: number
+  refresh_token?: string
+}
+
+const { state, refreshMock, sendMock } = vi.hoisted(() => ({
+  state: { config: {} as TestConfig },
+  refreshMock: vi.fn(),
+  sendMock: vi.fn()
+}))
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  loadConfigFromDisk: async () => JSON.parse(JSON.stringify(state.config)),
+  updateConfig: async (mutate: (config: TestConfig) => void) => {
+    const next = JSON.parse(JSON.stringify(state.config))
+    mutate(next)
+    state.config = next
+    return next
+  },
+  sharedState: {
+    mainWindow: { isDestroyed: () => false, webContents: { send: sendMock } }
+  }
+}))
+
+vi.mock('../prizmApi', () => ({
+  PrizmApi: class {
+    refreshToken = refreshMock
+  }
+}))
+
+import { PrizmError } from '../errors'
+import {
+  REFRESH_MARGIN_MS,
+  TokenRefresher,
+  applyTokenExpiry,
+  refreshDelay
+} from '../tokenRefresher'
+import type { PrizmConfig } from '../config'
+
+describe('refreshDelay', () => {
+  it('refreshes ahead of expiry', () => {
+    expect(refreshDelay(10 * 60_000, 0)).toBe(10 * 60_000 - REFRESH_MARGIN_MS)
+  })
+
+  it('uses half the remaining lifetime for short-lived keys', () => {
+    expect(refreshDelay(20_000, 0)).toBe(10_000)
+  })
+
+  it('refreshes immediately when already expired', () => {
+    expect(refreshDelay(1_000, 5_000)).toBe(0)
+  })
+})
+
+describe('applyTokenExpiry', () => {
+  it('keeps the previous refresh token when the server does not rotate it', () => {
+    const config = { refresh_token: 'r-1' } as PrizmConfig
+    applyTokenExpiry(config, { expiresAt: 123 })
+    expect(config).toMatchObject({ api_key_expires_at: 123, refresh_token: 'r-1' })
+  })
+
+  it('clears expiry for non-expiring keys', () => {
+    const config = { api_key_expires_at: 123, refresh_token: 'r-1' } as PrizmConfig
+    applyTokenExpiry(config, {})
+    expect(config.api_key_expires_at).toBeUndefined()
+    expect(config.refresh_token).toBeUndefined()
+  })
+})
+
+describe('TokenRefresher.refresh', () => {
+  beforeEach(() => {
+    refreshMock.mockReset()
+    sendMock.mockReset()
+    state.config = {
+      server: { host: '127.0.0.1', port: 4127 },
+      client: { name: 'client-1' },
+      api_key: 'old-key',
+      api_key_expires_at: 1_000,
+      refresh_token: 'r-1'
+    }
+  })
+
+  it('stores the renewed key and expiry', async () => {
+    refreshMock.mockResolvedValue({ apiKey: 'new-key', expiresAt: 2_000, refreshToken: 'r-2' })
+    await expect(new TokenRefresher().refresh()).resolves.toBe('new-key')
+    expect(refreshMock).toHaveBeenCalledWith('r-1')
+    expect(state.config).toMatchObject({
+      api_key: 'new-key',
+      api_key_expires_at: 2_000,
+      refresh_token: 'r-2'
+    })
+  })
+
+  it('asks for re-authentication when the refresh token is rejected', async () => {
+    refreshMock.mockRejectedValue(PrizmError.auth('invalid refresh token'))
+    await expect(new TokenRefresher().refresh()).resolves.toBeNull()
+    expect(sendMock).toHaveBeenCalledWith('auth://reauth-required', {
+      serverUrl: 'http://127.0.0.1:4127',
+      reason: 'refresh_rejected'
+    })
+    expect(state.config.api_key).toBe('old-key')
+  })
+
+  it('asks for re-authentication without a refresh token', async () => {
+    delete state.config.refresh_token
+    await expect(new TokenRefresher().refresh()).resolves.toBeNull()
+    expect(refreshMock).not.toHaveBeenCalled()
+    expect(sendMock).toHaveBeenCalledWith(
+      'auth://reauth-required',
+      expect.objectContaining({ reason: 'missing_refresh_token' })
+    )
+  })
+
+  it('does nothing for non-expiring keys', async () => {
+    delete state.config.api_key_expires_at
+    await expect(new TokenRefresher().refresh()).resolves.toBeNull()
+    expect(refreshMock).not.toHaveBeenCalled()
+  })
+})
@@ -0,0 +1,33 @@
+import { describe, it, expect } from 'vitest'
+import { parseToml, stringifyToml } from '../toml'
+
+describe('toml', () => {
+  it('round-trips a config-shaped object', () => {
+    const config = {
+      api_key: '',
+      server: { host: '127.0.0.1', port: 4127, is_dev: true },
+      client: { name: 'Client "A"', auto_register: false, requested_scopes: ['default', 'online'] },
+      profiles: {
+        '10.0.0.2:4127': { api_key: 'k', server: { host: '10.0.0.2', port: 4127 } }
+      }
+    }
+    expect(parseToml(stringifyToml(config))).toEqual(config)
+  })
+
+  it('parses comments, dotted keys and literal strings', () => {
+    const parsed = parseToml(
+      [
+        '# comment',
+        'a = 1 # trailing',
+        "[x.'y z']",
+        "b = [ 'a', \"b\\u0041\" ]",
+        'c.d = -2.5'
+      ].join('\n')
+    )
+    expect(parsed).toEqual({ a: 1, x: { 'y z': { b: ['a', 'bA'], c: { d: -2.5 } } } })
+  })
+
+  it('rejects duplicate keys', () => {
+    expect(() => parseToml('a = 1\na = 2')).toThrow(/duplicate key/)
+  })
+})
@@ -0,0 +1,185 @@
+import { describe, it, expect, vi, afterEach } from 'vitest'
+
+vi.mock('electron', () => {
+  const electronMock = { app: {}, Menu: {}, Tray: vi.fn(), nativeImage: {} }
+  return { ...electronMock, default: electronMock }
+})
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({
+  sharedState: { tray: null, mainWindow: null },
+  DEFAULT_TRAY_CLICK_BINDINGS: {
+    left_click: 'toggle_window',
+    right_click: 'menu',
+    double_click: 'dashboard'
+  },
+  normalizeTrayClickBindings: vi.fn()
+}))
+vi.mock('../realtime', () => ({ realtimeConnection: { restart: vi.fn() } }))
+vi.mock('../serverNotifications', () => ({ setNotificationsPaused: vi.fn() }))
+vi.mock('../notificationHistory', () => ({ notificationHistory: { unreadCount: () => 0 } }))
+vi.mock('../trayStatus', () => ({ trayStatusMonitor: {} }))
+vi.mock('../windowManager', () => ({ openDashboard: vi.fn(), showMainWindow: vi.fn() }))
+
+import {
+  TRAY_ICON_COLORS,
+  buildTrayMenuTemplate,
+  createTrayClickHandlers,
+  trayTooltip
+} from '../trayManager'
+
+import type { TrayMenuState } from '../trayManager'
+
+function actions() {
+  return {
+    toggleWindow: vi.fn(),
+    openDashboard: vi.fn(),
+    reconnect: vi.fn(),
+    setNotificationsPaused: vi.fn(),
+    copyServerUrl: vi.fn(),
+    quit: vi.fn()
+  }
+}
+
+function menuState(state: Partial<TrayMenuState> = {}): TrayMenuState {
+  return {
+    windowVisible: false,
+    connection: 'connected',
+    serverUrl: 'http://h:4127',
+    notificationsPaused: false,
+    ...state
+  }
+}
+
+function click(template: ReturnType<typeof buildTrayMenuTemplate>, label: string): void {
+  const item = template.find((entry) => entry.label === label)
+  ;(item?.click as unknown as () => void)()
+}
+
+describe('buildTrayMenuTemplate', () => {
+  it('reflects window visibility, connection state and pause state', () => {
+    const template = buildTrayMenuTemplate(
+      menuState({ windowVisible: true, connection: 'degraded', notificationsPaused: true }),
+      actions()
+    )
+    expect(template.map((item) => item.label ?? item.type)).toEqual([
+      '隐藏窗口',
+      '打开管理面板',
+      'separator',
+      '连接状态：连接不稳定',
+      '重新连接',
+      'separator',
+      '暂停通知',
+      '复制服务器地址',
+      'separator',
+      '退出'
+    ])
+    expect(template[3].enabled).toBe(false)
+    expect(template[6]).toMatchObject({ type: 'checkbox', checked: true })
+  })
+
+  it('wires menu items to actions', () => {
+    const handlers = actions()
+    const template = buildTrayMenuTemplate(menuState(), handlers)
+    expect(template[0].label).toBe('显示窗口')
+
+    for (const label of ['显示窗口', '打开管理面板', '重新连接', '暂停通知', '复制服务器地址', '退出']) {
+      click(template, label)
+    }
+    expect(handlers.toggleWindow).toHaveBeenCalledTimes(1)
+    expect(handlers.openDashboard).toHaveBeenCalledWith('http://h:4127/dashboard/')
+    expect(handlers.reconnect).toHaveBeenCalledTimes(1)
+    expect(handlers.setNotificationsPaused).toHaveBeenCalledWith(true)
+    expect(handlers.copyServerUrl).toHaveBeenCalledWith('http://h:4127')
+    expect(handlers.quit).toHaveBeenCalledTimes(1)
+  })
+
+  it('disables server actions without a configured server', () => {
+    const template = buildTrayMenuTemplate(menuState({ serverUrl: null }), actions())
+    const disabled = template.filter((item) => item.enabled === false).map((item) => item.label)
+    expect(disabled).toEqual(['打开管理面板', '连接状态：已连接', '重新连接', '复制服务器地址'])
+  })
+})
+
+describe('trayTooltip', () => {
+  const base = {
+    connection: 'connected' as const,
+    serverUrl: 'http://127.0.0.1:4127',
+    unread: 0,
+    lastHealthyAt: null,
+    latencyMs: null
+  }
+
+  it('shows the connection state and server address', () => {
+    expect(trayTooltip(base)).toBe('Prizm · 已连接\nhttp://127.0.0.1:4127')
+    expect(trayTooltip({ ...base, connection: 'disconnected', serverUrl: null })).toBe(
+      'Prizm · 未连接\n未配置服务器'
+    )
+  })
+
+  it('adds the last health check, latency and unread count', () => {
+    const lastHealthyAt = new Date(2026, 0, 1, 9, 5, 7).getTime()
+    expect(trayTooltip({ ...base, lastHealthyAt, latencyMs: 23, unread: 3 })).toBe(
+      'Prizm · 已连接\nhttp://127.0.0.1:4127\n健康检查 09:05:07 · 23 ms\n3 条未读通知'
+    )
+    expect(trayTooltip({ ...base, connection: 'degraded', lastHealthyAt })).toContain(
+      '上次正常 09:05:07，最近一次检查失败'
+    )
+  })
+
+  it('fits the Windows tooltip length limit', () => {
+    const serverUrl = `http://${'a'.repeat(200)}.example`
+    expect(trayTooltip({ ...base, serverUrl }).length).toBeLessThanOrEqual(127)
+  })
+})
+
+describe('TRAY_ICON_COLORS', () => {
+  it('uses a distinct hex color for every connection state', () => {
+    const colors = Object.values(TRAY_ICON_COLORS)
+    expect(new Set(colors).size).toBe(4)
+    for (const color of colors) expect(color).toMatch(/^#[0-9a-f]{6}$/)
+  })
+})
+
+describe('createTrayClickHandlers', () => {
+  afterEach(() => {
+    vi.useRealTimers()
+  })
+
+  it('runs the bound actions and lets a double-click cancel the pending click', () => {
+    vi.useFakeTimers()
+    const run = vi.fn()
+    const bindings = {
+      left_click: 'toggle_window' as const,
+      right_click: 'menu' as const,
+      double_click: 'dashboard' as const
+    }
+    const handlers = createTrayClickHandlers(() => bindings, run, 250)
+
+    handlers.click()
+    vi.advanceTimersByTime(250)
+    expect(run).toHaveBeenLastCalledWith('toggle_window')
+
+    run.mockClear()
+    handlers.click()
+    handlers.doubleClick()
+    vi.advanceTimersByTime(250)
+    expect(run.mock.calls).toEqual([['dashboard']])
+
+    handlers.rightClick()
+    expect(run).toHaveBeenLastCalledWith('menu')
+  })
+
+  it('responds to a click immediately when double-click is unbound', () => {
+    const run = vi.fn()
+    const handlers = createTrayClickHandlers(
+      () => ({ left_click: 'menu', right_click: 'none', double_click: 'none' }),
+      run
+    )
+    handlers.click()
+    expect(run).toHaveBeenCalledWith('menu')
+  })
+})
@@ -0,0 +1,36 @@
+import { describe, it, expect, vi } from 'vitest'
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+vi.mock('../serverHealth', () => ({ isServerHealthy: vi.fn() }))
+
+import { TrayStatusMonitor } from '../trayStatus'
+
+describe('TrayStatusMonitor', () => {
+  it('records the last healthy time and latency, keeping it across failures', async () => {
+    let clock = 1_000
+    const probe = vi.fn(async () => {
+      clock += 40
+      return probe.mock.calls.length === 1
+    })
+    const onUpdate = vi.fn()
+    const monitor = new TrayStatusMonitor(probe, () => clock, 60_000)
+
+    monitor.start('http://127.0.0.1:4127', onUpdate)
+    await vi.waitFor(() => expect(onUpdate).toHaveBeenCalledTimes(1))
+    expect(monitor.get()).toEqual({ lastHealthyAt: 1_040, latencyMs: 40 })
+
+    await monitor.check()
+    expect(monitor.get()).toEqual({ lastHealthyAt: 1_040, latencyMs: null })
+
+    monitor.stop()
+    expect(monitor.get()).toEqual({ lastHealthyAt: null, latencyMs: null })
+  })
+
+  it('does not check without a server', () => {
+    const probe = vi.fn(async () => true)
+    new TrayStatusMonitor(probe).start(null, vi.fn())
+    expect(probe).not.toHaveBeenCalled()
+  })
+})
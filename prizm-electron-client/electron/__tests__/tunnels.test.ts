@@ -0,0 +1,100 @@
+import { describe, it, expect, vi } from 'vitest'
+
+vi.mock('electron-log/main', () => ({
+  default: { info: vi.fn(), warn: vi.fn(), error: vi.fn() }
+}))
+
+vi.mock('../config', () => ({ updateConfig: vi.fn() }))
+vi.mock('../realtime', () => ({ realtimeConnection: {} }))
+
+import type { PrizmConfig, TunnelConfig } from '../config'
+import type { MessageListener, RealtimeConnection } from '../realtime'
+import { TunnelClient } from '../tunnels'
+
+function fakeConnection() {
+  const listeners = new Set<MessageListener>()
+  return {
+    sendMessage: vi.fn(() => true),
+    onMessage: (listener: MessageListener) => {
+      listeners.add(listener)
+      return () => listeners.delete(listener)
+    },
+    emit: (message: { type: string; [key: string]: unknown }) =>
+      listeners.forEach((l) => l(message))
+  }
+}
+
+function config(...tunnels: Partial<TunnelConfig>[]): PrizmConfig {
+  return {
+    tunnels: tunnels.map((t) => ({
+      name: 'grafana',
+      local_port: 3000,
+      local_host: '127.0.0.1',
+      scope: 'default',
+      enabled: true,
+      ...t
+    }))
+  } as unknown as PrizmConfig
+}
+
+describe('TunnelClient', () => {
+  it('announces enabled tunnels and closes them when disabled', () => {
+    const connection = fakeConnection()
+    const client = new TunnelClient(connection as unknown as RealtimeConnection)
+    client.start()
+    client.apply(config({}, { name: 'db', enabled: false }))
+    expect(connection.sendMessage).toHaveBeenCalledTimes(1)
+    expect(connection.sendMessage).toHaveBeenCalledWith({
+      type: 'tunnel_open',
+      name: 'grafana',
+      scope: 'default'
+    })
+
+    connection.emit({
+      type: 'tunnel_status',
+      name: 'grafana',
+      open: true,
+      path: '/tunnels/c/grafana'
+    })
+    expect(client.list()[0]).toMatchObject({ open: true, path: '/tunnels/c/grafana' })
+
+    connection.emit({ type: 'connected' })
+    expect(connection.sendMessage).toHaveBeenCalledTimes(2)
+    expect(client.list()[0].open).toBe(false)
+
+    client.apply(config({ enabled: false }))
+    expect(connection.sendMessage).toHaveBeenLastCalledWith({
+      type: 'tunnel_close',
+      name: 'grafana'
+    })
+  })
+
+  it('proxies requests only for enabled tunnels', async () => {
+    const connection = fakeConnection()
+    const requester = vi.fn(async () => ({
+      status: 200,
+      headers: { 'content-type': 'text/plain' },
+      body: Buffer.from('ok')
+    }))
+    const client = new TunnelClient(connection as unknown as RealtimeConnection, requester)
+    client.start()
+    client.apply(config({}, { name: 'db', enabled: false }))
+    const request = { method: 'POST', path: '/q', headers: {}, body: 'aGk=' }
+
+    connection.emit({ type: 'tunnel_request', requestId: 'r1', name: 'grafana', ...request })
+    connection.emit({ type: 'tunnel_request', requestId: 'r2', name: 'db', ...request })
+    await vi.waitFor(() => expect(connection.sendMessage).toHaveBeenCalledTimes(3))
+
+    expect(requester).toHaveBeenCalledTimes(1)
+    expect(requester.mock.calls[0][4]).toEqual(Buffer.from('hi'))
+    const responses = connection.sendMessage.mock.calls.map((call) => call[0] as unknown)
+    expect(responses).toContainEqual(
+      expect.objectContaining({
+        requestId: 'r1',
+        status: 200,
+        body: Buffer.from('ok').toString('base64')
+      })
+    )
+    expect(responses).toContainEqual(expect.objectContaining({ requestId: 'r2', status: 404 }))
+  })
+})
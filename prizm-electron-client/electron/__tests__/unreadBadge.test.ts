@@ -0,0 +1,58 @@
+import { describe, it, expect, vi } from 'vitest'
+
+const { app, history, mainWindow } = vi.hoisted(() => {
+  const listeners = new Map<string, (...args: unknown[]) => void>()
+  return {
+    mainWindow: { isDestroyed: () => false, setOverlayIcon: vi.fn() },
+    app: {
+      listeners,
+      on: vi.fn((event: string, listener: (...args: unknown[]) => void) => {
+        listeners.set(event, listener)
+      }),
+      removeListener: vi.fn(),
+      setBadgeCount: vi.fn(),
+      dock: { setBadge: vi.fn() }
+    },
+    history: {
+      unread: 2,
+      listener: null as ((unread: number) => void) | null,
+      unreadCount: () => history.unread,
+      markRead: vi.fn(() => {
+        history.unread = 0
+        history.listener?.(0)
+      }),
+      onChange: (listener: (unread: number) => void) => {
+        history.listener = listener
+        return () => undefined
+      }
+    }
+  }
+})
+
+vi.mock('electron', () => ({ app, default: { app } }))
+vi.mock('../config', () => ({ sharedState: { tray: null, mainWindow } }))
+vi.mock('../notificationHistory', () => ({ notificationHistory: history }))
+vi.mock('../trayManager', () => ({ createDotIcon: () => ({}), refreshTray: vi.fn() }))
+
+import { badgeText, watchUnreadBadge } from '../unreadBadge'
+
+describe('badgeText', () => {
+  it('hides zero and caps large counts', () => {
+    expect(badgeText(0)).toBe('')
+    expect(badgeText(7)).toBe('7')
+    expect(badgeText(120)).toBe('99+')
+  })
+})
+
+describe('watchUnreadBadge', () => {
+  it('marks notifications read when the main window gains focus', () => {
+    watchUnreadBadge()
+    const onFocus = app.listeners.get('browser-window-focus')
+    onFocus?.({}, {})
+    expect(history.markRead).not.toHaveBeenCalled()
+    onFocus?.({}, mainWindow)
+    expect(history.markRead).toHaveBeenCalledTimes(1)
+    onFocus?.({}, mainWindow)
+    expect(history.markRead).toHaveBeenCalledTimes(1)
+  })
+})
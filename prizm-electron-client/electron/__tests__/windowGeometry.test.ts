@@ -0,0 +1,95 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest'
+import type { PrizmConfig } from '../config'
+
+const { state } = vi.hoisted(() => ({
+  state: { config: {} as Partial<PrizmConfig>, saves: 0 }
+}))
+
+vi.mock('electron', () => {
+  const m = { screen: { getAllDisplays: () => [] } }
+  return { ...m, default: m }
+})
+vi.mock('electron-log/main', () => ({ default: { warn: vi.fn() } }))
+vi.mock('../config', () => ({
+  updateConfig: vi.fn(async (mutate: (config: Partial<PrizmConfig>) => void) => {
+    state.saves++
+    mutate(state.config)
+    return state.config
+  })
+}))
+
+import {
+  applyWindowConfig,
+  displayLayoutKey,
+  isVisibleOn,
+  saveWindowGeometry,
+  savedWindowGeometry
+} from '../windowGeometry'
+
+const laptop = {
+  bounds: { x: 0, y: 0, width: 1440, height: 900 },
+  workArea: { x: 0, y: 25, width: 1440, height: 875 },
+  scaleFactor: 2
+}
+const monitor = {
+  bounds: { x: 1440, y: 0, width: 2560, height: 1440 },
+  workArea: { x: 1440, y: 0, width: 2560, height: 1440 },
+  scaleFactor: 1
+}
+const geometry = { x: 1600, y: 100, width: 1200, height: 800, maximized: true }
+
+beforeEach(() => {
+  state.config = {}
+  state.saves = 0
+  applyWindowConfig(state.config as PrizmConfig)
+})
+
+describe('displayLayoutKey', () => {
+  it('ignores display order', () => {
+    expect(displayLayoutKey([laptop, monitor])).toBe(displayLayoutKey([monitor, laptop]))
+    expect(displayLayoutKey([laptop])).not.toBe(displayLayoutKey([laptop, monitor]))
+  })
+})
+
+describe('isVisibleOn', () => {
+  it('requires part of the window inside a work area', () => {
+    expect(isVisibleOn(geometry, [laptop, monitor])).toBe(true)
+    expect(isVisibleOn(geometry, [laptop])).toBe(false)
+    expect(isVisibleOn({ x: 1400, y: 100, width: 800, height: 600 }, [laptop])).toBe(false)
+  })
+})
+
+describe('window geometry', () => {
+  it('restores per display layout', async () => {
+    await saveWindowGeometry(geometry, 'main', displayLayoutKey([laptop, monitor]))
+    expect(savedWindowGeometry('main', [monitor, laptop])).toEqual(geometry)
+    expect(savedWindowGeometry('main', [laptop])).toBeNull()
+  })
+
+  it('keeps each window separate', async () => {
+    const key = displayLayoutKey([laptop, monitor])
+    await saveWindowGeometry(geometry, 'dashboard', key)
+    expect(state.config.dashboard_window?.layouts[key]).toEqual(geometry)
+    expect(state.config.window).toBeUndefined()
+    expect(savedWindowGeometry('main', [laptop, monitor])).toBeNull()
+    expect(savedWindowGeometry('dashboard', [laptop, monitor])).toEqual(geometry)
+  })
+
+  it('skips unchanged geometry', async () => {
+    await saveWindowGeometry(geometry, 'main', 'a')
+    await saveWindowGeometry({ ...geometry }, 'main', 'a')
+    expect(state.saves).toBe(1)
+    await saveWindowGeometry({ ...geometry, maximized: false }, 'main', 'a')
+    expect(state.saves).toBe(2)
+  })
+
+  it('keeps only the most recently used layouts', async () => {
+    for (let i = 0; i < 10; i++) await saveWindowGeometry(geometry, 'main', `layout-${i}`)
+    await saveWindowGeometry({ ...geometry, x: 0 }, 'main', 'layout-3')
+    const keys = Object.keys(state.config.window?.layouts ?? {})
+    expect(keys).toHaveLength(8)
+    expect(keys).not.toContain('layout-1')
+    expect(keys).toContain('layout-2')
+    expect(keys.at(-1)).toBe('layout-3')
+  })
+})
@@ -0,0 +1,109 @@
+import log from 'electron-log/main'
+import { detectCapabilities } from './clientCapabilities'
+import { loadConfigFromDisk, sharedState, updateConfig } from './config'
+import { requestConsent } from './consent'
+import { toPrizmError } from './errors'
+import { upsertIdentity } from './identities'
+import { PrizmApi, grantedScopesOf, isPendingApproval } from './prizmApi'
+import { upsertActiveProfile } from './profiles'
+import { resumeRegistration, startRegistration } from './registration'
+import { serverConfigToUrl } from './serverUrl'
+import { applyTokenExpiry } from './tokenRefresher'
+
+export interface ReregisteredEvent {
+  clientId: string
+  serverUrl: string
+}
+
+/** 启动时后台自动注册的结果（auth://auto-register） */
+export type AutoRegisterEvent =
+  | { status: 'registered'; clientId: string; serverUrl: string }
+  | { status: 'failed'; serverUrl: string; message: string }
+
+function emitAutoRegister(event: AutoRegisterEvent): void {
+  const win = sharedState.mainWindow
+  if (win && !win.isDestroyed()) {
+    win.webContents.send('auth://auto-register', event)
+  }
+}
+
+/**
+ * API Key 失效（401）时按注册时的名称与 scope 重新注册并写回配置（同名时服务端复用原 clientId）。
+ * 未开启 client.auto_register 或用户拒绝授予新的敏感 scope 时返回 null，由调用方按原样报告鉴权错误
+ */
+export async function reregisterClient(): Promise<string | null> {
+  const config = await loadConfigFromDisk()
+  if (!config.client.auto_register) return null
+  const serverUrl = serverConfigToUrl(config.server)
+  const { requested_scopes } = config.client
+  // client.name 已是 clientId，按它注册会在服务端另建一个以旧 ID 命名的客户端
+  const name = config.client.registration_name || config.client.name
+  log.warn(`[Auth] API key rejected by ${serverUrl}, re-registering as "${name}"`)
+
+  const register = await new PrizmApi(serverUrl).register(
+    name,
+    requested_scopes,
+    undefined,
+    detectCapabilities()
+  )
+  if (isPendingApproval(register)) {
+    // 需管理员批准时无法在请求中途静默完成，交由注册流程（register_client）等待审批
+    log.warn(`[Auth] ${serverUrl} requires approval for new clients, skipping re-registration`)
+    return null
+  }
+  if (!register.apiKey) return null
+  const granted = grantedScopesOf(register, requested_scopes)
+  const allowed = await requestConsent({
+    serverUrl,
+    reason: 'auto_register',
+    granted,
+    previous: config.client.granted_scopes
+  })
+  if (!allowed) return null
+  await updateConfig((current) => {
+    current.client.name = register.clientId || name
+    current.client.registration_name = name
+    current.api_key = register.apiKey
+    applyTokenExpiry(current, register)
+    current.client.granted_scopes = granted
+    if (current.active_identity) upsertIdentity(current, current.active_identity)
+    if (current.active_profile) upsertActiveProfile(current)
+  })
+
+  const win = sharedState.mainWindow
+  if (win && !win.isDestroyed()) {
+    const event: ReregisteredEvent = { clientId: register.clientId || name, serverUrl }
+    win.webContents.send('auth://reregistered', event)
+  }
+  return register.apiKey
+}
+
+/**
+ * 启动时开启了 client.auto_register、配置了服务器与客户端名称且尚无 API Key 时，在后台完成注册
+ * （有未完成的注册则从中断处继续），结果通过 auth://auto-register 通知渲染进程。
+ * 未满足条件时返回 false 且不发送事件；注销后 client.name 为空，不再自动注册
+ */
+export async function autoRegisterOnStartup(): Promise<boolean> {
+  const config = await loadConfigFromDisk()
+  if (!config.client.auto_register || config.api_key || !config.server.host) return false
+  if (!config.client.name) return false
+  const serverUrl = serverConfigToUrl(config.server)
+  const { requested_scopes } = config.client
+  const name = config.client.registration_name || config.client.name
+  log.info(`[Auth] No API key stored, auto-registering as "${name}" on ${serverUrl}`)
+  try {
+    const result =
+      (await resumeRegistration()) ??
+      (await startRegistration({
+        serverUrl,
+        name,
+        requestedScopes: requested_scopes,
+        unattended: true
+      }))
+    emitAutoRegister({ status: 'registered', clientId: result.clientId, serverUrl })
+  } catch (err) {
+    log.warn(`[Auth] Auto-registration on ${serverUrl} failed:`, err)
+    emitAutoRegister({ status: 'failed', serverUrl, message: toPrizmError(err).message })
+  }
+  return true
+}
@@ -0,0 +1,51 @@
+/**
+ * 实时连接的二进制帧，与 @prizm/shared 的 encodeBinaryFrame / decodeBinaryFrame 一致：
+ * version(1) | flags(1) | channel(uint32 BE) | index(uint32 BE)，其后为数据
+ */
+export const BINARY_FRAME_VERSION = 1
+export const BINARY_FRAME_HEADER_SIZE = 10
+const FLAG_LAST = 0x01
+/** 默认分块大小 */
+export const BINARY_FRAME_CHUNK_SIZE = 64 * 1024
+
+export interface BinaryFrame {
+  /** 逻辑流 id，由收发双方约定 */
+  channel: number
+  /** 块序号，从 0 开始 */
+  index: number
+  last: boolean
+  data: Buffer
+}
+
+export function encodeBinaryFrame(frame: BinaryFrame): Buffer {
+  const header = Buffer.alloc(BINARY_FRAME_HEADER_SIZE)
+  header.writeUInt8(BINARY_FRAME_VERSION, 0)
+  header.writeUInt8(frame.last ? FLAG_LAST : 0, 1)
+  header.writeUInt32BE(frame.channel, 2)
+  header.writeUInt32BE(frame.index, 6)
+  return Buffer.concat([header, frame.data])
+}
+
+/** 解析二进制帧；长度不足或版本不符时返回 null */
+export function decodeBinaryFrame(bytes: Buffer): BinaryFrame | null {
+  if (bytes.length < BINARY_FRAME_HEADER_SIZE) return null
+  if (bytes.readUInt8(0) !== BINARY_FRAME_VERSION) return null
+  return {
+    channel: bytes.readUInt32BE(2),
+    index: bytes.readUInt32BE(6),
+    last: (bytes.readUInt8(1) & FLAG_LAST) !== 0,
+    data: bytes.subarray(BINARY_FRAME_HEADER_SIZE)
+  }
+}
+
+/** 把数据按 chunkSize 拆成同一 channel 的连续帧；空数据也产生一个 last 帧 */
+export function splitBinaryFrames(channel: number, data: Buffer, chunkSize: number): BinaryFrame[] {
+  const size = Math.max(1, Math.floor(chunkSize))
+  const count = Math.max(1, Math.ceil(data.length / size))
+  return Array.from({ length: count }, (_, index) => ({
+    channel,
+    index,
+    last: index === count - 1,
+    data: data.subarray(index * size, (index + 1) * size)
+  }))
+}
@@ -0,0 +1,141 @@
+import type { CircuitBreakerConfig } from './config'
+import { PrizmError } from './errors'
+
+export type CircuitState = 'closed' | 'open' | 'half_open'
+
+export interface CircuitStatus {
+  /** 服务器 origin，如 http://127.0.0.1:4127 */
+  origin: string
+  state: CircuitState
+  failures: number
+  /** open 状态下允许再次尝试的时间戳（毫秒） */
+  retry_at: number | null
+}
+
+interface CircuitEntry {
+  state: CircuitState
+  failures: number
+  openedAt: number
+  /** half_open 时是否已有试探请求在进行 */
+  probing: boolean
+}
+
+/**
+ * 按服务器 origin 记录连续失败次数：closed → open（快速失败）→ 冷却后 half_open 放行一个试探请求，
+ * 试探成功回到 closed，失败重新 open
+ */
+export class CircuitBreaker {
+  private circuits = new Map<string, CircuitEntry>()
+
+  constructor(
+    private config: CircuitBreakerConfig,
+    private readonly onStateChange: (status: CircuitStatus) => void = () => undefined,
+    private readonly now: () => number = Date.now
+  ) {}
+
+  configure(config: CircuitBreakerConfig): void {
+    this.config = config
+    if (config.failure_threshold === 0) {
+      for (const origin of [...this.circuits.keys()]) this.reset(origin)
+    }
+  }
+
+  /**
+   * 请求前调用：熔断中抛出 network 类型的 PrizmError（离线队列等按不可达处理）
+   */
+  check(origin: string): void {
+    const entry = this.circuits.get(origin)
+    if (!entry || entry.state === 'closed') return
+    if (entry.state === 'open') {
+      if (this.now() - entry.openedAt < this.config.cooldown_ms) {
+        throw this.openError(origin, entry)
+      }
+      this.transition(origin, entry, 'half_open')
+    }
+    if (entry.probing) throw this.openError(origin, entry)
+    entry.probing = true
+  }
+
+  recordSuccess(origin: string): void {
+    const entry = this.circuits.get(origin)
+    if (!entry) return
+    if (entry.state === 'closed') {
+      entry.failures = 0
+      return
+    }
+    this.reset(origin)
+  }
+
+  recordFailure(origin: string): void {
+    if (this.config.failure_threshold === 0) return
+    const entry = this.circuits.get(origin) ?? {
+      state: 'closed' as CircuitState,
+      failures: 0,
+      openedAt: 0,
+      probing: false
+    }
+    this.circuits.set(origin, entry)
+    entry.failures++
+    entry.probing = false
+    if (entry.state === 'half_open' || entry.failures >= this.config.failure_threshold) {
+      entry.openedAt = this.now()
+      this.transition(origin, entry, 'open', true)
+    }
+  }
+
+  /** 取消等既非成功也非失败的结果：释放 half_open 的试探名额 */
+  recordIgnored(origin: string): void {
+    const entry = this.circuits.get(origin)
+    if (entry) entry.probing = false
+  }
+
+  reset(origin: string): void {
+    const entry = this.circuits.get(origin)
+    if (!entry) return
+    this.circuits.delete(origin)
+    if (entry.state !== 'closed') {
+      this.onStateChange({ origin, state: 'closed', failures: 0, retry_at: null })
+    }
+  }
+
+  status(origin: string): CircuitStatus {
+    const entry = this.circuits.get(origin)
+    if (entry) return this.toStatus(origin, entry)
+    return { origin, state: 'closed', failures: 0, retry_at: null }
+  }
+
+  /** 所有非 closed 的线路 */
+  list(): CircuitStatus[] {
+    return [...this.circuits.entries()]
+      .filter(([, entry]) => entry.state !== 'closed')
+      .map(([origin, entry]) => this.toStatus(origin, entry))
+  }
+
+  private transition(
+    origin: string,
+    entry: CircuitEntry,
+    state: CircuitState,
+    force = false
+  ): void {
+    if (entry.state === state && !force) return
+    entry.state = state
+    this.onStateChange(this.toStatus(origin, entry))
+  }
+
+  private toStatus(origin: string, entry: CircuitEntry): CircuitStatus {
+    return {
+      origin,
+      state: entry.state,
+      failures: entry.failures,
+      retry_at: entry.state === 'open' ? entry.openedAt + this.config.cooldown_ms : null
+    }
+  }
+
+  private openError(origin: string, entry: CircuitEntry): PrizmError {
+    const retryIn = Math.max(0, entry.openedAt + this.config.cooldown_ms - this.now())
+    return PrizmError.network(
+      `Circuit open for ${origin} after ${entry.failures} consecutive failures, ` +
+        `retry in ${Math.ceil(retryIn / 1000)}s`
+    )
+  }
+}
@@ -0,0 +1,19 @@
+import { app, Notification } from 'electron'
+import type { ClientCapabilities } from './prizmApi'
+
+/** 本客户端内置、无需运行时检测的功能 */
+const BUILTIN_FEATURES = ['ws', 'file-transfer', 'clipboard-sync', 'browser-node']
+
+/**
+ * 注册时向服务端声明的能力：平台与架构、应用版本，
+ * 以及内置功能加上当前系统实际可用的功能（如系统通知）
+ */
+export function detectCapabilities(): ClientCapabilities {
+  const features = [...BUILTIN_FEATURES]
+  if (Notification.isSupported()) features.push('notifications')
+  return {
+    platform: `${process.platform}-${process.arch}`,
+    appVersion: app.getVersion(),
+    features
+  }
+}
@@ -1,6 +1,7 @@
 import { clipboard } from 'electron'
 import log from 'electron-log/main'
 import { sharedState } from './config'
+import { httpClient } from './httpClient'
 
 let clipboardSyncInterval: ReturnType<typeof setInterval> | null = null
 let lastClipboardText = ''
@@ -23,19 +24,17 @@ export function startClipboardSync(serverUrl: string, apiKey: string, scope: str
 
     const base = serverUrl.replace(/\/+$/, '')
     const url = `${base}/clipboard`
-    fetch(url, {
-      method: 'POST',
-      headers: {
-        'Content-Type': 'application/json',
-        ...(apiKey ? { Authorization: `Bearer ${apiKey}` } : {})
-      },
-      body: JSON.stringify({
-        type: 'text',
-        content: text,
-        createdAt: Date.now(),
-        scope: scope || 'default'
-      })
-    })
+    httpClient
+      .post(
+        url,
+        {
+          type: 'text',
+          content: text,
+          createdAt: Date.now(),
+          scope: scope || 'default'
+        },
+        { headers: apiKey ? { Authorization: `Bearer ${apiKey}` } : {} }
+      )
       .then((resp) => {
         if (resp.ok && sharedState.mainWindow && !sharedState.mainWindow.isDestroyed()) {
           sharedState.mainWindow.webContents.send('clipboard-item-added')
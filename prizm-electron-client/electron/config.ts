@@ -2,22 +2,393 @@ import { app } from 'electron'
 import type { BrowserWindow, Tray } from 'electron'
 import * as path from 'path'
 import * as fs from 'fs'
+import * as net from 'net'
 import log from 'electron-log/main'
+import { parseToml, stringifyToml } from './toml'
+import { writeFileAtomic } from './fsUtils'
+import { PrizmError } from './errors'
+import { maskSecrets } from './configMask'
+import { SERVER_SCHEMES, normalizeBasePath } from './serverUrl'
+import type { ServerScheme } from './serverUrl'
+import { decryptSecret, encryptSecret, isEncryptedSecret, useSecretKeyFile } from './secretCrypto'
+import {
+  ADMIN_KEY_ACCOUNT,
+  API_KEY_ACCOUNT,
+  REFRESH_TOKEN_ACCOUNT,
+  identityAccount,
+  isKeyringAvailable,
+  profileAccount,
+  profileAdminAccount,
+  readCredentials,
+  writeCredentials
+} from './credentialStore'
 
 export type ThemeMode = 'auto' | 'light' | 'dark'
 
+/** API Key 存储位置：keyring 为系统凭据存储，file 为加密后写入配置文件（便携安装） */
+export type CredentialStore = 'keyring' | 'file'
+
+/** 内置 scope 预设 */
+export const DEFAULT_SCOPE_PRESETS: Record<string, string[]> = {
+  standard: ['default', 'online'],
+  'default-only': ['default'],
+  'online-only': ['online']
+}
+
+/** 网络相关设置，所有对服务端的 HTTP 请求共用 */
+export interface NetworkConfig {
+  /** 等待响应头（建立连接 + 首字节）的超时，毫秒 */
+  connect_timeout_ms: number
+  /** 整个请求（含读取响应体）的超时，毫秒 */
+  read_timeout_ms: number
+  /** 健康检查 / 测试连接的超时，毫秒 */
+  health_timeout_ms: number
+  retry: RetryPolicy
+  proxy: ProxyConfig
+  /** 额外信任的 CA 证书（PEM 文件路径，相对路径相对配置目录），用于私有 CA 签发的服务器证书 */
+  extra_ca_certs: string[]
+  /** 只允许加密连接：http 请求自动升级为 https，无法升级的地址直接拒绝 */
+  require_tls: boolean
+  /** 每个请求附带的额外静态请求头；鉴权、Host 等保留头会被忽略 */
+  extra_headers: Record<string, string>
+  /**
+   * 主机名 → IP 的静态解析（类似 hosts 文件），URL 与 TLS 校验仍使用原主机名。
+   * 通过 Chromium 启动参数生效，修改后需重启应用
+   */
+  host_overrides: Record<string, string>
+  /**
+   * 空闲连接保留时间上限，毫秒；超过后关闭连接池中的空闲连接。
+   * 部分反向代理会提前关闭 keep-alive 连接，此时应调小
+   */
+  pool_idle_timeout_ms: number
+  /** 每个主机保留的空闲连接数；0 表示不复用连接（每次请求后关闭） */
+  pool_max_idle_per_host: number
+  /** 是否允许 HTTP/2；通过 Chromium 启动参数生效，修改后需重启应用 */
+  http2: boolean
+  /**
+   * 对需鉴权的请求做 HMAC-SHA256 签名（时间戳 + 随机数防重放），不再发送原始 API Key；
+   * 需服务端支持，见 requestSigning.ts
+   */
+  sign_requests: boolean
+  circuit_breaker: CircuitBreakerConfig
+  /**
+   * 健康检查依次尝试的路径（相对 server.base_path），404 时尝试下一个；
+   * 成功的路径记录到 server.health_path，下次优先使用
+   */
+  health_paths: string[]
+  heartbeat: HeartbeatConfig
+  /**
+   * 实时连接协商 permessage-deflate 压缩，事件密集或按流量计费时可明显减少流量；
+   * 压缩会占用 CPU，低性能设备可关闭。修改后自动重连生效
+   */
+  realtime_compression: boolean
+}
+
+export type ProxyMode = 'system' | 'manual' | 'none'
+export const PROXY_MODES: ProxyMode[] = ['system', 'manual', 'none']
+
+/** 代理设置：system 跟随系统，manual 使用 url，none 直连 */
+export interface ProxyConfig {
+  mode: ProxyMode
+  /** manual 模式的代理地址，如 http://proxy.corp:8080、socks5://127.0.0.1:1080 */
+  url: string
+  /** 不走代理的主机，逗号分隔，如 localhost,127.0.0.1,.corp */
+  bypass: string
+}
+
+export const DEFAULT_PROXY_CONFIG: ProxyConfig = { mode: 'system', url: '', bypass: '' }
+
+/** 幂等请求的重试策略：第 n 次重试等待 min(base * 2^(n-1), max)，开启 jitter 时在 [0, 该值] 内随机 */
+export interface RetryPolicy {
+  /** 总尝试次数（含首次），1 表示不重试 */
+  attempts: number
+  base_delay_ms: number
+  max_delay_ms: number
+  jitter: boolean
+}
+
+export const DEFAULT_RETRY_POLICY: RetryPolicy = {
+  attempts: 3,
+  base_delay_ms: 300,
+  max_delay_ms: 5_000,
+  jitter: true
+}
+
+/** 按服务器熔断：连续失败 failure_threshold 次后 cooldown_ms 内请求直接失败，冷却后放行一个试探请求 */
+export interface CircuitBreakerConfig {
+  /** 触发熔断的连续失败次数，0 表示关闭熔断 */
+  failure_threshold: number
+  cooldown_ms: number
+}
+
+export const DEFAULT_CIRCUIT_BREAKER: CircuitBreakerConfig = {
+  failure_threshold: 5,
+  cooldown_ms: 30_000
+}
+
+/** 实时连接（WebSocket）心跳：每 interval_ms 发送 ping，连续 max_missed 次未收到 pong 即判定连接已断 */
+export interface HeartbeatConfig {
+  /** 心跳间隔，毫秒；0 表示关闭心跳 */
+  interval_ms: number
+  max_missed: number
+}
+
+export const DEFAULT_HEARTBEAT: HeartbeatConfig = {
+  interval_ms: 25_000,
+  max_missed: 2
+}
+
+export const DEFAULT_NETWORK_CONFIG: NetworkConfig = {
+  connect_timeout_ms: 10_000,
+  read_timeout_ms: 30_000,
+  health_timeout_ms: 5_000,
+  retry: { ...DEFAULT_RETRY_POLICY },
+  proxy: { ...DEFAULT_PROXY_CONFIG },
+  extra_ca_certs: [],
+  require_tls: false,
+  extra_headers: {},
+  host_overrides: {},
+  pool_idle_timeout_ms: 90_000,
+  pool_max_idle_per_host: 6,
+  http2: true,
+  sign_requests: false,
+  circuit_breaker: { ...DEFAULT_CIRCUIT_BREAKER },
+  health_paths: ['/health', '/healthz'],
+  heartbeat: { ...DEFAULT_HEARTBEAT },
+  realtime_compression: true
+}
+
+export interface ServerConfig {
+  host: string
+  port: number
+  is_dev?: boolean
+  /** 协议，默认 http；ws/wss 与 http/https 等价，按用途自动映射 */
+  scheme?: ServerScheme
+  /**
+   * 是否校验该服务器的 TLS 证书，默认 true；false 时 HTTP 请求与实时连接都不校验，
+   * 仅用于自签名证书的测试环境（长期使用应改用 network.extra_ca_certs）
+   */
+  verify_tls?: boolean
+  /** 服务挂在反向代理子路径下时的前缀，如 /prizm */
+  base_path?: string
+  /**
+   * 本机服务端的 Unix 域套接字路径或 Windows 命名管道（\\.\pipe\prizm）。
+   * 设置后主进程发往该服务器的 HTTP 请求走本地套接字而非 TCP；WebSocket 仍使用 host:port
+   */
+  socket_path?: string
+  /** 上次健康检查成功的路径（见 network.health_paths） */
+  health_path?: string
+}
+
+/** 单个服务器档案：切换时整体替换顶层 server / api_key / requested_scopes */
+export interface ServerProfile {
+  server: ServerConfig
+  api_key: string
+  /** 该服务器的管理员 Key */
+  admin_key?: string
+  requested_scopes: string[]
+  granted_scopes?: string[]
+}
+
+/**
+ * 同一服务器上的一个客户端身份（如“管理模式”与“只读模式”各自注册的客户端），
+ * 切换时替换顶层 client.name / api_key / scope
+ */
+export interface ClientIdentity {
+  /** 所属服务器，host:port */
+  server: string
+  /** 服务端 clientId */
+  client_name: string
+  /** 注册时使用的名称，见 client.registration_name */
+  registration_name?: string
+  api_key: string
+  requested_scopes: string[]
+  granted_scopes: string[]
+}
+
+/**
+ * 通知分类：connection 与服务器的连接状态；server_alerts 服务端推送的通知；
+ * sync 数据同步（如 TODO 列表更新）；updates 应用更新
+ */
+export type NotificationCategory = 'connection' | 'server_alerts' | 'sync' | 'updates'
+export const NOTIFICATION_CATEGORIES: readonly NotificationCategory[] = [
+  'connection',
+  'server_alerts',
+  'sync',
+  'updates'
+]
+
+/** 通知级别，与 @prizm/shared 的 NotificationSeverity 一致 */
+export type NotificationSeverity = 'info' | 'warning' | 'error'
+export const NOTIFICATION_SEVERITIES: readonly NotificationSeverity[] = ['info', 'warning', 'error']
+
+/** 免打扰时段（本机时间）：期间不弹出系统通知，但仍记入通知历史 */
+export interface QuietHours {
+  enabled: boolean
+  /** HH:MM，start 晚于 end 时跨越午夜，如 22:00–08:00；两者相同视为不设时段 */
+  start: string
+  end: string
+  /** 周六、周日全天免打扰 */
+  weekends: boolean
+}
+
+/** 系统通知偏好；tray.show_notification 关闭时全部不弹出 */
+export interface NotificationPreferences {
+  /** 各分类是否弹出系统通知，默认全部开启 */
+  categories: Record<NotificationCategory, boolean>
+  /** 低于该级别的通知不弹出，默认 info */
+  min_severity: NotificationSeverity
+  quiet_hours: QuietHours
+  /**
+   * 各分类的提示音：default 为系统默认提示音，none 为静音，其余为音频文件的绝对路径
+   * （由本机播放器播放，重要通知可选用更醒目的声音）
+   */
+  sounds: Record<NotificationCategory, string>
+  /** 与服务器断开超过该时长（毫秒）才提示“服务器无法连接”，短暂断线不打扰；默认 30 秒 */
+  server_down_grace_ms: number
+}
+
+export const DEFAULT_QUIET_HOURS: QuietHours = {
+  enabled: false,
+  start: '22:00',
+  end: '08:00',
+  weekends: false
+}
+
+export const DEFAULT_NOTIFICATION_PREFERENCES: NotificationPreferences = {
+  categories: { connection: true, server_alerts: true, sync: true, updates: true },
+  min_severity: 'info',
+  quiet_hours: DEFAULT_QUIET_HOURS,
+  sounds: { connection: 'default', server_alerts: 'default', sync: 'default', updates: 'default' },
+  server_down_grace_ms: 30_000
+}
+
+/**
+ * 托盘图标的点击动作：toggle_window 显示并聚焦或隐藏主窗口，menu 弹出托盘菜单，
+ * dashboard 打开管理面板，none 不响应
+ */
+export type TrayClickAction = 'toggle_window' | 'menu' | 'dashboard' | 'none'
+export const TRAY_CLICK_ACTIONS: readonly TrayClickAction[] = [
+  'toggle_window',
+  'menu',
+  'dashboard',
+  'none'
+]
+
+/** 托盘图标单击、右键与双击的动作（Linux 托盘只能弹出菜单，不受此配置影响） */
+export interface TrayClickBindings {
+  left_click: TrayClickAction
+  right_click: TrayClickAction
+  double_click: TrayClickAction
+}
+
+export const DEFAULT_TRAY_CLICK_BINDINGS: TrayClickBindings = {
+  left_click: 'toggle_window',
+  right_click: 'menu',
+  double_click: 'dashboard'
+}
+
+/** 显示/隐藏主窗口的默认全局快捷键 */
+export const DEFAULT_GLOBAL_HOTKEY = 'CommandOrControl+Shift+P'
+
+/** 主窗口的位置、大小（未最大化时）与最大化状态 */
+export interface WindowGeometry {
+  x: number
+  y: number
+  width: number
+  height: number
+  maximized: boolean
+}
+
+/** 窗口状态，按显示器布局分别记录，接上或拔掉外接显示器后各自恢复 */
+export interface WindowConfig {
+  /** key 为显示器布局标识（各显示器的位置、尺寸与缩放） */
+  layouts: Record<string, WindowGeometry>
+}
+
+/** 主窗口最小尺寸，与 BrowserWindow 的 minWidth、minHeight 一致 */
+export const MIN_WINDOW_WIDTH = 400
+export const MIN_WINDOW_HEIGHT = 500
+
+/** 经实时连接向服务端开放的本机服务（反向隧道），需服务端同时开启 */
+export interface TunnelConfig {
+  /** 隧道名，服务端访问路径为 /tunnels/{clientId}/{name}/ */
+  name: string
+  local_port: number
+  /** 只允许本机回环地址，默认 127.0.0.1 */
+  local_host: string
+  /** 访问该隧道需要持有的 scope，默认 default */
+  scope: string
+  /** 默认关闭，须逐个手动开启 */
+  enabled: boolean
+}
+
 export interface PrizmConfig {
-  server: { host: string; port: string; is_dev?: string }
-  client: { name: string; auto_register: string; requested_scopes: string[] }
+  /** 配置结构版本，加载时按版本逐级迁移 */
+  version?: number
+  server: ServerConfig
+  client: {
+    /** 注册前为要使用的名称，注册后为服务端分配的 clientId */
+    name: string
+    /**
+     * 注册时用户填写的名称。client.name 注册后被 clientId 覆盖，
+     * 重新注册、配对时用这里的名称，以便服务端按名称复用原有 clientId
+     */
+    registration_name?: string
+    auto_register: boolean
+    requested_scopes: string[]
+    /** 注册时服务端实际授予的 scope，未注册时为空 */
+    granted_scopes: string[]
+    /** 预设的 scope 组合，注册界面可直接选用 */
+    scope_presets?: Record<string, string[]>
+    /** 是否通过实时连接上报在线状态（online / idle / away），默认开启 */
+    report_presence?: boolean
+  }
   api_key: string
+  /**
+   * api_key 的过期时间（毫秒时间戳），服务端签发永久 Key 时为空。
+   * 仅描述当前 api_key，切换档案或身份后清除
+   */
+  api_key_expires_at?: number
+  /** 续期 api_key 用的刷新令牌，与 api_key 一样加密或存入系统凭据存储 */
+  refresh_token?: string
+  /**
+   * 当前服务器的管理员 Key，用于审批客户端、吊销或重新生成其他客户端的 Key 等管理操作；
+   * 与 api_key 分开保存，随档案切换
+   */
+  admin_key?: string
   tray: {
-    enabled: string
-    minimize_to_tray: string
-    show_notification: string
-  }
+    enabled: boolean
+    minimize_to_tray: boolean
+    show_notification: boolean
+    /** 启动时不显示主窗口，直接进入托盘（适合开机自启），默认关闭；也可用 --minimized 启动 */
+    start_minimized: boolean
+    /** 显示/隐藏主窗口的全局快捷键（Electron accelerator 格式），空字符串表示不注册 */
+    global_hotkey: string
+  } & TrayClickBindings
   notify_events?: string[]
+  /** 按分类与级别过滤系统通知 */
+  notifications?: NotificationPreferences
+  /** 已保存的服务器档案，key 为档案名 */
+  profiles?: Record<string, ServerProfile>
+  /** 当前使用的档案名 */
+  active_profile?: string
+  /** 已注册的客户端身份，key 为身份名 */
+  identities?: Record<string, ClientIdentity>
+  /** 当前使用的身份名 */
+  active_identity?: string
+  /** API Key 存储位置，默认 keyring（便携模式默认 file）；凭据存储不可用时回退到 file */
+  credential_store?: CredentialStore
+  /** 网络设置：超时等 */
+  network?: NetworkConfig
   /** 用户手动选择的主题模式，持久化以便主进程启动时读取 */
   themeMode?: ThemeMode
+  /** 反向隧道 */
+  tunnels?: TunnelConfig[]
+  /** 主窗口位置与大小，由主进程在移动、缩放后自动保存 */
+  window?: WindowConfig
+  /** 内嵌管理面板窗口的位置与大小，保存方式同 window */
+  dashboard_window?: WindowConfig
 }
 
 export interface NotificationQueueItem {
@@ -34,73 +405,1106 @@ export const sharedState: {
   mainWindow: BrowserWindow | null
   notificationWindow: BrowserWindow | null
   quickPanelWindow: BrowserWindow | null
+  /** 内嵌的服务端管理面板窗口 */
+  dashboardWindow: BrowserWindow | null
   tray: Tray | null
   isQuitting: boolean
   trayEnabled: boolean
   minimizeToTray: boolean
+  /** tray.show_notification：服务端推送的通知是否弹出系统通知 */
+  showNotification: boolean
+  /** 托盘“暂停通知”（立即免打扰）：临时不弹出系统通知，不写入配置，重启后恢复 */
+  notificationsPaused: boolean
+  /** notifications：按分类与级别过滤系统通知 */
+  notificationPreferences: NotificationPreferences
   notificationQueue: NotificationQueueItem[]
 } = {
   mainWindow: null,
   notificationWindow: null,
   quickPanelWindow: null,
+  dashboardWindow: null,
   tray: null,
   isQuitting: false,
   trayEnabled: true,
   minimizeToTray: true,
+  showNotification: true,
+  notificationsPaused: false,
+  notificationPreferences: DEFAULT_NOTIFICATION_PREFERENCES,
   notificationQueue: []
 }
 
+/** 最近一次由本进程写入的配置内容，供文件监听区分外部修改 */
+let lastWrittenContent: string | null = null
+
+/**
+ * 判断磁盘内容是否就是本进程最近一次写入的内容
+ */
+export function isOwnConfigWrite(content: string): boolean {
+  return lastWrittenContent !== null && content === lastWrittenContent
+}
+
+export type ConfigFormat = 'json' | 'toml'
+
+let portableMode: boolean | null = null
+
+/**
+ * 便携模式：可执行文件旁存在 portable.flag 或以 --portable 启动。
+ * 便携模式下配置存放在可执行文件旁的 data/ 目录，不写入宿主机用户目录。
+ */
+export function isPortableMode(): boolean {
+  if (portableMode === null) {
+    portableMode =
+      process.argv.includes('--portable') ||
+      fs.existsSync(path.join(path.dirname(app.getPath('exe')), 'portable.flag'))
+  }
+  return portableMode
+}
+
+/**
+ * 启动时是否直接进入托盘：以 --minimized 启动或开启 tray.start_minimized。
+ * 托盘关闭时隐藏的窗口无从打开，仍然显示主窗口
+ */
+export function shouldStartMinimized(config: PrizmConfig, argv: string[] = process.argv): boolean {
+  if (config.tray.enabled === false) return false
+  return argv.includes('--minimized') || config.tray.start_minimized === true
+}
+
+/**
+ * 显式指定的配置目录：--config-dir=<path> 优先于 PRIZM_CONFIG_DIR 环境变量
+ */
+export function getConfigDirOverride(): string | null {
+  const flag = '--config-dir'
+  for (let i = 0; i < process.argv.length; i++) {
+    const arg = process.argv[i]
+    if (arg.startsWith(`${flag}=`)) {
+      const value = arg.slice(flag.length + 1).trim()
+      if (value) return path.resolve(value)
+    } else if (arg === flag && process.argv[i + 1]) {
+      return path.resolve(process.argv[i + 1])
+    }
+  }
+  const env = process.env.PRIZM_CONFIG_DIR?.trim()
+  return env ? path.resolve(env) : null
+}
+
+/**
+ * 配置目录：显式指定 > 便携模式 <exe 目录>/data > 用户配置目录下的 prizm-client
+ */
+function resolveConfigDir(): string {
+  const override = getConfigDirOverride()
+  if (override) {
+    return override
+  }
+  if (isPortableMode()) {
+    const dataDir = path.join(path.dirname(app.getPath('exe')), 'data')
+    // 机器绑定密钥会让配置无法随 U 盘迁移，便携模式改用数据目录内的随机密钥
+    useSecretKeyFile(path.join(dataDir, '.secret-key'))
+    return dataDir
+  }
+  return path.join(app.getPath('appData'), 'prizm-client')
+}
+
+/**
+ * 配置格式：PRIZM_CONFIG_FORMAT 环境变量优先，否则仅存在 config.toml 时使用 TOML
+ */
+function resolveConfigFormat(configDir: string): ConfigFormat {
+  const env = process.env.PRIZM_CONFIG_FORMAT?.trim().toLowerCase()
+  if (env === 'json' || env === 'toml') return env
+  if (
+    !fs.existsSync(path.join(configDir, 'config.json')) &&
+    fs.existsSync(path.join(configDir, 'config.toml'))
+  ) {
+    return 'toml'
+  }
+  return 'json'
+}
+
+/**
+ * 获取配置文件路径：与 Tauri 大致对齐，默认存放在用户配置目录下的 prizm-client/config.json（或 config.toml），
+ * 可通过 --config-dir / PRIZM_CONFIG_DIR / 便携模式改变目录
+ */
+export function getConfigPath(): { configDir: string; configPath: string; format: ConfigFormat } {
+  const configDir = resolveConfigDir()
+  const format = resolveConfigFormat(configDir)
+  const configPath = path.join(configDir, `config.${format}`)
+  return { configDir, configPath, format }
+}
+
+/**
+ * 按格式解析配置文件内容（未做规范化）
+ */
+export function parseConfigContent(content: string, format: ConfigFormat): unknown {
+  return format === 'toml' ? parseToml(content) : JSON.parse(content)
+}
+
+/**
+ * 按格式序列化配置
+ */
+export function serializeConfig(config: PrizmConfig, format: ConfigFormat): string {
+  return format === 'toml'
+    ? stringifyToml(config as unknown as Record<string, unknown>)
+    : JSON.stringify(config, null, 2)
+}
+
+/**
+ * 默认配置
+ */
+export function createDefaultConfig(): PrizmConfig {
+  return {
+    version: CURRENT_CONFIG_VERSION,
+    server: {
+      host: '127.0.0.1',
+      port: 4127,
+      is_dev: true,
+      scheme: 'http',
+      verify_tls: true,
+      base_path: ''
+    },
+    client: {
+      name: 'Prizm Electron Client',
+      auto_register: true,
+      requested_scopes: ['default', 'online'],
+      granted_scopes: [],
+      scope_presets: { ...DEFAULT_SCOPE_PRESETS },
+      report_presence: true
+    },
+    api_key: '',
+    tray: {
+      enabled: true,
+      minimize_to_tray: true,
+      show_notification: true,
+      start_minimized: false,
+      global_hotkey: DEFAULT_GLOBAL_HOTKEY,
+      ...DEFAULT_TRAY_CLICK_BINDINGS
+    },
+    notify_events: ['notification', 'todo_list:created', 'todo_list:updated', 'todo_list:deleted'],
+    notifications: normalizeNotificationPreferences(undefined),
+    network: {
+      ...DEFAULT_NETWORK_CONFIG,
+      retry: { ...DEFAULT_RETRY_POLICY },
+      proxy: { ...DEFAULT_PROXY_CONFIG },
+      extra_ca_certs: [],
+      require_tls: false,
+      extra_headers: {},
+      host_overrides: {},
+      circuit_breaker: { ...DEFAULT_CIRCUIT_BREAKER },
+      health_paths: [...DEFAULT_NETWORK_CONFIG.health_paths],
+      heartbeat: { ...DEFAULT_HEARTBEAT }
+    }
+  }
+}
+
+/**
+ * 旧版配置（与 Tauri 客户端共用格式）以字符串 "true"/"false" 存储布尔值，读取时兼容
+ */
+function coerceBool(value: unknown, fallback: boolean): { value: boolean; legacy: boolean } {
+  if (typeof value === 'boolean') return { value, legacy: false }
+  if (typeof value === 'string') {
+    const v = value.trim().toLowerCase()
+    if (v === 'true') return { value: true, legacy: true }
+    if (v === 'false') return { value: false, legacy: true }
+  }
+  return { value: fallback, legacy: value !== undefined }
+}
+
+/**
+ * 端口兼容旧版字符串形式，非法值回退为默认端口
+ */
+function coercePort(value: unknown, fallback: number): { value: number; legacy: boolean } {
+  if (typeof value === 'number' && Number.isInteger(value) && value > 0 && value <= 65535) {
+    return { value, legacy: false }
+  }
+  if (typeof value === 'string' && /^\d+$/.test(value.trim())) {
+    const n = parseInt(value.trim(), 10)
+    if (n > 0 && n <= 65535) return { value: n, legacy: true }
+  }
+  return { value: fallback, legacy: value !== undefined }
+}
+
+/**
+ * 正整数毫秒值，非法时回退默认
+ */
+function coerceTimeout(value: unknown, fallback: number): number {
+  const n = typeof value === 'string' ? Number(value) : value
+  return typeof n === 'number' && Number.isFinite(n) && n > 0 ? Math.round(n) : fallback
+}
+
+/** 非负整数（允许 0），无效时使用默认值 */
+function coerceCount(value: unknown, fallback: number): number {
+  const n = typeof value === 'string' ? Number(value) : value
+  return typeof n === 'number' && Number.isFinite(n) && n >= 0 ? Math.round(n) : fallback
+}
+
+/**
+ * 网络设置：缺省字段使用默认值
+ */
+export function normalizeNetworkConfig(value: unknown): NetworkConfig {
+  const network = (value && typeof value === 'object' ? value : {}) as Record<string, unknown>
+  return {
+    ...network,
+    connect_timeout_ms: coerceTimeout(
+      network.connect_timeout_ms,
+      DEFAULT_NETWORK_CONFIG.connect_timeout_ms
+    ),
+    read_timeout_ms: coerceTimeout(network.read_timeout_ms, DEFAULT_NETWORK_CONFIG.read_timeout_ms),
+    health_timeout_ms: coerceTimeout(
+      network.health_timeout_ms,
+      DEFAULT_NETWORK_CONFIG.health_timeout_ms
+    ),
+    retry: normalizeRetryPolicy(network.retry),
+    proxy: normalizeProxyConfig(network.proxy),
+    extra_ca_certs: Array.isArray(network.extra_ca_certs)
+      ? network.extra_ca_certs.filter((f): f is string => typeof f === 'string' && f.trim() !== '')
+      : [],
+    require_tls: coerceBool(network.require_tls, false).value,
+    extra_headers: normalizeExtraHeaders(network.extra_headers),
+    host_overrides: normalizeHostOverrides(network.host_overrides),
+    pool_idle_timeout_ms: coerceCount(
+      network.pool_idle_timeout_ms,
+      DEFAULT_NETWORK_CONFIG.pool_idle_timeout_ms
+    ),
+    pool_max_idle_per_host: coerceCount(
+      network.pool_max_idle_per_host,
+      DEFAULT_NETWORK_CONFIG.pool_max_idle_per_host
+    ),
+    http2: coerceBool(network.http2, true).value,
+    sign_requests: coerceBool(network.sign_requests, false).value,
+    circuit_breaker: normalizeCircuitBreaker(network.circuit_breaker),
+    health_paths: normalizeHealthPaths(network.health_paths),
+    heartbeat: normalizeHeartbeat(network.heartbeat),
+    realtime_compression: coerceBool(network.realtime_compression, true).value
+  }
+}
+
+/** 健康检查路径：以 / 开头、去重，为空时使用默认列表 */
+function normalizeHealthPaths(value: unknown): string[] {
+  const paths = Array.isArray(value)
+    ? value
+        .filter((p): p is string => typeof p === 'string' && p.trim() !== '')
+        .map((p) => normalizeHealthPath(p))
+    : []
+  const unique = [...new Set(paths)]
+  return unique.length > 0 ? unique : [...DEFAULT_NETWORK_CONFIG.health_paths]
+}
+
+function normalizeHealthPath(path: string): string {
+  const trimmed = path.trim()
+  return trimmed.startsWith('/') ? trimmed : `/${trimmed}`
+}
+
+function normalizeCircuitBreaker(value: unknown): CircuitBreakerConfig {
+  const breaker = (value && typeof value === 'object' ? value : {}) as Record<string, unknown>
+  return {
+    failure_threshold: coerceCount(
+      breaker.failure_threshold,
+      DEFAULT_CIRCUIT_BREAKER.failure_threshold
+    ),
+    cooldown_ms: coerceTimeout(breaker.cooldown_ms, DEFAULT_CIRCUIT_BREAKER.cooldown_ms)
+  }
+}
+
+function normalizeHeartbeat(value: unknown): HeartbeatConfig {
+  const heartbeat = (value && typeof value === 'object' ? value : {}) as Record<string, unknown>
+  return {
+    interval_ms: coerceCount(heartbeat.interval_ms, DEFAULT_HEARTBEAT.interval_ms),
+    max_missed: coerceTimeout(heartbeat.max_missed, DEFAULT_HEARTBEAT.max_missed)
+  }
+}
+
+/** 主机名（可用 * 通配前缀，如 *.corp） */
+const HOSTNAME = /^(\*\.)?[A-Za-z0-9]([A-Za-z0-9.-]{0,251}[A-Za-z0-9])?$/
+
+function normalizeHostOverrides(value: unknown): Record<string, string> {
+  if (!value || typeof value !== 'object' || Array.isArray(value)) return {}
+  const overrides: Record<string, string> = {}
+  for (const [host, ip] of Object.entries(value)) {
+    if (typeof ip !== 'string') continue
+    const address = ip.trim().replace(/^\[|\]$/g, '')
+    if (HOSTNAME.test(host) && net.isIP(address)) {
+      overrides[host.toLowerCase()] = address
+    }
+  }
+  return overrides
+}
+
+/** 不允许通过 extra_headers 覆盖的请求头 */
+const RESERVED_HEADERS = new Set([
+  'authorization',
+  'x-prizm-api-key',
+  'x-prizm-client-id',
+  'x-prizm-signature',
+  'x-prizm-timestamp',
+  'x-prizm-nonce',
+  'host',
+  'content-length',
+  'content-type',
+  'user-agent'
+])
+const HEADER_NAME = /^[!#$%&'*+.^_`|~0-9A-Za-z-]+$/
+
+function normalizeExtraHeaders(value: unknown): Record<string, string> {
+  if (!value || typeof value !== 'object' || Array.isArray(value)) return {}
+  const headers: Record<string, string> = {}
+  for (const [name, headerValue] of Object.entries(value)) {
+    if (!HEADER_NAME.test(name) || RESERVED_HEADERS.has(name.toLowerCase())) continue
+    if (typeof headerValue !== 'string' && typeof headerValue !== 'number') continue
+    const text = String(headerValue)
+    if (/[\r\n]/.test(text)) continue
+    headers[name] = text
+  }
+  return headers
+}
+
+function normalizeProxyConfig(value: unknown): ProxyConfig {
+  const proxy = (value && typeof value === 'object' ? value : {}) as Record<string, unknown>
+  return {
+    mode: PROXY_MODES.includes(proxy.mode as ProxyMode)
+      ? (proxy.mode as ProxyMode)
+      : DEFAULT_PROXY_CONFIG.mode,
+    url: typeof proxy.url === 'string' ? proxy.url.trim() : '',
+    bypass: typeof proxy.bypass === 'string' ? proxy.bypass.trim() : ''
+  }
+}
+
+function normalizeRetryPolicy(value: unknown): RetryPolicy {
+  const retry = (value && typeof value === 'object' ? value : {}) as Record<string, unknown>
+  const base = coerceTimeout(retry.base_delay_ms, DEFAULT_RETRY_POLICY.base_delay_ms)
+  return {
+    attempts: Math.min(coerceTimeout(retry.attempts, DEFAULT_RETRY_POLICY.attempts), 10),
+    base_delay_ms: base,
+    max_delay_ms: Math.max(
+      coerceTimeout(retry.max_delay_ms, DEFAULT_RETRY_POLICY.max_delay_ms),
+      base
+    ),
+    jitter: coerceBool(retry.jitter, DEFAULT_RETRY_POLICY.jitter).value
+  }
+}
+
+const CLOCK_TIME_PATTERN = /^([01]?\d|2[0-3]):([0-5]\d)$/
+
+/** 规范为两位数的 HH:MM，无法解析时返回 fallback */
+function normalizeClockTime(value: unknown, fallback: string): string {
+  const match = typeof value === 'string' ? CLOCK_TIME_PATTERN.exec(value.trim()) : null
+  return match ? `${match[1].padStart(2, '0')}:${match[2]}` : fallback
+}
+
+function normalizeQuietHours(value: unknown): QuietHours {
+  const quiet = (value && typeof value === 'object' ? value : {}) as Record<string, unknown>
+  return {
+    enabled: coerceBool(quiet.enabled, DEFAULT_QUIET_HOURS.enabled).value,
+    start: normalizeClockTime(quiet.start, DEFAULT_QUIET_HOURS.start),
+    end: normalizeClockTime(quiet.end, DEFAULT_QUIET_HOURS.end),
+    weekends: coerceBool(quiet.weekends, DEFAULT_QUIET_HOURS.weekends).value
+  }
+}
+
+/**
+ * 非法动作回退为默认值；托盘菜单是退出应用的入口，三者都不是 menu 时右键恢复为弹出菜单
+ */
+export function normalizeTrayClickBindings(value: unknown): TrayClickBindings {
+  const tray = (value && typeof value === 'object' ? value : {}) as Record<string, unknown>
+  const bindings = { ...DEFAULT_TRAY_CLICK_BINDINGS }
+  for (const key of Object.keys(bindings) as Array<keyof TrayClickBindings>) {
+    if (TRAY_CLICK_ACTIONS.includes(tray[key] as TrayClickAction)) {
+      bindings[key] = tray[key] as TrayClickAction
+    }
+  }
+  if (!Object.values(bindings).includes('menu')) bindings.right_click = 'menu'
+  return bindings
+}
+
+/** default、none 或音频文件的绝对路径，其余值回退为 default */
+function normalizeNotificationSound(value: unknown): string {
+  const sound = typeof value === 'string' ? value.trim() : ''
+  if (sound === 'none' || path.isAbsolute(sound)) return sound
+  return 'default'
+}
+
+/** 未知分类忽略，缺省分类开启，非法级别回退到 info，非法提示音回退到 default */
+export function normalizeNotificationPreferences(value: unknown): NotificationPreferences {
+  const prefs = (value && typeof value === 'object' ? value : {}) as Record<string, unknown>
+  const categories = (
+    prefs.categories && typeof prefs.categories === 'object' ? prefs.categories : {}
+  ) as Record<string, unknown>
+  const sounds = (
+    prefs.sounds && typeof prefs.sounds === 'object' ? prefs.sounds : {}
+  ) as Record<string, unknown>
+  const normalized = { ...DEFAULT_NOTIFICATION_PREFERENCES.categories }
+  const normalizedSounds = { ...DEFAULT_NOTIFICATION_PREFERENCES.sounds }
+  for (const category of NOTIFICATION_CATEGORIES) {
+    normalized[category] = coerceBool(categories[category], true).value
+    normalizedSounds[category] = normalizeNotificationSound(sounds[category])
+  }
+  return {
+    categories: normalized,
+    min_severity: NOTIFICATION_SEVERITIES.includes(prefs.min_severity as NotificationSeverity)
+      ? (prefs.min_severity as NotificationSeverity)
+      : 'info',
+    quiet_hours: normalizeQuietHours(prefs.quiet_hours),
+    sounds: normalizedSounds,
+    server_down_grace_ms: coerceCount(
+      prefs.server_down_grace_ms,
+      DEFAULT_NOTIFICATION_PREFERENCES.server_down_grace_ms
+    )
+  }
+}
+
+/** 丢弃坐标或尺寸非法的记录，尺寸不小于窗口最小尺寸 */
+function normalizeWindowConfig(value: unknown): WindowConfig | undefined {
+  if (!value || typeof value !== 'object') return undefined
+  const layouts = (value as Record<string, unknown>).layouts
+  const normalized: Record<string, WindowGeometry> = {}
+  if (layouts && typeof layouts === 'object') {
+    for (const [key, item] of Object.entries(layouts as Record<string, unknown>)) {
+      if (!item || typeof item !== 'object') continue
+      const g = item as Record<string, unknown>
+      const [x, y, width, height] = [g.x, g.y, g.width, g.height].map(Number)
+      if (![x, y, width, height].every(Number.isFinite)) continue
+      normalized[key] = {
+        x: Math.round(x),
+        y: Math.round(y),
+        width: Math.max(MIN_WINDOW_WIDTH, Math.round(width)),
+        height: Math.max(MIN_WINDOW_HEIGHT, Math.round(height)),
+        maximized: coerceBool(g.maximized, false).value
+      }
+    }
+  }
+  return { layouts: normalized }
+}
+
+const TUNNEL_NAME_PATTERN = /^[a-z0-9][a-z0-9-]{0,31}$/
+const LOOPBACK_HOSTS = ['127.0.0.1', '::1', 'localhost']
+
+/** 丢弃名称非法、重名、端口非法或指向非回环地址的隧道 */
+function normalizeTunnels(value: unknown): TunnelConfig[] | undefined {
+  if (!Array.isArray(value)) return undefined
+  const tunnels: TunnelConfig[] = []
+  for (const item of value) {
+    if (!item || typeof item !== 'object') continue
+    const tunnel = item as Record<string, unknown>
+    const name = typeof tunnel.name === 'string' ? tunnel.name.trim() : ''
+    if (!TUNNEL_NAME_PATTERN.test(name) || tunnels.some((t) => t.name === name)) continue
+    const port = Number(tunnel.local_port)
+    if (!Number.isInteger(port) || port < 1 || port > 65535) continue
+    const host = typeof tunnel.local_host === 'string' ? tunnel.local_host.trim() : '127.0.0.1'
+    if (!LOOPBACK_HOSTS.includes(host)) continue
+    tunnels.push({
+      name,
+      local_port: port,
+      local_host: host,
+      scope:
+        typeof tunnel.scope === 'string' && tunnel.scope.trim() ? tunnel.scope.trim() : 'default',
+      enabled: coerceBool(tunnel.enabled, false).value
+    })
+  }
+  return tunnels
+}
+
+/**
+ * scope 预设：缺省时使用内置预设，丢弃非字符串数组的条目
+ */
+function normalizeScopePresets(value: unknown): Record<string, string[]> {
+  if (!value || typeof value !== 'object' || Array.isArray(value)) {
+    return { ...DEFAULT_SCOPE_PRESETS }
+  }
+  return Object.fromEntries(
+    Object.entries(value as Record<string, unknown>).filter(
+      (entry): entry is [string, string[]] =>
+        Array.isArray(entry[1]) && entry[1].every((s) => typeof s === 'string')
+    )
+  )
+}
+
+/**
+ * 将磁盘上读到的原始 JSON 规范化为 PrizmConfig。
+ * migrated 为 true 表示存在旧版字符串字段，调用方应回写文件。
+ */
+export function normalizeConfig(raw: unknown): { config: PrizmConfig; migrated: boolean } {
+  const defaults = createDefaultConfig()
+  const obj = (raw && typeof raw === 'object' ? raw : {}) as Record<string, any>
+  const server = (obj.server ?? {}) as Record<string, unknown>
+  const client = (obj.client ?? {}) as Record<string, unknown>
+  const tray = (obj.tray ?? {}) as Record<string, unknown>
+  let migrated = false
+
+  const port = coercePort(server.port, defaults.server.port)
+  const isDev = coerceBool(server.is_dev, defaults.server.is_dev ?? true)
+  const autoRegister = coerceBool(client.auto_register, defaults.client.auto_register)
+  const trayEnabled = coerceBool(tray.enabled, defaults.tray.enabled)
+  const minimizeToTray = coerceBool(tray.minimize_to_tray, defaults.tray.minimize_to_tray)
+  const showNotification = coerceBool(tray.show_notification, defaults.tray.show_notification)
+  for (const field of [port, isDev, autoRegister, trayEnabled, minimizeToTray, showNotification]) {
+    if (field.legacy) migrated = true
+  }
+
+  const config: PrizmConfig = {
+    ...obj,
+    version: typeof obj.version === 'number' ? obj.version : CURRENT_CONFIG_VERSION,
+    server: {
+      ...server,
+      host: typeof server.host === 'string' ? server.host : defaults.server.host,
+      port: port.value,
+      is_dev: isDev.value,
+      scheme: SERVER_SCHEMES.includes(server.scheme as ServerScheme)
+        ? (server.scheme as ServerScheme)
+        : 'http',
+      verify_tls: coerceBool(server.verify_tls, true).value,
+      base_path: normalizeBasePath(server.base_path as string | undefined),
+      socket_path:
+        typeof server.socket_path === 'string' && server.socket_path.trim()
+          ? server.socket_path.trim()
+          : undefined,
+      health_path:
+        typeof server.health_path === 'string' && server.health_path.trim()
+          ? normalizeHealthPath(server.health_path)
+          : undefined
+    },
+    client: {
+      ...client,
+      name: typeof client.name === 'string' ? client.name : defaults.client.name,
+      registration_name:
+        typeof client.registration_name === 'string' ? client.registration_name : undefined,
+      auto_register: autoRegister.value,
+      requested_scopes: Array.isArray(client.requested_scopes)
+        ? (client.requested_scopes as string[])
+        : defaults.client.requested_scopes,
+      granted_scopes: Array.isArray(client.granted_scopes)
+        ? client.granted_scopes.filter((s): s is string => typeof s === 'string')
+        : [],
+      scope_presets: normalizeScopePresets(client.scope_presets),
+      report_presence: coerceBool(client.report_presence, true).value
+    },
+    api_key: typeof obj.api_key === 'string' ? obj.api_key : '',
+    api_key_expires_at:
+      typeof obj.api_key_expires_at === 'number' && obj.api_key_expires_at > 0
+        ? obj.api_key_expires_at
+        : undefined,
+    refresh_token:
+      typeof obj.refresh_token === 'string' && obj.refresh_token ? obj.refresh_token : undefined,
+    admin_key: typeof obj.admin_key === 'string' && obj.admin_key ? obj.admin_key : undefined,
+    tray: {
+      ...tray,
+      enabled: trayEnabled.value,
+      minimize_to_tray: minimizeToTray.value,
+      show_notification: showNotification.value,
+      start_minimized: coerceBool(tray.start_minimized, false).value,
+      global_hotkey:
+        typeof tray.global_hotkey === 'string' ? tray.global_hotkey.trim() : DEFAULT_GLOBAL_HOTKEY,
+      ...normalizeTrayClickBindings(tray)
+    },
+    notifications: normalizeNotificationPreferences(obj.notifications),
+    network: normalizeNetworkConfig(obj.network),
+    tunnels: normalizeTunnels(obj.tunnels),
+    window: normalizeWindowConfig(obj.window),
+    dashboard_window: normalizeWindowConfig(obj.dashboard_window)
+  }
+  return { config, migrated }
+}
+
+/** 当前配置结构版本 */
+export const CURRENT_CONFIG_VERSION = 2
+
+type RawConfig = Record<string, any>
+
+/**
+ * 配置迁移步骤：migrations[n] 将 vN 升级到 v(N+1)
+ */
+const CONFIG_MIGRATIONS: Record<number, (raw: RawConfig) => RawConfig> = {
+  /** v1（无 version 字段）：布尔与端口以字符串存储 */
+  1: (raw) => {
+    const defaults = createDefaultConfig()
+    const server = { ...(raw.server ?? {}) }
+    const client = { ...(raw.client ?? {}) }
+    const tray = { ...(raw.tray ?? {}) }
+    server.port = coercePort(server.port, defaults.server.port).value
+    if (server.is_dev !== undefined) server.is_dev = coerceBool(server.is_dev, true).value
+    client.auto_register = coerceBool(client.auto_register, defaults.client.auto_register).value
+    for (const key of ['enabled', 'minimize_to_tray', 'show_notification'] as const) {
+      tray[key] = coerceBool(tray[key], defaults.tray[key]).value
+    }
+    return { ...raw, server, client, tray }
+  }
+}
+
+/**
+ * 读取原始配置的版本号，无 version 字段视为 v1
+ */
+function getRawConfigVersion(raw: RawConfig): number {
+  return typeof raw.version === 'number' && Number.isInteger(raw.version) ? raw.version : 1
+}
+
+/**
+ * 逐级执行迁移，返回升级后的原始配置与起始版本
+ */
+export function migrateConfig(raw: unknown): { raw: RawConfig; fromVersion: number } {
+  let current: RawConfig = raw && typeof raw === 'object' ? { ...(raw as RawConfig) } : {}
+  const fromVersion = getRawConfigVersion(current)
+  if (fromVersion > CURRENT_CONFIG_VERSION) {
+    log.warn(
+      `[Electron] Config version ${fromVersion} is newer than supported ${CURRENT_CONFIG_VERSION}`
+    )
+    return { raw: current, fromVersion }
+  }
+  for (let v = fromVersion; v < CURRENT_CONFIG_VERSION; v++) {
+    const step = CONFIG_MIGRATIONS[v]
+    if (!step) {
+      throw new Error(`Missing config migration from v${v} to v${v + 1}`)
+    }
+    current = { ...step(current), version: v + 1 }
+  }
+  return { raw: current, fromVersion }
+}
+
+/**
+ * 解密顶层与各档案中的 api_key、admin_key（以及顶层 refresh_token）。plaintext 为 true 表示文件中仍有明文密钥，应回写加密。
+ */
+function decryptConfigSecrets(raw: RawConfig): { raw: RawConfig; plaintext: boolean } {
+  let plaintext = false
+  const decrypt = (value: unknown): string => {
+    if (typeof value !== 'string' || !value) return ''
+    if (!isEncryptedSecret(value)) {
+      plaintext = true
+      return value
+    }
+    try {
+      return decryptSecret(value)
+    } catch (err) {
+      log.warn('[Electron] Failed to decrypt api_key (config copied from another machine?):', err)
+      return ''
+    }
+  }
+  const result: RawConfig = { ...raw, api_key: decrypt(raw.api_key) }
+  if (raw.refresh_token) result.refresh_token = decrypt(raw.refresh_token)
+  if (raw.admin_key) result.admin_key = decrypt(raw.admin_key)
+  if (raw.profiles && typeof raw.profiles === 'object') {
+    result.profiles = Object.fromEntries(
+      Object.entries(raw.profiles as RawConfig).map(([name, profile]) => [
+        name,
+        {
+          ...profile,
+          api_key: decrypt(profile?.api_key),
+          ...(profile?.admin_key ? { admin_key: decrypt(profile.admin_key) } : {})
+        }
+      ])
+    )
+  }
+  if (raw.identities && typeof raw.identities === 'object') {
+    result.identities = Object.fromEntries(
+      Object.entries(raw.identities as RawConfig).map(([name, identity]) => [
+        name,
+        { ...identity, api_key: decrypt(identity?.api_key) }
+      ])
+    )
+  }
+  return { raw: result, plaintext }
+}
+
+/**
+ * 生成写盘用副本：api_key、admin_key 与 refresh_token 仅以密文形式序列化
+ */
+function encryptConfigSecrets(config: PrizmConfig): PrizmConfig {
+  const result: PrizmConfig = { ...config, api_key: encryptSecret(config.api_key) }
+  if (config.refresh_token) result.refresh_token = encryptSecret(config.refresh_token)
+  if (config.admin_key) result.admin_key = encryptSecret(config.admin_key)
+  if (config.profiles) {
+    result.profiles = Object.fromEntries(
+      Object.entries(config.profiles).map(([name, profile]) => [
+        name,
+        {
+          ...profile,
+          api_key: encryptSecret(profile.api_key),
+          ...(profile.admin_key ? { admin_key: encryptSecret(profile.admin_key) } : {})
+        }
+      ])
+    )
+  }
+  if (config.identities) {
+    result.identities = Object.fromEntries(
+      Object.entries(config.identities).map(([name, identity]) => [
+        name,
+        { ...identity, api_key: encryptSecret(identity.api_key) }
+      ])
+    )
+  }
+  return result
+}
+
+/**
+ * 将任意来源的配置文本解析为规范化的 PrizmConfig（迁移 + 解密 + 规范化），不落盘
+ */
+export function configFromContent(content: string, format: ConfigFormat): PrizmConfig {
+  const { raw: migratedRaw } = migrateConfig(parseConfigContent(content, format))
+  return normalizeConfig(decryptConfigSecrets(migratedRaw).raw).config
+}
+
+/**
+ * 同步读取网络设置，供 app ready 之前就要确定的启动参数（如 host_overrides）使用；失败时返回默认值
+ */
+export function readNetworkConfigSync(): NetworkConfig {
+  try {
+    const { configPath, format } = getConfigPath()
+    const raw = parseConfigContent(fs.readFileSync(configPath, 'utf-8'), format)
+    return normalizeNetworkConfig((raw as { network?: unknown } | null)?.network)
+  } catch {
+    return normalizeNetworkConfig(undefined)
+  }
+}
+
+/**
+ * 是否把 api_key 存入系统凭据存储；便携模式未显式指定时使用文件存储
+ */
+function usesKeyring(config: PrizmConfig): boolean {
+  const store = config.credential_store ?? (isPortableMode() ? 'file' : 'keyring')
+  return store === 'keyring' && isKeyringAvailable()
+}
+
+/**
+ * 收集需要写入凭据存储的 api_key、admin_key 与 refresh_token
+ */
+function collectCredentials(config: PrizmConfig): Record<string, string> {
+  const credentials: Record<string, string> = {
+    [API_KEY_ACCOUNT]: config.api_key,
+    [REFRESH_TOKEN_ACCOUNT]: config.refresh_token ?? '',
+    [ADMIN_KEY_ACCOUNT]: config.admin_key ?? ''
+  }
+  for (const [name, profile] of Object.entries(config.profiles ?? {})) {
+    credentials[profileAccount(name)] = profile.api_key
+    credentials[profileAdminAccount(name)] = profile.admin_key ?? ''
+  }
+  for (const [name, identity] of Object.entries(config.identities ?? {})) {
+    credentials[identityAccount(name)] = identity.api_key
+  }
+  return credentials
+}
+
+/**
+ * 从凭据存储回填 api_key。返回 true 表示配置文件里仍有密钥，应回写以迁入凭据存储。
+ */
+async function fillCredentialsFromKeyring(
+  config: PrizmConfig,
+  configDir: string
+): Promise<boolean> {
+  const credentials = await readCredentials(configDir)
+  let hasFileSecrets = false
+  if (config.api_key) {
+    hasFileSecrets = true
+  } else {
+    config.api_key = credentials[API_KEY_ACCOUNT] ?? ''
+  }
+  if (config.refresh_token) {
+    hasFileSecrets = true
+  } else if (credentials[REFRESH_TOKEN_ACCOUNT]) {
+    config.refresh_token = credentials[REFRESH_TOKEN_ACCOUNT]
+  }
+  if (config.admin_key) {
+    hasFileSecrets = true
+  } else if (credentials[ADMIN_KEY_ACCOUNT]) {
+    config.admin_key = credentials[ADMIN_KEY_ACCOUNT]
+  }
+  for (const [name, profile] of Object.entries(config.profiles ?? {})) {
+    if (profile.api_key) {
+      hasFileSecrets = true
+    } else {
+      profile.api_key = credentials[profileAccount(name)] ?? ''
+    }
+    if (profile.admin_key) {
+      hasFileSecrets = true
+    } else if (credentials[profileAdminAccount(name)]) {
+      profile.admin_key = credentials[profileAdminAccount(name)]
+    }
+  }
+  for (const [name, identity] of Object.entries(config.identities ?? {})) {
+    if (identity.api_key) {
+      hasFileSecrets = true
+    } else {
+      identity.api_key = credentials[identityAccount(name)] ?? ''
+    }
+  }
+  return hasFileSecrets
+}
+
 /**
- * 获取配置文件路径：与 Tauri 大致对齐，存放在用户配置目录下的 prizm-client/config.json
+ * 由文件内容还原配置，必要时（迁移、规范化、密钥迁移）回写磁盘
  */
-function getConfigPath(): { configDir: string; configPath: string } {
-  const configDir = path.join(app.getPath('appData'), 'prizm-client')
-  const configPath = path.join(configDir, 'config.json')
-  return { configDir, configPath }
+async function materializeConfig(
+  content: string,
+  format: ConfigFormat,
+  configDir: string,
+  configPath: string
+): Promise<PrizmConfig> {
+  const { raw: migratedRaw, fromVersion } = migrateConfig(parseConfigContent(content, format))
+  const { raw, plaintext } = decryptConfigSecrets(migratedRaw)
+  const { config, migrated } = normalizeConfig(raw)
+  const moveToKeyring =
+    usesKeyring(config) && (await fillCredentialsFromKeyring(config, configDir))
+  if (fromVersion < CURRENT_CONFIG_VERSION) {
+    const backupPath = `${configPath}.v${fromVersion}.bak`
+    await writeFileAtomic(backupPath, content)
+    log.info(
+      `[Electron] Migrated config v${fromVersion} -> v${CURRENT_CONFIG_VERSION}, backup:`,
+      backupPath
+    )
+    await saveConfigToDisk(config)
+  } else if (migrated) {
+    log.info('[Electron] Normalized legacy string-typed config fields:', configPath)
+    await saveConfigToDisk(config)
+  } else if (moveToKeyring) {
+    log.info('[Electron] Moving api_key from config file to OS credential store')
+    await saveConfigToDisk(config)
+  } else if (plaintext) {
+    log.info('[Electron] Encrypting plaintext api_key in config:', configPath)
+    await saveConfigToDisk(config)
+  }
+  return config
 }
 
 /**
- * 加载配置（如果不存在则返回默认配置）
+ * 加载配置（如果不存在则返回默认配置）。
+ * 旧版本配置会逐级迁移，原文件备份为 config.<ext>.v<N>.bak 后回写；
+ * 配置文件损坏时从上次保存前的 config.<ext>.bak 恢复。
  */
 export async function loadConfigFromDisk(): Promise<PrizmConfig> {
-  const { configDir, configPath } = getConfigPath()
+  const { configDir, configPath, format } = getConfigPath()
 
   await fs.promises.mkdir(configDir, { recursive: true })
 
+  let content: string
   try {
-    const content = await fs.promises.readFile(configPath, 'utf-8')
-    return JSON.parse(content) as PrizmConfig
+    content = await fs.promises.readFile(configPath, 'utf-8')
   } catch {
-    return {
-      server: {
-        host: '127.0.0.1',
-        port: '4127',
-        is_dev: 'true'
-      },
-      client: {
-        name: 'Prizm Electron Client',
-        auto_register: 'true',
-        requested_scopes: ['default', 'online']
-      },
-      api_key: '',
-      tray: {
-        enabled: 'true',
-        minimize_to_tray: 'true',
-        show_notification: 'true'
-      },
-      notify_events: ['notification', 'todo_list:created', 'todo_list:updated', 'todo_list:deleted']
-    }
+    return createDefaultConfig()
+  }
+
+  try {
+    return await materializeConfig(content, format, configDir, configPath)
+  } catch (err) {
+    log.warn('[Electron] Failed to parse config, trying backup:', err)
+  }
+
+  const backupPath = `${configPath}.bak`
+  try {
+    // 先保留损坏的原文件：materializeConfig 迁移备份时可能写回 configPath
+    await fs.promises.copyFile(configPath, `${configPath}.corrupt`)
+    const backup = await fs.promises.readFile(backupPath, 'utf-8')
+    const config = await materializeConfig(backup, format, configDir, configPath)
+    await saveConfigToDisk(config)
+    log.warn('[Electron] Restored config from backup:', backupPath)
+    return config
+  } catch (err) {
+    log.warn('[Electron] Config backup unavailable, using defaults:', err)
+    return createDefaultConfig()
   }
 }
 
 /**
- * 保存配置到磁盘
+ * 保存配置到磁盘：api_key 写入系统凭据存储；回退到文件存储时加密后写入配置文件。
+ * 通过临时文件 + rename 原子替换，并保留上一版本为 .bak
  */
 export async function saveConfigToDisk(config: PrizmConfig): Promise<void> {
-  const { configDir, configPath } = getConfigPath()
+  const { configDir, configPath, format } = getConfigPath()
   await fs.promises.mkdir(configDir, { recursive: true })
-  const content = JSON.stringify(config, null, 2)
-  await fs.promises.writeFile(configPath, content, 'utf-8')
+  let toWrite = config
+  if (usesKeyring(config)) {
+    await writeCredentials(configDir, collectCredentials(config))
+    toWrite = {
+      ...config,
+      api_key: '',
+      refresh_token: undefined,
+      admin_key: undefined,
+      profiles: config.profiles
+        ? Object.fromEntries(
+            Object.entries(config.profiles).map(([name, p]) => [
+              name,
+              { ...p, api_key: '', admin_key: undefined }
+            ])
+          )
+        : undefined,
+      identities: config.identities
+        ? Object.fromEntries(
+            Object.entries(config.identities).map(([name, i]) => [name, { ...i, api_key: '' }])
+          )
+        : undefined
+    }
+  } else if (config.credential_store !== 'file' && !isPortableMode()) {
+    log.warn('[Electron] OS credential store unavailable, storing encrypted api_key in config')
+  }
+  const content = serializeConfig(encryptConfigSecrets(toWrite), format)
+  lastWrittenContent = content
+  // 保留上一份可解析的配置，供文件损坏时恢复；内容有实质变化时轮转历史备份
+  try {
+    const previous = await fs.promises.readFile(configPath, 'utf-8')
+    const previousRaw = decryptConfigSecrets(parseConfigContent(previous, format) as RawConfig).raw
+    await writeFileAtomic(`${configPath}.bak`, previous)
+    if (stableStringify(previousRaw) !== stableStringify(toWrite)) {
+      await rotateConfigBackups(configPath, previous)
+    }
+  } catch {
+    // 首次保存或旧文件已损坏时不更新备份
+  }
+  await writeFileAtomic(configPath, content)
+}
+
+/** 保留的历史配置份数：config.<ext>.1（最新）… config.<ext>.N */
+export const MAX_CONFIG_BACKUPS = 5
+
+/**
+ * 键排序后的 JSON，用于比较配置内容是否有实质变化（忽略字段顺序与 undefined）
+ */
+function stableStringify(value: unknown): string {
+  return JSON.stringify(value, (_key, v: unknown) =>
+    v && typeof v === 'object' && !Array.isArray(v)
+      ? Object.fromEntries(
+          Object.entries(v as Record<string, unknown>).sort(([a], [b]) => a.localeCompare(b))
+        )
+      : v
+  )
+}
+
+/**
+ * 轮转历史备份：.N-1 → .N，…，.1 → .2，previous → .1
+ */
+async function rotateConfigBackups(configPath: string, previous: string): Promise<void> {
+  for (let i = MAX_CONFIG_BACKUPS - 1; i >= 1; i--) {
+    try {
+      await fs.promises.rename(`${configPath}.${i}`, `${configPath}.${i + 1}`)
+    } catch {
+      // 该序号不存在
+    }
+  }
+  await writeFileAtomic(`${configPath}.1`, previous)
+}
+
+export interface ConfigBackupInfo {
+  index: number
+  path: string
+  modifiedAt: number
+}
+
+/**
+ * 列出已有的历史备份
+ */
+export async function listConfigBackups(): Promise<ConfigBackupInfo[]> {
+  const { configPath } = getConfigPath()
+  const result: ConfigBackupInfo[] = []
+  for (let index = 1; index <= MAX_CONFIG_BACKUPS; index++) {
+    const backupPath = `${configPath}.${index}`
+    try {
+      const stat = await fs.promises.stat(backupPath)
+      result.push({ index, path: backupPath, modifiedAt: stat.mtimeMs })
+    } catch {
+      // 该序号不存在
+    }
+  }
+  return result
+}
+
+/**
+ * 从第 index 份历史备份恢复配置（当前配置会先被轮转为 .1）。
+ * 使用系统凭据存储时，备份中缺失的 api_key 从凭据存储回填。
+ */
+export async function restoreConfigBackup(index: number): Promise<PrizmConfig> {
+  if (!Number.isInteger(index) || index < 1 || index > MAX_CONFIG_BACKUPS) {
+    throw PrizmError.invalidInput(`Invalid backup index: ${index}`)
+  }
+  const { configDir, configPath, format } = getConfigPath()
+  const content = await fs.promises.readFile(`${configPath}.${index}`, 'utf-8')
+  const restored = configFromContent(content, format)
+  if (usesKeyring(restored)) {
+    await fillCredentialsFromKeyring(restored, configDir)
+  }
+  const config = await updateConfig(() => restored)
+  log.info('[Electron] Restored config from backup:', `${configPath}.${index}`)
+  return config
+}
+
+type ConfigListener = (config: PrizmConfig) => void
+const configListeners: ConfigListener[] = []
+
+/**
+ * 订阅主进程内的配置变更（内部写入与外部编辑），用于让 HTTP 客户端等模块即时生效
+ */
+export function onConfigUpdated(listener: ConfigListener): () => void {
+  configListeners.push(listener)
+  return () => {
+    const idx = configListeners.indexOf(listener)
+    if (idx !== -1) configListeners.splice(idx, 1)
+  }
+}
+
+/**
+ * 通知主进程内的配置订阅者
+ */
+export function notifyConfigListeners(config: PrizmConfig): void {
+  for (const listener of configListeners) {
+    try {
+      listener(config)
+    } catch (err) {
+      log.warn('[Electron] Config listener failed:', err)
+    }
+  }
+}
+
+/**
+ * 推送 config://updated 事件，携带发生变化的顶层配置段，设置页据此同步而无需轮询
+ */
+function emitConfigUpdated(before: PrizmConfig, after: PrizmConfig): void {
+  const keys = new Set([...Object.keys(before), ...Object.keys(after)])
+  const sections = [...keys].filter(
+    (key) =>
+      stableStringify((before as unknown as Record<string, unknown>)[key]) !==
+      stableStringify((after as unknown as Record<string, unknown>)[key])
+  )
+  if (sections.length === 0) return
+  notifyConfigListeners(after)
+  const win = sharedState.mainWindow
+  if (win && !win.isDestroyed()) {
+    win.webContents.send('config://updated', { sections, config: maskSecrets(after) })
+  }
+}
+
+/** 配置写操作串行队列，避免并发的读-改-写互相覆盖 */
+let configMutationQueue: Promise<unknown> = Promise.resolve()
+
+/**
+ * 原子地读取、修改并保存配置。所有主进程内的配置写入都应经由此函数。
+ */
+export function updateConfig(
+  mutate: (config: PrizmConfig) => PrizmConfig | void | Promise<PrizmConfig | void>
+): Promise<PrizmConfig> {
+  const run = async (): Promise<PrizmConfig> => {
+    const current = await loadConfigFromDisk()
+    const before = JSON.parse(JSON.stringify(current)) as PrizmConfig
+    const next = (await mutate(current)) ?? current
+    await saveConfigToDisk(next)
+    emitConfigUpdated(before, next)
+    return next
+  }
+  const result = configMutationQueue.then(run, run)
+  configMutationQueue = result.catch(() => undefined)
+  return result
+}
+
+/**
+ * 深度合并补丁：对象递归合并，数组与标量整体替换，null 表示删除该字段
+ */
+export function deepMergeConfig<T>(target: T, patch: unknown): T {
+  if (!patch || typeof patch !== 'object' || Array.isArray(patch)) {
+    return patch as T
+  }
+  const base: Record<string, unknown> =
+    target && typeof target === 'object' && !Array.isArray(target)
+      ? { ...(target as Record<string, unknown>) }
+      : {}
+  for (const [key, value] of Object.entries(patch as Record<string, unknown>)) {
+    if (value === null) {
+      delete base[key]
+    } else if (typeof value === 'object' && !Array.isArray(value)) {
+      base[key] = deepMergeConfig(base[key], value)
+    } else if (value !== undefined) {
+      base[key] = value
+    }
+  }
+  return base as T
 }
 
 /**
@@ -109,20 +1513,27 @@ export async function saveConfigToDisk(config: PrizmConfig): Promise<void> {
 export async function loadThemeMode(): Promise<ThemeMode> {
   try {
     const config = await loadConfigFromDisk()
-    const mode = config.themeMode
-    if (mode === 'light' || mode === 'dark' || mode === 'auto') return mode
+    return normalizeThemeMode(config.themeMode)
   } catch {}
   return 'auto'
 }
 
+/**
+ * 主题模式：system 视为 auto（跟随系统），无法识别的值回退为 auto
+ */
+export function normalizeThemeMode(value: unknown): ThemeMode {
+  if (value === 'light' || value === 'dark' || value === 'auto') return value
+  return 'auto'
+}
+
 /**
  * 保存主题模式到配置文件
  */
 export async function saveThemeMode(mode: ThemeMode): Promise<void> {
   try {
-    const config = await loadConfigFromDisk()
-    config.themeMode = mode
-    await saveConfigToDisk(config)
+    await updateConfig((config) => {
+      config.themeMode = mode
+    })
   } catch (err) {
     log.warn('[Electron] Failed to save theme mode:', err)
   }
@@ -135,11 +1546,14 @@ export async function loadTraySettings(): Promise<void> {
   try {
     const config = await loadConfigFromDisk()
     const trayConfig = config.tray || {}
-    sharedState.trayEnabled = trayConfig.enabled !== 'false'
-    sharedState.minimizeToTray = trayConfig.minimize_to_tray !== 'false'
+    sharedState.trayEnabled = trayConfig.enabled !== false
+    sharedState.minimizeToTray = trayConfig.minimize_to_tray !== false
+    sharedState.showNotification = trayConfig.show_notification !== false
+    sharedState.notificationPreferences = normalizeNotificationPreferences(config.notifications)
   } catch (err) {
     log.warn('[Electron] Failed to load tray settings, using defaults:', err)
     sharedState.trayEnabled = true
     sharedState.minimizeToTray = true
+    sharedState.showNotification = true
   }
 }
@@ -0,0 +1,87 @@
+import { createDefaultConfig } from './config'
+import type { PrizmConfig } from './config'
+import { PrizmError } from './errors'
+
+/** 可单独重置的配置段 */
+export const CONFIG_SECTIONS = [
+  'server',
+  'client',
+  'api_key',
+  'tray',
+  'notify_events',
+  'profiles',
+  'identities',
+  'themeMode'
+] as const
+
+export type ConfigSection = (typeof CONFIG_SECTIONS)[number]
+
+/** diff 中需要掩码的字段名 */
+const SECRET_KEYS = new Set(['api_key', 'refresh_token', 'admin_key'])
+
+export interface ConfigDiffEntry {
+  /** 点分字段路径，如 tray.enabled */
+  path: string
+  current: unknown
+  default: unknown
+}
+
+/**
+ * 恢复默认值：不传 section 时重置整份配置，否则只重置该段（无默认值的段直接移除）
+ */
+export function resetConfigSection(config: PrizmConfig, section?: string): PrizmConfig {
+  const defaults = createDefaultConfig()
+  if (!section) {
+    return defaults
+  }
+  if (!(CONFIG_SECTIONS as readonly string[]).includes(section)) {
+    throw PrizmError.invalidInput(`Unknown config section: ${section}`)
+  }
+  const result = { ...config } as Record<string, unknown>
+  const defaultValue = (defaults as unknown as Record<string, unknown>)[section]
+  if (defaultValue === undefined) {
+    delete result[section]
+    if (section === 'profiles') delete result.active_profile
+    if (section === 'identities') delete result.active_identity
+  } else {
+    result[section] = defaultValue
+  }
+  // 过期时间与刷新令牌只对应当前 api_key
+  if (section === 'api_key') {
+    delete result.api_key_expires_at
+    delete result.refresh_token
+  }
+  return result as unknown as PrizmConfig
+}
+
+function isPlainObject(value: unknown): value is Record<string, unknown> {
+  return !!value && typeof value === 'object' && !Array.isArray(value)
+}
+
+function collectDiff(
+  current: unknown,
+  defaults: unknown,
+  path: string,
+  out: ConfigDiffEntry[]
+): void {
+  if (isPlainObject(current) && isPlainObject(defaults)) {
+    const keys = new Set([...Object.keys(current), ...Object.keys(defaults)])
+    for (const key of keys) {
+      collectDiff(current[key], defaults[key], path ? `${path}.${key}` : key, out)
+    }
+    return
+  }
+  if (JSON.stringify(current) === JSON.stringify(defaults)) return
+  const key = path.split('.').pop() ?? path
+  const mask = (v: unknown) => (SECRET_KEYS.has(key) && v ? '***' : v)
+  out.push({ path, current: mask(current), default: mask(defaults) })
+}
+
+/**
+ * 列出与默认配置不同的字段（叶子级别），api_key、admin_key 与 refresh_token 以 *** 代替
+ */
+export function diffConfig(config: PrizmConfig): ConfigDiffEntry[] {
+  const out: ConfigDiffEntry[] = []
+  collectDiff(config, createDefaultConfig(), '', out)
+  return out
+}
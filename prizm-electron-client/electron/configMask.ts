@@ -0,0 +1,80 @@
+import type { PrizmConfig } from './config'
+
+/**
+ * 发给渲染进程的配置中代替密钥的占位符。渲染进程把配置原样存回时，
+ * 占位符表示“保持不变”，由 restoreMaskedSecrets 换回磁盘上的值
+ */
+export const MASKED_SECRET = '********'
+
+function mask(value: string | undefined): string | undefined {
+  return value ? MASKED_SECRET : value
+}
+
+function restore(value: string | undefined, current: string | undefined): string | undefined {
+  return value === MASKED_SECRET ? current : value
+}
+
+/**
+ * 把 api_key、refresh_token、admin_key（顶层、档案与身份）替换为占位符。
+ * 未设置的密钥保持为空，渲染进程仍可据此判断是否已注册
+ */
+export function maskSecrets(config: PrizmConfig): PrizmConfig {
+  return {
+    ...config,
+    api_key: mask(config.api_key) ?? '',
+    refresh_token: mask(config.refresh_token),
+    admin_key: mask(config.admin_key),
+    profiles: config.profiles
+      ? Object.fromEntries(
+          Object.entries(config.profiles).map(([name, p]) => [
+            name,
+            { ...p, api_key: mask(p.api_key) ?? '', admin_key: mask(p.admin_key) }
+          ])
+        )
+      : undefined,
+    identities: config.identities
+      ? Object.fromEntries(
+          Object.entries(config.identities).map(([name, i]) => [
+            name,
+            { ...i, api_key: mask(i.api_key) ?? '' }
+          ])
+        )
+      : undefined
+  }
+}
+
+/**
+ * 渲染进程回写的配置中仍为占位符的密钥换回当前值；改为其他值（包括清空）时照常生效
+ */
+export function restoreMaskedSecrets(next: PrizmConfig, current: PrizmConfig): PrizmConfig {
+  const restored: PrizmConfig = {
+    ...next,
+    api_key: restore(next.api_key, current.api_key) ?? '',
+    refresh_token: restore(next.refresh_token, current.refresh_token),
+    admin_key: restore(next.admin_key, current.admin_key)
+  }
+  if (next.profiles) {
+    restored.profiles = Object.fromEntries(
+      Object.entries(next.profiles).map(([name, p]) => {
+        const before = current.profiles?.[name]
+        return [
+          name,
+          {
+            ...p,
+            api_key: restore(p.api_key, before?.api_key) ?? '',
+            admin_key: restore(p.admin_key, before?.admin_key)
+          }
+        ]
+      })
+    )
+  }
+  if (next.identities) {
+    restored.identities = Object.fromEntries(
+      Object.entries(next.identities).map(([name, i]) => [
+        name,
+        { ...i, api_key: restore(i.api_key, current.identities?.[name]?.api_key) ?? '' }
+      ])
+    )
+  }
+  return restored
+}
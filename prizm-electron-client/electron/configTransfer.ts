@@ -0,0 +1,84 @@
+import * as path from 'path'
+import * as fs from 'fs'
+import log from 'electron-log/main'
+import { configFromContent, loadConfigFromDisk, serializeConfig, updateConfig } from './config'
+import type { ConfigFormat, PrizmConfig } from './config'
+import { validateConfig } from './configValidation'
+import type { ConfigDiagnostic } from './configValidation'
+
+/**
+ * 按扩展名决定导入/导出格式
+ */
+function formatFromPath(filePath: string): ConfigFormat {
+  return path.extname(filePath).toLowerCase() === '.toml' ? 'toml' : 'json'
+}
+
+/**
+ * 去除所有 api_key（顶层、档案与身份）、admin_key 与 refresh_token
+ */
+export function redactSecrets(config: PrizmConfig): PrizmConfig {
+  return {
+    ...config,
+    api_key: '',
+    refresh_token: undefined,
+    admin_key: undefined,
+    profiles: config.profiles
+      ? Object.fromEntries(
+          Object.entries(config.profiles).map(([name, p]) => [
+            name,
+            { ...p, api_key: '', admin_key: undefined }
+          ])
+        )
+      : undefined,
+    identities: config.identities
+      ? Object.fromEntries(
+          Object.entries(config.identities).map(([name, i]) => [name, { ...i, api_key: '' }])
+        )
+      : undefined
+  }
+}
+
+/**
+ * 导出当前配置。默认不包含 api_key；includeSecrets 时以明文导出（机器绑定密文无法跨机器解密）
+ */
+export async function exportConfig(filePath: string, includeSecrets: boolean): Promise<void> {
+  const config = await loadConfigFromDisk()
+  const data = includeSecrets ? config : redactSecrets(config)
+  await fs.promises.writeFile(filePath, serializeConfig(data, formatFromPath(filePath)), 'utf-8')
+  log.info('[Electron] Config exported:', filePath, includeSecrets ? '(with secrets)' : '')
+}
+
+export interface ImportConfigResult {
+  imported: boolean
+  diagnostics: ConfigDiagnostic[]
+}
+
+/**
+ * 导入配置：先校验，存在 error 级诊断时不覆盖当前配置
+ */
+export async function importConfig(filePath: string): Promise<ImportConfigResult> {
+  const content = await fs.promises.readFile(filePath, 'utf-8')
+  let config: PrizmConfig
+  try {
+    config = configFromContent(content, formatFromPath(filePath))
+  } catch (err) {
+    return {
+      imported: false,
+      diagnostics: [
+        {
+          path: '',
+          severity: 'error',
+          code: 'parse_failed',
+          message: `无法解析配置文件: ${err instanceof Error ? err.message : String(err)}`
+        }
+      ]
+    }
+  }
+  const diagnostics = validateConfig(config)
+  if (diagnostics.some((d) => d.severity === 'error')) {
+    return { imported: false, diagnostics }
+  }
+  await updateConfig(() => config)
+  log.info('[Electron] Config imported:', filePath)
+  return { imported: true, diagnostics }
+}
@@ -0,0 +1,92 @@
+import type { PrizmConfig } from './config'
+import { isNamedPipePath } from './socketTransport'
+
+export type DiagnosticSeverity = 'error' | 'warning' | 'info'
+
+/** 单条配置诊断，path 为点分字段路径（如 server.port），供设置页定位表单项 */
+export interface ConfigDiagnostic {
+  path: string
+  severity: DiagnosticSeverity
+  code: string
+  message: string
+}
+
+/** 服务端内置 scope，与 @prizm/shared BUILTIN_SCOPES 保持一致 */
+const BUILTIN_SCOPES = ['default', 'online']
+
+const SCOPE_NAME = /^[A-Za-z0-9_-]+$/
+
+/**
+ * 检查配置中的问题。knownScopes 为服务端已有 scope 列表（可选），缺省时只认内置 scope。
+ */
+export function validateConfig(config: PrizmConfig, knownScopes?: string[]): ConfigDiagnostic[] {
+  const diagnostics: ConfigDiagnostic[] = []
+  const push = (path: string, severity: DiagnosticSeverity, code: string, message: string) =>
+    diagnostics.push({ path, severity, code, message })
+
+  const host = typeof config.server?.host === 'string' ? config.server.host.trim() : ''
+  if (!host) {
+    push('server.host', 'error', 'host_empty', '服务器地址不能为空')
+  } else if (/\s/.test(host)) {
+    push('server.host', 'error', 'host_invalid', '服务器地址不能包含空白字符')
+  }
+
+  const rawPort = config.server?.port as unknown
+  const port =
+    typeof rawPort === 'string' && /^\d+$/.test(rawPort.trim()) ? Number(rawPort) : rawPort
+  if (typeof port !== 'number' || !Number.isInteger(port)) {
+    push('server.port', 'error', 'port_not_numeric', '端口必须是整数')
+  } else if (port < 1 || port > 65535) {
+    push('server.port', 'error', 'port_out_of_range', '端口必须在 1-65535 之间')
+  }
+
+  const socketPath = config.server?.socket_path
+  if (socketPath && isNamedPipePath(socketPath) && process.platform !== 'win32') {
+    push('server.socket_path', 'error', 'named_pipe_unsupported', '命名管道仅在 Windows 上可用')
+  }
+
+  if (!config.client?.name?.trim()) {
+    push('client.name', 'warning', 'client_name_empty', '客户端名称为空，注册时将使用默认名称')
+  }
+
+  const scopes = Array.isArray(config.client?.requested_scopes)
+    ? config.client.requested_scopes
+    : []
+  if (scopes.length === 0) {
+    push('client.requested_scopes', 'warning', 'scopes_empty', '未请求任何 scope，将使用服务端默认值')
+  }
+  const known = new Set([...BUILTIN_SCOPES, ...(knownScopes ?? [])])
+  scopes.forEach((scope, index) => {
+    const path = `client.requested_scopes[${index}]`
+    if (typeof scope !== 'string' || !SCOPE_NAME.test(scope)) {
+      push(path, 'error', 'scope_invalid', `非法的 scope 名称: ${String(scope)}`)
+    } else if (!known.has(scope)) {
+      push(path, 'warning', 'scope_unknown', `未知的 scope: ${scope}`)
+    }
+  })
+
+  const proxy = config.network?.proxy
+  if (proxy?.mode === 'manual') {
+    if (!proxy.url) {
+      push('network.proxy.url', 'error', 'proxy_url_empty', '手动代理模式需要填写代理地址')
+    } else if (!/^(https?|socks[45]):\/\/[^\s/]+/.test(proxy.url)) {
+      push('network.proxy.url', 'error', 'proxy_url_invalid', `无法识别的代理地址: ${proxy.url}`)
+    }
+  }
+
+  const scheme = config.server?.scheme ?? 'http'
+  if (config.network?.require_tls && scheme !== 'https' && scheme !== 'wss') {
+    push(
+      'server.scheme',
+      'warning',
+      'scheme_upgraded',
+      '已开启仅加密连接，请求将自动升级为 https，请确认服务器已启用 TLS'
+    )
+  }
+
+  if (!config.api_key) {
+    push('api_key', 'warning', 'api_key_missing', '尚未注册客户端，缺少 API Key')
+  }
+
+  return diagnostics
+}
@@ -0,0 +1,93 @@
+import * as fs from 'fs'
+import * as path from 'path'
+import log from 'electron-log/main'
+import {
+  getConfigPath,
+  isOwnConfigWrite,
+  loadConfigFromDisk,
+  notifyConfigListeners,
+  parseConfigContent,
+  sharedState
+} from './config'
+import type { ConfigFormat } from './config'
+import { maskSecrets } from './configMask'
+
+const RELOAD_DEBOUNCE_MS = 300
+
+let watcher: fs.FSWatcher | null = null
+let reloadTimer: ReturnType<typeof setTimeout> | null = null
+
+/**
+ * 重新读取配置并推送 config-changed 事件到渲染进程
+ */
+async function reloadConfig(configPath: string, format: ConfigFormat): Promise<void> {
+  let content: string
+  try {
+    content = await fs.promises.readFile(configPath, 'utf-8')
+  } catch {
+    return
+  }
+  if (isOwnConfigWrite(content)) {
+    return
+  }
+  try {
+    parseConfigContent(content, format)
+  } catch (err) {
+    log.warn('[Electron] Config file changed but failed to parse, ignoring:', err)
+    return
+  }
+
+  const config = await loadConfigFromDisk()
+  sharedState.trayEnabled = config.tray.enabled !== false
+  sharedState.minimizeToTray = config.tray.minimize_to_tray !== false
+  log.info('[Electron] Config file changed on disk, reloaded:', configPath)
+  notifyConfigListeners(config)
+
+  if (sharedState.mainWindow && !sharedState.mainWindow.isDestroyed()) {
+    sharedState.mainWindow.webContents.send('config-changed', maskSecrets(config))
+  }
+}
+
+/**
+ * 启动配置文件监听：手动编辑 config.json / config.toml 后自动重新加载
+ */
+export function startConfigWatcher(): void {
+  if (watcher) {
+    return
+  }
+  const { configDir, configPath, format } = getConfigPath()
+  const fileName = path.basename(configPath)
+  try {
+    fs.mkdirSync(configDir, { recursive: true })
+    // 监听目录而非文件本身，编辑器“写临时文件再重命名”时文件句柄会失效
+    watcher = fs.watch(configDir, (_eventType, changed) => {
+      if (changed && changed.toString() !== fileName) {
+        return
+      }
+      if (reloadTimer) clearTimeout(reloadTimer)
+      reloadTimer = setTimeout(() => {
+        reloadTimer = null
+        void reloadConfig(configPath, format)
+      }, RELOAD_DEBOUNCE_MS)
+    })
+    watcher.on('error', (err) => {
+      log.warn('[Electron] Config watcher error:', err)
+    })
+  } catch (err) {
+    log.warn('[Electron] Failed to watch config directory:', err)
+  }
+}
+
+/**
+ * 停止配置文件监听
+ */
+export function stopConfigWatcher(): void {
+  if (reloadTimer) {
+    clearTimeout(reloadTimer)
+    reloadTimer = null
+  }
+  if (watcher) {
+    watcher.close()
+    watcher = null
+  }
+}
@@ -0,0 +1,43 @@
+import * as http from 'http'
+import { app } from 'electron'
+import log from 'electron-log/main'
+import type { NetworkConfig } from './config'
+
+let http2Disabled = false
+
+/**
+ * 启动时应用 network.http2（需在 app ready 之前调用）。
+ * Chromium 不支持按 session 或运行时切换 HTTP/2，关闭后对所有连接生效
+ */
+export function applyHttp2Setting(enabled: boolean): void {
+  http2Disabled = !enabled
+  if (!http2Disabled) return
+  app.commandLine.appendSwitch('disable-http2')
+  log.info('[Network] HTTP/2 disabled')
+}
+
+/**
+ * 配置中的 http2 与启动时应用的不同时返回 true（需重启）
+ */
+export function http2NeedsRestart(enabled: boolean): boolean {
+  return enabled === http2Disabled
+}
+
+/**
+ * 空闲连接实际保留时间：pool_max_idle_per_host 为 0 时不保留
+ */
+export function effectiveIdleTimeout(network: NetworkConfig): number {
+  return network.pool_max_idle_per_host === 0 ? 0 : network.pool_idle_timeout_ms
+}
+
+/**
+ * 本地套接字请求使用的 Node http.Agent，按连接池设置控制 keep-alive 与空闲连接数
+ */
+export function createSocketAgent(network: NetworkConfig): http.Agent {
+  const idleMs = effectiveIdleTimeout(network)
+  return new http.Agent({
+    keepAlive: idleMs > 0,
+    maxFreeSockets: Math.max(1, network.pool_max_idle_per_host),
+    timeout: idleMs > 0 ? idleMs : undefined
+  })
+}
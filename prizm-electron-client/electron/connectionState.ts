@@ -0,0 +1,111 @@
+import log from 'electron-log/main'
+import type { CircuitStatus } from './circuitBreaker'
+import { sharedState } from './config'
+import type { RealtimeState } from './realtime'
+
+/** 与服务器的总体连接状态，托盘、窗口标题与界面统一以此为准 */
+export type ConnectionState = 'disconnected' | 'connecting' | 'connected' | 'degraded'
+
+/** 连接状态，变化时推送 connection://state */
+export interface ConnectionSnapshot {
+  state: ConnectionState
+  /** 实时连接（WebSocket）的状态 */
+  realtime: RealtimeState
+  /** HTTP 熔断中（open / half_open）的服务器 origin */
+  trippedOrigins: string[]
+  changedAt: number
+}
+
+export type ConnectionStateListener = (snapshot: ConnectionSnapshot) => void
+
+export const CONNECTION_STATE_LABELS: Record<ConnectionState, string> = {
+  disconnected: '未连接',
+  connecting: '连接中',
+  connected: '已连接',
+  degraded: '连接不稳定'
+}
+
+/**
+ * 由实时连接状态与 HTTP 熔断推导总体状态：实时连接正常但 HTTP 熔断、
+ * 或实时连接断线重连中视为 degraded
+ */
+export function deriveConnectionState(
+  realtime: RealtimeState,
+  circuitTripped: boolean
+): ConnectionState {
+  switch (realtime) {
+    case 'connected':
+      return circuitTripped ? 'degraded' : 'connected'
+    case 'reconnecting':
+      return 'degraded'
+    case 'connecting':
+      return 'connecting'
+    default:
+      return 'disconnected'
+  }
+}
+
+/**
+ * 汇总实时连接与 HTTP 熔断器上报的状态，只在总体状态变化时通知监听者并推送 connection://state
+ */
+export class ConnectionStateTracker {
+  private realtime: RealtimeState = 'disconnected'
+  private readonly tripped = new Set<string>()
+  private readonly listeners = new Set<ConnectionStateListener>()
+  private current: ConnectionSnapshot
+
+  constructor(private readonly now: () => number = Date.now) {
+    this.current = {
+      state: 'disconnected',
+      realtime: 'disconnected',
+      trippedOrigins: [],
+      changedAt: now()
+    }
+  }
+
+  get(): ConnectionSnapshot {
+    return { ...this.current, trippedOrigins: [...this.current.trippedOrigins] }
+  }
+
+  onChange(listener: ConnectionStateListener): () => void {
+    this.listeners.add(listener)
+    return () => this.listeners.delete(listener)
+  }
+
+  updateRealtime(state: RealtimeState): void {
+    this.realtime = state
+    this.refresh()
+  }
+
+  updateCircuit(status: CircuitStatus): void {
+    if (status.state === 'closed') this.tripped.delete(status.origin)
+    else this.tripped.add(status.origin)
+    this.refresh()
+  }
+
+  private refresh(): void {
+    const previous = this.current.state
+    const state = deriveConnectionState(this.realtime, this.tripped.size > 0)
+    this.current = this.snapshot(state)
+    if (state === previous) return
+    log.info(`[Connection] State is ${state}`)
+    const win = sharedState.mainWindow
+    if (win && !win.isDestroyed()) {
+      win.webContents.send('connection://state', this.get())
+    }
+    for (const listener of this.listeners) {
+      try {
+        listener(this.get())
+      } catch (err) {
+        log.warn('[Connection] State listener failed:', err)
+      }
+    }
+  }
+
+  private snapshot(state: ConnectionState): ConnectionSnapshot {
+    const changedAt = state === this.current.state ? this.current.changedAt : this.now()
+    return { state, realtime: this.realtime, trippedOrigins: [...this.tripped], changedAt }
+  }
+}
+
+export const connectionState = new ConnectionStateTracker()
@@ -0,0 +1,92 @@
+import { randomUUID } from 'crypto'
+import log from 'electron-log/main'
+import { sharedState } from './config'
+import { PrizmError } from './errors'
+
+/** 授予前需用户确认的敏感 scope（'*' 即全部权限） */
+export const SENSITIVE_SCOPES = ['*', 'clipboard', 'filesystem']
+/** 用户未作答时视为拒绝的等待上限 */
+const CONSENT_TIMEOUT_MS = 10 * 60 * 1000
+
+/**
+ * 授权来源：auto_register 为无人值守注册（启动时或 Key 失效后），
+ * scope_change 为服务端批准 scope 申请时授予了未申请的 scope
+ */
+export type ConsentReason = 'auto_register' | 'scope_change'
+
+/** 推送给渲染进程的确认请求（auth://consent-request），须通过 respond_consent 作答 */
+export interface ConsentRequestEvent {
+  id: string
+  serverUrl: string
+  reason: ConsentReason
+  /** 待确认的敏感 scope */
+  scopes: string[]
+  expiresAt: number
+}
+
+interface PendingConsent {
+  event: ConsentRequestEvent
+  resolve: (allow: boolean) => void
+}
+
+const pending = new Map<string, PendingConsent>()
+
+/** scopes 中尚未拥有（不在 previous 中）的敏感 scope */
+export function newSensitiveScopes(scopes: string[], previous: string[] = []): string[] {
+  return [...new Set(scopes)].filter((s) => SENSITIVE_SCOPES.includes(s) && !previous.includes(s))
+}
+
+/**
+ * 授予 granted 中新增的敏感 scope 前请求用户确认：推送 auth://consent-request 并等待
+ * respond_consent 作答。没有新增敏感 scope 时直接返回 true；超时、取消或窗口不存在时视为拒绝
+ */
+export function requestConsent(
+  request: { serverUrl: string; reason: ConsentReason; granted: string[]; previous?: string[] },
+  signal?: AbortSignal
+): Promise<boolean> {
+  const scopes = newSensitiveScopes(request.granted, request.previous)
+  if (scopes.length === 0) return Promise.resolve(true)
+  if (signal?.aborted) return Promise.resolve(false)
+  const win = sharedState.mainWindow
+  if (!win || win.isDestroyed()) {
+    log.warn(`[Consent] No window to confirm scopes ${scopes.join(', ')}, denying`)
+    return Promise.resolve(false)
+  }
+
+  const event: ConsentRequestEvent = {
+    id: randomUUID(),
+    serverUrl: request.serverUrl,
+    reason: request.reason,
+    scopes,
+    expiresAt: Date.now() + CONSENT_TIMEOUT_MS
+  }
+  return new Promise((resolve) => {
+    const finish = (allow: boolean): void => {
+      clearTimeout(timer)
+      signal?.removeEventListener('abort', onAbort)
+      pending.delete(event.id)
+      log.info(`[Consent] Scopes ${scopes.join(', ')} ${allow ? 'allowed' : 'denied'}`)
+      resolve(allow)
+    }
+    const onAbort = (): void => finish(false)
+    const timer = setTimeout(() => finish(false), CONSENT_TIMEOUT_MS)
+    signal?.addEventListener('abort', onAbort, { once: true })
+    pending.set(event.id, { event, resolve: finish })
+    log.info(`[Consent] Asking to grant ${scopes.join(', ')} on ${request.serverUrl}`)
+    win.webContents.send('auth://consent-request', event)
+  })
+}
+
+/** 尚未作答的确认请求（渲染进程加载晚于事件时补齐） */
+export function listPendingConsents(): ConsentRequestEvent[] {
+  return [...pending.values()].map((p) => p.event)
+}
+
+/** 回答确认请求；请求不存在（已作答或已超时）时抛出 invalid_input */
+export function respondConsent(id: string, allow: boolean): void {
+  const entry = pending.get(id)
+  if (!entry) {
+    throw PrizmError.invalidInput('Consent request not found or already answered')
+  }
+  entry.resolve(allow === true)
+}
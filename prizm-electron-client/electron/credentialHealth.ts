@@ -0,0 +1,62 @@
+import { loadConfigFromDisk } from './config'
+import { toPrizmError } from './errors'
+import { PrizmApi } from './prizmApi'
+import { serverConfigToUrl } from './serverUrl'
+
+/** 已保存凭据的健康状况，供界面展示（不含 API Key） */
+export interface CredentialStatus {
+  serverUrl: string
+  valid: boolean
+  /** 无效原因：未配置 Key，或被服务端拒绝（已吊销、已重新生成或已过期） */
+  reason?: 'missing' | 'rejected'
+  clientId?: string
+  name?: string
+  scopes: string[]
+  /** 本次检查之前最近一次使用时间，服务端未记录时为 null */
+  lastUsed: number | null
+  /** Key 过期时间，永久 Key 为 null */
+  expiresAt: number | null
+}
+
+/**
+ * 检查配置中的 API Key 是否仍有效：只发一次无副作用的 GET /auth/me，
+ * 不触发重新注册、不续期、不改写配置。服务器不可达时抛错
+ */
+export async function verifyCredentials(signal?: AbortSignal): Promise<CredentialStatus> {
+  const config = await loadConfigFromDisk()
+  const serverUrl = serverConfigToUrl(config.server)
+  const base: CredentialStatus = {
+    serverUrl,
+    valid: false,
+    scopes: [],
+    lastUsed: null,
+    expiresAt: config.api_key_expires_at ?? null
+  }
+  if (!config.api_key) return { ...base, reason: 'missing' }
+
+  const api = new PrizmApi(serverUrl)
+  try {
+    const client = await api.currentClient(config.api_key, signal)
+    if (!client) return { ...base, reason: 'rejected' }
+    return {
+      ...base,
+      valid: true,
+      clientId: client.clientId,
+      name: client.name,
+      scopes: Array.isArray(client.allowedScopes) ? [...client.allowedScopes] : [],
+      lastUsed: client.lastSeenAt ?? null
+    }
+  } catch (err) {
+    // 旧版服务端没有 /auth/me：只能确认 Key 是否有效，scope 取注册时记录的
+    if (toPrizmError(err).status !== 404) throw err
+    const valid = await api.verifyApiKey(config.api_key, signal)
+    return valid
+      ? {
+          ...base,
+          valid,
+          clientId: config.client.name,
+          scopes: [...config.client.granted_scopes]
+        }
+      : { ...base, reason: 'rejected' }
+  }
+}
@@ -0,0 +1,85 @@
+import { safeStorage } from 'electron'
+import * as path from 'path'
+import * as fs from 'fs'
+import log from 'electron-log/main'
+import { writeFileAtomic } from './fsUtils'
+
+/**
+ * 系统凭据存储：通过 safeStorage 使用 Windows DPAPI / macOS Keychain / Secret Service 加密，
+ * 密文写入配置目录下的 credentials.json，与 config.json 分离。
+ */
+const CREDENTIALS_FILE = 'credentials.json'
+
+/** 顶层 api_key 对应的凭据名 */
+export const API_KEY_ACCOUNT = 'api_key'
+
+/** 顶层 refresh_token 对应的凭据名 */
+export const REFRESH_TOKEN_ACCOUNT = 'refresh_token'
+
+/** 顶层 admin_key 对应的凭据名 */
+export const ADMIN_KEY_ACCOUNT = 'admin_key'
+
+/** 档案 api_key 对应的凭据名 */
+export function profileAccount(name: string): string {
+  return `profile:${name}`
+}
+
+/** 档案 admin_key 对应的凭据名 */
+export function profileAdminAccount(name: string): string {
+  return `profile-admin:${name}`
+}
+
+/** 身份 api_key 对应的凭据名 */
+export function identityAccount(name: string): string {
+  return `identity:${name}`
+}
+
+/**
+ * 系统凭据存储是否可用（Linux 无 Secret Service 时不可用）
+ */
+export function isKeyringAvailable(): boolean {
+  try {
+    return safeStorage.isEncryptionAvailable()
+  } catch {
+    return false
+  }
+}
+
+/**
+ * 读取全部凭据，解密失败的条目跳过
+ */
+export async function readCredentials(configDir: string): Promise<Record<string, string>> {
+  let stored: Record<string, string>
+  try {
+    const content = await fs.promises.readFile(path.join(configDir, CREDENTIALS_FILE), 'utf-8')
+    stored = JSON.parse(content) as Record<string, string>
+  } catch {
+    return {}
+  }
+  const result: Record<string, string> = {}
+  for (const [account, value] of Object.entries(stored)) {
+    try {
+      result[account] = safeStorage.decryptString(Buffer.from(value, 'base64'))
+    } catch (err) {
+      log.warn('[Electron] Failed to decrypt credential:', account, err)
+    }
+  }
+  return result
+}
+
+/**
+ * 以给定集合整体覆盖凭据（空值不写入），使已删除的档案不残留密钥
+ */
+export async function writeCredentials(
+  configDir: string,
+  credentials: Record<string, string>
+): Promise<void> {
+  const stored: Record<string, string> = {}
+  for (const [account, value] of Object.entries(credentials)) {
+    if (value) stored[account] = safeStorage.encryptString(value).toString('base64')
+  }
+  await fs.promises.mkdir(configDir, { recursive: true })
+  await writeFileAtomic(path.join(configDir, CREDENTIALS_FILE), JSON.stringify(stored, null, 2), {
+    mode: 0o600
+  })
+}
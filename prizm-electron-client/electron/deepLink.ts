@@ -0,0 +1,173 @@
+import { app, dialog } from 'electron'
+import * as os from 'os'
+import * as path from 'path'
+import log from 'electron-log/main'
+import { loadConfigFromDisk } from './config'
+import { encodePairingPayload, parsePairingPayload, pairFromQr } from './pairing'
+import { navigateMainWindow, showLocalNotification } from './serverNotifications'
+import type { AppRoute } from './serverNotifications'
+import { serverConfigToUrl } from './serverUrl'
+import { DEEP_LINK_PREFIX, extractDeepLink, onSecondInstance } from './singleInstance'
+import { openDashboard, showMainWindow } from './windowManager'
+
+const PROTOCOL = 'prizm'
+
+/**
+ * 支持的深链接：
+ * - prizm://register?server=…&token=… 或 prizm://pair?…（配对二维码内容）：用一次性令牌配对注册
+ * - prizm://open 显示主窗口；prizm://open/dashboard 打开管理面板；
+ *   prizm://open/settings[/connection|devices] 跳转到设置
+ */
+export type DeepLink =
+  | { type: 'register'; serverUrl: string; token: string }
+  | { type: 'open'; target: 'window' }
+  | { type: 'open'; target: 'dashboard' }
+  | { type: 'open'; target: 'settings'; section: AppRoute['section'] }
+
+const SETTINGS_SECTIONS: AppRoute['section'][] = ['connection', 'devices']
+
+/** 解析深链接，格式不支持时返回 null */
+export function parseDeepLink(link: string): DeepLink | null {
+  let url: URL
+  try {
+    url = new URL(link.trim())
+  } catch {
+    return null
+  }
+  if (url.protocol !== `${PROTOCOL}:`) return null
+  const host = url.hostname.toLowerCase()
+  const segments = url.pathname.split('/').filter(Boolean)
+  if (host === 'register' || host === 'pair') {
+    try {
+      const params = url.searchParams.toString()
+      return { type: 'register', ...parsePairingPayload(`${DEEP_LINK_PREFIX}pair?${params}`) }
+    } catch {
+      return null
+    }
+  }
+  if (host !== 'open') return null
+  const [target, section] = segments
+  if (!target) return { type: 'open', target: 'window' }
+  if (target === 'dashboard' && !section) return { type: 'open', target: 'dashboard' }
+  if (target === 'settings') {
+    if (!section) return { type: 'open', target: 'settings', section: 'connection' }
+    const match = SETTINGS_SECTIONS.find((s) => s === section)
+    if (match) return { type: 'open', target: 'settings', section: match }
+  }
+  return null
+}
+
+/**
+ * 注册前让用户确认：链接可能来自任意网页，不能静默切换到链接指定的服务器
+ */
+async function confirmRegistration(serverUrl: string): Promise<boolean> {
+  const win = showMainWindow()
+  const { response } = await dialog.showMessageBox(win, {
+    type: 'question',
+    title: '配对到服务器',
+    message: `是否将本机注册到 ${serverUrl}？`,
+    detail: '该请求来自外部链接。确认后当前的服务器与 API Key 将被替换。',
+    buttons: ['配对', '取消'],
+    defaultId: 1,
+    cancelId: 1
+  })
+  return response === 0
+}
+
+async function registerFromLink(serverUrl: string, token: string): Promise<void> {
+  if (!(await confirmRegistration(serverUrl))) {
+    log.info('[DeepLink] Registration declined by user')
+    return
+  }
+  const config = await loadConfigFromDisk()
+  const name = config.client.registration_name || config.client.name || os.hostname()
+  try {
+    const result = await pairFromQr(encodePairingPayload({ serverUrl, token }), name)
+    showLocalNotification({
+      title: '配对成功',
+      body: `已作为 ${result.clientId} 注册到 ${serverUrl}`,
+      category: 'connection',
+      severity: 'info'
+    })
+  } catch (err) {
+    log.error('[DeepLink] Registration failed:', err)
+    showLocalNotification({
+      title: '配对失败',
+      body: err instanceof Error ? err.message : String(err),
+      category: 'connection',
+      severity: 'error'
+    })
+  }
+}
+
+async function openDashboardFromLink(): Promise<void> {
+  const config = await loadConfigFromDisk()
+  if (!config.server.host) {
+    navigateMainWindow({ page: 'settings', section: 'connection' })
+    return
+  }
+  await openDashboard(`${serverConfigToUrl(config.server)}/dashboard/`)
+}
+
+/**
+ * 处理一个深链接；不支持的链接只记录日志
+ */
+export async function handleDeepLink(link: string): Promise<void> {
+  const parsed = parseDeepLink(link)
+  if (!parsed) {
+    log.warn('[DeepLink] Unsupported link:', link)
+    return
+  }
+  log.info('[DeepLink] Handling', parsed.type === 'open' ? `open/${parsed.target}` : parsed.type)
+  if (parsed.type === 'register') {
+    await registerFromLink(parsed.serverUrl, parsed.token)
+  } else if (parsed.target === 'dashboard') {
+    await openDashboardFromLink()
+  } else if (parsed.target === 'settings') {
+    navigateMainWindow({ page: 'settings', section: parsed.section })
+  } else {
+    showMainWindow()
+  }
+}
+
+/** 主进程初始化完成前收到的深链接 */
+const queuedLinks: string[] = []
+let accepting = false
+
+function dispatch(link: string): void {
+  if (!accepting) {
+    queuedLinks.push(link)
+    return
+  }
+  handleDeepLink(link).catch((err) => log.error('[DeepLink] Failed to handle link:', err))
+}
+
+/** 主窗口与配置就绪后调用：处理此前排队的深链接，之后收到的立即处理 */
+export function flushDeepLinks(): void {
+  accepting = true
+  for (const link of queuedLinks.splice(0)) dispatch(link)
+}
+
+/**
+ * 注册 prizm:// 协议并接收深链接：macOS 经 open-url 事件，Windows 与 Linux 经启动参数
+ * （首次启动时的 process.argv，或再次启动时由单实例锁转交的参数）。须在 app ready 之前调用，
+ * 以免错过 macOS 启动时的 open-url；收到的链接在 flushDeepLinks 之后才处理
+ */
+export function watchDeepLinks(argv: string[] = process.argv): void {
+  // 开发模式下由 electron 可执行文件启动，需带上入口脚本路径
+  const devArgs = process.defaultApp && process.argv.length >= 2
+  const registered = devArgs
+    ? app.setAsDefaultProtocolClient(PROTOCOL, process.execPath, [path.resolve(process.argv[1])])
+    : app.setAsDefaultProtocolClient(PROTOCOL)
+  if (!registered) log.warn(`[DeepLink] Failed to register ${PROTOCOL}:// handler`)
+
+  app.on('open-url', (event, url) => {
+    event.preventDefault()
+    dispatch(url)
+  })
+  onSecondInstance((_argv, deepLink) => {
+    if (deepLink) dispatch(deepLink)
+  })
+  const initial = extractDeepLink(argv)
+  if (initial) dispatch(initial)
+}
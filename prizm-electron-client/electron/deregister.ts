@@ -0,0 +1,63 @@
+import log from 'electron-log/main'
+import { loadConfigFromDisk, sharedState, updateConfig } from './config'
+import { toPrizmError } from './errors'
+import { PrizmApi } from './prizmApi'
+import { upsertActiveProfile } from './profiles'
+import { serverConfigToUrl } from './serverUrl'
+
+export interface DeregisterResult {
+  /** 服务端是否确认吊销（客户端已不存在、Key 已失效也算） */
+  revoked: boolean
+  serverUrl: string
+}
+
+/**
+ * 注销客户端：调用服务端吊销接口，然后清除配置中的 api_key 与 client.name，
+ * 并关闭 client.auto_register，以免下次启动又自动注册。
+ * 服务端返回 401 / 404 说明客户端已失效，照常清除；服务器不可达时报错，force 为 true 时仍清除本地配置
+ */
+export async function deregisterClient(force = false): Promise<DeregisterResult> {
+  const config = await loadConfigFromDisk()
+  const serverUrl = serverConfigToUrl(config.server)
+  let revoked = false
+  if (config.api_key && config.client.name) {
+    try {
+      await new PrizmApi(serverUrl).revokeClient(config.client.name)
+      revoked = true
+    } catch (err) {
+      const error = toPrizmError(err)
+      if (error.status === 401 || error.status === 404) {
+        revoked = true
+      } else if (!force) {
+        throw error
+      } else {
+        log.warn(`[Auth] Could not revoke client on ${serverUrl}, clearing locally:`, error.message)
+      }
+    }
+  }
+
+  await updateConfig((current) => {
+    current.api_key = ''
+    delete current.api_key_expires_at
+    delete current.refresh_token
+    current.client.name = ''
+    current.client.granted_scopes = []
+    current.client.auto_register = false
+    // 当前身份的客户端已吊销，一并移除
+    if (current.active_identity && current.identities) {
+      const identities = { ...current.identities }
+      delete identities[current.active_identity]
+      current.identities = identities
+      delete current.active_identity
+    }
+    if (current.active_profile) upsertActiveProfile(current)
+  })
+  log.info(`[Auth] Client deregistered from ${serverUrl} (revoked: ${revoked})`)
+
+  const result: DeregisterResult = { revoked, serverUrl }
+  const win = sharedState.mainWindow
+  if (win && !win.isDestroyed()) {
+    win.webContents.send('auth://deregistered', result)
+  }
+  return result
+}
@@ -0,0 +1,75 @@
+import log from 'electron-log/main'
+import { sharedState } from './config'
+import { PrizmError } from './errors'
+import { sleep } from './httpClient'
+import { PrizmApi } from './prizmApi'
+import { saveRegistration } from './registration'
+import type { RegistrationRequest, RegistrationResult } from './registration'
+import { parseServerUrl, serverConfigToUrl } from './serverUrl'
+
+/** RFC 8628 默认轮询间隔与 slow_down 时的增量（秒） */
+const DEFAULT_INTERVAL_S = 5
+const SLOW_DOWN_STEP_S = 5
+
+/** 推送给渲染进程的设备码，界面据此展示验证地址与用户码 */
+export interface DeviceCodeEvent {
+  requestId?: string
+  userCode: string
+  verificationUri: string
+  verificationUriComplete?: string
+  expiresAt: number
+}
+
+/**
+ * 设备码授权：申请用户码并推送 auth://device-code，按服务端要求的间隔轮询令牌端点，
+ * 用户在服务端确认后把令牌作为 API Key 写入配置。可通过 signal 取消
+ */
+export async function registerWithDeviceCode(
+  request: RegistrationRequest,
+  options: { requestId?: string; signal?: AbortSignal } = {}
+): Promise<RegistrationResult> {
+  const { requestId, signal } = options
+  const serverUrl = serverConfigToUrl(parseServerUrl(request.serverUrl))
+  const api = new PrizmApi(serverUrl)
+
+  const code = await api.requestDeviceCode(request.name, request.requestedScopes, signal)
+  const expiresAt = Date.now() + code.expires_in * 1000
+  const event: DeviceCodeEvent = {
+    requestId,
+    userCode: code.user_code,
+    verificationUri: code.verification_uri,
+    verificationUriComplete: code.verification_uri_complete,
+    expiresAt
+  }
+  const win = sharedState.mainWindow
+  if (win && !win.isDestroyed()) {
+    win.webContents.send('auth://device-code', event)
+  }
+  log.info(`[Auth] Waiting for device authorization on ${serverUrl} (code ${code.user_code})`)
+
+  let intervalS = code.interval ?? DEFAULT_INTERVAL_S
+  while (Date.now() < expiresAt) {
+    await sleep(intervalS * 1000, signal)
+    const result = await api.pollDeviceToken(code.device_code, signal)
+    if (result.status === 'slow_down') {
+      intervalS += SLOW_DOWN_STEP_S
+      continue
+    }
+    if (result.status === 'authorization_pending') continue
+
+    const { token } = result
+    const registered: RegistrationResult = {
+      clientId: token.client_id || request.name,
+      apiKey: token.access_token,
+      grantedScopes: token.scope
+        ? token.scope.split(' ').filter(Boolean)
+        : [...request.requestedScopes],
+      expiresAt: token.expires_in ? Date.now() + token.expires_in * 1000 : undefined,
+      refreshToken: token.refresh_token
+    }
+    await saveRegistration(request, registered)
+    log.info(`[Auth] Device authorization granted for "${registered.clientId}" on ${serverUrl}`)
+    return registered
+  }
+  throw PrizmError.auth('Device code expired')
+}
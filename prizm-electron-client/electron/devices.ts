@@ -0,0 +1,77 @@
+import log from 'electron-log/main'
+import { loadConfigFromDisk } from './config'
+import { deregisterClient } from './deregister'
+import { PrizmError } from './errors'
+import { PrizmApi } from './prizmApi'
+import type { RegisteredClient } from './prizmApi'
+import { serverConfigToUrl } from './serverUrl'
+
+/** 设备页展示的客户端 */
+export interface ClientInfo {
+  id: string
+  name: string
+  scopes: string[]
+  /** 最近一次使用时间（毫秒时间戳），服务端未记录时为 null */
+  lastSeen: number | null
+  createdAt: number
+  /** 是否为本客户端 */
+  current: boolean
+}
+
+export function toClientInfo(client: RegisteredClient, currentId?: string): ClientInfo {
+  return {
+    id: client.clientId,
+    name: client.name || client.clientId,
+    scopes: Array.isArray(client.allowedScopes) ? [...client.allowedScopes] : [],
+    lastSeen: typeof client.lastSeenAt === 'number' ? client.lastSeenAt : null,
+    createdAt: client.createdAt,
+    current: !!currentId && client.clientId === currentId
+  }
+}
+
+/**
+ * 列出当前服务器上注册的全部客户端，最近使用的在前
+ * （管理操作：需管理员 Key 或拥有 '*' scope 的客户端 Key）
+ */
+export async function listClients(signal?: AbortSignal): Promise<ClientInfo[]> {
+  const config = await loadConfigFromDisk()
+  const clients = await new PrizmApi(serverConfigToUrl(config.server)).listClients(signal)
+  const currentId = config.api_key ? config.client.name : undefined
+  return clients
+    .map((client) => toClientInfo(client, currentId))
+    .sort((a, b) => (b.lastSeen ?? b.createdAt) - (a.lastSeen ?? a.createdAt))
+}
+
+/**
+ * 吊销服务器上的某个客户端（管理操作）。吊销的是本客户端时按注销处理，同时清除本地凭据
+ */
+export async function revokeClient(clientId: string): Promise<void> {
+  const id = typeof clientId === 'string' ? clientId.trim() : ''
+  if (!id) throw PrizmError.invalidInput('Client id is empty')
+  const config = await loadConfigFromDisk()
+  if (config.api_key && id === config.client.name) {
+    await deregisterClient()
+    return
+  }
+  const serverUrl = serverConfigToUrl(config.server)
+  await new PrizmApi(serverUrl).revokeClient(id)
+  log.info(`[Auth] Revoked client ${id} on ${serverUrl}`)
+}
+
+/**
+ * 批准或拒绝其他客户端的注册申请（管理操作）
+ */
+export async function resolveRegistrationRequest(
+  requestId: string,
+  action: 'approve' | 'deny'
+): Promise<void> {
+  const id = typeof requestId === 'string' ? requestId.trim() : ''
+  if (!id) throw PrizmError.invalidInput('Registration request id is empty')
+  if (action !== 'approve' && action !== 'deny') {
+    throw PrizmError.invalidInput(`Unknown registration action: ${String(action)}`)
+  }
+  const config = await loadConfigFromDisk()
+  const serverUrl = serverConfigToUrl(config.server)
+  await new PrizmApi(serverUrl).resolveRegistrationRequest(id, action)
+  log.info(`[Auth] Registration request ${id} ${action === 'approve' ? 'approved' : 'denied'}`)
+}
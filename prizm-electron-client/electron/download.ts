@@ -0,0 +1,120 @@
+import * as fs from 'fs'
+import * as path from 'path'
+import { once } from 'events'
+import { httpClient } from './httpClient'
+import { PrizmError } from './errors'
+
+/** 两次进度事件的最小间隔 */
+const PROGRESS_INTERVAL_MS = 200
+
+export interface DownloadProgress {
+  url: string
+  /** 已写入字节数 */
+  bytes: number
+  /** Content-Length；服务端未提供时为 null */
+  total: number | null
+  /** 平均速率（字节 / 秒） */
+  rate: number
+  done: boolean
+}
+
+export interface DownloadOptions {
+  /** 附带 API Key */
+  auth?: boolean
+  signal?: AbortSignal
+  onProgress?: (progress: DownloadProgress) => void
+}
+
+export interface DownloadResult {
+  path: string
+  bytes: number
+}
+
+/**
+ * 流式下载到 dest：边读边写入 dest.part，完成后重命名，失败或取消时删除临时文件。
+ * 不设整体超时，两次数据块之间超过 network.read_timeout_ms 视为超时。
+ */
+export async function downloadFile(
+  url: string,
+  dest: string,
+  options: DownloadOptions = {}
+): Promise<DownloadResult> {
+  if (!path.isAbsolute(dest)) {
+    throw PrizmError.invalidInput(`Download destination must be an absolute path: ${dest}`)
+  }
+  const idleMs = httpClient.getNetworkConfig().read_timeout_ms
+  const idle = new AbortController()
+  let idleTimer: NodeJS.Timeout | undefined
+  const resetIdle = (): void => {
+    clearTimeout(idleTimer)
+    idleTimer = setTimeout(() => {
+      idle.abort(PrizmError.timeout(`Download stalled for ${idleMs}ms`))
+    }, idleMs)
+  }
+  const signal = options.signal ? AbortSignal.any([options.signal, idle.signal]) : idle.signal
+  const requestOptions = { signal, timeoutMs: Number.POSITIVE_INFINITY }
+
+  const tmpPath = `${dest}.part`
+  let file: fs.WriteStream | undefined
+  // 读取响应体期间连接仍在使用，避免被连接池空闲回收
+  const release = httpClient.acquireConnection()
+  resetIdle()
+  try {
+    const resp = options.auth
+      ? await httpClient.getAuth(url, requestOptions)
+      : await httpClient.get(url, requestOptions)
+    if (!resp.ok) {
+      throw await PrizmError.fromResponse(resp)
+    }
+    const length = Number(resp.headers.get('content-length'))
+    const total = Number.isFinite(length) && length > 0 ? length : null
+    const started = Date.now()
+    let bytes = 0
+    let lastEmit = 0
+    const emit = (done: boolean): void => {
+      const elapsed = Math.max(1, Date.now() - started)
+      options.onProgress?.({ url, bytes, total, rate: Math.round((bytes * 1000) / elapsed), done })
+    }
+
+    await fs.promises.mkdir(path.dirname(dest), { recursive: true })
+    file = fs.createWriteStream(tmpPath)
+    if (resp.body) {
+      const reader = resp.body.getReader()
+      const onAbort = (): void => {
+        reader.cancel(signal.reason).catch(() => undefined)
+      }
+      signal.addEventListener('abort', onAbort, { once: true })
+      try {
+        for (;;) {
+          const { done, value } = await reader.read()
+          if (signal.aborted) throw signal.reason
+          if (done) break
+          resetIdle()
+          bytes += value.byteLength
+          if (!file.write(value)) await once(file, 'drain')
+          if (Date.now() - lastEmit >= PROGRESS_INTERVAL_MS) {
+            lastEmit = Date.now()
+            emit(false)
+          }
+        }
+      } finally {
+        signal.removeEventListener('abort', onAbort)
+      }
+    }
+    clearTimeout(idleTimer)
+    file.end()
+    await once(file, 'close')
+    await fs.promises.rename(tmpPath, dest)
+    emit(true)
+    return { path: dest, bytes }
+  } catch (err) {
+    clearTimeout(idleTimer)
+    if (file) {
+      file.destroy()
+      await fs.promises.rm(tmpPath, { force: true })
+    }
+    throw signal.aborted ? signal.reason : err
+  } finally {
+    release()
+  }
+}
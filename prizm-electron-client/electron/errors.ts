@@ -0,0 +1,155 @@
+/**
+ * 主进程统一错误类型。IPC 只能传递 message，因此序列化为带标记的 JSON，
+ * 渲染进程用 src/utils/ipcError.ts 的 parseIpcError 还原。
+ */
+export type PrizmErrorKind =
+  | 'network'
+  | 'timeout'
+  | 'bad_status'
+  | 'parse'
+  | 'config'
+  | 'auth'
+  | 'invalid_input'
+  | 'cancelled'
+  | 'internal'
+
+export interface PrizmErrorPayload {
+  kind: PrizmErrorKind
+  message: string
+  /** HTTP 状态码（bad_status / auth） */
+  status?: number
+  /** 服务端返回的原始响应体（截断） */
+  body?: string
+  /** 服务端 ErrorResponse 中的错误码（如 VALIDATION、NOT_FOUND） */
+  code?: string
+  /** 服务端 ErrorResponse 中的错误消息 */
+  serverMessage?: string
+}
+
+export const PRIZM_ERROR_MARKER = '__PRIZM_ERROR__'
+
+const MAX_BODY_LENGTH = 2000
+
+export class PrizmError extends Error {
+  readonly kind: PrizmErrorKind
+  readonly status?: number
+  readonly body?: string
+  readonly code?: string
+  readonly serverMessage?: string
+
+  constructor(
+    kind: PrizmErrorKind,
+    message: string,
+    extra: Omit<PrizmErrorPayload, 'kind' | 'message'> = {}
+  ) {
+    super(message)
+    this.name = 'PrizmError'
+    this.kind = kind
+    this.status = extra.status
+    this.body = extra.body?.slice(0, MAX_BODY_LENGTH)
+    this.code = extra.code
+    this.serverMessage = extra.serverMessage
+  }
+
+  static network(message: string): PrizmError {
+    return new PrizmError('network', message)
+  }
+
+  static timeout(message: string): PrizmError {
+    return new PrizmError('timeout', message)
+  }
+
+  /**
+   * 非 2xx 响应。body 为服务端 ErrorResponse（{ error, code? }）时提取其中的消息与错误码
+   */
+  static badStatus(status: number, body: string): PrizmError {
+    const kind = status === 401 || status === 403 ? 'auth' : 'bad_status'
+    const parsed = parseErrorResponse(body)
+    const message = parsed.serverMessage
+      ? `Server responded with ${status}: ${parsed.serverMessage}`
+      : `Server responded with ${status}`
+    return new PrizmError(kind, message, { status, body, ...parsed })
+  }
+
+  /**
+   * 读取响应体并构造 badStatus 错误
+   */
+  static async fromResponse(resp: Response): Promise<PrizmError> {
+    return PrizmError.badStatus(resp.status, await resp.text().catch(() => ''))
+  }
+
+  static auth(message: string): PrizmError {
+    return new PrizmError('auth', message)
+  }
+
+  static parse(message: string): PrizmError {
+    return new PrizmError('parse', message)
+  }
+
+  static config(message: string): PrizmError {
+    return new PrizmError('config', message)
+  }
+
+  static invalidInput(message: string): PrizmError {
+    return new PrizmError('invalid_input', message)
+  }
+
+  static cancelled(message: string): PrizmError {
+    return new PrizmError('cancelled', message)
+  }
+
+  toJSON(): PrizmErrorPayload {
+    return {
+      kind: this.kind,
+      message: this.message,
+      status: this.status,
+      body: this.body,
+      code: this.code,
+      serverMessage: this.serverMessage
+    }
+  }
+}
+
+/**
+ * 解析服务端 ErrorResponse；非 JSON 或缺少 error 字段时返回空对象
+ */
+export function parseErrorResponse(body: string): { code?: string; serverMessage?: string } {
+  try {
+    const parsed = JSON.parse(body) as { error?: unknown; code?: unknown }
+    if (!parsed || typeof parsed !== 'object' || typeof parsed.error !== 'string') return {}
+    return {
+      serverMessage: parsed.error,
+      code: typeof parsed.code === 'string' ? parsed.code : undefined
+    }
+  } catch {
+    return {}
+  }
+}
+
+/**
+ * 将任意异常归类为 PrizmError
+ */
+export function toPrizmError(err: unknown): PrizmError {
+  if (err instanceof PrizmError) return err
+  if (err instanceof Error) {
+    if (err.name === 'TimeoutError' || err.name === 'AbortError') {
+      return PrizmError.timeout(err.message || 'Request timed out')
+    }
+    if (err instanceof SyntaxError) {
+      return PrizmError.parse(err.message)
+    }
+    // Chromium 网络栈错误形如 net::ERR_CONNECTION_REFUSED；Node fetch 为 TypeError: fetch failed
+    if (/net::ERR_|fetch failed|ECONNREFUSED|ENOTFOUND|EAI_AGAIN|ECONNRESET/.test(err.message)) {
+      return PrizmError.network(err.message)
+    }
+    return new PrizmError('internal', err.message)
+  }
+  return new PrizmError('internal', String(err))
+}
+
+/**
+ * 生成可跨 IPC 传递的 Error：message 中携带序列化的 PrizmError
+ */
+export function toIpcError(err: unknown): Error {
+  return new Error(PRIZM_ERROR_MARKER + JSON.stringify(toPrizmError(err).toJSON()))
+}
@@ -0,0 +1,61 @@
+/** 缓存条目上限，超出时淘汰最久未使用的条目 */
+const MAX_ENTRIES = 100
+/** 超过此大小的响应体不缓存 */
+const MAX_BODY_BYTES = 1024 * 1024
+
+interface CacheEntry {
+  etag: string
+  status: number
+  headers: [string, string][]
+  body: ArrayBuffer
+}
+
+/**
+ * 基于 ETag 的 GET 响应缓存：请求时带 If-None-Match，服务端返回 304 时用缓存内容构造 200 响应
+ */
+export class EtagCache {
+  private entries = new Map<string, CacheEntry>()
+
+  /** 该 URL 的缓存 ETag，用于 If-None-Match */
+  etagFor(url: string): string | undefined {
+    return this.entries.get(url)?.etag
+  }
+
+  /**
+   * 处理响应：304 且有缓存时返回缓存副本；带 ETag 的 200 写入缓存；其他原样返回
+   */
+  async handle(url: string, resp: Response): Promise<Response> {
+    if (resp.status === 304) {
+      const entry = this.entries.get(url)
+      if (!entry) return resp
+      // 刷新 LRU 顺序
+      this.entries.delete(url)
+      this.entries.set(url, entry)
+      await resp.body?.cancel()
+      return new Response(entry.body.slice(0), { status: entry.status, headers: entry.headers })
+    }
+    const etag = resp.headers.get('etag')
+    if (resp.status !== 200 || !etag) {
+      this.entries.delete(url)
+      return resp
+    }
+    const body = await resp.arrayBuffer()
+    const headers = [...resp.headers.entries()]
+    if (body.byteLength <= MAX_BODY_BYTES) {
+      this.entries.delete(url)
+      this.entries.set(url, { etag, status: resp.status, headers, body })
+      while (this.entries.size > MAX_ENTRIES) {
+        this.entries.delete(this.entries.keys().next().value as string)
+      }
+    }
+    return new Response(body.slice(0), { status: resp.status, headers })
+  }
+
+  clear(): void {
+    this.entries.clear()
+  }
+
+  get size(): number {
+    return this.entries.size
+  }
+}
@@ -0,0 +1,79 @@
+import * as fs from 'fs'
+import * as path from 'path'
+import log from 'electron-log/main'
+import { getConfigPath } from './config'
+import { writeFileAtomic } from './fsUtils'
+
+const CURSOR_FILE = 'realtime-cursor.json'
+/** 事件频繁时合并写入 */
+const SAVE_DELAY_MS = 1_000
+
+/** 最近处理的服务端事件，重连时据此请求重放 */
+export interface EventCursor {
+  /** 连接地址（不含 API Key），换服务器后游标失效 */
+  url: string
+  /** 服务端事件流 id，服务端重启后游标失效 */
+  streamId: string
+  lastEventId: number
+}
+
+export interface EventCursorStore {
+  load(): EventCursor | null
+  save(cursor: EventCursor): void
+}
+
+function isCursor(value: unknown): value is EventCursor {
+  const c = value as EventCursor
+  return (
+    !!c &&
+    typeof c.url === 'string' &&
+    typeof c.streamId === 'string' &&
+    Number.isInteger(c.lastEventId) &&
+    c.lastEventId >= 0
+  )
+}
+
+/** 保存在配置目录 realtime-cursor.json 的游标，写入经过防抖 */
+export class FileEventCursorStore implements EventCursorStore {
+  private cached: EventCursor | null | undefined
+  private timer: NodeJS.Timeout | undefined
+
+  load(): EventCursor | null {
+    if (this.cached !== undefined) return this.cached
+    try {
+      const parsed = JSON.parse(fs.readFileSync(this.filePath(), 'utf-8')) as unknown
+      this.cached = isCursor(parsed) ? parsed : null
+    } catch (err) {
+      if ((err as NodeJS.ErrnoException).code !== 'ENOENT') {
+        log.warn('[Realtime] Failed to read event cursor:', err)
+      }
+      this.cached = null
+    }
+    return this.cached
+  }
+
+  save(cursor: EventCursor): void {
+    this.cached = { ...cursor }
+    if (this.timer) return
+    this.timer = setTimeout(() => void this.flush(), SAVE_DELAY_MS)
+    this.timer.unref?.()
+  }
+
+  /** 立即写入尚未落盘的游标（退出前调用） */
+  async flush(): Promise<void> {
+    clearTimeout(this.timer)
+    this.timer = undefined
+    if (!this.cached) return
+    try {
+      await writeFileAtomic(this.filePath(), JSON.stringify(this.cached, null, 2))
+    } catch (err) {
+      log.warn('[Realtime] Failed to save event cursor:', err)
+    }
+  }
+
+  private filePath(): string {
+    return path.join(getConfigPath().configDir, CURSOR_FILE)
+  }
+}
+
+export const eventCursorStore = new FileEventCursorStore()
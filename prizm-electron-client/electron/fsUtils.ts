@@ -0,0 +1,28 @@
+import * as path from 'path'
+import * as fs from 'fs'
+
+/**
+ * 原子写文件：先写入同目录临时文件并 fsync，再 rename 覆盖目标，
+ * 写入中途崩溃时原文件保持完整
+ */
+export async function writeFileAtomic(
+  filePath: string,
+  content: string,
+  options: { mode?: number } = {}
+): Promise<void> {
+  const dir = path.dirname(filePath)
+  const tmpPath = path.join(dir, `.${path.basename(filePath)}.${process.pid}.${Date.now()}.tmp`)
+  const handle = await fs.promises.open(tmpPath, 'w', options.mode ?? 0o666)
+  try {
+    await handle.writeFile(content, 'utf-8')
+    await handle.sync()
+  } finally {
+    await handle.close()
+  }
+  try {
+    await fs.promises.rename(tmpPath, filePath)
+  } catch (err) {
+    await fs.promises.rm(tmpPath, { force: true })
+    throw err
+  }
+}
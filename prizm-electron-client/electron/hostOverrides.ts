@@ -0,0 +1,32 @@
+import { app } from 'electron'
+import log from 'electron-log/main'
+import { formatHostForUrl } from './serverUrl'
+
+let appliedRules = ''
+
+/**
+ * 转换为 Chromium --host-resolver-rules 参数，如 "MAP prizm.corp 10.0.0.2, MAP v6.corp [fd00::2]"
+ */
+export function toHostResolverRules(overrides: Record<string, string>): string {
+  return Object.entries(overrides)
+    .map(([host, ip]) => `MAP ${host} ${formatHostForUrl(ip)}`)
+    .join(', ')
+}
+
+/**
+ * 启动时应用 network.host_overrides（需在 app ready 之前调用）。
+ * 只替换 DNS 解析结果，URL 与证书校验仍使用原主机名；主进程请求与渲染进程 WebSocket 均生效
+ */
+export function applyHostOverrides(overrides: Record<string, string>): void {
+  appliedRules = toHostResolverRules(overrides)
+  if (!appliedRules) return
+  app.commandLine.appendSwitch('host-resolver-rules', appliedRules)
+  log.info('[Network] Host overrides:', appliedRules)
+}
+
+/**
+ * 配置中的 host_overrides 与启动时应用的不同时返回 true（Chromium 不支持运行时修改，需重启）
+ */
+export function hostOverridesNeedRestart(overrides: Record<string, string>): boolean {
+  return toHostResolverRules(overrides) !== appliedRules
+}
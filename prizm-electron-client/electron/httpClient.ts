@@ -0,0 +1,617 @@
+import * as http from 'http'
+import { app, session } from 'electron'
+import type { Session } from 'electron'
+import log from 'electron-log/main'
+import { DEFAULT_NETWORK_CONFIG, getConfigPath, sharedState } from './config'
+import type { NetworkConfig, RetryPolicy, ServerConfig } from './config'
+import { PrizmError, toPrizmError } from './errors'
+import { applyProxy } from './proxy'
+import { applyExtraCaCertificates, loadCaCertificates } from './tlsTrust'
+import { normalizeBasePath, serverConfigToUrl, upgradeToTls } from './serverUrl'
+import { deriveSigningSecret, signatureHeaders } from './requestSigning'
+import { socketFetch } from './socketTransport'
+import { isNetworkLoggingEnabled, logNetworkExchange } from './networkLog'
+import { EtagCache } from './etagCache'
+import { countResponseBytes, headersByteLength, networkStats } from './networkStats'
+import { createSocketAgent, effectiveIdleTimeout } from './connectionPool'
+import { CircuitBreaker } from './circuitBreaker'
+import type { CircuitStatus } from './circuitBreaker'
+import { connectionState } from './connectionState'
+
+/** 共享 HTTP session 的分区名（内存分区，不落盘 cookie） */
+const HTTP_PARTITION = 'prizm-http'
+
+export interface HttpRequestOptions {
+  headers?: Record<string, string>
+  /** 非字符串时按 JSON 序列化 */
+  body?: unknown
+  signal?: AbortSignal
+  /**
+   * 覆盖整体超时（毫秒），如健康检查使用 network.health_timeout_ms；
+   * Infinity 表示不限制（流式下载由调用方自行处理空闲超时）
+   */
+  timeoutMs?: number
+  /** 是否按 network.retry 重试；默认仅幂等方法（GET / HEAD / OPTIONS）重试 */
+  retry?: boolean
+  /** GET 请求使用 ETag 缓存（If-None-Match，304 时返回缓存内容） */
+  cache?: boolean
+  /** *Auth 请求遇到 401 时是否尝试重新注册，默认 true */
+  reauth?: boolean
+  /**
+   * *Auth 请求使用的凭据：admin 时使用管理员 Key（config.admin_key），未设置时回退到客户端 Key。
+   * 管理员 Key 被拒时不会触发重新注册
+   */
+  credential?: 'client' | 'admin'
+  /** 用该签名密钥对请求签名（network.sign_requests），每次重试重新签名；由 *Auth 请求设置 */
+  signWith?: string
+}
+
+let userAgent: string | null = null
+
+/**
+ * User-Agent：应用名 / 版本、操作系统与架构、Electron 版本
+ */
+export function buildUserAgent(): string {
+  if (!userAgent) {
+    userAgent =
+      `PrizmElectronClient/${app.getVersion()} ` +
+      `(${process.platform}; ${process.arch}) Electron/${process.versions.electron ?? 'unknown'}`
+  }
+  return userAgent
+}
+
+const IDEMPOTENT_METHODS = new Set(['GET', 'HEAD', 'OPTIONS'])
+/** 视为暂时性故障、可重试的状态码 */
+const RETRYABLE_STATUS = new Set([429, 502, 503, 504])
+/** 计入熔断失败次数的状态码（429 说明服务端仍在响应，不计入） */
+const CIRCUIT_FAILURE_STATUS = new Set([502, 503, 504])
+
+function emitCircuitState(status: CircuitStatus): void {
+  log.warn(`[Http] Circuit for ${status.origin} is ${status.state}`)
+  const win = sharedState.mainWindow
+  if (win && !win.isDestroyed()) {
+    win.webContents.send('circuit://state', status)
+  }
+  connectionState.updateCircuit(status)
+}
+
+/**
+ * 第 attempt 次重试（从 1 开始）前的等待时间
+ */
+export function computeBackoffDelay(
+  policy: RetryPolicy,
+  attempt: number,
+  random: () => number = Math.random
+): number {
+  const exp = Math.min(policy.base_delay_ms * 2 ** (attempt - 1), policy.max_delay_ms)
+  return policy.jitter ? Math.round(exp * random()) : exp
+}
+
+/** 可被 signal 中断的延时，中断时以 signal.reason 拒绝 */
+export function sleep(ms: number, signal?: AbortSignal): Promise<void> {
+  return new Promise((resolve, reject) => {
+    if (signal?.aborted) {
+      reject(signal.reason)
+      return
+    }
+    const timer = setTimeout(() => {
+      signal?.removeEventListener('abort', onAbort)
+      resolve()
+    }, ms)
+    const onAbort = (): void => {
+      clearTimeout(timer)
+      reject(signal?.reason)
+    }
+    signal?.addEventListener('abort', onAbort, { once: true })
+  })
+}
+
+/**
+ * 主进程共享 HTTP 客户端：所有对 Prizm 服务端的请求都经由同一个 Electron session，
+ * 复用连接池与 TLS 会话，并集中配置默认请求头
+ */
+export class HttpClient {
+  private httpSession: Session | null = null
+  private defaultHeaders: Record<string, string> = { Accept: 'application/json' }
+  private network: NetworkConfig = { ...DEFAULT_NETWORK_CONFIG }
+  /** 注册后的客户端 ID，作为 X-Prizm-Client-Id 发送，便于服务端日志区分客户端 */
+  private clientId = ''
+  /** 注册后获得的 API Key，由 *Auth 请求自动附带 */
+  private apiKey = ''
+  /** 管理员 Key，由 credential: 'admin' 的 *Auth 请求附带 */
+  private adminKey = ''
+  /** 当前服务器的 base_path，签名时从路径中去掉（反向代理转发给服务端前会去掉前缀） */
+  private basePath = ''
+  /** 当前服务器的源（协议 + 主机 + 端口）；*Auth 请求只向它附带凭据 */
+  private serverOrigin: string | null = null
+  /** server.verify_tls 为 false 时当前服务器的主机名，其证书不做校验 */
+  private insecureHost: string | null = null
+  /** 配置了 server.socket_path 时，发往该 host:port 的请求走本地套接字（不区分 http/https） */
+  private localSocket: { path: string; host: string } | null = null
+  private etagCache = new EtagCache()
+  /** 代理设置生效前请求需等待 */
+  private proxyReady: Promise<void> = Promise.resolve()
+  /** 占用连接的请求 / 流式读取数，为 0 时开始计算空闲时间 */
+  private activeConnections = 0
+  private idleTimer: NodeJS.Timeout | undefined
+  private socketAgent: http.Agent = createSocketAgent(DEFAULT_NETWORK_CONFIG)
+  /** API Key 被拒（401）时重新注册，见 setReauthHandler */
+  private reauthHandler: (() => Promise<string | null>) | null = null
+  private reauthInFlight: Promise<string | null> | null = null
+  private circuitBreaker = new CircuitBreaker(
+    DEFAULT_NETWORK_CONFIG.circuit_breaker,
+    emitCircuitState
+  )
+
+  /**
+   * 启动时创建共享 session（需在 app ready 之后调用）
+   */
+  init(network?: NetworkConfig): void {
+    this.configure(network)
+    this.getSession()
+  }
+
+  /**
+   * 获取共享 session。Chromium 网络栈自动声明 Accept-Encoding（gzip / deflate / br）并透明解压，
+   * 本地套接字请求由 socketFetch 做同样处理
+   */
+  getSession(): Session {
+    if (!this.httpSession) {
+      this.httpSession = session.fromPartition(HTTP_PARTITION)
+      this.httpSession.setUserAgent(buildUserAgent())
+      log.info('[Http] Shared HTTP session initialized')
+      this.updateProxy()
+      this.updateTrustedCas()
+    }
+    return this.httpSession
+  }
+
+  /**
+   * 应用配置中的网络设置
+   */
+  configure(network: NetworkConfig | undefined): void {
+    const prevProxy = JSON.stringify(this.network.proxy)
+    const prevCas = JSON.stringify(this.network.extra_ca_certs)
+    const prev = this.network
+    this.network = { ...DEFAULT_NETWORK_CONFIG, ...network }
+    this.circuitBreaker.configure(this.network.circuit_breaker)
+    if (
+      this.network.pool_idle_timeout_ms !== prev.pool_idle_timeout_ms ||
+      this.network.pool_max_idle_per_host !== prev.pool_max_idle_per_host
+    ) {
+      this.socketAgent.destroy()
+      this.socketAgent = createSocketAgent(this.network)
+    }
+    if (!this.httpSession) return
+    if (JSON.stringify(this.network.proxy) !== prevProxy) {
+      this.updateProxy()
+    }
+    if (JSON.stringify(this.network.extra_ca_certs) !== prevCas) {
+      this.updateTrustedCas()
+      void this.httpSession.closeAllConnections()
+    }
+  }
+
+  private updateTrustedCas(): void {
+    const cas = loadCaCertificates(this.network.extra_ca_certs, getConfigPath().configDir)
+    applyExtraCaCertificates(this.getSession(), cas, this.insecureHost ? [this.insecureHost] : [])
+  }
+
+  private updateProxy(): void {
+    const proxy = this.network.proxy
+    this.proxyReady = applyProxy(this.getSession(), proxy)
+      .then(() => log.info(`[Http] Proxy mode set to ${proxy.mode}`))
+      .catch((err) => {
+        log.error('[Http] Failed to apply proxy:', err)
+      })
+  }
+
+  /**
+   * 标记连接占用（请求或流式读取响应体期间），返回释放函数。
+   * 全部释放后空闲超过 pool_idle_timeout_ms 时关闭共享 session 的连接；
+   * Chromium 自身的空闲回收更早时以 Chromium 为准
+   */
+  acquireConnection(): () => void {
+    this.activeConnections++
+    clearTimeout(this.idleTimer)
+    let released = false
+    return () => {
+      if (released) return
+      released = true
+      this.activeConnections--
+      if (this.activeConnections === 0) this.scheduleIdleClose()
+    }
+  }
+
+  private scheduleIdleClose(): void {
+    this.idleTimer = setTimeout(() => {
+      if (this.activeConnections > 0 || !this.httpSession) return
+      void this.httpSession.closeAllConnections()
+    }, effectiveIdleTimeout(this.network))
+    this.idleTimer.unref?.()
+  }
+
+  /** 处于熔断（open / half_open）状态的服务器 */
+  getCircuitStates(): CircuitStatus[] {
+    return this.circuitBreaker.list()
+  }
+
+  /** 当前网络设置 */
+  getNetworkConfig(): NetworkConfig {
+    return this.network
+  }
+
+  /**
+   * 更新当前服务器：设置了 socket_path 时，发往该服务器的请求改走本地套接字；
+   * verify_tls 为 false 时不校验该服务器的证书
+   */
+  setServer(server: ServerConfig | undefined): void {
+    this.basePath = normalizeBasePath(server?.base_path)
+    this.serverOrigin = server?.host ? new URL(serverConfigToUrl(server)).origin : null
+    const insecureHost = server?.host && server.verify_tls === false ? server.host : null
+    if (insecureHost !== this.insecureHost) {
+      this.insecureHost = insecureHost
+      if (this.httpSession) {
+        this.updateTrustedCas()
+        void this.httpSession.closeAllConnections()
+      }
+    }
+    if (!server?.socket_path) {
+      this.localSocket = null
+      return
+    }
+    const host = new URL(serverConfigToUrl(server)).host
+    this.localSocket = { path: server.socket_path, host }
+    log.info(`[Http] Requests to ${host} go through local socket ${server.socket_path}`)
+  }
+
+  /** 该 URL 是否指向当前服务器（同源）；URL 无效时为 false */
+  isServerUrl(url: string): boolean {
+    try {
+      return this.serverOrigin !== null && new URL(url).origin === this.serverOrigin
+    } catch {
+      return false
+    }
+  }
+
+  /** 该 URL 的请求是否走本地套接字 */
+  usesLocalSocket(url: string): boolean {
+    return this.localSocket !== null && new URL(url).host === this.localSocket.host
+  }
+
+  /**
+   * 更新客户端 ID（注册后为服务端分配的 clientId），空串表示未注册
+   */
+  setClientId(clientId: string | undefined): void {
+    this.clientId = clientId ?? ''
+  }
+
+  /**
+   * 更新 API Key（注册、切换档案或配置变更后调用）
+   */
+  setApiKey(apiKey: string | undefined): void {
+    const next = apiKey ?? ''
+    // 不同 Key 可见的数据不同，换 Key 后缓存作废
+    if (next !== this.apiKey) this.etagCache.clear()
+    this.apiKey = next
+  }
+
+  /**
+   * 更新管理员 Key（配置变更后调用），空串表示未设置
+   */
+  setAdminKey(adminKey: string | undefined): void {
+    const next = adminKey ?? ''
+    if (next !== this.adminKey) this.etagCache.clear()
+    this.adminKey = next
+  }
+
+  /** 是否设置了管理员 Key */
+  hasAdminKey(): boolean {
+    return this.adminKey !== ''
+  }
+
+  /**
+   * 合并默认请求头
+   */
+  setDefaultHeaders(headers: Record<string, string>): void {
+    this.defaultHeaders = { ...this.defaultHeaders, ...headers }
+  }
+
+  /**
+   * 发送请求；幂等请求遇到网络错误、超时或 429/502/503/504 时按 network.retry 退避重试。
+   * 开启 network.require_tls 时 http 地址先升级为 https；options.cache 时走 ETag 缓存。
+   * 同一服务器连续失败达到 network.circuit_breaker.failure_threshold 次后熔断，冷却期内直接失败。
+   */
+  async request(method: string, url: string, options: HttpRequestOptions = {}): Promise<Response> {
+    if (this.network.require_tls) {
+      url = upgradeToTls(url)
+    }
+    const origin = new URL(url).origin
+    this.circuitBreaker.check(origin)
+    const release = this.acquireConnection()
+    try {
+      const resp = await this.requestWithCache(method, url, options)
+      if (CIRCUIT_FAILURE_STATUS.has(resp.status)) {
+        this.circuitBreaker.recordFailure(origin)
+      } else {
+        this.circuitBreaker.recordSuccess(origin)
+      }
+      return resp
+    } catch (err) {
+      const kind = toPrizmError(err).kind
+      if (kind === 'network' || kind === 'timeout') {
+        this.circuitBreaker.recordFailure(origin)
+      } else {
+        this.circuitBreaker.recordIgnored(origin)
+      }
+      throw err
+    } finally {
+      release()
+    }
+  }
+
+  private async requestWithCache(
+    method: string,
+    url: string,
+    options: HttpRequestOptions
+  ): Promise<Response> {
+    if (!options.cache || method.toUpperCase() !== 'GET') {
+      return this.requestWithRetry(method, url, options)
+    }
+    const etag = this.etagCache.etagFor(url)
+    const resp = await this.requestWithRetry(method, url, {
+      ...options,
+      headers: etag ? { 'If-None-Match': etag, ...options.headers } : options.headers
+    })
+    return this.etagCache.handle(url, resp)
+  }
+
+  private async requestWithRetry(
+    method: string,
+    url: string,
+    options: HttpRequestOptions
+  ): Promise<Response> {
+    const policy = this.network.retry
+    const retryable = options.retry ?? IDEMPOTENT_METHODS.has(method.toUpperCase())
+    const attempts = retryable ? Math.max(1, policy.attempts) : 1
+    for (let attempt = 1; ; attempt++) {
+      const isLast = attempt >= attempts
+      try {
+        const resp = await this.requestOnce(method, url, options)
+        if (isLast || !RETRYABLE_STATUS.has(resp.status)) return resp
+        await resp.body?.cancel()
+        log.warn(`[HTTP] ${method} ${url} -> ${resp.status}, retry ${attempt}/${attempts - 1}`)
+      } catch (err) {
+        const kind = toPrizmError(err).kind
+        const transient = kind === 'network' || kind === 'timeout'
+        if (isLast || !transient || options.signal?.aborted) throw err
+        log.warn(`[HTTP] ${method} ${url} failed (${kind}), retry ${attempt}/${attempts - 1}`)
+      }
+      await sleep(computeBackoffDelay(policy, attempt), options.signal)
+    }
+  }
+
+  private async requestOnce(
+    method: string,
+    url: string,
+    options: HttpRequestOptions
+  ): Promise<Response> {
+    await this.proxyReady
+    const headers: Record<string, string> = {
+      ...this.defaultHeaders,
+      ...this.network.extra_headers,
+      'User-Agent': buildUserAgent(),
+      ...(this.clientId ? { 'X-Prizm-Client-Id': this.clientId } : {}),
+      ...options.headers
+    }
+    let body: string | undefined
+    if (options.body !== undefined) {
+      if (typeof options.body === 'string') {
+        body = options.body
+      } else {
+        body = JSON.stringify(options.body)
+        headers['Content-Type'] = headers['Content-Type'] ?? 'application/json'
+      }
+    }
+    if (options.signWith) {
+      Object.assign(
+        headers,
+        signatureHeaders(options.signWith, { method, path: this.signedPath(url), body: body ?? '' })
+      )
+    }
+    // 连接超时：响应头到达前有效；整体超时：覆盖读取响应体
+    const totalMs = options.timeoutMs ?? this.network.read_timeout_ms
+    const connectMs = Math.min(this.network.connect_timeout_ms, totalMs)
+    const connectController = new AbortController()
+    const connectTimer = setTimeout(() => {
+      connectController.abort(PrizmError.timeout(`Connection timed out after ${connectMs}ms`))
+    }, connectMs)
+    const totalController = new AbortController()
+    const totalTimer = Number.isFinite(totalMs)
+      ? setTimeout(() => {
+          totalController.abort(PrizmError.timeout(`Request timed out after ${totalMs}ms`))
+        }, totalMs)
+      : undefined
+    totalTimer?.unref?.()
+    const signals = [connectController.signal, totalController.signal]
+    if (options.signal) signals.push(options.signal)
+    const started = Date.now()
+    const stats = networkStats.recordRequest(
+      method,
+      url,
+      headersByteLength(headers) + (body ? Buffer.byteLength(body) : 0)
+    )
+    try {
+      const init = { method, headers, body, signal: AbortSignal.any(signals) }
+      const socket = this.localSocket
+      const resp =
+        socket && this.usesLocalSocket(url)
+          ? await socketFetch(socket.path, url, { ...init, agent: this.socketAgent })
+          : await this.getSession().fetch(url, init)
+      if (isNetworkLoggingEnabled()) {
+        const latencyMs = Date.now() - started
+        // 流式 / 二进制响应不读取，避免日志阻塞调用方
+        const contentType = resp.headers.get('content-type') ?? ''
+        const responseBody = /event-stream|octet-stream/.test(contentType)
+          ? `<${contentType}>`
+          : await resp
+              .clone()
+              .text()
+              .catch(() => '')
+        logNetworkExchange({
+          method,
+          url,
+          headers,
+          requestBody: body,
+          status: resp.status,
+          responseBody,
+          latencyMs
+        })
+      }
+      return countResponseBytes(resp, (n) => {
+        stats.bytes_received += n
+      })
+    } catch (err) {
+      stats.errors++
+      clearTimeout(totalTimer)
+      logNetworkExchange({
+        method,
+        url,
+        headers,
+        requestBody: body,
+        latencyMs: Date.now() - started,
+        error: toPrizmError(err).message
+      })
+      throw err
+    } finally {
+      clearTimeout(connectTimer)
+    }
+  }
+
+  /**
+   * 健康检查类请求：使用较短的 network.health_timeout_ms
+   */
+  healthGet(url: string, options?: HttpRequestOptions): Promise<Response> {
+    return this.get(url, { timeoutMs: this.network.health_timeout_ms, ...options })
+  }
+
+  /**
+   * 设置 401 时的重新注册回调：返回新的 API Key，返回 null 表示不重新注册
+   */
+  setReauthHandler(handler: (() => Promise<string | null>) | null): void {
+    this.reauthHandler = handler
+  }
+
+  /**
+   * 用 rejectedKey 发出的请求被拒后获取新 Key；并发的 401 共用同一次重新注册，
+   * 其他请求已换过 Key 时直接使用当前 Key
+   */
+  private async reauthenticate(rejectedKey: string): Promise<string | null> {
+    if (this.apiKey && this.apiKey !== rejectedKey) return this.apiKey
+    if (!this.reauthHandler) return null
+    if (!this.reauthInFlight) {
+      this.reauthInFlight = this.reauthHandler()
+        .then((key) => {
+          if (key) this.setApiKey(key)
+          return key
+        })
+        .catch((err) => {
+          log.error('[Http] Re-registration failed:', err)
+          return null
+        })
+        .finally(() => {
+          this.reauthInFlight = null
+        })
+    }
+    return this.reauthInFlight
+  }
+
+  /**
+   * 附带 API Key 的请求（开启 network.sign_requests 时改为签名）：未注册时直接报 auth 错误，服务端返回 401/403 时抛出 auth 类型的 PrizmError。
+   * 401 且设置了重新注册回调时，先重新注册再重试一次原请求。
+   * options.credential 为 admin 且设置了管理员 Key 时改用管理员 Key，不签名也不重新注册。
+   * URL 不属于当前服务器时拒绝发送，凭据不会离开配置的服务器
+   */
+  async requestAuth(
+    method: string,
+    url: string,
+    options: HttpRequestOptions = {}
+  ): Promise<Response> {
+    if (!this.isServerUrl(url)) {
+      throw PrizmError.invalidInput('Credentials are only sent to the configured server')
+    }
+    if (options.credential === 'admin' && this.adminKey) {
+      const resp = await this.request(method, url, {
+        ...options,
+        headers: { Authorization: `Bearer ${this.adminKey}`, ...options.headers }
+      })
+      return this.checkAuthStatus(resp)
+    }
+    const usedKey = this.apiKey
+    const resp = await this.sendWithApiKey(method, url, options)
+    if (resp.status === 401 && this.reauthHandler && options.reauth !== false) {
+      const nextKey = await this.reauthenticate(usedKey)
+      if (nextKey) {
+        await resp.body?.cancel()
+        const retried = await this.sendWithApiKey(method, url, options)
+        return this.checkAuthStatus(retried)
+      }
+    }
+    return this.checkAuthStatus(resp)
+  }
+
+  private async sendWithApiKey(
+    method: string,
+    url: string,
+    options: HttpRequestOptions
+  ): Promise<Response> {
+    if (!this.apiKey) {
+      throw PrizmError.auth('Client is not registered: missing API key')
+    }
+    // 签名需要服务端按 X-Prizm-Client-Id 找到对应的 Key，未知 clientId 时仍发送 Bearer
+    if (this.network.sign_requests && this.clientId) {
+      const signWith = deriveSigningSecret(this.apiKey, this.clientId)
+      return this.request(method, url, { ...options, signWith })
+    }
+    return this.request(method, url, {
+      ...options,
+      headers: { Authorization: `Bearer ${this.apiKey}`, ...options.headers }
+    })
+  }
+
+  /** 服务端看到的路径与查询串：去掉当前服务器的 base_path 前缀 */
+  private signedPath(url: string): string {
+    const { pathname, search } = new URL(url)
+    const base = this.basePath
+    const path =
+      base && (pathname === base || pathname.startsWith(`${base}/`))
+        ? pathname.slice(base.length) || '/'
+        : pathname
+    return `${path}${search}`
+  }
+
+  private async checkAuthStatus(resp: Response): Promise<Response> {
+    if (resp.status === 401 || resp.status === 403) {
+      throw await PrizmError.fromResponse(resp)
+    }
+    return resp
+  }
+
+  getAuth(url: string, options?: HttpRequestOptions): Promise<Response> {
+    return this.requestAuth('GET', url, options)
+  }
+
+  postAuth(url: string, body?: unknown, options?: HttpRequestOptions): Promise<Response> {
+    return this.requestAuth('POST', url, { ...options, body })
+  }
+
+  get(url: string, options?: HttpRequestOptions): Promise<Response> {
+    return this.request('GET', url, options)
+  }
+
+  post(url: string, body?: unknown, options?: HttpRequestOptions): Promise<Response> {
+    return this.request('POST', url, { ...options, body })
+  }
+}
+
+export const httpClient = new HttpClient()
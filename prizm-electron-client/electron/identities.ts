@@ -0,0 +1,97 @@
+import type { ClientIdentity, PrizmConfig } from './config'
+import { PrizmError } from './errors'
+import { defaultProfileName, upsertActiveProfile } from './profiles'
+
+export interface IdentitySummary {
+  name: string
+  clientName: string
+  grantedScopes: string[]
+  hasApiKey: boolean
+  active: boolean
+}
+
+/**
+ * 从当前顶层配置生成身份快照
+ */
+function snapshotIdentity(config: PrizmConfig): ClientIdentity {
+  return {
+    server: defaultProfileName(config),
+    client_name: config.client.name,
+    ...(config.client.registration_name
+      ? { registration_name: config.client.registration_name }
+      : {}),
+    api_key: config.api_key,
+    requested_scopes: [...config.client.requested_scopes],
+    granted_scopes: [...config.client.granted_scopes]
+  }
+}
+
+/**
+ * 将当前客户端凭据保存为指定身份并设为当前身份
+ */
+export function upsertIdentity(config: PrizmConfig, name: string): PrizmConfig {
+  config.identities = { ...(config.identities ?? {}), [name]: snapshotIdentity(config) }
+  config.active_identity = name
+  return config
+}
+
+/**
+ * 列出当前服务器上的身份
+ */
+export function listIdentities(config: PrizmConfig): IdentitySummary[] {
+  const server = defaultProfileName(config)
+  return Object.entries(config.identities ?? {})
+    .filter(([, identity]) => identity.server === server)
+    .map(([name, identity]) => ({
+      name,
+      clientName: identity.client_name,
+      grantedScopes: [...identity.granted_scopes],
+      hasApiKey: !!identity.api_key,
+      active: config.active_identity === name
+    }))
+}
+
+/**
+ * 切换到当前服务器上的指定身份：先保存当前身份，再把目标身份展开到顶层字段
+ */
+export function useIdentity(config: PrizmConfig, name: string): PrizmConfig {
+  const target = config.identities?.[name]
+  if (!target) {
+    throw PrizmError.invalidInput(`Identity not found: ${name}`)
+  }
+  if (target.server !== defaultProfileName(config)) {
+    throw PrizmError.invalidInput(`Identity ${name} belongs to another server: ${target.server}`)
+  }
+  if (config.active_identity && config.active_identity !== name) {
+    upsertIdentity(config, config.active_identity)
+  }
+  config.api_key = target.api_key
+  delete config.api_key_expires_at
+  delete config.refresh_token
+  config.client = {
+    ...config.client,
+    name: target.client_name,
+    registration_name: target.registration_name,
+    requested_scopes: [...target.requested_scopes],
+    granted_scopes: [...target.granted_scopes]
+  }
+  config.active_identity = name
+  if (config.active_profile) upsertActiveProfile(config)
+  return config
+}
+
+/**
+ * 删除身份；不允许删除当前正在使用的身份
+ */
+export function deleteIdentity(config: PrizmConfig, name: string): PrizmConfig {
+  if (!config.identities?.[name]) {
+    throw PrizmError.invalidInput(`Identity not found: ${name}`)
+  }
+  if (config.active_identity === name) {
+    throw PrizmError.invalidInput(`Cannot delete the active identity: ${name}`)
+  }
+  const identities = { ...config.identities }
+  delete identities[name]
+  config.identities = identities
+  return config
+}
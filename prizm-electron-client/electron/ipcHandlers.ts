@@ -1,93 +1,85 @@
-import { app, ipcMain, shell, dialog, clipboard, nativeTheme } from 'electron'
+import { app, ipcMain, shell, dialog, clipboard } from 'electron'
 import * as path from 'path'
 import * as fs from 'fs'
 import log from 'electron-log/main'
 import { sharedState } from './config'
-import type { PrizmConfig, ThemeMode } from './config'
-import { loadConfigFromDisk, saveConfigToDisk, saveThemeMode } from './config'
+import type {
+  NotificationCategory,
+  NotificationSeverity,
+  PrizmConfig,
+  ProxyConfig,
+  ThemeMode
+} from './config'
+import {
+  deepMergeConfig,
+  listConfigBackups,
+  loadConfigFromDisk,
+  normalizeConfig,
+  restoreConfigBackup,
+  updateConfig
+} from './config'
 import { startClipboardSync, stopClipboardSync } from './clipboardSync'
-import { showNotificationInWindow } from './windowManager'
+import { openDashboard, openDashboardWindow, showNotificationInWindow } from './windowManager'
 import { browserNodeService } from './browserNodeService'
+import { httpClient } from './httpClient'
+import { connectionState } from './connectionState'
+import { testProxy } from './proxy'
+import { isNetworkLoggingEnabled, setNetworkLogging } from './networkLog'
+import { networkStats } from './networkStats'
+import {
+  clearOfflineQueue,
+  getOfflineQueueStatus,
+  listOfflineQueue,
+  replayOfflineQueue,
+  sendOrQueue
+} from './offlineQueue'
+import type { QueuedRequest } from './offlineQueue'
+import { checkAllServers, healthTargets, isServerHealthy } from './serverHealth'
+import { downloadFile } from './download'
+import { deregisterClient } from './deregister'
+import { verifyCredentials } from './credentialHealth'
+import { listClients, resolveRegistrationRequest, revokeClient } from './devices'
+import { listPendingConsents, respondConsent } from './consent'
+import { registerWithDeviceCode } from './deviceAuth'
+import { generatePairingQr, pairFromQr, pairWithCode } from './pairing'
+import { measureLatency } from './latency'
+import { requestAdditionalScopes } from './scopeElevation'
+import {
+  getPendingRegistration,
+  resumeRegistration,
+  startRegistration,
+  toRegisteredClient
+} from './registration'
+import { serverConfigToUrl } from './serverUrl'
+import { PrizmError, toIpcError, toPrizmError } from './errors'
+import { cancelRequest, runCancellable } from './requestRegistry'
+import { validateConfig } from './configValidation'
+import { exportConfig, importConfig } from './configTransfer'
+import { maskSecrets, restoreMaskedSecrets } from './configMask'
+import { requireUserVerification } from './osAuth'
+import { diffConfig, resetConfigSection } from './configDiff'
+import { deleteProfile, listProfiles, switchProfile } from './profiles'
+import { deleteIdentity, listIdentities, useIdentity } from './identities'
+import { realtimeConnection } from './realtime'
+import { tunnelClient } from './tunnels'
+import { setNotificationsPaused } from './serverNotifications'
+import { notificationHistory } from './notificationHistory'
+import type { NotificationListOptions } from './notificationHistory'
+import {
+  isDoNotDisturb,
+  isNotificationAllowed,
+  notificationCategoryOf,
+  toNotificationSeverity
+} from './notificationPolicy'
+import { refreshTray } from './trayManager'
+import { getGlobalHotkey, setGlobalHotkey } from './shortcuts'
+import { getThemeState, setThemeMode } from './theme'
 
 const DEBUG_NOTIFY = true
 function logNotify(...args: unknown[]) {
   if (DEBUG_NOTIFY) log.info('[Notify]', ...args)
 }
 
-/**
- * 从 serverUrl 中提取 host 和 port
- */
-function extractHostPort(url: string): { host: string; port: string } {
-  let clean = url
-  const prefixes = ['http://', 'https://', 'ws://', 'wss://']
-  for (const p of prefixes) {
-    if (clean.startsWith(p)) {
-      clean = clean.slice(p.length)
-      break
-    }
-  }
-  const idx = clean.lastIndexOf(':')
-  if (idx === -1) {
-    return { host: clean, port: '4127' }
-  }
-  return {
-    host: clean.slice(0, idx),
-    port: clean.slice(idx + 1)
-  }
-}
-
-/**
- * 注册客户端：健康检查 + /auth/register
- */
-async function registerClientOnServer(
-  serverUrl: string,
-  name: string,
-  requestedScopes: string[]
-): Promise<{ clientId?: string; apiKey?: string }> {
-  const healthUrl = `${serverUrl.replace(/\/+$/, '')}/health`
-  const resp = await fetch(healthUrl)
-  if (!resp.ok) {
-    throw new Error(`Health check failed: ${resp.status}`)
-  }
-  const health = (await resp.json()) as { status: string }
-  if (health.status !== 'ok') {
-    throw new Error('Server health check failed')
-  }
-
-  const registerUrl = `${serverUrl.replace(/\/+$/, '')}/auth/register`
-  const body = {
-    name,
-    requestedScopes: requestedScopes && requestedScopes.length > 0 ? requestedScopes : undefined
-  }
-
-  // POST /auth/register is exempt from auth (no API key required)
-  const registerResp = await fetch(registerUrl, {
-    method: 'POST',
-    headers: { 'Content-Type': 'application/json' },
-    body: JSON.stringify(body)
-  })
-
-  if (!registerResp.ok) {
-    const text = await registerResp.text()
-    throw new Error(`Register failed: ${registerResp.status} ${text}`)
-  }
-
-  return (await registerResp.json()) as { clientId?: string; apiKey?: string }
-}
-
-/**
- * 测试服务器连接
- */
-async function testConnectionOnServer(serverUrl: string): Promise<boolean> {
-  const healthUrl = `${serverUrl.replace(/\/+$/, '')}/health`
-  const resp = await fetch(healthUrl)
-  if (!resp.ok) {
-    return false
-  }
-  const health = (await resp.json()) as { status: string }
-  return health.status === 'ok'
-}
-
 /** 文本文件扩展名白名单 */
 const TEXT_EXTS = new Set([
   '.txt',
@@ -184,16 +176,36 @@ function readFilesFromPaths(paths: string[]): ReadFileResult[] {
   return results
 }
 
+/** 配对时未指定名称则沿用注册时的名称（client.name 注册后已是 clientId） */
+async function registrationName(): Promise<string> {
+  const { client } = await loadConfigFromDisk()
+  return client.registration_name || client.name
+}
+
 /**
  * 注册 IPC 处理器
  */
 export function registerIpcHandlers(): void {
+  // 返回给渲染进程的配置一律不含密钥（见 configMask.ts），Key 原文只能经 reveal_api_key 取得
   ipcMain.handle('load_config', async () => {
     try {
-      return await loadConfigFromDisk()
+      return maskSecrets(await loadConfigFromDisk())
     } catch (err) {
       log.error('[Electron] load_config failed:', err)
-      throw err
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('reveal_api_key', async () => {
+    try {
+      const config = await loadConfigFromDisk()
+      if (!config.api_key) throw PrizmError.config('No API key is configured')
+      await requireUserVerification('查看 Prizm API Key')
+      log.info('[Electron] API key revealed after system authentication')
+      return config.api_key
+    } catch (err) {
+      log.error('[Electron] reveal_api_key failed:', err)
+      throw toIpcError(err)
     }
   })
 
@@ -207,14 +219,131 @@ export function registerIpcHandlers(): void {
         typeof config.api_key === 'undefined' ||
         !config.tray
       ) {
-        throw new Error('Invalid config payload')
+        throw PrizmError.invalidInput('Invalid config payload')
       }
 
-      await saveConfigToDisk(config)
+      await updateConfig((current) =>
+        normalizeConfig(restoreMaskedSecrets(config, current)).config
+      )
       return true
     } catch (err) {
       log.error('[Electron] save_config failed:', err)
-      throw err
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('update_config', async (_event, patch: Record<string, unknown>) => {
+    try {
+      if (!patch || typeof patch !== 'object' || Array.isArray(patch)) {
+        throw PrizmError.invalidInput('Invalid config patch')
+      }
+      const updated = await updateConfig(
+        (config) =>
+          normalizeConfig(restoreMaskedSecrets(deepMergeConfig(config, patch), config)).config
+      )
+      return maskSecrets(updated)
+    } catch (err) {
+      log.error('[Electron] update_config failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('reset_config', async (_event, payload?: { section?: string }) => {
+    try {
+      return maskSecrets(
+        await updateConfig((config) => resetConfigSection(config, payload?.section))
+      )
+    } catch (err) {
+      log.error('[Electron] reset_config failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('diff_config', async () => {
+    try {
+      return diffConfig(await loadConfigFromDisk())
+    } catch (err) {
+      log.error('[Electron] diff_config failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('list_config_backups', async () => {
+    try {
+      return await listConfigBackups()
+    } catch (err) {
+      log.error('[Electron] list_config_backups failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('restore_config_backup', async (_event, { index }: { index: number }) => {
+    try {
+      return maskSecrets(await restoreConfigBackup(index))
+    } catch (err) {
+      log.error('[Electron] restore_config_backup failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle(
+    'validate_config',
+    async (_event, payload?: { config?: PrizmConfig; knownScopes?: string[] }) => {
+      try {
+        const config = payload?.config ?? (await loadConfigFromDisk())
+        return validateConfig(config, payload?.knownScopes)
+      } catch (err) {
+        log.error('[Electron] validate_config failed:', err)
+        throw toIpcError(err)
+      }
+    }
+  )
+
+  ipcMain.handle(
+    'export_config',
+    async (_event, payload?: { path?: string; includeSecrets?: boolean }) => {
+      try {
+        let filePath = payload?.path
+        if (!filePath) {
+          const opts = {
+            title: '导出配置',
+            defaultPath: 'prizm-config.json',
+            filters: [{ name: '配置文件', extensions: ['json', 'toml'] }]
+          }
+          const result = sharedState.mainWindow
+            ? await dialog.showSaveDialog(sharedState.mainWindow, opts)
+            : await dialog.showSaveDialog(opts)
+          if (result.canceled || !result.filePath) return null
+          filePath = result.filePath
+        }
+        await exportConfig(filePath, payload?.includeSecrets === true)
+        return filePath
+      } catch (err) {
+        log.error('[Electron] export_config failed:', err)
+        throw toIpcError(err)
+      }
+    }
+  )
+
+  ipcMain.handle('import_config', async (_event, payload?: { path?: string }) => {
+    try {
+      let filePath = payload?.path
+      if (!filePath) {
+        const opts = {
+          title: '导入配置',
+          properties: ['openFile' as const],
+          filters: [{ name: '配置文件', extensions: ['json', 'toml'] }]
+        }
+        const result = sharedState.mainWindow
+          ? await dialog.showOpenDialog(sharedState.mainWindow, opts)
+          : await dialog.showOpenDialog(opts)
+        if (result.canceled || !result.filePaths.length) return null
+        filePath = result.filePaths[0]
+      }
+      return await importConfig(filePath)
+    } catch (err) {
+      log.error('[Electron] import_config failed:', err)
+      throw toIpcError(err)
     }
   })
 
@@ -225,38 +354,502 @@ export function registerIpcHandlers(): void {
       {
         serverUrl,
         name,
-        requestedScopes
-      }: { serverUrl: string; name: string; requestedScopes: string[] }
+        requestedScopes,
+        profileName,
+        requestId
+      }: {
+        serverUrl: string
+        name: string
+        requestedScopes: string[]
+        profileName?: string
+        requestId?: string
+      }
+    ) => {
+      try {
+        const register = await runCancellable(requestId, (signal) =>
+          startRegistration({ serverUrl, name, requestedScopes, profileName }, signal)
+        )
+        return toRegisteredClient(register)
+      } catch (err) {
+        log.error('[Electron] register_client failed:', err)
+        throw toIpcError(err)
+      }
+    }
+  )
+
+  ipcMain.handle(
+    'register_with_preset',
+    async (
+      _event,
+      {
+        serverUrl,
+        name,
+        preset,
+        profileName,
+        requestId
+      }: {
+        serverUrl: string
+        name: string
+        preset: string
+        profileName?: string
+        requestId?: string
+      }
     ) => {
       try {
-        const register = await registerClientOnServer(serverUrl, name, requestedScopes)
         const config = await loadConfigFromDisk()
+        const scopes = config.client.scope_presets?.[preset]
+        if (!scopes) {
+          throw PrizmError.config(`Unknown scope preset: ${preset}`)
+        }
+        const register = await runCancellable(requestId, (signal) =>
+          startRegistration({ serverUrl, name, requestedScopes: scopes, profileName }, signal)
+        )
+        return toRegisteredClient(register)
+      } catch (err) {
+        log.error('[Electron] register_with_preset failed:', err)
+        throw toIpcError(err)
+      }
+    }
+  )
 
-        const { host, port } = extractHostPort(serverUrl)
-        config.server.host = host
-        config.server.port = port
-        config.server.is_dev = 'true'
-        config.client.name = register.clientId || name
-        config.api_key = register.apiKey || ''
+  ipcMain.handle(
+    'register_identity',
+    async (
+      _event,
+      {
+        identity,
+        name,
+        requestedScopes,
+        requestId
+      }: { identity: string; name: string; requestedScopes: string[]; requestId?: string }
+    ) => {
+      try {
+        if (!identity?.trim()) {
+          throw PrizmError.invalidInput('Identity name is empty')
+        }
+        const config = await loadConfigFromDisk()
+        const serverUrl = serverConfigToUrl(config.server)
+        const register = await runCancellable(requestId, (signal) =>
+          startRegistration({ serverUrl, name, requestedScopes, identity: identity.trim() }, signal)
+        )
+        return toRegisteredClient(register)
+      } catch (err) {
+        log.error('[Electron] register_identity failed:', err)
+        throw toIpcError(err)
+      }
+    }
+  )
 
-        await saveConfigToDisk(config)
-        return register.apiKey
+  ipcMain.handle(
+    'register_with_device_code',
+    async (
+      _event,
+      {
+        serverUrl,
+        name,
+        requestedScopes,
+        profileName,
+        requestId
+      }: {
+        serverUrl: string
+        name: string
+        requestedScopes: string[]
+        profileName?: string
+        requestId?: string
+      }
+    ) => {
+      try {
+        const register = await runCancellable(requestId, (signal) =>
+          registerWithDeviceCode(
+            { serverUrl, name, requestedScopes, profileName },
+            { requestId, signal }
+          )
+        )
+        return toRegisteredClient(register)
       } catch (err) {
-        log.error('[Electron] register_client failed:', err)
-        throw err
+        log.error('[Electron] register_with_device_code failed:', err)
+        throw toIpcError(err)
       }
     }
   )
 
-  ipcMain.handle('test_connection', async (_event, { serverUrl }: { serverUrl: string }) => {
+  ipcMain.handle('generate_pairing_qr', async (_event, payload?: { scopes?: string[] }) => {
     try {
-      return await testConnectionOnServer(serverUrl)
+      return await generatePairingQr(payload?.scopes)
     } catch (err) {
-      log.error('[Electron] test_connection failed:', err)
-      return false
+      log.error('[Electron] generate_pairing_qr failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle(
+    'pair_from_qr',
+    async (
+      _event,
+      {
+        payload,
+        name,
+        profileName,
+        requestId
+      }: { payload: string; name?: string; profileName?: string; requestId?: string }
+    ) => {
+      try {
+        const clientName = name || (await registrationName())
+        const register = await runCancellable(requestId, (signal) =>
+          pairFromQr(payload, clientName, profileName, signal)
+        )
+        return toRegisteredClient(register)
+      } catch (err) {
+        log.error('[Electron] pair_from_qr failed:', err)
+        throw toIpcError(err)
+      }
+    }
+  )
+
+  ipcMain.handle(
+    'pair_with_code',
+    async (
+      _event,
+      {
+        code,
+        name,
+        profileName,
+        requestId
+      }: { code: string; name?: string; profileName?: string; requestId?: string }
+    ) => {
+      try {
+        const clientName = name || (await registrationName())
+        const register = await runCancellable(requestId, (signal) =>
+          pairWithCode(code, clientName, profileName, signal)
+        )
+        return toRegisteredClient(register)
+      } catch (err) {
+        log.error('[Electron] pair_with_code failed:', err)
+        throw toIpcError(err)
+      }
+    }
+  )
+
+  ipcMain.handle('resume_registration', async (_event, payload?: { requestId?: string }) => {
+    try {
+      const register = await runCancellable(payload?.requestId, (signal) =>
+        resumeRegistration(signal)
+      )
+      return register ? toRegisteredClient(register) : null
+    } catch (err) {
+      log.error('[Electron] resume_registration failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('get_pending_registration', async () => {
+    return getPendingRegistration()
+  })
+
+  ipcMain.handle('deregister_client', async (_event, payload?: { force?: boolean }) => {
+    try {
+      return await deregisterClient(payload?.force === true)
+    } catch (err) {
+      log.error('[Electron] deregister_client failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('verify_credentials', async (_event, payload?: { requestId?: string }) => {
+    try {
+      return await runCancellable(payload?.requestId, (signal) => verifyCredentials(signal))
+    } catch (err) {
+      log.error('[Electron] verify_credentials failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('list_clients', async (_event, payload?: { requestId?: string }) => {
+    try {
+      return await runCancellable(payload?.requestId, (signal) => listClients(signal))
+    } catch (err) {
+      log.error('[Electron] list_clients failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('revoke_client', async (_event, { clientId }: { clientId: string }) => {
+    try {
+      await revokeClient(clientId)
+      return true
+    } catch (err) {
+      log.error('[Electron] revoke_client failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  for (const action of ['approve', 'deny'] as const) {
+    ipcMain.handle(
+      `${action}_registration_request`,
+      async (_event, { requestId }: { requestId: string }) => {
+        try {
+          await resolveRegistrationRequest(requestId, action)
+          return true
+        } catch (err) {
+          log.error(`[Electron] ${action}_registration_request failed:`, err)
+          throw toIpcError(err)
+        }
+      }
+    )
+  }
+
+  ipcMain.handle('get_pending_consents', async () => listPendingConsents())
+
+  ipcMain.handle(
+    'respond_consent',
+    async (_event, { id, allow }: { id: string; allow: boolean }) => {
+      try {
+        respondConsent(id, allow)
+        return true
+      } catch (err) {
+        log.error('[Electron] respond_consent failed:', err)
+        throw toIpcError(err)
+      }
+    }
+  )
+
+  ipcMain.handle('set_admin_key', async (_event, { adminKey }: { adminKey: string }) => {
+    try {
+      const key = typeof adminKey === 'string' ? adminKey.trim() : ''
+      if (!key) throw PrizmError.invalidInput('Admin key is empty')
+      await updateConfig((config) => {
+        config.admin_key = key
+      })
+      return true
+    } catch (err) {
+      log.error('[Electron] set_admin_key failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('clear_admin_key', async () => {
+    try {
+      await updateConfig((config) => {
+        delete config.admin_key
+      })
+      return true
+    } catch (err) {
+      log.error('[Electron] clear_admin_key failed:', err)
+      throw toIpcError(err)
     }
   })
 
+  ipcMain.handle('has_admin_key', async () => {
+    const config = await loadConfigFromDisk()
+    return !!config.admin_key
+  })
+
+  ipcMain.handle(
+    'request_additional_scopes',
+    async (_event, { scopes, requestId }: { scopes: string[]; requestId?: string }) => {
+      try {
+        return await runCancellable(requestId, (signal) =>
+          requestAdditionalScopes(Array.isArray(scopes) ? scopes : [], { requestId, signal })
+        )
+      } catch (err) {
+        log.error('[Electron] request_additional_scopes failed:', err)
+        throw toIpcError(err)
+      }
+    }
+  )
+
+  ipcMain.handle('get_granted_scopes', async () => {
+    const config = await loadConfigFromDisk()
+    return config.client.granted_scopes
+  })
+
+  ipcMain.handle('list_profiles', async () => {
+    const config = await loadConfigFromDisk()
+    return listProfiles(config)
+  })
+
+  ipcMain.handle('switch_profile', async (_event, { name }: { name: string }) => {
+    try {
+      return maskSecrets(await updateConfig((config) => switchProfile(config, name)))
+    } catch (err) {
+      log.error('[Electron] switch_profile failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('delete_profile', async (_event, { name }: { name: string }) => {
+    try {
+      await updateConfig((config) => deleteProfile(config, name))
+      return true
+    } catch (err) {
+      log.error('[Electron] delete_profile failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('list_identities', async () => {
+    const config = await loadConfigFromDisk()
+    return listIdentities(config)
+  })
+
+  ipcMain.handle('use_identity', async (_event, { name }: { name: string }) => {
+    try {
+      return maskSecrets(await updateConfig((config) => useIdentity(config, name)))
+    } catch (err) {
+      log.error('[Electron] use_identity failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('delete_identity', async (_event, { name }: { name: string }) => {
+    try {
+      await updateConfig((config) => deleteIdentity(config, name))
+      return true
+    } catch (err) {
+      log.error('[Electron] delete_identity failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle(
+    'test_connection',
+    async (_event, { serverUrl, requestId }: { serverUrl: string; requestId?: string }) => {
+      try {
+        const healthy = await runCancellable(requestId, (signal) =>
+          isServerHealthy(serverUrl, signal)
+        )
+        if (healthy) {
+          // 连接恢复后重放离线期间排队的请求
+          void replayOfflineQueue()
+        }
+        return healthy
+      } catch (err) {
+        // 被取消时让前端区分“已取消”与“连接失败”
+        if (toPrizmError(err).kind === 'cancelled') throw toIpcError(err)
+        log.error('[Electron] test_connection failed:', err)
+        return false
+      }
+    }
+  )
+
+  ipcMain.handle('cancel_request', (_event, { requestId }: { requestId: string }) => {
+    return cancelRequest(requestId)
+  })
+
+  ipcMain.handle(
+    'download_file',
+    async (
+      event,
+      {
+        url,
+        dest,
+        auth,
+        requestId
+      }: { url: string; dest: string; auth?: boolean; requestId?: string }
+    ) => {
+      try {
+        return await runCancellable(requestId, (signal) =>
+          downloadFile(url, dest, {
+            auth,
+            signal,
+            onProgress: (progress) => {
+              if (!event.sender.isDestroyed()) {
+                event.sender.send('download://progress', { requestId, dest, ...progress })
+              }
+            }
+          })
+        )
+      } catch (err) {
+        log.error('[Electron] download_file failed:', err)
+        throw toIpcError(err)
+      }
+    }
+  )
+
+  ipcMain.handle(
+    'test_proxy',
+    async (_event, { proxy, serverUrl }: { proxy: ProxyConfig; serverUrl: string }) => {
+      const healthUrl = `${serverUrl.replace(/\/+$/, '')}/health`
+      const result = await testProxy(
+        proxy,
+        healthUrl,
+        httpClient.getNetworkConfig().health_timeout_ms
+      )
+      log.info('[Electron] test_proxy:', proxy.mode, result.resolved, result.ok)
+      return result
+    }
+  )
+
+  ipcMain.handle(
+    'send_or_queue',
+    async (_event, entry: Omit<QueuedRequest, 'id' | 'createdAt'>) => {
+      try {
+        return await sendOrQueue({ ...entry, auth: entry.auth === true })
+      } catch (err) {
+        throw toIpcError(err)
+      }
+    }
+  )
+
+  ipcMain.handle('get_offline_queue', () => {
+    return { ...getOfflineQueueStatus(), items: listOfflineQueue() }
+  })
+
+  ipcMain.handle('replay_offline_queue', () => replayOfflineQueue())
+
+  ipcMain.handle('clear_offline_queue', async () => {
+    await clearOfflineQueue()
+    return getOfflineQueueStatus()
+  })
+
+  ipcMain.handle('set_network_logging', (_event, { enabled }: { enabled: boolean }) => {
+    setNetworkLogging(!!enabled)
+    return isNetworkLoggingEnabled()
+  })
+
+  ipcMain.handle('get_network_logging', () => {
+    return isNetworkLoggingEnabled()
+  })
+
+  ipcMain.handle('get_network_stats', () => {
+    return networkStats.snapshot()
+  })
+
+  ipcMain.handle('reset_network_stats', () => {
+    networkStats.reset()
+    return networkStats.snapshot()
+  })
+
+  ipcMain.handle('get_circuit_states', () => {
+    return httpClient.getCircuitStates()
+  })
+
+  ipcMain.handle('get_connection_state', () => {
+    return connectionState.get()
+  })
+
+  ipcMain.handle('check_all_servers', async () => {
+    try {
+      const config = await loadConfigFromDisk()
+      return await checkAllServers(healthTargets(config))
+    } catch (err) {
+      log.error('[Electron] check_all_servers failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle(
+    'measure_latency',
+    async (_event, { serverUrl, samples }: { serverUrl: string; samples?: number }) => {
+      try {
+        return await measureLatency(serverUrl, samples)
+      } catch (err) {
+        log.error('[Electron] measure_latency failed:', err)
+        throw toIpcError(err)
+      }
+    }
+  )
+
   ipcMain.handle('get_app_version', () => {
     return app.getVersion()
   })
@@ -264,12 +857,22 @@ export function registerIpcHandlers(): void {
   ipcMain.handle('open_dashboard', async (_event, { serverUrl }: { serverUrl: string }) => {
     try {
       const base = serverUrl.replace(/\/+$/, '')
-      const dashboardUrl = `${base}/dashboard/`
-      await shell.openExternal(dashboardUrl)
+      await openDashboard(`${base}/dashboard/`)
       return true
     } catch (err) {
       log.error('[Electron] open_dashboard failed:', err)
-      throw err
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('open_dashboard_window', (_event, { serverUrl }: { serverUrl: string }) => {
+    try {
+      const base = serverUrl.replace(/\/+$/, '')
+      openDashboardWindow(`${base}/dashboard/`)
+      return true
+    } catch (err) {
+      log.error('[Electron] open_dashboard_window failed:', err)
+      throw toIpcError(err)
     }
   })
 
@@ -287,11 +890,9 @@ export function registerIpcHandlers(): void {
 
   ipcMain.handle(
     'clipboard_start_sync',
-    async (
-      _event,
-      { serverUrl, apiKey, scope }: { serverUrl: string; apiKey?: string; scope?: string }
-    ) => {
-      startClipboardSync(serverUrl, apiKey || '', scope || 'default')
+    async (_event, { serverUrl, scope }: { serverUrl: string; scope?: string }) => {
+      // API Key 只在主进程读取，不经渲染进程传入
+      startClipboardSync(serverUrl, (await loadConfigFromDisk()).api_key, scope || 'default')
       return true
     }
   )
@@ -312,14 +913,51 @@ export function registerIpcHandlers(): void {
         updateId?: string
         eventType?: string
         payload?: unknown
+        category?: NotificationCategory
+        severity?: NotificationSeverity
       }
     ) => {
       logNotify('IPC show_notification 收到', payload)
-      showNotificationInWindow(payload)
-      return true
+      const category = notificationCategoryOf(payload.eventType, payload.category)
+      const severity = toNotificationSeverity(payload.severity)
+      if (!isNotificationAllowed(category, severity)) {
+        logNotify('按通知偏好跳过', category, severity)
+        return false
+      }
+      const silent = isDoNotDisturb()
+      if (!silent) showNotificationInWindow(payload)
+      notificationHistory.record({
+        title: payload.title || payload.eventType || '通知',
+        body: payload.body ?? '',
+        source: 'local',
+        eventType: payload.eventType,
+        updateId: payload.updateId,
+        category,
+        severity,
+        silent
+      })
+      return !silent
     }
   )
 
+  ipcMain.handle('list_notifications', (_event, options?: NotificationListOptions) =>
+    notificationHistory.list(options)
+  )
+
+  ipcMain.handle('mark_read', (_event, ids?: string[]) =>
+    notificationHistory.markRead(Array.isArray(ids) ? ids : undefined)
+  )
+
+  ipcMain.handle('clear_notifications', () => notificationHistory.clear())
+
+  ipcMain.handle('get_notifications_paused', () => sharedState.notificationsPaused)
+
+  ipcMain.handle('set_notifications_paused', (_event, paused: boolean) => {
+    setNotificationsPaused(paused === true)
+    refreshTray()
+    return sharedState.notificationsPaused
+  })
+
   ipcMain.handle('log_from_renderer', (_event, payload: { message: string; type: string }) => {
     const { message, type } = payload
     if (type === 'error') log.error(message)
@@ -416,26 +1054,24 @@ export function registerIpcHandlers(): void {
   )
 
   ipcMain.handle('set_native_theme', async (_event, { mode }: { mode: ThemeMode }) => {
-    const source = mode === 'auto' ? 'system' : mode
-    nativeTheme.themeSource = source
-    log.info('[Electron] nativeTheme.themeSource changed to:', source)
-    await saveThemeMode(mode)
-
-    // 同步更新 Windows 标题栏控件颜色
-    if (
-      process.platform === 'win32' &&
-      sharedState.mainWindow &&
-      !sharedState.mainWindow.isDestroyed()
-    ) {
-      const isDark = nativeTheme.shouldUseDarkColors
-      sharedState.mainWindow.setTitleBarOverlay({
-        color: '#00000000',
-        symbolColor: isDark ? '#CCCCCC' : '#333333'
-      })
-    }
+    // 同时同步 Windows 标题栏控件颜色并推送 theme://changed
+    await setThemeMode(mode)
     return true
   })
 
+  ipcMain.handle('get_system_theme', () => getThemeState())
+
+  ipcMain.handle('get_global_hotkey', () => getGlobalHotkey())
+
+  ipcMain.handle('set_global_hotkey', async (_event, { accelerator }: { accelerator: string }) => {
+    try {
+      return await setGlobalHotkey(accelerator)
+    } catch (err) {
+      log.error('[Electron] set_global_hotkey failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
   ipcMain.handle('open_in_explorer', async (_event, { dirPath }: { dirPath: string }) => {
     try {
       if (!dirPath) return false
@@ -475,6 +1111,75 @@ export function registerIpcHandlers(): void {
     return browserNodeService.getStatus()
   })
 
+  // ==========================================
+  // Realtime Connection (WebSocket)
+  // ==========================================
+  ipcMain.handle('realtime:connect', async () => {
+    try {
+      return await realtimeConnection.connect()
+    } catch (err) {
+      log.error('[Electron] realtime:connect failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('realtime:disconnect', async () => realtimeConnection.disconnect())
+
+  ipcMain.handle('realtime:status', async () => realtimeConnection.status())
+
+  ipcMain.handle('realtime:reconnect', async () => {
+    try {
+      return await realtimeConnection.restart()
+    } catch (err) {
+      log.error('[Electron] realtime:reconnect failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('realtime:subscribe', async (_event, topic: string) => {
+    try {
+      return realtimeConnection.subscribe(topic)
+    } catch (err) {
+      log.error('[Electron] realtime:subscribe failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('realtime:unsubscribe', async (_event, topic: string) => {
+    try {
+      return realtimeConnection.unsubscribe(topic)
+    } catch (err) {
+      log.error('[Electron] realtime:unsubscribe failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('realtime:subscriptions', async () => realtimeConnection.subscriptions())
+
+  ipcMain.handle('realtime:missed_event_count', async () =>
+    realtimeConnection.getMissedEventCount()
+  )
+
+  ipcMain.handle('tunnel:list', async () => tunnelClient.list())
+
+  ipcMain.handle('tunnel:enable', async (_event, name: string) => {
+    try {
+      return await tunnelClient.setEnabled(name, true)
+    } catch (err) {
+      log.error('[Electron] tunnel:enable failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
+  ipcMain.handle('tunnel:disable', async (_event, name: string) => {
+    try {
+      return await tunnelClient.setEnabled(name, false)
+    } catch (err) {
+      log.error('[Electron] tunnel:disable failed:', err)
+      throw toIpcError(err)
+    }
+  })
+
   ipcMain.on('quick-panel-action', (_event, payload: { action: string; selectedText: string }) => {
     if (sharedState.quickPanelWindow && !sharedState.quickPanelWindow.isDestroyed()) {
       sharedState.quickPanelWindow.hide()
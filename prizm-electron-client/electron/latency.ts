@@ -0,0 +1,141 @@
+import * as dns from 'dns'
+import * as net from 'net'
+import * as tls from 'tls'
+import { toPrizmError } from './errors'
+import type { PrizmErrorPayload } from './errors'
+import { httpClient } from './httpClient'
+import { PrizmApi } from './prizmApi'
+import { upgradeToTls } from './serverUrl'
+
+/** 单次测量的采样次数上限 */
+export const MAX_LATENCY_SAMPLES = 20
+
+/** 冷连接各阶段耗时（毫秒）；http 地址 tls_ms 为 null */
+export interface ConnectionPhases {
+  address: string
+  dns_ms: number
+  tcp_ms: number
+  tls_ms: number | null
+}
+
+export interface LatencyReport {
+  url: string
+  /** 每次 /health 往返耗时，失败的采样不计入 */
+  samples: number[]
+  failures: number
+  min_ms: number | null
+  avg_ms: number | null
+  p95_ms: number | null
+  phases: ConnectionPhases | null
+  /** 阶段测量失败原因（如 DNS 解析失败） */
+  phase_error?: PrizmErrorPayload
+}
+
+/**
+ * 最小值、平均值与 p95（最近秩法），空数组时均为 null
+ */
+export function summarizeLatencies(
+  samples: number[]
+): Pick<LatencyReport, 'min_ms' | 'avg_ms' | 'p95_ms'> {
+  if (samples.length === 0) return { min_ms: null, avg_ms: null, p95_ms: null }
+  const sorted = [...samples].sort((a, b) => a - b)
+  const sum = sorted.reduce((acc, n) => acc + n, 0)
+  return {
+    min_ms: sorted[0],
+    avg_ms: Math.round((sum / sorted.length) * 100) / 100,
+    p95_ms: sorted[Math.ceil(sorted.length * 0.95) - 1]
+  }
+}
+
+function elapsed(started: bigint): number {
+  return Math.round(Number(process.hrtime.bigint() - started) / 1e4) / 100
+}
+
+/**
+ * 直连服务器测量 DNS 解析、TCP 建连与 TLS 握手耗时。
+ * 不经过代理，也不校验证书（只关心握手耗时，证书问题由正常请求报告）
+ */
+export async function measureConnectionPhases(
+  serverUrl: string,
+  timeoutMs: number
+): Promise<ConnectionPhases> {
+  const url = new URL(serverUrl)
+  const https = url.protocol === 'https:'
+  const port = Number(url.port) || (https ? 443 : 80)
+  const hostname = url.hostname.replace(/^\[|\]$/g, '')
+
+  let started = process.hrtime.bigint()
+  const { address } = await dns.promises.lookup(hostname)
+  const dnsMs = elapsed(started)
+
+  started = process.hrtime.bigint()
+  const socket = net.connect({ host: address, port, timeout: timeoutMs })
+  try {
+    await new Promise<void>((resolve, reject) => {
+      socket.once('connect', resolve)
+      socket.once('error', reject)
+      socket.once('timeout', () => reject(new Error(`TCP connect timed out after ${timeoutMs}ms`)))
+    })
+    const tcpMs = elapsed(started)
+    if (!https) return { address, dns_ms: dnsMs, tcp_ms: tcpMs, tls_ms: null }
+
+    started = process.hrtime.bigint()
+    const secure = tls.connect({
+      socket,
+      servername: net.isIP(hostname) ? undefined : hostname,
+      rejectUnauthorized: false
+    })
+    secure.setTimeout(timeoutMs)
+    await new Promise<void>((resolve, reject) => {
+      secure.once('secureConnect', resolve)
+      secure.once('error', reject)
+      secure.once('timeout', () => {
+        reject(new Error(`TLS handshake timed out after ${timeoutMs}ms`))
+      })
+    })
+    const tlsMs = elapsed(started)
+    secure.destroy()
+    return { address, dns_ms: dnsMs, tcp_ms: tcpMs, tls_ms: tlsMs }
+  } finally {
+    socket.destroy()
+  }
+}
+
+/**
+ * 连续 samples 次 /health 往返（走共享客户端，含代理与连接复用）并统计延迟，
+ * 另外直连一次测量各阶段耗时。服务器走本地套接字时不测量阶段
+ */
+export async function measureLatency(serverUrl: string, samples = 5): Promise<LatencyReport> {
+  const count = Math.min(Math.max(1, Math.round(samples)), MAX_LATENCY_SAMPLES)
+  const api = new PrizmApi(serverUrl)
+  const timings: number[] = []
+  let failures = 0
+  for (let i = 0; i < count; i++) {
+    const started = process.hrtime.bigint()
+    try {
+      await api.health()
+      timings.push(elapsed(started))
+    } catch {
+      failures++
+    }
+  }
+
+  const report: LatencyReport = {
+    url: serverUrl,
+    samples: timings,
+    failures,
+    ...summarizeLatencies(timings),
+    phases: null
+  }
+  if (httpClient.usesLocalSocket(serverUrl)) return report
+  const network = httpClient.getNetworkConfig()
+  try {
+    report.phases = await measureConnectionPhases(
+      network.require_tls ? upgradeToTls(serverUrl) : serverUrl,
+      network.connect_timeout_ms
+    )
+  } catch (err) {
+    report.phase_error = toPrizmError(err).toJSON()
+  }
+  return report
+}
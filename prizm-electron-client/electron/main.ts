@@ -1,25 +1,84 @@
-import { app, BrowserWindow, Menu, globalShortcut, nativeTheme } from 'electron'
+import { app, BrowserWindow, Menu, globalShortcut, powerMonitor } from 'electron'
 import * as path from 'path'
 import * as util from 'util'
 import log from 'electron-log/main'
 
 import { sharedState } from './config'
-import { loadTraySettings, loadThemeMode } from './config'
+import {
+  getConfigDirOverride,
+  isPortableMode,
+  loadConfigFromDisk,
+  loadTraySettings,
+  loadThemeMode,
+  normalizeNotificationPreferences,
+  onConfigUpdated,
+  readNetworkConfigSync,
+  shouldStartMinimized
+} from './config'
 import { registerIpcHandlers } from './ipcHandlers'
-import { createMainWindow, createQuickPanelWindow } from './windowManager'
-import { createTray } from './trayManager'
 import {
+  createMainWindow,
+  createQuickPanelWindow,
+  showMainWindow,
+  updateMainWindowTitle
+} from './windowManager'
+import { applyTrayConfig, refreshTray } from './trayManager'
+import { applyWindowConfig } from './windowGeometry'
+import { applyRendererAuth, installRendererAuth } from './rendererAuth'
+import {
+  applyGlobalHotkey,
   registerGlobalShortcuts,
   registerQuickPanelDoubleTap,
   stopQuickPanelHook
 } from './shortcuts'
 import { stopClipboardSync } from './clipboardSync'
+import { startConfigWatcher, stopConfigWatcher } from './configWatcher'
+import { httpClient } from './httpClient'
+import { applyHostOverrides, hostOverridesNeedRestart } from './hostOverrides'
+import { applyHttp2Setting, http2NeedsRestart } from './connectionPool'
+import { autoRegisterOnStartup, reregisterClient } from './autoRegister'
+import { tokenRefresher } from './tokenRefresher'
+import { connectionState } from './connectionState'
+import { presenceReporter } from './presence'
+import { realtimeConnection } from './realtime'
+import { tunnelClient } from './tunnels'
+import { eventCursorStore } from './eventCursor'
+import { notificationHistory } from './notificationHistory'
+import { bridgeServerEvents } from './serverEvents'
+import { bridgeServerNotifications } from './serverNotifications'
+import { watchUnreadBadge } from './unreadBadge'
+import { serverWatchdog } from './serverWatchdog'
+import { acquireSingleInstanceLock } from './singleInstance'
+import { flushDeepLinks, watchDeepLinks } from './deepLink'
+import { applyThemeMode, watchSystemTheme } from './theme'
 
 // 启用 Electron 自身的远程调试能力，使其可以作为 Internal Browser Node 参与 Agent 执行
 app.commandLine.appendSwitch('remote-debugging-port', '9222')
 
+// 指定配置目录时 Chromium 数据随之隔离，以便多个实例并行运行；
+// 便携模式：Chromium 缓存、localStorage 等也放到可执行文件旁，不触碰宿主机用户目录
+const configDirOverride = getConfigDirOverride()
+if (configDirOverride) {
+  app.setPath('userData', path.join(configDirOverride, 'userData'))
+} else if (isPortableMode()) {
+  app.setPath('userData', path.join(path.dirname(app.getPath('exe')), 'data', 'userData'))
+}
+
 log.initialize()
 
+// 同一配置目录只允许一个实例，避免同时读写 config.json；再次启动时把参数转交给已运行的实例
+if (!acquireSingleInstanceLock()) {
+  log.info('[Electron] Another instance is running, handing off and exiting')
+  app.exit(0)
+} else {
+  watchDeepLinks()
+}
+
+// host_overrides 与 http2 通过 Chromium 启动参数实现，必须在 app ready 之前读取
+const startupNetwork = readNetworkConfigSync()
+applyHostOverrides(startupNetwork.host_overrides)
+applyHttp2Setting(startupNetwork.http2)
+
 process.on('uncaughtException', (err) => {
   log.error('[UncaughtException]', err)
 })
@@ -68,25 +127,81 @@ app
     // 1. BrowserWindow.backgroundColor 使用正确主题色
     // 2. CSS prefers-color-scheme 媒体查询匹配用户选择
     // 3. 消除窗口预加载时的主题闪烁
-    const themeMode = await loadThemeMode()
-    nativeTheme.themeSource = themeMode === 'auto' ? 'system' : themeMode
-    log.info('[Electron] nativeTheme.themeSource set to:', nativeTheme.themeSource)
+    applyThemeMode(await loadThemeMode())
+    watchSystemTheme()
 
+    const initialConfig = await loadConfigFromDisk()
+    httpClient.init(initialConfig.network)
+    httpClient.setServer(initialConfig.server)
+    httpClient.setApiKey(initialConfig.api_key)
+    httpClient.setAdminKey(initialConfig.admin_key)
+    httpClient.setClientId(initialConfig.api_key ? initialConfig.client.name : '')
+    // 渲染进程不持有 API Key，其发往服务端的请求由主进程附加 Authorization
+    installRendererAuth()
+    applyRendererAuth(initialConfig)
+    // API Key 失效时先尝试用刷新令牌续期，再按 client.auto_register 自动重新注册
+    httpClient.setReauthHandler(
+      async () => (await tokenRefresher.refresh()) ?? (await reregisterClient())
+    )
+    tokenRefresher.schedule(initialConfig)
+    presenceReporter.schedule(initialConfig)
+    tunnelClient.start()
+    tunnelClient.apply(initialConfig)
+    onConfigUpdated((config) => {
+      httpClient.configure(config.network)
+      httpClient.setServer(config.server)
+      httpClient.setApiKey(config.api_key)
+      httpClient.setAdminKey(config.admin_key)
+      httpClient.setClientId(config.api_key ? config.client.name : '')
+      applyRendererAuth(config)
+      tokenRefresher.schedule(config)
+      realtimeConnection.reconfigure(config)
+      presenceReporter.schedule(config)
+      tunnelClient.apply(config)
+      applyTrayConfig(config)
+      applyWindowConfig(config)
+      applyGlobalHotkey(config.tray.global_hotkey)
+      sharedState.showNotification = config.tray.show_notification !== false
+      sharedState.notificationPreferences = normalizeNotificationPreferences(config.notifications)
+      if (config.network && hostOverridesNeedRestart(config.network.host_overrides)) {
+        log.warn('[Electron] network.host_overrides changed, restart to apply')
+      }
+      if (config.network && http2NeedsRestart(config.network.http2)) {
+        log.warn('[Electron] network.http2 changed, restart to apply')
+      }
+    })
     registerIpcHandlers()
-    createMainWindow()
+    bridgeServerEvents(realtimeConnection)
+    bridgeServerNotifications(realtimeConnection)
+    serverWatchdog.start()
+    realtimeConnection.onStatusChange((status) => connectionState.updateRealtime(status.state))
+    connectionState.onChange((snapshot) => {
+      updateMainWindowTitle(snapshot.state)
+      refreshTray()
+    })
+    startConfigWatcher()
+    applyWindowConfig(initialConfig)
+    const startMinimized = shouldStartMinimized(initialConfig)
+    if (startMinimized) log.info('[Electron] Starting minimized to tray')
+    createMainWindow({ showOnReady: !startMinimized })
     createQuickPanelWindow()
-    if (sharedState.trayEnabled) {
-      createTray()
-    }
-    registerGlobalShortcuts()
+    applyTrayConfig(initialConfig)
+    watchUnreadBadge()
+    registerGlobalShortcuts(initialConfig.tray.global_hotkey)
     registerQuickPanelDoubleTap()
+    flushDeepLinks()
+    // 睡眠唤醒后网络通常已恢复，不必等到下一次退避重连
+    powerMonitor.on('resume', () => realtimeConnection.retryNow())
+    // 无人值守部署：未注册时按 client.auto_register 在后台注册，不阻塞启动
+    void autoRegisterOnStartup().catch((err) => {
+      log.error('[Electron] Startup auto-registration failed:', err)
+    })
 
     app.on('activate', () => {
       if (BrowserWindow.getAllWindows().length === 0) {
         createMainWindow()
       } else if (sharedState.mainWindow) {
-        sharedState.mainWindow.show()
-        sharedState.mainWindow.focus()
+        showMainWindow()
       }
     })
   })
@@ -95,7 +210,8 @@ app
   })
 
 app.on('window-all-closed', () => {
-  if (process.platform !== 'darwin') {
+  // 托盘存在时留在托盘中，从托盘菜单退出
+  if (process.platform !== 'darwin' && !sharedState.tray) {
     app.quit()
   }
 })
@@ -104,6 +220,14 @@ app.on('before-quit', () => {
   log.info('[Electron] before-quit')
   sharedState.isQuitting = true
   stopClipboardSync()
+  stopConfigWatcher()
+  tokenRefresher.stop()
+  presenceReporter.stop()
+  tunnelClient.stop()
+  serverWatchdog.stop()
+  realtimeConnection.disconnect()
+  void eventCursorStore.flush()
+  void notificationHistory.flush()
 })
 
 app.on('will-quit', () => {
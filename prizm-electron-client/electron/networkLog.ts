@@ -0,0 +1,96 @@
+import log from 'electron-log/main'
+
+/** 日志中记录的响应 / 请求体最大长度 */
+const MAX_LOGGED_BODY = 500
+
+const SECRET_HEADERS = new Set(['authorization', 'x-prizm-api-key', 'cookie', 'set-cookie'])
+const SECRET_KEYS = /^(api_?key|apikey|token|access_?token|refresh_?token|password|secret)$/i
+const MASK = '***'
+
+let enabled = false
+
+/**
+ * 开关网络日志（运行时生效，不写入配置）
+ */
+export function setNetworkLogging(value: boolean): void {
+  enabled = value
+  log.info(`[Net] Network logging ${value ? 'enabled' : 'disabled'}`)
+}
+
+export function isNetworkLoggingEnabled(): boolean {
+  return enabled
+}
+
+/**
+ * 隐去 URL 查询串中的密钥参数（如 ?apiKey=）
+ */
+export function redactUrl(rawUrl: string): string {
+  try {
+    const url = new URL(rawUrl)
+    for (const key of [...url.searchParams.keys()]) {
+      if (SECRET_KEYS.test(key)) url.searchParams.set(key, MASK)
+    }
+    if (url.password) url.password = MASK
+    return url.toString()
+  } catch {
+    return rawUrl
+  }
+}
+
+export function redactHeaders(headers: Record<string, string>): Record<string, string> {
+  const result: Record<string, string> = {}
+  for (const [name, value] of Object.entries(headers)) {
+    result[name] = SECRET_HEADERS.has(name.toLowerCase()) ? MASK : value
+  }
+  return result
+}
+
+function redactValue(value: unknown): unknown {
+  if (Array.isArray(value)) return value.map(redactValue)
+  if (value && typeof value === 'object') {
+    return Object.fromEntries(
+      Object.entries(value).map(([k, v]) => [k, SECRET_KEYS.test(k) ? MASK : redactValue(v)])
+    )
+  }
+  return value
+}
+
+/**
+ * 隐去 JSON 体中的密钥字段并截断；非 JSON 原样截断
+ */
+export function redactBody(body: string | undefined): string {
+  if (!body) return ''
+  let text = body
+  try {
+    text = JSON.stringify(redactValue(JSON.parse(body)))
+  } catch {
+    // 非 JSON：按原文截断
+  }
+  return text.length > MAX_LOGGED_BODY ? `${text.slice(0, MAX_LOGGED_BODY)}…` : text
+}
+
+export interface NetworkExchange {
+  method: string
+  url: string
+  headers: Record<string, string>
+  requestBody?: string
+  status?: number
+  responseBody?: string
+  latencyMs: number
+  error?: string
+}
+
+/**
+ * 写一条请求日志；未开启网络日志时不做任何事
+ */
+export function logNetworkExchange(exchange: NetworkExchange): void {
+  if (!enabled) return
+  const line = `[Net] ${exchange.method} ${redactUrl(exchange.url)} -> ${
+    exchange.error ? `error: ${exchange.error}` : exchange.status
+  } (${exchange.latencyMs}ms)`
+  log.info(line, {
+    headers: redactHeaders(exchange.headers),
+    request: redactBody(exchange.requestBody),
+    response: redactBody(exchange.responseBody)
+  })
+}
@@ -0,0 +1,113 @@
+/** 统计的端点数量上限，超出后归入 OTHER_ENDPOINT */
+const MAX_ENDPOINTS = 200
+const OTHER_ENDPOINT = '*'
+
+/** 路径中的 ID 段（数字、UUID、长十六进制 / base64url 串）归一化，避免端点数量膨胀 */
+const ID_SEGMENT = /^(\d+|[0-9a-f]{8}(-[0-9a-f]{4}){3}-[0-9a-f]{12}|[\w-]{24,})$/i
+
+export interface EndpointStats {
+  /** 如 "GET http://127.0.0.1:4127/files/:id" */
+  endpoint: string
+  requests: number
+  errors: number
+  /** 请求体 + 请求头的近似字节数（不含 TLS / HTTP 帧开销） */
+  bytes_sent: number
+  /** 响应体字节数（Chromium 已解压时为解压后大小） */
+  bytes_received: number
+}
+
+export interface NetworkStatsSnapshot {
+  /** 开始统计（启动或上次重置）的时间戳 */
+  since: number
+  totals: Omit<EndpointStats, 'endpoint'>
+  endpoints: EndpointStats[]
+}
+
+/**
+ * 端点标识：方法 + origin + 路径（ID 段替换为 :id，忽略查询参数）
+ */
+export function endpointKey(method: string, url: string): string {
+  try {
+    const parsed = new URL(url)
+    const path = parsed.pathname
+      .split('/')
+      .map((segment) => (ID_SEGMENT.test(segment) ? ':id' : segment))
+      .join('/')
+    return `${method.toUpperCase()} ${parsed.origin}${path}`
+  } catch {
+    return `${method.toUpperCase()} ${url}`
+  }
+}
+
+/** 请求头的近似字节数："Name: value\r\n" */
+export function headersByteLength(headers: Record<string, string>): number {
+  return Object.entries(headers).reduce(
+    (sum, [name, value]) => sum + Buffer.byteLength(name) + Buffer.byteLength(value) + 4,
+    0
+  )
+}
+
+/**
+ * 主进程 HTTP 流量统计：按端点累计请求数、失败数与收发字节数（每次重试单独计数）
+ */
+export class NetworkStats {
+  private endpoints = new Map<string, EndpointStats>()
+  private since = Date.now()
+
+  /** 记录一次请求发出，返回该端点的统计条目供后续累计 */
+  recordRequest(method: string, url: string, bytesSent: number): EndpointStats {
+    let key = endpointKey(method, url)
+    if (!this.endpoints.has(key) && this.endpoints.size >= MAX_ENDPOINTS) {
+      key = OTHER_ENDPOINT
+    }
+    let entry = this.endpoints.get(key)
+    if (!entry) {
+      entry = { endpoint: key, requests: 0, errors: 0, bytes_sent: 0, bytes_received: 0 }
+      this.endpoints.set(key, entry)
+    }
+    entry.requests++
+    entry.bytes_sent += bytesSent
+    return entry
+  }
+
+  snapshot(): NetworkStatsSnapshot {
+    const endpoints = [...this.endpoints.values()].map((entry) => ({ ...entry }))
+    const totals = { requests: 0, errors: 0, bytes_sent: 0, bytes_received: 0 }
+    for (const entry of endpoints) {
+      totals.requests += entry.requests
+      totals.errors += entry.errors
+      totals.bytes_sent += entry.bytes_sent
+      totals.bytes_received += entry.bytes_received
+    }
+    endpoints.sort((a, b) => b.bytes_sent + b.bytes_received - (a.bytes_sent + a.bytes_received))
+    return { since: this.since, totals, endpoints }
+  }
+
+  reset(): void {
+    this.endpoints.clear()
+    this.since = Date.now()
+  }
+}
+
+export const networkStats = new NetworkStats()
+
+/**
+ * 包装响应体，在调用方读取时累计字节数；无响应体时原样返回
+ */
+export function countResponseBytes(resp: Response, onBytes: (n: number) => void): Response {
+  if (!resp.body) return resp
+  const counter = new TransformStream<Uint8Array, Uint8Array>({
+    transform(chunk, controller) {
+      onBytes(chunk.byteLength)
+      controller.enqueue(chunk)
+    }
+  })
+  const counted = new Response(resp.body.pipeThrough(counter), {
+    status: resp.status,
+    statusText: resp.statusText,
+    headers: resp.headers
+  })
+  // 保留原始 URL，供错误信息使用
+  Object.defineProperty(counted, 'url', { value: resp.url })
+  return counted
+}
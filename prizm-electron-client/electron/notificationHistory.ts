@@ -0,0 +1,179 @@
+import * as fs from 'fs'
+import * as path from 'path'
+import { randomUUID } from 'crypto'
+import log from 'electron-log/main'
+import { getConfigPath, sharedState } from './config'
+import type { NotificationCategory, NotificationSeverity } from './config'
+import { writeFileAtomic } from './fsUtils'
+
+const HISTORY_FILE = 'notification-history.json'
+/** 保留的条数上限，超出时丢弃最旧的 */
+export const MAX_HISTORY_ENTRIES = 500
+/** 通知密集时合并写入 */
+const SAVE_DELAY_MS = 1_000
+
+export type NotificationSource = 'server' | 'local'
+
+/** 通知中心的一条记录 */
+export interface NotificationEntry {
+  id: string
+  title: string
+  body: string
+  /** server：服务端推送；local：本机界面发出 */
+  source: NotificationSource
+  /** 服务端事件名，如 notification、todo_list:updated */
+  eventType?: string
+  /** 相同 updateId 的新通知替换旧记录 */
+  updateId?: string
+  category?: NotificationCategory
+  severity?: NotificationSeverity
+  /** 免打扰期间收到，未弹出系统通知 */
+  silent?: boolean
+  createdAt: number
+  read: boolean
+}
+
+export type NotificationInput = Pick<NotificationEntry, 'title' | 'body' | 'source'> &
+  Partial<Pick<NotificationEntry, 'eventType' | 'updateId' | 'category' | 'severity' | 'silent'>>
+
+export interface NotificationListOptions {
+  unreadOnly?: boolean
+  limit?: number
+}
+
+type ChangeListener = (unread: number) => void
+
+function isEntry(value: unknown): value is NotificationEntry {
+  const e = value as NotificationEntry
+  return (
+    !!e &&
+    typeof e.id === 'string' &&
+    typeof e.title === 'string' &&
+    typeof e.body === 'string' &&
+    typeof e.createdAt === 'number' &&
+    typeof e.read === 'boolean'
+  )
+}
+
+/**
+ * 通知历史：记录弹出过的每条通知，以及免打扰期间未弹出的通知，
+ * 保存在配置目录 notification-history.json，离开期间错过的通知可在通知中心查看。写入经过防抖，变化时推送未读数
+ */
+export class NotificationHistory {
+  private entries: NotificationEntry[] | null = null
+  private timer: NodeJS.Timeout | undefined
+  private readonly listeners = new Set<ChangeListener>()
+
+  constructor(
+    private readonly filePath: () => string = () =>
+      path.join(getConfigPath().configDir, HISTORY_FILE),
+    private readonly now: () => number = Date.now
+  ) {}
+
+  /** 记录一条通知；有相同 updateId 的旧记录时替换并重新标为未读 */
+  record(input: NotificationInput): NotificationEntry {
+    const entries = this.load()
+    if (input.updateId) {
+      const index = entries.findIndex((e) => e.updateId === input.updateId)
+      if (index >= 0) entries.splice(index, 1)
+    }
+    const entry: NotificationEntry = {
+      id: randomUUID(),
+      title: input.title,
+      body: input.body,
+      source: input.source,
+      ...(input.eventType && { eventType: input.eventType }),
+      ...(input.updateId && { updateId: input.updateId }),
+      ...(input.category && { category: input.category }),
+      ...(input.severity && { severity: input.severity }),
+      ...(input.silent && { silent: true }),
+      createdAt: this.now(),
+      read: false
+    }
+    entries.unshift(entry)
+    if (entries.length > MAX_HISTORY_ENTRIES) entries.length = MAX_HISTORY_ENTRIES
+    this.changed()
+    return entry
+  }
+
+  /** 按时间倒序列出 */
+  list(options: NotificationListOptions = {}): NotificationEntry[] {
+    const entries = this.load().filter((e) => !options.unreadOnly || !e.read)
+    const limit = options.limit && options.limit > 0 ? options.limit : entries.length
+    return entries.slice(0, limit).map((e) => ({ ...e }))
+  }
+
+  unreadCount(): number {
+    return this.load().filter((e) => !e.read).length
+  }
+
+  /** 标为已读；不传 ids 时全部标为已读。返回实际变化的条数 */
+  markRead(ids?: string[]): number {
+    let count = 0
+    for (const entry of this.load()) {
+      if (entry.read || (ids && !ids.includes(entry.id))) continue
+      entry.read = true
+      count++
+    }
+    if (count) this.changed()
+    return count
+  }
+
+  /** 清空历史，返回清除的条数 */
+  clear(): number {
+    const entries = this.load()
+    const count = entries.length
+    entries.length = 0
+    if (count) this.changed()
+    return count
+  }
+
+  /** 监听变化（参数为未读数），返回取消函数 */
+  onChange(listener: ChangeListener): () => void {
+    this.listeners.add(listener)
+    return () => {
+      this.listeners.delete(listener)
+    }
+  }
+
+  /** 立即写入尚未落盘的记录（退出前调用） */
+  async flush(): Promise<void> {
+    clearTimeout(this.timer)
+    this.timer = undefined
+    if (!this.entries) return
+    try {
+      await writeFileAtomic(this.filePath(), JSON.stringify(this.entries, null, 2))
+    } catch (err) {
+      log.warn('[Notify] Failed to save notification history:', err)
+    }
+  }
+
+  private load(): NotificationEntry[] {
+    if (this.entries) return this.entries
+    try {
+      const parsed = JSON.parse(fs.readFileSync(this.filePath(), 'utf-8')) as unknown
+      this.entries = Array.isArray(parsed) ? parsed.filter(isEntry) : []
+    } catch (err) {
+      if ((err as NodeJS.ErrnoException).code !== 'ENOENT') {
+        log.warn('[Notify] Failed to read notification history, starting empty:', err)
+      }
+      this.entries = []
+    }
+    return this.entries
+  }
+
+  private changed(): void {
+    if (!this.timer) {
+      this.timer = setTimeout(() => void this.flush(), SAVE_DELAY_MS)
+      this.timer.unref?.()
+    }
+    const unread = this.unreadCount()
+    for (const listener of this.listeners) listener(unread)
+    const win = sharedState.mainWindow
+    if (win && !win.isDestroyed()) {
+      win.webContents.send('notifications://changed', { unread })
+    }
+  }
+}
+
+export const notificationHistory = new NotificationHistory()
@@ -0,0 +1,72 @@
+import { NOTIFICATION_CATEGORIES, NOTIFICATION_SEVERITIES, sharedState } from './config'
+import type {
+  NotificationCategory,
+  NotificationPreferences,
+  NotificationSeverity,
+  QuietHours
+} from './config'
+
+/** 非法值按 info 处理 */
+export function toNotificationSeverity(value: unknown): NotificationSeverity {
+  return NOTIFICATION_SEVERITIES.includes(value as NotificationSeverity)
+    ? (value as NotificationSeverity)
+    : 'info'
+}
+
+/**
+ * 通知所属分类：显式给出的合法分类优先；否则服务端 notification 事件（或无事件名）归为
+ * server_alerts，其余事件（如 todo_list:updated）归为 sync
+ */
+export function notificationCategoryOf(
+  eventType: string | undefined,
+  explicit?: unknown
+): NotificationCategory {
+  if (NOTIFICATION_CATEGORIES.includes(explicit as NotificationCategory)) {
+    return explicit as NotificationCategory
+  }
+  return !eventType || eventType === 'notification' ? 'server_alerts' : 'sync'
+}
+
+/**
+ * 按 notifications 偏好判断是否弹出：分类已关闭或级别低于 min_severity 时不弹出
+ */
+export function isNotificationAllowed(
+  category: NotificationCategory,
+  severity: NotificationSeverity,
+  preferences: NotificationPreferences = sharedState.notificationPreferences
+): boolean {
+  if (!preferences.categories[category]) return false
+  return (
+    NOTIFICATION_SEVERITIES.indexOf(severity) >=
+    NOTIFICATION_SEVERITIES.indexOf(preferences.min_severity)
+  )
+}
+
+function minutesOf(clock: string): number {
+  const [hours, minutes] = clock.split(':').map(Number)
+  return hours * 60 + minutes
+}
+
+/**
+ * 是否处于免打扰时段（本机时间）：周末全天，或 start–end 之间（含 start，不含 end）
+ */
+export function isQuietTime(quiet: QuietHours, date: Date): boolean {
+  if (!quiet.enabled) return false
+  const day = date.getDay()
+  if (quiet.weekends && (day === 0 || day === 6)) return true
+  const start = minutesOf(quiet.start)
+  const end = minutesOf(quiet.end)
+  if (start === end) return false
+  const now = date.getHours() * 60 + date.getMinutes()
+  return start < end ? now >= start && now < end : now >= start || now < end
+}
+
+/**
+ * 免打扰：托盘“暂停通知”开启或处于免打扰时段。此时通知只记入历史，不弹出
+ */
+export function isDoNotDisturb(
+  date: Date = new Date(),
+  preferences: NotificationPreferences = sharedState.notificationPreferences
+): boolean {
+  return sharedState.notificationsPaused || isQuietTime(preferences.quiet_hours, date)
+}
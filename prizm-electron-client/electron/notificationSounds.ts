@@ -0,0 +1,51 @@
+import { execFile } from 'child_process'
+import * as fs from 'fs'
+import log from 'electron-log/main'
+import { sharedState } from './config'
+import type { NotificationCategory } from './config'
+
+/** 单次播放的时长上限，避免误配的长音频一直占用播放器 */
+const PLAY_TIMEOUT_MS = 15_000
+
+/** 由本机播放器播放的提示音文件；default、none 时为 null */
+export function soundFileOf(category: NotificationCategory): string | null {
+  const sound = sharedState.notificationPreferences.sounds[category]
+  return sound === 'default' || sound === 'none' ? null : sound
+}
+
+/** 该分类的通知是否应关闭系统提示音（静音或改用自定义提示音） */
+export function usesCustomSound(category: NotificationCategory): boolean {
+  return sharedState.notificationPreferences.sounds[category] !== 'default'
+}
+
+/**
+ * 各平台播放音频文件的命令：macOS 用 afplay，Windows 用 PowerShell 的 SoundPlayer（仅 wav），
+ * Linux 用 PulseAudio 的 paplay
+ */
+export function soundCommand(
+  file: string,
+  platform: NodeJS.Platform = process.platform
+): [string, string[]] {
+  if (platform === 'darwin') return ['afplay', [file]]
+  if (platform === 'win32') {
+    const script = `(New-Object Media.SoundPlayer '${file.replace(/'/g, "''")}').PlaySync()`
+    return ['powershell', ['-NoProfile', '-NonInteractive', '-Command', script]]
+  }
+  return ['paplay', [file]]
+}
+
+/**
+ * 按通知分类播放自定义提示音；文件不存在或播放失败时只记录日志
+ */
+export function playNotificationSound(category: NotificationCategory): void {
+  const file = soundFileOf(category)
+  if (!file) return
+  if (!fs.existsSync(file)) {
+    log.warn(`[Notify] Sound file for ${category} not found:`, file)
+    return
+  }
+  const [command, args] = soundCommand(file)
+  execFile(command, args, { timeout: PLAY_TIMEOUT_MS, windowsHide: true }, (err) => {
+    if (err) log.warn(`[Notify] Failed to play sound ${file}:`, err.message)
+  })
+}
@@ -0,0 +1,188 @@
+import * as fs from 'fs'
+import * as path from 'path'
+import { randomUUID } from 'crypto'
+import log from 'electron-log/main'
+import { getConfigPath, sharedState } from './config'
+import { writeFileAtomic } from './fsUtils'
+import { httpClient } from './httpClient'
+import { PrizmError, toPrizmError } from './errors'
+
+const QUEUE_FILE = 'offline-queue.json'
+/** 队列上限，超出时丢弃最旧的请求 */
+const MAX_QUEUED_REQUESTS = 200
+
+export interface QueuedRequest {
+  id: string
+  method: string
+  url: string
+  body?: unknown
+  /** 重放时是否附带 API Key（使用重放时的当前 Key），默认不附带 */
+  auth: boolean
+  /** 展示给用户的说明，如“更新通知设置” */
+  description?: string
+  createdAt: number
+}
+
+export interface OfflineQueueStatus {
+  pending: number
+  /** 正在重放时为 true */
+  replaying: boolean
+  /** 最近一次重放成功发送的数量 */
+  replayed: number
+  /** 最近一次重放因服务端拒绝而丢弃的数量 */
+  dropped: number
+}
+
+export type SendOrQueueResult =
+  | { queued: false; status: number; body: string }
+  | { queued: true; id: string }
+
+let queue: QueuedRequest[] | null = null
+let replaying: Promise<OfflineQueueStatus> | null = null
+let lastReplay = { replayed: 0, dropped: 0 }
+
+function queuePath(): string {
+  return path.join(getConfigPath().configDir, QUEUE_FILE)
+}
+
+function loadQueue(): QueuedRequest[] {
+  if (queue) return queue
+  try {
+    const parsed = JSON.parse(fs.readFileSync(queuePath(), 'utf-8')) as unknown
+    queue = Array.isArray(parsed) ? (parsed as QueuedRequest[]) : []
+  } catch (err) {
+    if ((err as NodeJS.ErrnoException).code !== 'ENOENT') {
+      log.warn('[OfflineQueue] Failed to read queue, starting empty:', err)
+    }
+    queue = []
+  }
+  return queue
+}
+
+async function persistQueue(): Promise<void> {
+  await writeFileAtomic(queuePath(), JSON.stringify(loadQueue(), null, 2))
+}
+
+export function getOfflineQueueStatus(): OfflineQueueStatus {
+  return { pending: loadQueue().length, replaying: replaying !== null, ...lastReplay }
+}
+
+export function listOfflineQueue(): QueuedRequest[] {
+  return [...loadQueue()]
+}
+
+function emitQueueUpdated(): void {
+  const win = sharedState.mainWindow
+  if (win && !win.isDestroyed()) {
+    win.webContents.send('offline-queue://updated', getOfflineQueueStatus())
+  }
+}
+
+function isTransient(err: unknown): boolean {
+  const kind = toPrizmError(err).kind
+  return kind === 'network' || kind === 'timeout'
+}
+
+/** 队列只接受发往当前服务器的请求；入队与重放前都会检查（队列文件可能被改动，服务器也可能已切换） */
+function assertServerUrl(url: string): void {
+  if (!httpClient.isServerUrl(url)) {
+    throw PrizmError.invalidInput('Only requests to the configured server can be queued')
+  }
+}
+
+async function send(entry: Omit<QueuedRequest, 'id' | 'createdAt'>): Promise<Response> {
+  assertServerUrl(entry.url)
+  return entry.auth
+    ? httpClient.requestAuth(entry.method, entry.url, { body: entry.body, retry: false })
+    : httpClient.request(entry.method, entry.url, { body: entry.body, retry: false })
+}
+
+/**
+ * 发送非幂等请求；服务器不可达（网络错误 / 超时）时写入持久化队列，待连接恢复后按顺序重放
+ */
+export async function sendOrQueue(
+  entry: Omit<QueuedRequest, 'id' | 'createdAt'>
+): Promise<SendOrQueueResult> {
+  assertServerUrl(entry.url)
+  // 已有排队请求时直接排在后面，保证顺序
+  if (loadQueue().length === 0) {
+    try {
+      const resp = await send(entry)
+      return { queued: false, status: resp.status, body: await resp.text() }
+    } catch (err) {
+      if (!isTransient(err)) throw err
+      log.warn(`[OfflineQueue] ${entry.method} ${entry.url} unreachable, queued`)
+    }
+  }
+  const queued: QueuedRequest = { ...entry, id: randomUUID(), createdAt: Date.now() }
+  const items = loadQueue()
+  items.push(queued)
+  if (items.length > MAX_QUEUED_REQUESTS) {
+    const dropped = items.splice(0, items.length - MAX_QUEUED_REQUESTS)
+    log.warn(`[OfflineQueue] Queue full, dropped ${dropped.length} oldest request(s)`)
+  }
+  await persistQueue()
+  emitQueueUpdated()
+  return { queued: true, id: queued.id }
+}
+
+/**
+ * 按入队顺序重放；遇到网络错误或鉴权失败即暂停（保留剩余请求），
+ * 服务端拒绝（其他 4xx/5xx）的请求记录日志后丢弃
+ */
+export function replayOfflineQueue(): Promise<OfflineQueueStatus> {
+  if (replaying) return replaying
+  if (loadQueue().length === 0) return Promise.resolve(getOfflineQueueStatus())
+  replaying = (async () => {
+    lastReplay = { replayed: 0, dropped: 0 }
+    emitQueueUpdated()
+    const items = loadQueue()
+    while (items.length > 0) {
+      const entry = items[0]
+      try {
+        const resp = await send(entry)
+        if (resp.status >= 502 && resp.status <= 504) {
+          await resp.body?.cancel()
+          log.warn(`[OfflineQueue] Server unavailable (${resp.status}), replay paused`)
+          break
+        }
+        if (resp.ok) {
+          lastReplay.replayed++
+        } else {
+          lastReplay.dropped++
+          log.warn(`[OfflineQueue] ${entry.method} ${entry.url} rejected with ${resp.status}`)
+        }
+        await resp.body?.cancel()
+      } catch (err) {
+        if (isTransient(err) || toPrizmError(err).kind === 'auth') {
+          // 不可达或 API Key 失效：保留剩余请求，待重新连接 / 注册后再重放
+          log.warn('[OfflineQueue] Replay paused:', toPrizmError(err).message)
+          break
+        }
+        lastReplay.dropped++
+        log.warn(`[OfflineQueue] ${entry.method} ${entry.url} dropped:`, err)
+      }
+      items.shift()
+      await persistQueue()
+      emitQueueUpdated()
+    }
+    log.info(
+      `[OfflineQueue] Replay finished: ${lastReplay.replayed} sent, ${lastReplay.dropped} dropped`
+    )
+  })()
+    .finally(() => {
+      replaying = null
+      emitQueueUpdated()
+    })
+    .then(() => getOfflineQueueStatus())
+  return replaying
+}
+
+/**
+ * 清空队列（丢弃所有待发送请求）
+ */
+export async function clearOfflineQueue(): Promise<void> {
+  loadQueue().length = 0
+  await persistQueue()
+  emitQueueUpdated()
+}
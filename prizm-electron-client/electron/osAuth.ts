@@ -0,0 +1,121 @@
+import { execFile } from 'child_process'
+import { systemPreferences } from 'electron'
+import log from 'electron-log/main'
+import { PrizmError } from './errors'
+
+/** 等待用户完成系统验证的上限 */
+const VERIFY_TIMEOUT_MS = 2 * 60 * 1000
+
+/**
+ * Windows Hello：通过 PowerShell 调用 WinRT UserConsentVerifier，输出验证结果枚举名。
+ * 提示文案经环境变量传入，避免拼接进脚本
+ */
+const WINDOWS_HELLO_SCRIPT = `
+$ErrorActionPreference = 'Stop'
+Add-Type -AssemblyName System.Runtime.WindowsRuntime
+$asTask = [System.WindowsRuntimeSystemExtensions].GetMethods() | Where-Object {
+  $_.Name -eq 'AsTask' -and $_.GetParameters().Count -eq 1 -and
+  $_.GetParameters()[0].ParameterType.Name -eq 'IAsyncOperation\`1'
+} | Select-Object -First 1
+function Await($op, [Type]$type) {
+  $task = $asTask.MakeGenericMethod($type).Invoke($null, @($op))
+  $task.Wait(-1) | Out-Null
+  $task.Result
+}
+$verifier = [Windows.Security.Credentials.UI.UserConsentVerifier, Windows.Security.Credentials.UI, ContentType = WindowsRuntime]
+$availability = Await ($verifier::CheckAvailabilityAsync()) ([Windows.Security.Credentials.UI.UserConsentVerifierAvailability])
+if ($availability -ne 'Available') { Write-Output 'Unavailable'; exit 0 }
+$result = Await ($verifier::RequestVerificationAsync($env:PRIZM_AUTH_REASON)) ([Windows.Security.Credentials.UI.UserConsentVerificationResult])
+Write-Output $result
+`
+
+/** 表示设备或用户未配置 Windows Hello 的结果 */
+const WINDOWS_UNAVAILABLE_RESULTS = new Set([
+  'Unavailable',
+  'DeviceNotPresent',
+  'NotConfiguredForUser'
+])
+
+function run(
+  file: string,
+  args: string[],
+  env?: NodeJS.ProcessEnv
+): Promise<{ code: number; stdout: string }> {
+  return new Promise((resolve, reject) => {
+    execFile(
+      file,
+      args,
+      { timeout: VERIFY_TIMEOUT_MS, windowsHide: true, env: { ...process.env, ...env } },
+      (err, stdout) => {
+        if (err && typeof err.code !== 'number') {
+          reject(err)
+          return
+        }
+        resolve({ code: typeof err?.code === 'number' ? err.code : 0, stdout: String(stdout) })
+      }
+    )
+  })
+}
+
+function unavailable(): PrizmError {
+  return PrizmError.auth('System authentication is not available on this device')
+}
+
+function denied(): PrizmError {
+  return PrizmError.auth('System authentication was cancelled or failed')
+}
+
+async function verifyMac(reason: string): Promise<void> {
+  if (!systemPreferences.canPromptTouchID()) throw unavailable()
+  try {
+    await systemPreferences.promptTouchID(reason)
+  } catch {
+    throw denied()
+  }
+}
+
+async function verifyWindows(reason: string): Promise<void> {
+  const { stdout } = await run(
+    'powershell.exe',
+    ['-NoProfile', '-NonInteractive', '-Command', WINDOWS_HELLO_SCRIPT],
+    { PRIZM_AUTH_REASON: reason }
+  ).catch((err) => {
+    log.warn('[OsAuth] Windows Hello unavailable:', err)
+    throw unavailable()
+  })
+  const result = stdout.trim()
+  if (result === 'Verified') return
+  throw WINDOWS_UNAVAILABLE_RESULTS.has(result) ? unavailable() : denied()
+}
+
+/** Linux：通过 polkit 要求当前用户验证身份（不提升权限，只检查授权） */
+async function verifyLinux(): Promise<void> {
+  const { code } = await run('pkcheck', [
+    '--action-id',
+    'org.freedesktop.policykit.exec',
+    '--process',
+    String(process.pid),
+    '--allow-user-interaction'
+  ]).catch((err) => {
+    log.warn('[OsAuth] polkit unavailable:', err)
+    throw unavailable()
+  })
+  if (code !== 0) throw denied()
+}
+
+/**
+ * 要求用户通过系统验证（Touch ID / Windows Hello / polkit）确认本人在场。
+ * 验证失败、取消或系统不支持时抛出 auth 错误
+ */
+export async function requireUserVerification(reason: string): Promise<void> {
+  switch (process.platform) {
+    case 'darwin':
+      return verifyMac(reason)
+    case 'win32':
+      return verifyWindows(reason)
+    case 'linux':
+      return verifyLinux()
+    default:
+      throw unavailable()
+  }
+}
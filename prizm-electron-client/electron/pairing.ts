@@ -0,0 +1,125 @@
+import QRCode from 'qrcode'
+import log from 'electron-log/main'
+import { loadConfigFromDisk } from './config'
+import { PrizmError } from './errors'
+import { PrizmApi, grantedScopesOf } from './prizmApi'
+import type { RegisterResponse } from './prizmApi'
+import { saveRegistration } from './registration'
+import type { RegistrationResult } from './registration'
+import { parseServerUrl, serverConfigToUrl } from './serverUrl'
+
+/** 二维码内容：prizm://pair?server=<服务器地址>&token=<一次性配对令牌> */
+const PAIRING_PROTOCOL = 'prizm:'
+const PAIRING_HOST = 'pair'
+
+export interface PairingPayload {
+  serverUrl: string
+  token: string
+}
+
+export interface PairingQr {
+  /** 二维码编码的文本，可直接复制给无法扫码的设备 */
+  payload: string
+  /** PNG 图片的 base64（不含 data: 前缀） */
+  png: string
+  expiresAt: number
+}
+
+export function encodePairingPayload({ serverUrl, token }: PairingPayload): string {
+  const params = new URLSearchParams({ server: serverUrl, token })
+  return `${PAIRING_PROTOCOL}//${PAIRING_HOST}?${params.toString()}`
+}
+
+/**
+ * 解析配对二维码内容；格式不符或服务器地址无效时抛出 invalid_input
+ */
+export function parsePairingPayload(payload: string): PairingPayload {
+  let url: URL
+  try {
+    url = new URL(payload.trim())
+  } catch {
+    throw PrizmError.invalidInput('Invalid pairing payload')
+  }
+  const server = url.searchParams.get('server')
+  const token = url.searchParams.get('token')
+  if (url.protocol !== PAIRING_PROTOCOL || url.hostname !== PAIRING_HOST || !server || !token) {
+    throw PrizmError.invalidInput('Invalid pairing payload')
+  }
+  return { serverUrl: serverConfigToUrl(parseServerUrl(server)), token }
+}
+
+/**
+ * 向当前服务器申请一次性配对令牌，生成供其他设备扫描的二维码
+ */
+export async function generatePairingQr(scopes?: string[]): Promise<PairingQr> {
+  const config = await loadConfigFromDisk()
+  const serverUrl = serverConfigToUrl(config.server)
+  const ticket = await new PrizmApi(serverUrl).createPairing(scopes)
+  const payload = encodePairingPayload({ serverUrl, token: ticket.token })
+  const dataUrl = await QRCode.toDataURL(payload, { errorCorrectionLevel: 'M', margin: 2 })
+  const png = dataUrl.replace(/^data:image\/png;base64,/, '')
+  return { payload, png, expiresAt: ticket.expiresAt }
+}
+
+/** Dashboard 展示的 6 位数字配对码 */
+const PAIRING_CODE = /^\d{6}$/
+
+/**
+ * 规范化用户输入的配对码（允许带空格或连字符，如 123 456）；不是 6 位数字时抛出 invalid_input
+ */
+export function normalizePairingCode(code: string): string {
+  const digits = code.replace(/[\s-]/g, '')
+  if (!PAIRING_CODE.test(digits)) {
+    throw PrizmError.invalidInput('Pairing code must be 6 digits')
+  }
+  return digits
+}
+
+async function savePairing(
+  serverUrl: string,
+  name: string,
+  register: RegisterResponse,
+  profileName?: string
+): Promise<RegistrationResult> {
+  const grantedScopes = grantedScopesOf(register, [])
+  const result: RegistrationResult = {
+    clientId: register.clientId || name,
+    apiKey: register.apiKey,
+    grantedScopes,
+    expiresAt: register.expiresAt,
+    refreshToken: register.refreshToken
+  }
+  await saveRegistration({ serverUrl, name, requestedScopes: grantedScopes, profileName }, result)
+  log.info(`[Pairing] Paired as "${result.clientId}" with ${serverUrl}`)
+  return result
+}
+
+/**
+ * 用扫描得到的配对内容注册到对应服务器，并把凭据写入配置
+ */
+export async function pairFromQr(
+  payload: string,
+  name: string,
+  profileName?: string,
+  signal?: AbortSignal
+): Promise<RegistrationResult> {
+  const { serverUrl, token } = parsePairingPayload(payload)
+  const register = await new PrizmApi(serverUrl).pair(token, name, signal)
+  return savePairing(serverUrl, name, register, profileName)
+}
+
+/**
+ * 用服务端 Dashboard 展示的 6 位配对码注册到当前配置的服务器，并把凭据写入配置
+ */
+export async function pairWithCode(
+  code: string,
+  name: string,
+  profileName?: string,
+  signal?: AbortSignal
+): Promise<RegistrationResult> {
+  const normalized = normalizePairingCode(code)
+  const config = await loadConfigFromDisk()
+  const serverUrl = serverConfigToUrl(config.server)
+  const register = await new PrizmApi(serverUrl).pairWithCode(normalized, name, signal)
+  return savePairing(serverUrl, name, register, profileName)
+}
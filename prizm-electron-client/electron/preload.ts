@@ -1,5 +1,14 @@
 import { contextBridge, ipcRenderer, webUtils } from 'electron'
 
+/** 订阅主进程转发的服务端事件频道，返回取消函数 */
+function onServerEvent(channel: string, callback: (event: unknown) => void) {
+  const handler = (_: unknown, event: unknown) => callback(event)
+  ipcRenderer.on(channel, handler)
+  return () => {
+    ipcRenderer.removeListener(channel, handler)
+  }
+}
+
 /**
  * 向渲染进程暴露一个与 Tauri invoke 语义接近的 API
  */
@@ -12,18 +21,518 @@ contextBridge.exposeInMainWorld('prizm', {
     return ipcRenderer.invoke('save_config', config)
   },
 
-  testConnection(serverUrl: string) {
-    return ipcRenderer.invoke('test_connection', { serverUrl })
+  /** 经系统验证（Touch ID / Windows Hello / polkit）后返回 API Key 原文 */
+  revealApiKey() {
+    return ipcRenderer.invoke('reveal_api_key')
+  },
+
+  /** 深度合并部分配置并保存，返回合并后的完整配置 */
+  updateConfig(patch: unknown) {
+    return ipcRenderer.invoke('update_config', patch)
+  },
+
+  /** 恢复默认配置；section 为 server / client / tray 等时只重置该段 */
+  resetConfig(section?: string) {
+    return ipcRenderer.invoke('reset_config', { section })
+  },
+
+  /** 列出与默认值不同的字段 */
+  diffConfig() {
+    return ipcRenderer.invoke('diff_config')
+  },
+
+  listConfigBackups() {
+    return ipcRenderer.invoke('list_config_backups')
+  },
+
+  /** 从第 index 份历史备份（1 为最新）恢复配置 */
+  restoreConfigBackup(index: number) {
+    return ipcRenderer.invoke('restore_config_backup', { index })
+  },
+
+  /** 检查配置问题；不传 config 时校验磁盘上的配置 */
+  validateConfig(config?: unknown, knownScopes?: string[]) {
+    return ipcRenderer.invoke('validate_config', { config, knownScopes })
+  },
+
+  /** 导出配置，path 缺省时弹出保存对话框；默认不含 api_key */
+  exportConfig(path?: string, includeSecrets = false) {
+    return ipcRenderer.invoke('export_config', { path, includeSecrets })
+  },
+
+  /** 导入配置，path 缺省时弹出打开对话框；校验失败不会覆盖当前配置 */
+  importConfig(path?: string) {
+    return ipcRenderer.invoke('import_config', { path })
+  },
+
+  /** requestId 可用于 cancelRequest 中止本次检查 */
+  testConnection(serverUrl: string, requestId?: string) {
+    return ipcRenderer.invoke('test_connection', { serverUrl, requestId })
+  },
+
+  /** 中止带 requestId 发起的注册 / 连接测试，返回是否找到该请求 */
+  cancelRequest(requestId: string) {
+    return ipcRenderer.invoke('cancel_request', { requestId })
+  },
+
+  /** 流式下载到 dest（绝对路径），进度通过 onDownloadProgress 推送 */
+  downloadFile(url: string, dest: string, options?: { auth?: boolean; requestId?: string }) {
+    return ipcRenderer.invoke('download_file', { url, dest, ...options })
+  },
+
+  onDownloadProgress(
+    callback: (progress: {
+      requestId?: string
+      dest: string
+      url: string
+      bytes: number
+      total: number | null
+      rate: number
+      done: boolean
+    }) => void
+  ) {
+    const handler = (_: unknown, progress: Parameters<typeof callback>[0]) => callback(progress)
+    ipcRenderer.on('download://progress', handler)
+    return () => {
+      ipcRenderer.removeListener('download://progress', handler)
+    }
+  },
+
+  /** 用给定代理设置请求服务器 /health，不改变当前生效的代理 */
+  testProxy(proxy: { mode: string; url: string; bypass: string }, serverUrl: string) {
+    return ipcRenderer.invoke('test_proxy', { proxy, serverUrl })
+  },
+
+  /** 并发检查所有档案服务器的 /health */
+  checkAllServers() {
+    return ipcRenderer.invoke('check_all_servers')
+  },
+
+  /** 多次 /health 往返统计延迟（min / avg / p95），并测量 DNS / TCP / TLS 阶段耗时 */
+  measureLatency(serverUrl: string, samples?: number) {
+    return ipcRenderer.invoke('measure_latency', { serverUrl, samples })
+  },
+
+  /** 主进程 HTTP 流量统计：按端点的请求数、失败数与收发字节数 */
+  getNetworkStats() {
+    return ipcRenderer.invoke('get_network_stats')
+  },
+
+  resetNetworkStats() {
+    return ipcRenderer.invoke('reset_network_stats')
+  },
+
+  /** 处于熔断（open / half_open）状态的服务器 */
+  getCircuitStates() {
+    return ipcRenderer.invoke('get_circuit_states')
+  },
+
+  /** 某个服务器的熔断状态变化（open / half_open / closed） */
+  onCircuitStateChanged(
+    callback: (status: {
+      origin: string
+      state: 'closed' | 'open' | 'half_open'
+      failures: number
+      retry_at: number | null
+    }) => void
+  ) {
+    const handler = (_: unknown, status: Parameters<typeof callback>[0]) => callback(status)
+    ipcRenderer.on('circuit://state', handler)
+    return () => {
+      ipcRenderer.removeListener('circuit://state', handler)
+    }
+  },
+
+  /** 与服务器的总体连接状态（综合实时连接与 HTTP 熔断） */
+  getConnectionState() {
+    return ipcRenderer.invoke('get_connection_state')
+  },
+
+  /** 总体连接状态变化（disconnected / connecting / connected / degraded） */
+  onConnectionStateChanged(callback: (snapshot: unknown) => void) {
+    const handler = (_: unknown, snapshot: unknown) => callback(snapshot)
+    ipcRenderer.on('connection://state', handler)
+    return () => {
+      ipcRenderer.removeListener('connection://state', handler)
+    }
+  },
+
+  /** 发送非幂等请求，服务器不可达时进入离线队列，连接恢复后按顺序重放 */
+  sendOrQueue(entry: {
+    method: string
+    url: string
+    body?: unknown
+    /** 附带 API Key，默认不附带 */
+    auth?: boolean
+    description?: string
+  }) {
+    return ipcRenderer.invoke('send_or_queue', entry)
+  },
+
+  getOfflineQueue() {
+    return ipcRenderer.invoke('get_offline_queue')
   },
 
-  registerClient(serverUrl: string, name: string, scopes: string[]) {
+  replayOfflineQueue() {
+    return ipcRenderer.invoke('replay_offline_queue')
+  },
+
+  clearOfflineQueue() {
+    return ipcRenderer.invoke('clear_offline_queue')
+  },
+
+  /** 离线队列变化（入队、重放进度、清空） */
+  onOfflineQueueUpdated(
+    callback: (status: {
+      pending: number
+      replaying: boolean
+      replayed: number
+      dropped: number
+    }) => void
+  ) {
+    const handler = (
+      _: unknown,
+      status: { pending: number; replaying: boolean; replayed: number; dropped: number }
+    ) => callback(status)
+    ipcRenderer.on('offline-queue://updated', handler)
+    return () => {
+      ipcRenderer.removeListener('offline-queue://updated', handler)
+    }
+  },
+
+  /** 开关网络请求日志（运行时生效，密钥与鉴权头会被隐去） */
+  setNetworkLogging(enabled: boolean) {
+    return ipcRenderer.invoke('set_network_logging', { enabled })
+  },
+
+  getNetworkLogging() {
+    return ipcRenderer.invoke('get_network_logging')
+  },
+
+  registerClient(
+    serverUrl: string,
+    name: string,
+    scopes: string[],
+    profileName?: string,
+    requestId?: string
+  ) {
     return ipcRenderer.invoke('register_client', {
       serverUrl,
       name,
-      requestedScopes: scopes
+      requestedScopes: scopes,
+      profileName,
+      requestId
+    })
+  },
+
+  /** 设备码授权：服务端确认后完成注册，等待期间通过 onDeviceCode 推送用户码 */
+  registerWithDeviceCode(
+    serverUrl: string,
+    name: string,
+    scopes: string[],
+    profileName?: string,
+    requestId?: string
+  ) {
+    return ipcRenderer.invoke('register_with_device_code', {
+      serverUrl,
+      name,
+      requestedScopes: scopes,
+      profileName,
+      requestId
     })
   },
 
+  onDeviceCode(
+    callback: (event: {
+      requestId?: string
+      userCode: string
+      verificationUri: string
+      verificationUriComplete?: string
+      expiresAt: number
+    }) => void
+  ) {
+    const handler = (_: unknown, event: Parameters<typeof callback>[0]) => callback(event)
+    ipcRenderer.on('auth://device-code', handler)
+    return () => {
+      ipcRenderer.removeListener('auth://device-code', handler)
+    }
+  },
+
+  /** 申请一次性配对令牌并生成二维码（base64 PNG），供其他设备扫码接入当前服务器 */
+  generatePairingQr(scopes?: string[]) {
+    return ipcRenderer.invoke('generate_pairing_qr', { scopes })
+  },
+
+  /** 用扫码得到的配对内容注册；name 不传时使用配置中的客户端名称 */
+  pairFromQr(payload: string, name?: string, profileName?: string, requestId?: string) {
+    return ipcRenderer.invoke('pair_from_qr', { payload, name, profileName, requestId })
+  },
+
+  /** 用服务端 Dashboard 展示的 6 位配对码注册到当前服务器；name 不传时使用配置中的客户端名称 */
+  pairWithCode(code: string, name?: string, profileName?: string, requestId?: string) {
+    return ipcRenderer.invoke('pair_with_code', { code, name, profileName, requestId })
+  },
+
+  /** 从上次中断的步骤继续注册，没有未完成的注册时返回 null */
+  resumeRegistration(requestId?: string) {
+    return ipcRenderer.invoke('resume_registration', { requestId })
+  },
+
+  getPendingRegistration() {
+    return ipcRenderer.invoke('get_pending_registration')
+  },
+
+  /** 注册审批进度（服务端要求人工批准新客户端时）：pending、approved、denied、expired */
+  onRegistrationStatus(
+    callback: (event: {
+      serverUrl: string
+      name: string
+      status: 'pending' | 'approved' | 'denied' | 'expired'
+      expiresAt?: number
+    }) => void
+  ) {
+    const handler = (_: unknown, event: Parameters<typeof callback>[0]) => callback(event)
+    ipcRenderer.on('auth://registration-status', handler)
+    return () => {
+      ipcRenderer.removeListener('auth://registration-status', handler)
+    }
+  },
+
+  /** 注册完成（手动注册、继续注册或后台自动注册），此时配置中的 api_key 已写入 */
+  onRegistrationComplete(
+    callback: (event: { clientId: string; serverUrl: string; name: string }) => void
+  ) {
+    const handler = (_: unknown, event: Parameters<typeof callback>[0]) => callback(event)
+    ipcRenderer.on('registration://complete', handler)
+    return () => {
+      ipcRenderer.removeListener('registration://complete', handler)
+    }
+  },
+
+  /** 申请追加 scope 并等待管理员批准，返回批准后的全部 scope；可用 cancelRequest(requestId) 取消 */
+  requestAdditionalScopes(scopes: string[], requestId?: string) {
+    return ipcRenderer.invoke('request_additional_scopes', { scopes, requestId })
+  },
+
+  /** scope 申请进度：pending（等待批准）、approved、denied、expired */
+  onScopeRequest(
+    callback: (event: {
+      requestId?: string
+      status: 'pending' | 'approved' | 'denied' | 'expired'
+      scopes: string[]
+    }) => void
+  ) {
+    const handler = (_: unknown, event: Parameters<typeof callback>[0]) => callback(event)
+    ipcRenderer.on('auth://scope-request', handler)
+    return () => {
+      ipcRenderer.removeListener('auth://scope-request', handler)
+    }
+  },
+
+  /** 注册时服务端实际授予的 scope */
+  getGrantedScopes() {
+    return ipcRenderer.invoke('get_granted_scopes')
+  },
+
+  /** 保存当前服务器的管理员 Key（审批客户端、吊销 Key 等管理操作使用） */
+  setAdminKey(adminKey: string) {
+    return ipcRenderer.invoke('set_admin_key', { adminKey })
+  },
+
+  /** 清除当前服务器的管理员 Key */
+  clearAdminKey() {
+    return ipcRenderer.invoke('clear_admin_key')
+  },
+
+  /** 当前服务器是否已保存管理员 Key（不返回 Key 本身） */
+  hasAdminKey() {
+    return ipcRenderer.invoke('has_admin_key')
+  },
+
+  /** 在服务端吊销本客户端并清除 api_key / client.name；force 时服务器不可达也清除本地配置 */
+  deregisterClient(force = false) {
+    return ipcRenderer.invoke('deregister_client', { force })
+  },
+
+  /** 检查已保存的 API Key 是否仍有效（无副作用，不触发重新注册） */
+  verifyCredentials(requestId?: string) {
+    return ipcRenderer.invoke('verify_credentials', { requestId })
+  },
+
+  /** 服务器上注册的全部客户端（需管理员 Key 或拥有 '*' scope） */
+  listClients(requestId?: string) {
+    return ipcRenderer.invoke('list_clients', { requestId })
+  },
+
+  /** 吊销服务器上的客户端（需管理员权限）；吊销本客户端时等同 deregisterClient */
+  revokeClient(clientId: string) {
+    return ipcRenderer.invoke('revoke_client', { clientId })
+  },
+
+  /** 批准其他客户端的注册申请（需管理员权限） */
+  approveRegistrationRequest(requestId: string) {
+    return ipcRenderer.invoke('approve_registration_request', { requestId })
+  },
+
+  /** 拒绝其他客户端的注册申请（需管理员权限） */
+  denyRegistrationRequest(requestId: string) {
+    return ipcRenderer.invoke('deny_registration_request', { requestId })
+  },
+
+  /** 自动注册或服务端变更将授予敏感 scope 时的确认请求，须用 respondConsent 作答 */
+  onConsentRequest(
+    callback: (event: {
+      id: string
+      serverUrl: string
+      reason: 'auto_register' | 'scope_change'
+      scopes: string[]
+      expiresAt: number
+    }) => void
+  ) {
+    const handler = (_: unknown, event: Parameters<typeof callback>[0]) => callback(event)
+    ipcRenderer.on('auth://consent-request', handler)
+    return () => {
+      ipcRenderer.removeListener('auth://consent-request', handler)
+    }
+  },
+
+  /** 尚未作答的敏感 scope 确认请求 */
+  getPendingConsents() {
+    return ipcRenderer.invoke('get_pending_consents')
+  },
+
+  /** 回答敏感 scope 确认请求；拒绝时不保存授权 */
+  respondConsent(id: string, allow: boolean) {
+    return ipcRenderer.invoke('respond_consent', { id, allow })
+  },
+
+  /** 点击系统通知等主进程发起的页面跳转（app://navigate） */
+  onNavigate(
+    callback: (route: { page: 'settings'; section: 'connection' | 'devices' }) => void
+  ) {
+    const handler = (_: unknown, route: Parameters<typeof callback>[0]) => callback(route)
+    ipcRenderer.on('app://navigate', handler)
+    return () => {
+      ipcRenderer.removeListener('app://navigate', handler)
+    }
+  },
+
+  /** 客户端已注销，界面应回到引导流程 */
+  onDeregistered(callback: (result: { revoked: boolean; serverUrl: string }) => void) {
+    const handler = (_: unknown, result: { revoked: boolean; serverUrl: string }) =>
+      callback(result)
+    ipcRenderer.on('auth://deregistered', handler)
+    return () => {
+      ipcRenderer.removeListener('auth://deregistered', handler)
+    }
+  },
+
+  /** 启动时按 client.auto_register 后台注册的结果；registered 时配置中的 api_key 已写入 */
+  onAutoRegister(
+    callback: (
+      event:
+        | { status: 'registered'; clientId: string; serverUrl: string }
+        | { status: 'failed'; serverUrl: string; message: string }
+    ) => void
+  ) {
+    const handler = (_: unknown, event: Parameters<typeof callback>[0]) => callback(event)
+    ipcRenderer.on('auth://auto-register', handler)
+    return () => {
+      ipcRenderer.removeListener('auth://auto-register', handler)
+    }
+  },
+
+  /** API Key 失效后已自动重新注册（client.auto_register），配置中的 api_key 已更新 */
+  onReregistered(callback: (event: { clientId: string; serverUrl: string }) => void) {
+    const handler = (_: unknown, event: { clientId: string; serverUrl: string }) => callback(event)
+    ipcRenderer.on('auth://reregistered', handler)
+    return () => {
+      ipcRenderer.removeListener('auth://reregistered', handler)
+    }
+  },
+
+  /** api_key 即将或已经过期且无法续期（无刷新令牌或刷新令牌被拒），需要重新注册 */
+  onReauthRequired(
+    callback: (event: {
+      serverUrl: string
+      reason: 'missing_refresh_token' | 'refresh_rejected'
+    }) => void
+  ) {
+    const handler = (_: unknown, event: Parameters<typeof callback>[0]) => callback(event)
+    ipcRenderer.on('auth://reauth-required', handler)
+    return () => {
+      ipcRenderer.removeListener('auth://reauth-required', handler)
+    }
+  },
+
+  /** 使用配置中的 scope 预设注册 */
+  registerWithPreset(
+    serverUrl: string,
+    name: string,
+    preset: string,
+    profileName?: string,
+    requestId?: string
+  ) {
+    return ipcRenderer.invoke('register_with_preset', {
+      serverUrl,
+      name,
+      preset,
+      profileName,
+      requestId
+    })
+  },
+
+  listProfiles() {
+    return ipcRenderer.invoke('list_profiles')
+  },
+
+  switchProfile(name: string) {
+    return ipcRenderer.invoke('switch_profile', { name })
+  },
+
+  deleteProfile(name: string) {
+    return ipcRenderer.invoke('delete_profile', { name })
+  },
+
+  /** 在当前服务器上以另一名称 / scope 组合注册，并保存为身份 */
+  registerIdentity(identity: string, name: string, scopes: string[], requestId?: string) {
+    return ipcRenderer.invoke('register_identity', {
+      identity,
+      name,
+      requestedScopes: scopes,
+      requestId
+    })
+  },
+
+  listIdentities() {
+    return ipcRenderer.invoke('list_identities')
+  },
+
+  useIdentity(name: string) {
+    return ipcRenderer.invoke('use_identity', { name })
+  },
+
+  deleteIdentity(name: string) {
+    return ipcRenderer.invoke('delete_identity', { name })
+  },
+
+  onConfigChanged(callback: (config: unknown) => void) {
+    const handler = (_: unknown, config: unknown) => callback(config)
+    ipcRenderer.on('config-changed', handler)
+    return () => {
+      ipcRenderer.removeListener('config-changed', handler)
+    }
+  },
+
+  /** 主进程修改配置后推送，sections 为发生变化的顶层配置段 */
+  onConfigUpdated(callback: (event: { sections: string[]; config: unknown }) => void) {
+    const handler = (_: unknown, event: { sections: string[]; config: unknown }) => callback(event)
+    ipcRenderer.on('config://updated', handler)
+    return () => {
+      ipcRenderer.removeListener('config://updated', handler)
+    }
+  },
+
   getAppVersion() {
     return ipcRenderer.invoke('get_app_version')
   },
@@ -32,6 +541,11 @@ contextBridge.exposeInMainWorld('prizm', {
     return ipcRenderer.invoke('open_dashboard', { serverUrl })
   },
 
+  /** 在应用内窗口中打开管理面板 */
+  openDashboardWindow(serverUrl: string) {
+    return ipcRenderer.invoke('open_dashboard_window', { serverUrl })
+  },
+
   readClipboard() {
     return ipcRenderer.invoke('clipboard_read')
   },
@@ -40,7 +554,7 @@ contextBridge.exposeInMainWorld('prizm', {
     return ipcRenderer.invoke('clipboard_write', { text })
   },
 
-  startClipboardSync(config: { serverUrl: string; apiKey: string; scope?: string }) {
+  startClipboardSync(config: { serverUrl: string; scope?: string }) {
     return ipcRenderer.invoke('clipboard_start_sync', config)
   },
 
@@ -64,6 +578,35 @@ contextBridge.exposeInMainWorld('prizm', {
     return ipcRenderer.invoke('show_notification', payload)
   },
 
+  /** 通知中心：弹出过的通知记录 */
+  listNotifications(options?: { unreadOnly?: boolean; limit?: number }) {
+    return ipcRenderer.invoke('list_notifications', options)
+  },
+
+  markNotificationsRead(ids?: string[]) {
+    return ipcRenderer.invoke('mark_read', ids)
+  },
+
+  clearNotifications() {
+    return ipcRenderer.invoke('clear_notifications')
+  },
+
+  onNotificationsChanged(callback: (change: { unread: number }) => void) {
+    const handler = (_: unknown, change: { unread: number }) => callback(change)
+    ipcRenderer.on('notifications://changed', handler)
+    return () => {
+      ipcRenderer.removeListener('notifications://changed', handler)
+    }
+  },
+
+  getNotificationsPaused() {
+    return ipcRenderer.invoke('get_notifications_paused')
+  },
+
+  setNotificationsPaused(paused: boolean) {
+    return ipcRenderer.invoke('set_notifications_paused', paused)
+  },
+
   onLogFromMain(
     callback: (entry: { level: string; message: string; timestamp: string; source: string }) => void
   ) {
@@ -128,6 +671,32 @@ contextBridge.exposeInMainWorld('prizm', {
     return ipcRenderer.invoke('set_native_theme', { mode })
   },
 
+  getSystemTheme() {
+    return ipcRenderer.invoke('get_system_theme')
+  },
+
+  onThemeChanged(
+    callback: (state: {
+      mode: 'auto' | 'light' | 'dark'
+      effective: 'light' | 'dark'
+      highContrast: boolean
+    }) => void
+  ) {
+    const handler = (_: unknown, state: Parameters<typeof callback>[0]) => callback(state)
+    ipcRenderer.on('theme://changed', handler)
+    return () => {
+      ipcRenderer.removeListener('theme://changed', handler)
+    }
+  },
+
+  getGlobalHotkey() {
+    return ipcRenderer.invoke('get_global_hotkey')
+  },
+
+  setGlobalHotkey(accelerator: string) {
+    return ipcRenderer.invoke('set_global_hotkey', { accelerator })
+  },
+
   /** 在系统资源管理器中打开目录 */
   openInExplorer(dirPath: string) {
     return ipcRenderer.invoke('open_in_explorer', { dirPath })
@@ -137,5 +706,42 @@ contextBridge.exposeInMainWorld('prizm', {
     start: (mode?: 'internal' | 'external') => ipcRenderer.invoke('browser_node:start', mode),
     stop: () => ipcRenderer.invoke('browser_node:stop'),
     getStatus: () => ipcRenderer.invoke('browser_node:status')
+  },
+
+  /** 主进程持有的服务端 WebSocket 连接 */
+  realtime: {
+    connect: () => ipcRenderer.invoke('realtime:connect'),
+    disconnect: () => ipcRenderer.invoke('realtime:disconnect'),
+    getStatus: () => ipcRenderer.invoke('realtime:status'),
+    reconnect: () => ipcRenderer.invoke('realtime:reconnect'),
+    subscribe: (topic: string) => ipcRenderer.invoke('realtime:subscribe', topic),
+    unsubscribe: (topic: string) => ipcRenderer.invoke('realtime:unsubscribe', topic),
+    getSubscriptions: () => ipcRenderer.invoke('realtime:subscriptions'),
+    getMissedEventCount: () => ipcRenderer.invoke('realtime:missed_event_count'),
+    onState(callback: (status: unknown) => void) {
+      const handler = (_: unknown, status: unknown) => callback(status)
+      ipcRenderer.on('ws://state', handler)
+      return () => {
+        ipcRenderer.removeListener('ws://state', handler)
+      }
+    },
+    /** 服务端推送的通知（server://notification） */
+    onNotification: (callback: (event: unknown) => void) =>
+      onServerEvent('server://notification', callback),
+    /** 用户点击了由服务端通知弹出的系统通知（notification://clicked） */
+    onNotificationClick: (callback: (event: unknown) => void) =>
+      onServerEvent('notification://clicked', callback),
+    /** 任务、工作流、终端等运行状态变化（server://status） */
+    onStatus: (callback: (event: unknown) => void) => onServerEvent('server://status', callback),
+    /** 数据增删改（server://data-changed） */
+    onDataChanged: (callback: (event: unknown) => void) =>
+      onServerEvent('server://data-changed', callback)
+  },
+
+  /** 经实时连接向服务端开放的本机服务（config.tunnels） */
+  tunnels: {
+    list: () => ipcRenderer.invoke('tunnel:list'),
+    enable: (name: string) => ipcRenderer.invoke('tunnel:enable', name),
+    disable: (name: string) => ipcRenderer.invoke('tunnel:disable', name)
   }
 })
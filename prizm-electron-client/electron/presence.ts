@@ -0,0 +1,81 @@
+import { powerMonitor } from 'electron'
+import log from 'electron-log/main'
+import type { PrizmConfig } from './config'
+import { realtimeConnection } from './realtime'
+import type { RealtimeConnection } from './realtime'
+
+/** 在线状态，与 @prizm/shared 的 PresenceState 一致 */
+export type PresenceState = 'online' | 'idle' | 'away'
+
+/** 上报间隔；服务端只在状态变化时广播，定期上报用于刷新空闲时长 */
+export const PRESENCE_INTERVAL_MS = 60_000
+/** 系统空闲超过该秒数视为 idle */
+export const IDLE_AFTER_SECONDS = 5 * 60
+/** 系统空闲超过该秒数（或已锁屏）视为 away */
+export const AWAY_AFTER_SECONDS = 30 * 60
+
+/** 按系统空闲时长与锁屏状态推导在线状态 */
+export function presenceFromIdle(idleSeconds: number, locked = false): PresenceState {
+  if (locked || idleSeconds >= AWAY_AFTER_SECONDS) return 'away'
+  return idleSeconds >= IDLE_AFTER_SECONDS ? 'idle' : 'online'
+}
+
+interface IdleProbe {
+  idleSeconds(): number
+  locked(): boolean
+}
+
+const systemIdle: IdleProbe = {
+  idleSeconds: () => powerMonitor.getSystemIdleTime(),
+  locked: () => powerMonitor.getSystemIdleState(AWAY_AFTER_SECONDS) === 'locked'
+}
+
+/**
+ * 通过实时连接定期上报本机在线状态，供其他客户端和管理面板查看哪些设备在用。
+ * client.report_presence 为 false 时不上报；连接建立后立即上报一次
+ */
+export class PresenceReporter {
+  private timer: NodeJS.Timeout | undefined
+  private unsubscribe: (() => void) | null = null
+
+  constructor(
+    private readonly connection: RealtimeConnection = realtimeConnection,
+    private readonly probe: IdleProbe = systemIdle
+  ) {}
+
+  /** 按配置开始或停止上报（配置变更后调用） */
+  schedule(config: PrizmConfig): void {
+    if (config.client.report_presence === false) {
+      if (this.timer) log.info('[Presence] Reporting disabled')
+      this.stop()
+      return
+    }
+    if (this.timer) return
+    this.unsubscribe = this.connection.onMessage((message) => {
+      if (message.type === 'connected') this.report()
+    })
+    this.timer = setInterval(() => this.report(), PRESENCE_INTERVAL_MS)
+    this.timer.unref?.()
+    this.report()
+  }
+
+  stop(): void {
+    clearInterval(this.timer)
+    this.timer = undefined
+    this.unsubscribe?.()
+    this.unsubscribe = null
+  }
+
+  /** 上报当前状态；未启用或未连接时跳过，连接建立后会再上报 */
+  report(): void {
+    if (!this.timer || this.connection.status().state !== 'connected') return
+    try {
+      const idleSeconds = this.probe.idleSeconds()
+      this.connection.sendPresence(presenceFromIdle(idleSeconds, this.probe.locked()), idleSeconds)
+    } catch (err) {
+      log.warn('[Presence] Failed to read system idle state:', err)
+    }
+  }
+}
+
+export const presenceReporter = new PresenceReporter()
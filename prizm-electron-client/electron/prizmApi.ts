@@ -0,0 +1,466 @@
+import { httpClient } from './httpClient'
+import type { HttpClient } from './httpClient'
+import { PrizmError, toPrizmError } from './errors'
+import { realtimeConnection } from './realtime'
+
+/** GET /health */
+export interface HealthResponse {
+  status: string
+  service?: string
+  timestamp?: number
+  dataDir?: string
+  embedding?: {
+    state: string
+    model: string | null
+    dimension: number | null
+  }
+}
+
+/** 注册时声明的客户端能力，与 @prizm/shared 的 ClientCapabilities 一致 */
+export interface ClientCapabilities {
+  /** 运行平台，如 win32-x64 */
+  platform?: string
+  appVersion?: string
+  /** 支持的功能，如 notifications、ws、file-transfer */
+  features: string[]
+}
+
+/** POST /auth/register */
+export interface RegisterResponse {
+  clientId: string
+  apiKey: string
+  /** 服务端实际授予的 scope（旧版服务端不返回） */
+  grantedScopes?: string[]
+  /** apiKey 过期时间（毫秒时间戳），签发永久 Key 的服务端不返回 */
+  expiresAt?: number
+  /** 续期 apiKey 用的刷新令牌，与 expiresAt 一同返回 */
+  refreshToken?: string
+}
+
+export type RegistrationApprovalStatus = 'pending' | 'approved' | 'denied' | 'expired'
+
+/**
+ * 服务端要求人工批准新客户端时 POST /auth/register 返回 202 与该申请；
+ * GET /auth/register/:requestId 查询进度，批准后一次性附带注册结果
+ */
+export interface RegistrationApprovalResponse {
+  requestId: string
+  status: RegistrationApprovalStatus
+  /** 申请过期时间（毫秒时间戳） */
+  expiresAt?: number
+  result?: RegisterResponse
+}
+
+/** register 的返回是否为待审批的申请 */
+export function isPendingApproval(
+  response: RegisterResponse | RegistrationApprovalResponse
+): response is RegistrationApprovalResponse {
+  return (response as RegistrationApprovalResponse).status === 'pending'
+}
+
+/** POST /auth/refresh */
+export interface RefreshResponse {
+  apiKey: string
+  expiresAt?: number
+  /** 服务端轮换刷新令牌时返回新令牌，不返回时沿用旧令牌 */
+  refreshToken?: string
+}
+
+/**
+ * 注册结果中授予的 scope；旧版服务端不返回时按请求的 scope 记录
+ */
+export function grantedScopesOf(register: RegisterResponse, requestedScopes: string[]): string[] {
+  return Array.isArray(register.grantedScopes) ? [...register.grantedScopes] : [...requestedScopes]
+}
+
+/** GET /auth/clients 中的一项：注册在服务端的客户端（设备） */
+export interface RegisteredClient {
+  clientId: string
+  name: string
+  allowedScopes: string[]
+  createdAt: number
+  /** 最近一次通过鉴权的时间，旧版服务端或从未使用过时不返回 */
+  lastSeenAt?: number
+}
+
+/** GET /auth/me：当前凭据对应的客户端；lastSeenAt 为本次请求之前的最近使用时间 */
+export type CurrentClientResponse = RegisteredClient
+
+/** POST /auth/device/code：设备码授权（RFC 8628）第一步 */
+export interface DeviceCodeResponse {
+  device_code: string
+  user_code: string
+  verification_uri: string
+  verification_uri_complete?: string
+  /** 设备码有效期（秒） */
+  expires_in: number
+  /** 轮询间隔（秒），默认 5 */
+  interval?: number
+}
+
+/** POST /auth/device/token 授权成功的响应 */
+export interface DeviceTokenResponse {
+  access_token: string
+  token_type: string
+  expires_in?: number
+  refresh_token?: string
+  /** 空格分隔的授予 scope */
+  scope?: string
+  client_id?: string
+}
+
+export type DeviceTokenResult =
+  | { status: 'granted'; token: DeviceTokenResponse }
+  | { status: 'authorization_pending' | 'slow_down' }
+
+const DEVICE_CODE_GRANT = 'urn:ietf:params:oauth:grant-type:device_code'
+
+/** POST /auth/pairing：一次性配对令牌 */
+export interface PairingTicketResponse {
+  token: string
+  scopes: string[]
+  expiresAt: number
+}
+
+export type ScopeRequestStatus = 'pending' | 'approved' | 'denied' | 'expired'
+
+/** POST /auth/scope-requests、GET /auth/scope-requests/:id */
+export interface ScopeRequestResponse {
+  /** 无需审批（已拥有全部申请的 scope）时不返回 */
+  requestId?: string
+  status: ScopeRequestStatus
+  scopes?: string[]
+  expiresAt?: number
+  /** 批准后客户端拥有的全部 scope */
+  grantedScopes?: string[]
+}
+
+/** GET /auth/scopes */
+export interface ScopesResponse {
+  scopes: string[]
+  descriptions: Record<string, { label: string; description: string }>
+  scopeDetails: Record<string, { path: string | null; label: string; builtin: boolean }>
+}
+
+/** 可替代 HTTP 发送需鉴权请求的通道（实时连接的 WebSocket RPC，见 realtime.ts） */
+export interface RpcTransport {
+  /** 已连接到 baseUrl 对应的服务器 */
+  servesBaseUrl(baseUrl: string): boolean
+  request(
+    method: 'GET' | 'POST',
+    path: string,
+    body?: unknown,
+    signal?: AbortSignal
+  ): Promise<Response>
+}
+
+/**
+ * Prizm 服务端 API 的类型化封装：集中维护端点路径与响应模型，
+ * 状态码异常时抛出 PrizmError，调用方无需再拼 URL 与解析 JSON。
+ */
+export class PrizmApi {
+  private readonly baseUrl: string
+  /** 最近一次健康检查成功的路径；构造时可传入上次记住的路径优先尝试 */
+  healthPath: string | null
+
+  constructor(
+    baseUrl: string,
+    private readonly client: HttpClient = httpClient,
+    healthPath?: string,
+    private readonly rpc: RpcTransport | null = realtimeConnection
+  ) {
+    this.baseUrl = baseUrl.replace(/\/+$/, '')
+    this.healthPath = healthPath ?? null
+  }
+
+  private url(path: string): string {
+    return `${this.baseUrl}${path}`
+  }
+
+  /**
+   * 需鉴权的请求：实时连接已连到同一服务器时改走 WebSocket RPC，省去 HTTP 建连；
+   * 否则走 HTTP。RPC 返回 401 时不触发自动续期，由调用方按鉴权错误处理
+   */
+  private authRequest(
+    method: 'GET' | 'POST',
+    path: string,
+    body?: unknown,
+    signal?: AbortSignal
+  ): Promise<Response> {
+    if (this.rpc?.servesBaseUrl(this.baseUrl)) {
+      return this.rpc.request(method, path, body, signal)
+    }
+    return method === 'GET'
+      ? this.client.getAuth(this.url(path), { signal })
+      : this.client.postAuth(this.url(path), body, { signal })
+  }
+
+  private async readJson<T>(resp: Response): Promise<T> {
+    if (!resp.ok) {
+      throw await PrizmError.fromResponse(resp)
+    }
+    try {
+      return (await resp.json()) as T
+    } catch (err) {
+      throw PrizmError.parse(`Invalid JSON from ${resp.url || this.baseUrl}: ${String(err)}`)
+    }
+  }
+
+  /**
+   * 健康检查（同时返回服务名、数据目录与嵌入模型状态），使用 network.health_timeout_ms。
+   * 按 healthPath、network.health_paths 的顺序尝试，404 时换下一个路径；
+   * 非 JSON、非网页的 2xx 响应（如 /healthz 返回 "ok"）视为健康
+   */
+  async health(signal?: AbortSignal): Promise<HealthResponse> {
+    const paths = [
+      ...new Set([
+        ...(this.healthPath ? [this.healthPath] : []),
+        ...this.client.getNetworkConfig().health_paths
+      ])
+    ]
+    let notFound: PrizmError | null = null
+    for (const path of paths) {
+      const resp = await this.client.healthGet(this.url(path), { signal })
+      if (resp.status === 404) {
+        notFound = await PrizmError.fromResponse(resp)
+        continue
+      }
+      if (!resp.ok) {
+        throw await PrizmError.fromResponse(resp)
+      }
+      const text = await resp.text()
+      let health: HealthResponse
+      try {
+        health = JSON.parse(text) as HealthResponse
+      } catch {
+        // 反向代理把未知路径回退到网页时不能当作健康
+        if (/html/i.test(resp.headers.get('content-type') ?? '')) {
+          throw PrizmError.parse(`Unexpected HTML from ${this.url(path)}`)
+        }
+        health = { status: 'ok' }
+      }
+      this.healthPath = path
+      return health
+    }
+    throw notFound ?? PrizmError.config('No health check paths configured')
+  }
+
+  /** 健康检查且 status 为 ok 时返回 true；除被 signal 取消外不抛错 */
+  async isHealthy(signal?: AbortSignal): Promise<boolean> {
+    try {
+      return (await this.health(signal)).status === 'ok'
+    } catch {
+      if (signal?.aborted) throw toPrizmError(signal.reason)
+      return false
+    }
+  }
+
+  /** 注册客户端（免鉴权），同名客户端会重新生成 API Key */
+  async register(
+    name: string,
+    requestedScopes?: string[],
+    signal?: AbortSignal,
+    capabilities?: ClientCapabilities
+  ): Promise<RegisterResponse | RegistrationApprovalResponse> {
+    const body = {
+      name,
+      requestedScopes: requestedScopes && requestedScopes.length > 0 ? requestedScopes : undefined,
+      capabilities
+    }
+    return this.readJson<RegisterResponse | RegistrationApprovalResponse>(
+      await this.client.post(this.url('/auth/register'), body, { signal })
+    )
+  }
+
+  /** 查询待审批的注册申请（免鉴权，requestId 即凭据）；批准后的结果只返回一次 */
+  async getRegistrationRequest(
+    requestId: string,
+    signal?: AbortSignal
+  ): Promise<RegistrationApprovalResponse> {
+    return this.readJson<RegistrationApprovalResponse>(
+      await this.client.get(this.url(`/auth/register/${encodeURIComponent(requestId)}`), {
+        signal
+      })
+    )
+  }
+
+  /**
+   * 签发一次性配对令牌（需鉴权），新客户端可凭令牌注册；scopes 不传时转授本客户端的全部 scope
+   */
+  async createPairing(scopes?: string[]): Promise<PairingTicketResponse> {
+    return this.readJson<PairingTicketResponse>(
+      await this.authRequest('POST', '/auth/pairing', { scopes })
+    )
+  }
+
+  /**
+   * 为当前客户端申请追加 scope（需鉴权），通常返回 pending，待管理员在 Dashboard 批准
+   */
+  async requestScopes(scopes: string[], signal?: AbortSignal): Promise<ScopeRequestResponse> {
+    return this.readJson<ScopeRequestResponse>(
+      await this.authRequest('POST', '/auth/scope-requests', { scopes }, signal)
+    )
+  }
+
+  /** 查询 scope 申请状态（需鉴权） */
+  async getScopeRequest(requestId: string, signal?: AbortSignal): Promise<ScopeRequestResponse> {
+    return this.readJson<ScopeRequestResponse>(
+      await this.authRequest(
+        'GET',
+        `/auth/scope-requests/${encodeURIComponent(requestId)}`,
+        undefined,
+        signal
+      )
+    )
+  }
+
+  /**
+   * 用刷新令牌换取新的 API Key（免鉴权，旧 Key 可能已过期）。刷新令牌无效时服务端返回 401
+   */
+  async refreshToken(refreshToken: string, signal?: AbortSignal): Promise<RefreshResponse> {
+    return this.readJson<RefreshResponse>(
+      await this.client.post(this.url('/auth/refresh'), { refreshToken }, { signal })
+    )
+  }
+
+  /** 用配对令牌注册客户端（免鉴权），令牌无效或已使用时服务端返回 401 */
+  async pair(token: string, name: string, signal?: AbortSignal): Promise<RegisterResponse> {
+    return this.readJson<RegisterResponse>(
+      await this.client.post(this.url('/auth/pair'), { token, name }, { signal })
+    )
+  }
+
+  /** 用 Dashboard 展示的 6 位配对码注册客户端（免鉴权），配对码无效或已使用时服务端返回 401 */
+  async pairWithCode(code: string, name: string, signal?: AbortSignal): Promise<RegisterResponse> {
+    return this.readJson<RegisterResponse>(
+      await this.client.post(this.url('/auth/pair'), { code, name }, { signal })
+    )
+  }
+
+  /**
+   * 申请设备码与用户码。服务端不支持设备码授权时抛出 config 错误
+   * （未知路径在开启鉴权的服务端返回 401 而不是 404）
+   */
+  async requestDeviceCode(
+    name: string,
+    requestedScopes: string[],
+    signal?: AbortSignal
+  ): Promise<DeviceCodeResponse> {
+    const body = { client_name: name, scope: requestedScopes.join(' ') || undefined }
+    const resp = await this.client.post(this.url('/auth/device/code'), body, { signal })
+    if (resp.status === 404 || resp.status === 401) {
+      throw PrizmError.config('Server does not support device authorization')
+    }
+    return this.readJson<DeviceCodeResponse>(resp)
+  }
+
+  /**
+   * 轮询一次令牌端点：用户尚未确认时返回 authorization_pending / slow_down，
+   * 用户拒绝或设备码过期时抛出 auth 错误
+   */
+  async pollDeviceToken(deviceCode: string, signal?: AbortSignal): Promise<DeviceTokenResult> {
+    const body = { grant_type: DEVICE_CODE_GRANT, device_code: deviceCode }
+    const resp = await this.client.post(this.url('/auth/device/token'), body, { signal })
+    if (resp.ok) {
+      return { status: 'granted', token: await this.readJson<DeviceTokenResponse>(resp) }
+    }
+    if (resp.status !== 400) {
+      throw await PrizmError.fromResponse(resp)
+    }
+    const text = await resp.text()
+    let error: unknown
+    try {
+      error = (JSON.parse(text) as { error?: unknown }).error
+    } catch {
+      throw PrizmError.badStatus(resp.status, text)
+    }
+    if (error === 'authorization_pending' || error === 'slow_down') {
+      return { status: error }
+    }
+    if (error === 'access_denied') {
+      throw PrizmError.auth('Device authorization was denied')
+    }
+    if (error === 'expired_token') {
+      throw PrizmError.auth('Device code expired')
+    }
+    throw PrizmError.badStatus(resp.status, text)
+  }
+
+  /**
+   * 用指定 API Key 请求需鉴权的 /auth/clients，确认 Key 已生效（不依赖配置中的当前 Key）。
+   * Key 无效时返回 false（403 说明 Key 有效、只是没有管理权限），其他错误抛出
+   */
+  async verifyApiKey(apiKey: string, signal?: AbortSignal): Promise<boolean> {
+    const resp = await this.client.get(this.url('/auth/clients'), {
+      headers: { Authorization: `Bearer ${apiKey}` },
+      signal
+    })
+    if (resp.status === 401) return false
+    if (!resp.ok && resp.status !== 403) {
+      throw await PrizmError.fromResponse(resp)
+    }
+    return true
+  }
+
+  /**
+   * 查询指定 API Key 对应的客户端（无副作用：不带 reauth，Key 失效时不会触发重新注册）。
+   * Key 无效时返回 null；旧版服务端没有该接口时抛出 404
+   */
+  async currentClient(apiKey: string, signal?: AbortSignal): Promise<CurrentClientResponse | null> {
+    const resp = await this.client.get(this.url('/auth/me'), {
+      headers: { Authorization: `Bearer ${apiKey}` },
+      signal
+    })
+    if (resp.status === 401) return null
+    return this.readJson<CurrentClientResponse>(resp)
+  }
+
+  /**
+   * 列出服务端注册的全部客户端（管理操作，设置了管理员 Key 时优先使用）
+   */
+  async listClients(signal?: AbortSignal): Promise<RegisteredClient[]> {
+    const { clients } = await this.readJson<{ clients: RegisteredClient[] }>(
+      await this.client.requestAuth('GET', this.url('/auth/clients'), {
+        reauth: false,
+        credential: 'admin',
+        signal
+      })
+    )
+    return Array.isArray(clients) ? clients : []
+  }
+
+  /**
+   * 吊销客户端（管理操作，设置了管理员 Key 时优先使用），之后其 API Key 失效。不触发自动重新注册
+   */
+  async revokeClient(clientId: string): Promise<void> {
+    const resp = await this.client.requestAuth(
+      'DELETE',
+      this.url(`/auth/clients/${encodeURIComponent(clientId)}`),
+      { reauth: false, credential: 'admin' }
+    )
+    if (!resp.ok) {
+      throw await PrizmError.fromResponse(resp)
+    }
+  }
+
+  /**
+   * 批准或拒绝等待审批的注册申请（管理操作，设置了管理员 Key 时优先使用）
+   */
+  async resolveRegistrationRequest(requestId: string, action: 'approve' | 'deny'): Promise<void> {
+    const resp = await this.client.requestAuth(
+      'POST',
+      this.url(`/auth/registration-requests/${encodeURIComponent(requestId)}/${action}`),
+      { reauth: false, credential: 'admin' }
+    )
+    if (!resp.ok) {
+      throw await PrizmError.fromResponse(resp)
+    }
+  }
+
+  /** 列出服务端 scope 及说明 */
+  async scopes(): Promise<ScopesResponse> {
+    return this.readJson<ScopesResponse>(
+      await this.client.get(this.url('/auth/scopes'), { cache: true })
+    )
+  }
+}
@@ -0,0 +1,105 @@
+import type { PrizmConfig, ServerProfile } from './config'
+import { PrizmError } from './errors'
+
+export interface ProfileSummary {
+  name: string
+  host: string
+  port: number
+  hasApiKey: boolean
+  active: boolean
+}
+
+/**
+ * 默认档案名：host:port
+ */
+export function defaultProfileName(config: PrizmConfig): string {
+  return `${config.server.host}:${config.server.port}`
+}
+
+/**
+ * 从当前顶层配置生成档案快照
+ */
+function snapshotProfile(config: PrizmConfig): ServerProfile {
+  return {
+    server: { ...config.server },
+    api_key: config.api_key,
+    ...(config.admin_key ? { admin_key: config.admin_key } : {}),
+    requested_scopes: [...config.client.requested_scopes],
+    granted_scopes: [...config.client.granted_scopes]
+  }
+}
+
+/**
+ * 将当前连接信息写回当前档案（无档案名时按 host:port 新建）
+ */
+export function upsertActiveProfile(config: PrizmConfig, name?: string): PrizmConfig {
+  const profileName = name || config.active_profile || defaultProfileName(config)
+  config.profiles = { ...(config.profiles ?? {}), [profileName]: snapshotProfile(config) }
+  config.active_profile = profileName
+  return config
+}
+
+/**
+ * 列出所有档案
+ */
+export function listProfiles(config: PrizmConfig): ProfileSummary[] {
+  return Object.entries(config.profiles ?? {}).map(([name, profile]) => ({
+    name,
+    host: profile.server.host,
+    port: profile.server.port,
+    hasApiKey: !!profile.api_key,
+    active: config.active_profile === name
+  }))
+}
+
+/**
+ * 切换到指定档案：先保存当前档案，再把目标档案展开到顶层字段
+ */
+export function switchProfile(config: PrizmConfig, name: string): PrizmConfig {
+  const target = config.profiles?.[name]
+  if (!target) {
+    throw PrizmError.invalidInput(`Profile not found: ${name}`)
+  }
+  if (config.active_profile !== name) {
+    upsertActiveProfile(config)
+  }
+  config.server = { ...target.server }
+  config.api_key = target.api_key
+  // 管理员 Key 属于服务器，随档案切换
+  if (target.admin_key) {
+    config.admin_key = target.admin_key
+  } else {
+    delete config.admin_key
+  }
+  // 档案不保存过期信息，切换后按永久 Key 处理，失效时由重新注册兜底
+  delete config.api_key_expires_at
+  delete config.refresh_token
+  config.client = {
+    ...config.client,
+    requested_scopes: [...target.requested_scopes],
+    granted_scopes: [...(target.granted_scopes ?? [])]
+  }
+  config.active_profile = name
+  // 当前身份属于其他服务器时不再视为当前身份
+  const identity = config.active_identity && config.identities?.[config.active_identity]
+  if (identity && identity.server !== defaultProfileName(config)) {
+    delete config.active_identity
+  }
+  return config
+}
+
+/**
+ * 删除档案；不允许删除当前正在使用的档案
+ */
+export function deleteProfile(config: PrizmConfig, name: string): PrizmConfig {
+  if (!config.profiles?.[name]) {
+    throw PrizmError.invalidInput(`Profile not found: ${name}`)
+  }
+  if (config.active_profile === name) {
+    throw PrizmError.invalidInput(`Cannot delete the active profile: ${name}`)
+  }
+  const profiles = { ...config.profiles }
+  delete profiles[name]
+  config.profiles = profiles
+  return config
+}
@@ -0,0 +1,89 @@
+import { session } from 'electron'
+import type { ProxyConfig as ElectronProxyConfig, Session } from 'electron'
+import type { ProxyConfig } from './config'
+import { PrizmError, toPrizmError } from './errors'
+import type { PrizmErrorPayload } from './errors'
+
+/** 测试代理使用的独立分区，避免影响共享 session 的代理与连接池 */
+const PROXY_TEST_PARTITION = 'prizm-proxy-test'
+
+const PROXY_URL_SCHEMES = new Set(['http:', 'https:', 'socks4:', 'socks5:'])
+
+export interface ProxyTestResult {
+  ok: boolean
+  /** 服务器返回的状态码（未拿到响应时缺省） */
+  status?: number
+  latency_ms: number
+  /** Chromium 解析出的实际代理，如 "PROXY proxy.corp:8080" 或 "DIRECT" */
+  resolved: string
+  error?: PrizmErrorPayload
+}
+
+/**
+ * 将配置中的代理设置转换为 Electron session.setProxy 参数
+ */
+export function toElectronProxyConfig(proxy: ProxyConfig): ElectronProxyConfig {
+  switch (proxy.mode) {
+    case 'none':
+      return { mode: 'direct' }
+    case 'manual': {
+      let url: URL
+      try {
+        url = new URL(proxy.url)
+      } catch {
+        throw PrizmError.config(`Invalid proxy url: ${proxy.url || '(empty)'}`)
+      }
+      if (!PROXY_URL_SCHEMES.has(url.protocol)) {
+        throw PrizmError.config(`Unsupported proxy scheme: ${url.protocol}`)
+      }
+      if (url.username || url.password) {
+        throw PrizmError.config('Proxy credentials in url are not supported')
+      }
+      return {
+        mode: 'fixed_servers',
+        proxyRules: `${url.protocol}//${url.host}`,
+        proxyBypassRules: proxy.bypass || undefined
+      }
+    }
+    default:
+      return { mode: 'system' }
+  }
+}
+
+/**
+ * 为 session 应用代理并断开旧连接，使新设置立即生效
+ */
+export async function applyProxy(target: Session, proxy: ProxyConfig): Promise<void> {
+  await target.setProxy(toElectronProxyConfig(proxy))
+  await target.closeAllConnections()
+}
+
+/**
+ * 用给定代理设置请求 targetUrl，供设置页在保存前验证代理可用
+ */
+export async function testProxy(
+  proxy: ProxyConfig,
+  targetUrl: string,
+  timeoutMs: number
+): Promise<ProxyTestResult> {
+  const testSession = session.fromPartition(PROXY_TEST_PARTITION)
+  const started = Date.now()
+  let resolved = ''
+  try {
+    await applyProxy(testSession, proxy)
+    resolved = await testSession.resolveProxy(targetUrl)
+    const resp = await testSession.fetch(targetUrl, {
+      method: 'GET',
+      signal: AbortSignal.timeout(timeoutMs)
+    })
+    await resp.body?.cancel()
+    return { ok: resp.ok, status: resp.status, latency_ms: Date.now() - started, resolved }
+  } catch (err) {
+    return {
+      ok: false,
+      latency_ms: Date.now() - started,
+      resolved,
+      error: toPrizmError(err).toJSON()
+    }
+  }
+}
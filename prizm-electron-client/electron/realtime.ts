@@ -0,0 +1,754 @@
+import { randomUUID } from 'crypto'
+import log from 'electron-log/main'
+import WebSocket from 'ws'
+import { loadConfigFromDisk, sharedState } from './config'
+import type { HeartbeatConfig, PrizmConfig } from './config'
+import {
+  BINARY_FRAME_CHUNK_SIZE,
+  decodeBinaryFrame,
+  encodeBinaryFrame,
+  splitBinaryFrames
+} from './binaryFrames'
+import type { BinaryFrame } from './binaryFrames'
+import { PrizmError } from './errors'
+import { eventCursorStore } from './eventCursor'
+import type { EventCursorStore } from './eventCursor'
+import type { PresenceState } from './presence'
+import { serverConfigToUrl } from './serverUrl'
+
+/** 服务端 WebSocket 路径 */
+const WS_PATH = '/ws'
+/** 建立连接并等到服务端 connected 消息的上限 */
+const CONNECT_TIMEOUT_MS = 10_000
+/** 服务端鉴权失败时使用的关闭码（缺少 Key / Key 无效） */
+const AUTH_CLOSE_CODES = new Set([4001, 4003])
+/** 断线重连的首次等待，之后每次翻倍直到 RECONNECT_MAX_MS */
+const RECONNECT_BASE_MS = 1_000
+const RECONNECT_MAX_MS = 30_000
+/** 连续重连失败达到该次数后放弃，等待用户重新连接 */
+export const MAX_RECONNECT_ATTEMPTS = 10
+/** WebSocket RPC 等待 response 的默认上限 */
+const RPC_TIMEOUT_MS = 15_000
+/** 小于该字节数的消息不压缩，压缩收益抵不上开销 */
+const COMPRESSION_THRESHOLD = 1024
+/**
+ * 按通道流控的窗口（与 @prizm/shared 的 CHANNEL_DEFAULT_WINDOW 一致）：每个通道最多有这么多字节
+ * 在途，处理完过半后归还额度，数据同步积压时通知仍能及时送达
+ */
+const CHANNEL_WINDOW = 1024 * 1024
+/** 订阅的事件名长度上限 */
+const MAX_TOPIC_LENGTH = 128
+
+/** 逻辑通道，与 @prizm/shared 的 RealtimeChannel 一致 */
+export type RealtimeChannel = 'control' | 'notifications' | 'data-sync' | 'logs'
+
+export type RealtimeState = 'disconnected' | 'connecting' | 'connected' | 'reconnecting'
+
+/** 实时连接状态，变化时推送 ws://state */
+export interface RealtimeStatus {
+  state: RealtimeState
+  /** 连接地址（不含 API Key），从未连接时为 null */
+  url: string | null
+  /** 服务端确认的 clientId，仅 connected 时有值 */
+  clientId: string | null
+  connectedAt: number | null
+  /** 最近一次非主动断开的原因 */
+  lastError?: string
+  /** 当前是第几次重连，未在重连时为 0 */
+  attempt: number
+  /** reconnecting 时下次重连的时间 */
+  nextRetryAt: number | null
+  /** 重连后服务端开始重放时错过的事件数，重放完成后归零 */
+  missedEvents: number
+}
+
+/** 服务端推送的消息，与 @prizm/shared 的 ServerToClientMessage 一致 */
+export interface ServerMessage {
+  type: string
+  [key: string]: unknown
+}
+
+/** 实时连接需要的最小 WebSocket 接口，便于测试替换 */
+export interface RealtimeSocket {
+  on(event: 'message', listener: (data: WebSocket.RawData, isBinary: boolean) => void): unknown
+  on(event: 'close', listener: (code: number, reason: Buffer) => void): unknown
+  on(event: 'error', listener: (err: Error) => void): unknown
+  on(event: 'pong', listener: () => void): unknown
+  send(data: string | Buffer): void
+  ping(): void
+  close(code?: number, reason?: string): void
+  terminate(): void
+}
+
+export interface SocketOptions {
+  /** 协商 permessage-deflate 压缩（network.realtime_compression），服务端不支持时不压缩 */
+  compress: boolean
+  /** 校验服务器证书（server.verify_tls），仅 wss 有效 */
+  verifyTls: boolean
+}
+
+export type SocketFactory = (url: string, options: SocketOptions) => RealtimeSocket
+
+function createWebSocket(url: string, options: SocketOptions): RealtimeSocket {
+  return new WebSocket(url, {
+    perMessageDeflate: options.compress && { threshold: COMPRESSION_THRESHOLD },
+    rejectUnauthorized: options.verifyTls
+  })
+}
+
+export type MessageListener = (message: ServerMessage) => void
+
+export type BinaryListener = (frame: BinaryFrame) => void
+
+export type StatusListener = (status: RealtimeStatus) => void
+
+export type RpcMethod = 'GET' | 'POST' | 'PUT' | 'PATCH' | 'DELETE'
+
+interface PendingRpc {
+  resolve: (response: Response) => void
+  reject: (error: PrizmError) => void
+}
+
+/** 把服务端 response 消息还原为 Response，PrizmApi 可按 HTTP 响应同样处理 */
+function toResponse(message: ServerMessage): Response {
+  const status = typeof message.status === 'number' ? message.status : 502
+  if ([204, 205, 304].includes(status)) return new Response(null, { status })
+  const isText = typeof message.body === 'string'
+  return new Response(isText ? (message.body as string) : JSON.stringify(message.body ?? null), {
+    status,
+    headers: { 'content-type': isText ? 'text/plain' : 'application/json' }
+  })
+}
+
+/** 由 ServerConfig 推导的 WebSocket 地址（ws/wss 与 http/https 对应），不含 API Key */
+export function realtimeUrl(config: PrizmConfig): string {
+  return `${serverConfigToUrl(config.server, 'ws')}${WS_PATH}`
+}
+
+/**
+ * 第 attempt 次重连（从 1 开始）前的等待：指数退避并封顶，再在 [一半, 全部] 之间随机，
+ * 避免服务端重启后所有客户端同时重连
+ */
+export function reconnectDelay(attempt: number, random: () => number = Math.random): number {
+  const ceiling = Math.min(RECONNECT_MAX_MS, RECONNECT_BASE_MS * 2 ** Math.max(0, attempt - 1))
+  return Math.round(ceiling / 2 + (random() * ceiling) / 2)
+}
+
+function parseMessage(data: WebSocket.RawData): ServerMessage | null {
+  try {
+    const parsed = JSON.parse(String(data)) as unknown
+    const message = parsed as ServerMessage
+    if (message && typeof message === 'object' && typeof message.type === 'string') return message
+  } catch {
+    // 忽略非 JSON 帧
+  }
+  log.warn('[Realtime] Ignoring malformed message')
+  return null
+}
+
+function rawLength(data: WebSocket.RawData): number {
+  if (Array.isArray(data)) return data.reduce((total, chunk) => total + chunk.length, 0)
+  return Buffer.isBuffer(data) ? data.length : data.byteLength
+}
+
+function normalizeTopic(topic: unknown): string {
+  const eventType = typeof topic === 'string' ? topic.trim() : ''
+  if (!eventType || eventType.length > MAX_TOPIC_LENGTH) {
+    throw PrizmError.invalidInput('Topic must be a non-empty event type')
+  }
+  return eventType
+}
+
+function closeError(code: number, reason: Buffer): PrizmError {
+  const text = reason.toString() || `closed with code ${code}`
+  return AUTH_CLOSE_CODES.has(code)
+    ? PrizmError.auth(`WebSocket rejected: ${text}`)
+    : PrizmError.network(`WebSocket ${text}`)
+}
+
+/**
+ * 与 Prizm 服务端保持的 WebSocket 连接（主进程后台持有），用 API Key 鉴权。
+ * connect / disconnect 由渲染进程命令触发；心跳（network.heartbeat）超时视为断开，
+ * 意外断开后按 reconnectDelay 自动重连并恢复订阅，
+ * 鉴权失败或连续失败 MAX_RECONNECT_ATTEMPTS 次后停止。每次状态变化推送 ws://state
+ */
+export class RealtimeConnection {
+  private socket: RealtimeSocket | null = null
+  private apiKey = ''
+  private compress = true
+  private verifyTls = true
+  private connecting: Promise<RealtimeStatus> | null = null
+  /** 用户希望保持连接（connect 之后、disconnect 之前），意外断开时据此重连 */
+  private wanted = false
+  private attempt = 0
+  private retryTimer: NodeJS.Timeout | undefined
+  private heartbeatTimer: NodeJS.Timeout | undefined
+  /** 已订阅的服务端事件，重连后重新订阅 */
+  private readonly topics = new Set<string>()
+  private readonly listeners = new Set<MessageListener>()
+  private readonly binaryListeners = new Set<BinaryListener>()
+  private readonly statusListeners = new Set<StatusListener>()
+  /** 等待 response 的 RPC 请求，key 为请求 id */
+  private readonly pendingRpc = new Map<string, PendingRpc>()
+  /** 服务端支持逻辑通道时开启流控，记录各通道已处理、尚未归还的字节数 */
+  private flowControl = false
+  private readonly consumed = new Map<RealtimeChannel, number>()
+  /** 当前连接的服务端事件流 id，旧版服务端不支持重放时为 null */
+  private streamId: string | null = null
+  /** 尚未收到的重放事件数及其最大序号 */
+  private missed = 0
+  private replayUntil = 0
+  private current: RealtimeStatus = {
+    state: 'disconnected',
+    url: null,
+    clientId: null,
+    connectedAt: null,
+    attempt: 0,
+    nextRetryAt: null,
+    missedEvents: 0
+  }
+
+  constructor(
+    private readonly createSocket: SocketFactory = createWebSocket,
+    private readonly now: () => number = Date.now,
+    private readonly random: () => number = Math.random,
+    private readonly cursorStore: EventCursorStore = eventCursorStore
+  ) {}
+
+  status(): RealtimeStatus {
+    return { ...this.current }
+  }
+
+  /**
+   * 按当前配置建立连接，收到服务端 connected 消息后返回；已连接时直接返回当前状态，
+   * 连接中的并发调用共用同一次连接，等待重连时立即重试。没有 API Key 时抛出 config 错误；
+   * 其他原因失败时照常抛错，同时在后台继续重连
+   */
+  connect(): Promise<RealtimeStatus> {
+    this.wanted = true
+    if (this.current.state === 'connected') return Promise.resolve(this.status())
+    this.clearRetry()
+    if (!this.connecting) {
+      this.connecting = this.open().finally(() => {
+        this.connecting = null
+      })
+    }
+    return this.connecting
+  }
+
+  /** 主动断开并停止重连，不记录错误 */
+  disconnect(): RealtimeStatus {
+    this.wanted = false
+    this.attempt = 0
+    this.missed = 0
+    this.clearRetry()
+    this.stopHeartbeat()
+    this.failPendingRpc(PrizmError.cancelled('Realtime connection was closed by the client'))
+    const socket = this.socket
+    this.socket = null
+    socket?.close(1000, 'Client disconnect')
+    if (this.current.state !== 'disconnected') {
+      this.setStatus({ ...this.idle(this.current.url), lastError: undefined })
+    }
+    return this.status()
+  }
+
+  /** 断开当前连接并立即重新连接（托盘“重新连接”），重置重连计数 */
+  restart(): Promise<RealtimeStatus> {
+    log.info('[Realtime] Restarting connection')
+    this.disconnect()
+    return this.connect()
+  }
+
+  /** 等待重连时立即重试（如系统从睡眠中恢复），其他状态下忽略 */
+  retryNow(): void {
+    if (this.current.state !== 'reconnecting') return
+    log.info('[Realtime] Retrying connection now')
+    this.reconnect()
+  }
+
+  /**
+   * 订阅服务端事件（与 @prizm/shared 的 EventType 一致）；已连接时立即发送，
+   * 未连接或断线重连后在连接建立时重新订阅。返回当前订阅列表
+   */
+  subscribe(topic: string): string[] {
+    const eventType = normalizeTopic(topic)
+    if (!this.topics.has(eventType)) {
+      this.topics.add(eventType)
+      if (this.current.state === 'connected') this.send({ type: 'register', eventType })
+    }
+    return this.subscriptions()
+  }
+
+  /** 取消订阅；未订阅时忽略。返回当前订阅列表 */
+  unsubscribe(topic: string): string[] {
+    const eventType = normalizeTopic(topic)
+    if (this.topics.delete(eventType) && this.current.state === 'connected') {
+      this.send({ type: 'unregister', eventType })
+    }
+    return this.subscriptions()
+  }
+
+  /** 当前订阅的服务端事件（跨断开、重连保持） */
+  subscriptions(): string[] {
+    return [...this.topics]
+  }
+
+  /** 重连后仍在重放中的错过事件数，用于显示“正在同步”；不在重放时为 0 */
+  getMissedEventCount(): number {
+    return this.missed
+  }
+
+  /** 上报本机在线状态（见 presence.ts）；未连接时返回 false */
+  sendPresence(state: PresenceState, idleSeconds: number): boolean {
+    if (this.current.state !== 'connected') return false
+    this.send({ type: 'presence', state, idleSeconds })
+    return true
+  }
+
+  /** 发送任意客户端消息（如隧道应答）；未连接时返回 false */
+  sendMessage(message: object): boolean {
+    if (this.current.state !== 'connected') return false
+    this.send(message)
+    return true
+  }
+
+  /** 监听服务端推送的每条消息（跨重连保持），返回取消函数 */
+  onMessage(listener: MessageListener): () => void {
+    this.listeners.add(listener)
+    return () => {
+      this.listeners.delete(listener)
+    }
+  }
+
+  /** 监听服务端发来的二进制帧（跨重连保持），返回取消函数 */
+  onBinary(listener: BinaryListener): () => void {
+    this.binaryListeners.add(listener)
+    return () => {
+      this.binaryListeners.delete(listener)
+    }
+  }
+
+  /** 监听连接状态（state 字段）的变化，返回取消函数 */
+  onStatusChange(listener: StatusListener): () => void {
+    this.statusListeners.add(listener)
+    return () => {
+      this.statusListeners.delete(listener)
+    }
+  }
+
+  /**
+   * 以二进制帧发送大数据（文件分块、截图等），按 chunkSize 分块，返回发出的帧数。
+   * 未连接时抛出 network 错误
+   */
+  sendBinary(channel: number, data: Buffer, chunkSize = BINARY_FRAME_CHUNK_SIZE): number {
+    const socket = this.socket
+    if (this.current.state !== 'connected' || !socket) {
+      throw PrizmError.network('Realtime connection is not connected')
+    }
+    const frames = splitBinaryFrames(channel, data, chunkSize)
+    try {
+      for (const frame of frames) socket.send(encodeBinaryFrame(frame))
+    } catch (err) {
+      throw PrizmError.network(
+        `Failed to send binary frame: ${err instanceof Error ? err.message : String(err)}`
+      )
+    }
+    return frames.length
+  }
+
+  /** 当前连接的服务器是否就是 baseUrl（HTTP 地址），PrizmApi 据此决定能否改走 RPC */
+  servesBaseUrl(baseUrl: string): boolean {
+    const url = this.current.url
+    if (this.current.state !== 'connected' || !url) return false
+    const httpUrl = url.replace(/^ws/, 'http').slice(0, -WS_PATH.length)
+    return httpUrl === baseUrl.replace(/\/+$/, '')
+  }
+
+  /**
+   * 通过 WebSocket 调用 HTTP API（服务端以连接的 API Key 转发到同名路由），按 id 匹配
+   * response。未连接时抛出 network 错误，超时抛出 timeout，signal 取消时抛出 cancelled
+   */
+  request(
+    method: RpcMethod,
+    path: string,
+    body?: unknown,
+    signal?: AbortSignal,
+    timeoutMs = RPC_TIMEOUT_MS
+  ): Promise<Response> {
+    if (this.current.state !== 'connected' || !this.socket) {
+      return Promise.reject(PrizmError.network('Realtime connection is not connected'))
+    }
+    if (signal?.aborted) {
+      return Promise.reject(PrizmError.cancelled(`${method} ${path} was cancelled`))
+    }
+    const id = randomUUID()
+    return new Promise((resolve, reject) => {
+      const finish = (): void => {
+        clearTimeout(timer)
+        signal?.removeEventListener('abort', onAbort)
+        this.pendingRpc.delete(id)
+      }
+      const onAbort = (): void => {
+        finish()
+        reject(PrizmError.cancelled(`${method} ${path} was cancelled`))
+      }
+      const timer = setTimeout(() => {
+        finish()
+        reject(PrizmError.timeout(`No response to ${method} ${path} within ${timeoutMs}ms`))
+      }, timeoutMs)
+      signal?.addEventListener('abort', onAbort, { once: true })
+      this.pendingRpc.set(id, {
+        resolve: (response) => {
+          finish()
+          resolve(response)
+        },
+        reject: (error) => {
+          finish()
+          reject(error)
+        }
+      })
+      this.send({ type: 'request', id, method, path, body })
+    })
+  }
+
+  /** 配置变更后服务器地址、API Key、压缩或证书校验设置改变时按新配置重连；未连接时忽略 */
+  reconfigure(config: PrizmConfig): void {
+    if (this.current.state === 'disconnected') return
+    if (
+      realtimeUrl(config) === this.current.url &&
+      config.api_key === this.apiKey &&
+      config.network.realtime_compression === this.compress &&
+      (config.server.verify_tls !== false) === this.verifyTls
+    ) {
+      return
+    }
+    log.info('[Realtime] Connection settings changed, reconnecting')
+    this.disconnect()
+    if (config.api_key) {
+      void this.connect().catch((err) => log.warn('[Realtime] Reconnect failed:', err))
+    }
+  }
+
+  private idle(url: string | null): RealtimeStatus {
+    return {
+      state: 'disconnected',
+      url,
+      clientId: null,
+      connectedAt: null,
+      attempt: 0,
+      nextRetryAt: null,
+      missedEvents: 0
+    }
+  }
+
+  private send(message: object): void {
+    try {
+      this.socket?.send(JSON.stringify(message))
+    } catch (err) {
+      log.warn('[Realtime] Failed to send message:', err)
+    }
+  }
+
+  /**
+   * 连接建立后定时发送 ping；连续 max_missed 次没有 pong 时立即断开，
+   * 不必等 TCP 超时即可进入重连。pong 到达时清零计数
+   */
+  private startHeartbeat(
+    socket: RealtimeSocket,
+    heartbeat: HeartbeatConfig,
+    onDead: () => void
+  ): void {
+    this.stopHeartbeat()
+    if (heartbeat.interval_ms <= 0) return
+    let missed = 0
+    socket.on('pong', () => {
+      missed = 0
+    })
+    this.heartbeatTimer = setInterval(() => {
+      if (this.socket !== socket) {
+        this.stopHeartbeat()
+        return
+      }
+      if (missed >= heartbeat.max_missed) {
+        log.warn(`[Realtime] No pong after ${missed} heartbeats, dropping connection`)
+        this.stopHeartbeat()
+        onDead()
+        return
+      }
+      missed += 1
+      try {
+        socket.ping()
+      } catch (err) {
+        log.warn('[Realtime] Failed to send ping:', err)
+      }
+    }, heartbeat.interval_ms)
+    this.heartbeatTimer.unref?.()
+  }
+
+  private stopHeartbeat(): void {
+    clearInterval(this.heartbeatTimer)
+    this.heartbeatTimer = undefined
+  }
+
+  private clearRetry(): void {
+    clearTimeout(this.retryTimer)
+    this.retryTimer = undefined
+  }
+
+  private reconnect(): void {
+    this.clearRetry()
+    // 失败由 close 处理并安排下一次重连
+    void this.connect().catch(() => undefined)
+  }
+
+  /** 意外断开后安排下一次重连；鉴权失败或次数用尽时停止 */
+  private handleDrop(url: string, error: PrizmError): void {
+    this.missed = 0
+    if (!this.wanted || error.kind === 'auth' || this.attempt >= MAX_RECONNECT_ATTEMPTS) {
+      const gaveUp = this.wanted && error.kind !== 'auth'
+      if (gaveUp) log.warn(`[Realtime] Giving up after ${this.attempt} reconnect attempts`)
+      this.wanted = false
+      this.attempt = 0
+      this.setStatus({ ...this.idle(url), lastError: error.message })
+      return
+    }
+    this.attempt += 1
+    const delay = reconnectDelay(this.attempt, this.random)
+    log.info(`[Realtime] Reconnecting to ${url} in ${delay}ms (attempt ${this.attempt})`)
+    this.setStatus({
+      state: 'reconnecting',
+      url,
+      clientId: null,
+      connectedAt: null,
+      lastError: error.message,
+      attempt: this.attempt,
+      nextRetryAt: this.now() + delay,
+      missedEvents: 0
+    })
+    this.retryTimer = setTimeout(() => this.reconnect(), delay)
+    this.retryTimer.unref?.()
+  }
+
+  private async open(): Promise<RealtimeStatus> {
+    const config = await loadConfigFromDisk()
+    if (!config.api_key) {
+      const error = PrizmError.config('No API key configured, register the client first')
+      if (this.current.state !== 'disconnected') {
+        this.wanted = false
+        this.attempt = 0
+        this.setStatus({ ...this.idle(this.current.url), lastError: error.message })
+      }
+      throw error
+    }
+    const url = realtimeUrl(config)
+    this.apiKey = config.api_key
+    this.compress = config.network.realtime_compression
+    this.verifyTls = config.server.verify_tls !== false
+    this.setStatus({
+      state: 'connecting',
+      url,
+      clientId: null,
+      connectedAt: null,
+      lastError: this.current.lastError,
+      attempt: this.attempt,
+      nextRetryAt: null,
+      missedEvents: 0
+    })
+    log.info(`[Realtime] Connecting to ${url}`)
+    const socket = this.createSocket(`${url}?apiKey=${encodeURIComponent(config.api_key)}`, {
+      compress: this.compress,
+      verifyTls: this.verifyTls
+    })
+    this.socket = socket
+
+    return new Promise((resolve, reject) => {
+      let settled = false
+      let timedOut = false
+      let dead = false
+      const timer = setTimeout(() => {
+        timedOut = true
+        socket.terminate()
+      }, CONNECT_TIMEOUT_MS)
+
+      socket.on('message', (data, isBinary) => {
+        if (isBinary) {
+          if (this.socket === socket) {
+            this.dispatchBinary(data)
+            this.consume('data-sync', rawLength(data))
+          }
+          return
+        }
+        const message = parseMessage(data)
+        if (!message || this.socket !== socket) return
+        if (message.type === 'connected' && !settled) {
+          settled = true
+          clearTimeout(timer)
+          this.attempt = 0
+          this.setStatus({
+            state: 'connected',
+            url,
+            clientId: typeof message.clientId === 'string' ? message.clientId : null,
+            connectedAt: this.now(),
+            attempt: 0,
+            nextRetryAt: null,
+            missedEvents: 0
+          })
+          log.info(`[Realtime] Connected to ${url}`)
+          this.startHeartbeat(socket, config.network.heartbeat, () => {
+            dead = true
+            socket.terminate()
+          })
+          // 先开启流控，随后的重放事件也按通道限流
+          this.consumed.clear()
+          this.flowControl = Array.isArray(message.channels)
+          if (this.flowControl) this.send({ type: 'flow_control', window: CHANNEL_WINDOW })
+          for (const topic of this.topics) this.send({ type: 'register', eventType: topic })
+          this.resume(url, message)
+          resolve(this.status())
+        } else if (message.type === 'response' && typeof message.id === 'string') {
+          this.pendingRpc.get(message.id)?.resolve(toResponse(message))
+        } else if (message.type === 'replay') {
+          this.startReplay(message)
+        } else if (message.type === 'event' && typeof message.id === 'number') {
+          this.trackEvent(url, message.id)
+        } else if (message.type === 'error') {
+          log.warn(`[Realtime] Server error ${String(message.code)}: ${String(message.message)}`)
+        }
+        // 在更新状态之后分发，监听者收到 connected 时连接已可用
+        for (const listener of this.listeners) {
+          try {
+            listener(message)
+          } catch (err) {
+            log.warn('[Realtime] Message listener failed:', err)
+          }
+        }
+        if (typeof message.channel === 'string') {
+          this.consume(message.channel as RealtimeChannel, rawLength(data))
+        }
+      })
+
+      socket.on('error', (err) => {
+        // 随后总会触发 close，在那里更新状态
+        log.warn('[Realtime] Socket error:', err.message)
+      })
+
+      socket.on('close', (code, reason) => {
+        clearTimeout(timer)
+        const active = this.socket === socket
+        const error = !active
+          ? PrizmError.cancelled('WebSocket connection was closed by the client')
+          : timedOut
+            ? PrizmError.timeout(`No response from ${url} within ${CONNECT_TIMEOUT_MS}ms`)
+            : dead
+              ? PrizmError.timeout('Server stopped answering heartbeats')
+              : closeError(code, reason)
+        if (active) {
+          this.socket = null
+          this.stopHeartbeat()
+          this.failPendingRpc(
+            PrizmError.network(`Connection lost before response: ${error.message}`)
+          )
+          log.warn(`[Realtime] Disconnected from ${url}: ${error.message}`)
+          this.handleDrop(url, error)
+        }
+        if (!settled) {
+          settled = true
+          reject(error)
+        }
+      })
+    })
+  }
+
+  /**
+   * 连接建立（且已重新订阅）后，游标属于同一服务器和事件流时请求重放错过的事件；
+   * 否则以服务端当前最新序号作为新游标
+   */
+  private resume(url: string, connected: ServerMessage): void {
+    this.missed = 0
+    this.streamId = typeof connected.streamId === 'string' ? connected.streamId : null
+    if (!this.streamId) return
+    const head = typeof connected.lastEventId === 'number' ? connected.lastEventId : 0
+    const saved = this.cursorStore.load()
+    if (saved && saved.url === url && saved.streamId === this.streamId) {
+      if (saved.lastEventId < head) this.send({ type: 'resume', lastEventId: saved.lastEventId })
+      return
+    }
+    this.cursorStore.save({ url, streamId: this.streamId, lastEventId: head })
+  }
+
+  private startReplay(message: ServerMessage): void {
+    const count = typeof message.count === 'number' ? message.count : 0
+    this.replayUntil = typeof message.untilId === 'number' ? message.untilId : 0
+    if (message.truncated === true) {
+      log.warn('[Realtime] Some missed events are no longer available on the server')
+    }
+    log.info(`[Realtime] Catching up on ${count} missed events`)
+    this.missed = count
+    if (count > 0) this.setStatus({ ...this.current, missedEvents: count })
+  }
+
+  private trackEvent(url: string, id: number): void {
+    if (this.streamId) this.cursorStore.save({ url, streamId: this.streamId, lastEventId: id })
+    if (this.missed > 0 && id <= this.replayUntil) {
+      this.missed -= 1
+      if (this.missed === 0) this.setStatus({ ...this.current, missedEvents: 0 })
+    }
+  }
+
+  private failPendingRpc(error: PrizmError): void {
+    for (const pending of [...this.pendingRpc.values()]) pending.reject(error)
+  }
+
+  /** 记录已处理的字节数，累计过半窗口时向服务端归还该通道的额度 */
+  private consume(channel: RealtimeChannel, bytes: number): void {
+    if (!this.flowControl || channel === 'control') return
+    const total = (this.consumed.get(channel) ?? 0) + bytes
+    if (total < CHANNEL_WINDOW / 2) {
+      this.consumed.set(channel, total)
+      return
+    }
+    this.consumed.set(channel, 0)
+    this.send({ type: 'channel_window', channel, credit: total })
+  }
+
+  private dispatchBinary(data: WebSocket.RawData): void {
+    const bytes = Buffer.isBuffer(data)
+      ? data
+      : Array.isArray(data)
+        ? Buffer.concat(data)
+        : Buffer.from(data)
+    const frame = decodeBinaryFrame(bytes)
+    if (!frame) {
+      log.warn('[Realtime] Ignoring malformed binary frame')
+      return
+    }
+    for (const listener of this.binaryListeners) {
+      try {
+        listener(frame)
+      } catch (err) {
+        log.warn('[Realtime] Binary listener failed:', err)
+      }
+    }
+  }
+
+  private setStatus(status: RealtimeStatus): void {
+    const changed = status.state !== this.current.state
+    this.current = status
+    if (changed) {
+      for (const listener of this.statusListeners) {
+        try {
+          listener(this.status())
+        } catch (err) {
+          log.warn('[Realtime] Status listener failed:', err)
+        }
+      }
+    }
+    const win = sharedState.mainWindow
+    if (win && !win.isDestroyed()) {
+      win.webContents.send('ws://state', this.status())
+    }
+  }
+}
+
+export const realtimeConnection = new RealtimeConnection()
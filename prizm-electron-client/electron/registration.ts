@@ -0,0 +1,389 @@
+import * as fs from 'fs'
+import * as path from 'path'
+import log from 'electron-log/main'
+import { detectCapabilities } from './clientCapabilities'
+import { getConfigPath, sharedState, updateConfig } from './config'
+import { requestConsent } from './consent'
+import { PrizmError } from './errors'
+import { writeFileAtomic } from './fsUtils'
+import { sleep } from './httpClient'
+import { upsertIdentity } from './identities'
+import { PrizmApi, grantedScopesOf, isPendingApproval } from './prizmApi'
+import type { RegisterResponse, RegistrationApprovalStatus } from './prizmApi'
+import { upsertActiveProfile } from './profiles'
+import { decryptSecret, encryptSecret } from './secretCrypto'
+import { probeHealth } from './serverHealth'
+import { showLocalNotification } from './serverNotifications'
+import { parseServerUrl, serverConfigToUrl } from './serverUrl'
+import { applyTokenExpiry } from './tokenRefresher'
+import type { TokenExpiry } from './tokenRefresher'
+
+const PENDING_FILE = 'pending-registration.json'
+/** 等待管理员批准时轮询申请状态的间隔 */
+const APPROVAL_POLL_INTERVAL_MS = 3_000
+/** 服务端未返回申请过期时间时的等待上限 */
+const DEFAULT_APPROVAL_WAIT_MS = 30 * 60 * 1000
+
+/**
+ * 注册进度：requested（已记录意图）→ awaiting_approval（服务端要求人工批准，仅此时出现）→
+ * created（服务端已创建客户端）→ verified（新 Key 已通过鉴权）→ saved（已写入配置，进度文件随之删除）
+ */
+export type RegistrationStep =
+  | 'requested'
+  | 'awaiting_approval'
+  | 'created'
+  | 'verified'
+  | 'saved'
+
+export interface RegistrationRequest {
+  serverUrl: string
+  name: string
+  requestedScopes: string[]
+  profileName?: string
+  /** 保存为该名称的身份（见 identities.ts），不传时当前配置不再关联任何身份 */
+  identity?: string
+  /** 无人值守的自动注册：授予敏感 scope 前须用户确认（见 consent.ts） */
+  unattended?: boolean
+}
+
+interface PendingRegistration extends RegistrationRequest {
+  step: RegistrationStep
+  /** encryptSecret 加密后的审批申请 ID（凭它可领取 API Key） */
+  approvalId?: string
+  approvalExpiresAt?: number
+  clientId?: string
+  /** encryptSecret 加密后的 API Key */
+  apiKey?: string
+  grantedScopes?: string[]
+  expiresAt?: number
+  /** encryptSecret 加密后的刷新令牌 */
+  refreshToken?: string
+  updatedAt: number
+}
+
+/** 暴露给渲染进程的注册进度（不含 API Key） */
+export interface RegistrationStatus extends RegistrationRequest {
+  step: RegistrationStep
+  /** 等待批准时申请的过期时间 */
+  approvalExpiresAt?: number
+  clientId?: string
+  updatedAt: number
+}
+
+export interface RegistrationResult extends TokenExpiry {
+  clientId: string
+  apiKey: string
+  grantedScopes: string[]
+}
+
+/** 返回给渲染进程的注册结果，不含 API Key 与刷新令牌 */
+export interface RegisteredClient {
+  clientId: string
+  grantedScopes: string[]
+  expiresAt?: number
+}
+
+export function toRegisteredClient(result: RegistrationResult): RegisteredClient {
+  return {
+    clientId: result.clientId,
+    grantedScopes: result.grantedScopes,
+    expiresAt: result.expiresAt
+  }
+}
+
+/** 推送给渲染进程的审批进度（auth://registration-status） */
+export interface RegistrationStatusEvent {
+  serverUrl: string
+  name: string
+  status: RegistrationApprovalStatus
+  expiresAt?: number
+}
+
+function emitRegistrationStatus(event: RegistrationStatusEvent): void {
+  const win = sharedState.mainWindow
+  if (win && !win.isDestroyed()) {
+    win.webContents.send('auth://registration-status', event)
+  }
+}
+
+/** 注册完成（registration://complete） */
+export interface RegistrationCompleteEvent {
+  clientId: string
+  serverUrl: string
+  name: string
+}
+
+/**
+ * 推送 registration://complete 并弹出系统通知，后台启动（如自动注册）时也有可见的确认
+ */
+function announceRegistered(event: RegistrationCompleteEvent): void {
+  const win = sharedState.mainWindow
+  if (win && !win.isDestroyed()) {
+    win.webContents.send('registration://complete', event)
+  }
+  showLocalNotification({
+    title: '客户端注册完成',
+    body: `已在 ${event.serverUrl} 注册为 ${event.clientId}`,
+    category: 'connection',
+    severity: 'info'
+  })
+}
+
+function pendingPath(): string {
+  return path.join(getConfigPath().configDir, PENDING_FILE)
+}
+
+async function readPending(): Promise<PendingRegistration | null> {
+  try {
+    const parsed = JSON.parse(await fs.promises.readFile(pendingPath(), 'utf-8')) as unknown
+    const pending = parsed as PendingRegistration
+    if (!pending || typeof pending.serverUrl !== 'string' || typeof pending.name !== 'string') {
+      log.warn('[Register] Ignoring malformed pending registration')
+      return null
+    }
+    if (!Array.isArray(pending.requestedScopes)) pending.requestedScopes = []
+    return pending
+  } catch (err) {
+    if ((err as NodeJS.ErrnoException).code !== 'ENOENT') {
+      log.warn('[Register] Failed to read pending registration:', err)
+    }
+    return null
+  }
+}
+
+async function writePending(pending: PendingRegistration): Promise<void> {
+  pending.updatedAt = Date.now()
+  await writeFileAtomic(pendingPath(), JSON.stringify(pending, null, 2), { mode: 0o600 })
+}
+
+async function clearPending(): Promise<void> {
+  await fs.promises.rm(pendingPath(), { force: true })
+}
+
+/** 当前未完成的注册进度，无则返回 null */
+export async function getPendingRegistration(): Promise<RegistrationStatus | null> {
+  const pending = await readPending()
+  if (!pending) return null
+  return {
+    serverUrl: pending.serverUrl,
+    name: pending.name,
+    requestedScopes: pending.requestedScopes,
+    profileName: pending.profileName,
+    identity: pending.identity,
+    unattended: pending.unattended,
+    step: pending.step,
+    approvalExpiresAt: pending.approvalExpiresAt,
+    clientId: pending.clientId,
+    updatedAt: pending.updatedAt
+  }
+}
+
+/**
+ * 把注册得到的凭据写入配置（服务器地址、client.name、api_key、scope，以及身份与档案）
+ */
+export async function saveRegistration(
+  request: RegistrationRequest,
+  result: RegistrationResult
+): Promise<void> {
+  const parsed = parseServerUrl(request.serverUrl)
+  await updateConfig((config) => {
+    config.server = { ...config.server, ...parsed, is_dev: true }
+    config.client.name = result.clientId
+    config.client.registration_name = request.name
+    if (request.requestedScopes.length > 0) {
+      config.client.requested_scopes = [...request.requestedScopes]
+    }
+    config.client.granted_scopes = [...result.grantedScopes]
+    config.api_key = result.apiKey
+    applyTokenExpiry(config, result)
+    if (request.identity) {
+      upsertIdentity(config, request.identity)
+    } else {
+      delete config.active_identity
+    }
+    upsertActiveProfile(config, request.profileName)
+  })
+}
+
+/** 记录服务端创建的客户端（Key 与刷新令牌加密保存） */
+function recordCreated(pending: PendingRegistration, register: RegisterResponse): void {
+  if (!register.apiKey) {
+    throw PrizmError.parse('Registration response is missing apiKey')
+  }
+  pending.step = 'created'
+  pending.clientId = register.clientId || pending.name
+  pending.apiKey = encryptSecret(register.apiKey)
+  pending.grantedScopes = grantedScopesOf(register, pending.requestedScopes)
+  pending.expiresAt = register.expiresAt
+  pending.refreshToken = register.refreshToken ? encryptSecret(register.refreshToken) : undefined
+  delete pending.approvalId
+  delete pending.approvalExpiresAt
+}
+
+/**
+ * 轮询审批申请直到批准，推送 auth://registration-status；被拒或过期时回到 requested 并抛出 auth 错误
+ */
+async function waitForApproval(
+  api: PrizmApi,
+  serverUrl: string,
+  pending: PendingRegistration,
+  signal?: AbortSignal
+): Promise<RegisterResponse> {
+  const requestId = decryptSecret(pending.approvalId ?? '')
+  const deadline = pending.approvalExpiresAt ?? Date.now() + DEFAULT_APPROVAL_WAIT_MS
+  const emit = (status: RegistrationApprovalStatus): void =>
+    emitRegistrationStatus({ serverUrl, name: pending.name, status, expiresAt: deadline })
+  emit('pending')
+  log.info(`[Register] Waiting for "${pending.name}" to be approved on ${serverUrl}`)
+
+  let status: RegistrationApprovalStatus = 'pending'
+  while (status === 'pending') {
+    if (!requestId || Date.now() >= deadline) {
+      status = 'expired'
+      break
+    }
+    await sleep(APPROVAL_POLL_INTERVAL_MS, signal)
+    try {
+      const response = await api.getRegistrationRequest(requestId, signal)
+      status = response.status
+      if (status === 'approved') {
+        if (!response.result) {
+          throw PrizmError.parse('Approved registration is missing its result')
+        }
+        emit('approved')
+        return response.result
+      }
+    } catch (err) {
+      // 申请已被清理（过期后保留期已过，或结果已被领取）
+      if (err instanceof PrizmError && err.status === 404) status = 'expired'
+      else throw err
+    }
+  }
+
+  emit(status)
+  pending.step = 'requested'
+  delete pending.approvalId
+  delete pending.approvalExpiresAt
+  await writePending(pending)
+  throw PrizmError.auth(
+    status === 'denied' ? 'Registration was denied' : 'Registration expired before it was approved'
+  )
+}
+
+/**
+ * 从当前步骤推进到 saved，每完成一步都先落盘，中途失败后可从该步继续
+ */
+async function advance(
+  pending: PendingRegistration,
+  signal?: AbortSignal
+): Promise<RegistrationResult> {
+  const serverUrl = serverConfigToUrl(parseServerUrl(pending.serverUrl))
+  const api = new PrizmApi(serverUrl)
+
+  if (pending.step === 'requested') {
+    const health = await probeHealth(serverUrl, signal)
+    if (health.status !== 'ok') {
+      throw new PrizmError('bad_status', 'Server health check failed')
+    }
+    const register = await api.register(
+      pending.name,
+      pending.requestedScopes,
+      signal,
+      detectCapabilities()
+    )
+    if (isPendingApproval(register)) {
+      // 先保存申请 ID，中断后继续时接着等待而不是重复申请
+      pending.step = 'awaiting_approval'
+      pending.approvalId = encryptSecret(register.requestId)
+      pending.approvalExpiresAt = register.expiresAt
+    } else {
+      recordCreated(pending, register)
+    }
+    await writePending(pending)
+  }
+
+  if (pending.step === 'awaiting_approval') {
+    recordCreated(pending, await waitForApproval(api, serverUrl, pending, signal))
+    await writePending(pending)
+  }
+
+  const apiKey = decryptSecret(pending.apiKey ?? '')
+  const clientId = pending.clientId || pending.name
+  const grantedScopes = pending.grantedScopes ?? [...pending.requestedScopes]
+  const expiry: TokenExpiry = {
+    expiresAt: pending.expiresAt,
+    refreshToken: pending.refreshToken ? decryptSecret(pending.refreshToken) : undefined
+  }
+
+  if (pending.step === 'created') {
+    if (!(await api.verifyApiKey(apiKey, signal))) {
+      // Key 已失效（如被重新生成），下次继续时重新注册
+      pending.step = 'requested'
+      delete pending.apiKey
+      delete pending.refreshToken
+      await writePending(pending)
+      throw PrizmError.auth('API key from registration was rejected by the server')
+    }
+    pending.step = 'verified'
+    await writePending(pending)
+  }
+
+  if (pending.step === 'verified') {
+    if (
+      pending.unattended &&
+      !(await requestConsent(
+        { serverUrl, reason: 'auto_register', granted: grantedScopes },
+        signal
+      ))
+    ) {
+      await clearPending()
+      throw PrizmError.auth('Granting sensitive scopes was not allowed')
+    }
+    await saveRegistration(pending, { clientId, apiKey, grantedScopes, ...expiry })
+  }
+
+  // saved：配置已写入，进度文件可以删除（删除前崩溃时再次继续只会重复写入相同配置）
+  await clearPending()
+  log.info(`[Register] Registered "${clientId}" on ${serverUrl}`)
+  announceRegistered({ clientId, serverUrl, name: pending.name })
+  return { clientId, apiKey, grantedScopes, ...expiry }
+}
+
+/**
+ * 开始新的注册：先记录意图再逐步推进。会覆盖之前未完成的注册
+ */
+export async function startRegistration(
+  request: RegistrationRequest,
+  signal?: AbortSignal
+): Promise<RegistrationResult> {
+  // 先校验地址，无效时不记录也不发起请求
+  parseServerUrl(request.serverUrl)
+  const previous = await readPending()
+  if (previous && previous.step !== 'requested') {
+    log.warn(
+      `[Register] Discarding unfinished registration of "${previous.name}" (${previous.step})`
+    )
+  }
+  const pending: PendingRegistration = {
+    serverUrl: request.serverUrl,
+    name: request.name,
+    requestedScopes: [...request.requestedScopes],
+    profileName: request.profileName,
+    identity: request.identity,
+    unattended: request.unattended,
+    step: 'requested',
+    updatedAt: Date.now()
+  }
+  await writePending(pending)
+  return advance(pending, signal)
+}
+
+/**
+ * 从上次中断的步骤继续注册；没有未完成的注册时返回 null
+ */
+export async function resumeRegistration(signal?: AbortSignal): Promise<RegistrationResult | null> {
+  const pending = await readPending()
+  if (!pending) return null
+  log.info(`[Register] Resuming registration of "${pending.name}" from step ${pending.step}`)
+  return advance(pending, signal)
+}
@@ -0,0 +1,67 @@
+import { session } from 'electron'
+import type { Session } from 'electron'
+import type { PrizmConfig } from './config'
+import { serverConfigToUrl } from './serverUrl'
+
+/**
+ * 渲染进程不持有 API Key：应用窗口（默认会话）发往当前服务器的 HTTP 与 WebSocket 请求
+ * 由主进程在请求头中附加 Authorization。内嵌管理面板使用独立会话，不受影响
+ */
+
+let apiKey = ''
+/** 当前服务器的 http(s) 与 ws(s) 源 */
+let serverOrigins: string[] = []
+
+/** 同步当前服务器与 API Key（启动时与配置变更时调用） */
+export function applyRendererAuth(config: PrizmConfig): void {
+  apiKey = config.api_key
+  serverOrigins = config.server.host
+    ? [serverConfigToUrl(config.server), serverConfigToUrl(config.server, 'ws')].map(
+        (url) => new URL(url).origin
+      )
+    : []
+}
+
+function isServerUrl(url: string): boolean {
+  try {
+    return serverOrigins.includes(new URL(url).origin)
+  } catch {
+    return false
+  }
+}
+
+function hasCredentials(headers: Record<string, string>): boolean {
+  return Object.keys(headers).some((name) => {
+    const lower = name.toLowerCase()
+    return lower === 'authorization' || lower === 'x-prizm-api-key'
+  })
+}
+
+/**
+ * 需要时附加 Authorization：仅限渲染进程（有 webContentsId）发往当前服务器、且未自带凭据的请求
+ */
+export function withRendererCredentials(
+  url: string,
+  headers: Record<string, string>,
+  webContentsId: number | undefined
+): Record<string, string> {
+  if (!apiKey || webContentsId === undefined || !isServerUrl(url) || hasCredentials(headers)) {
+    return headers
+  }
+  return { ...headers, Authorization: `Bearer ${apiKey}` }
+}
+
+export function installRendererAuth(target: Session = session.defaultSession): void {
+  target.webRequest.onBeforeSendHeaders(
+    { urls: ['http://*/*', 'https://*/*', 'ws://*/*', 'wss://*/*'] },
+    (details, callback) => {
+      callback({
+        requestHeaders: withRendererCredentials(
+          details.url,
+          details.requestHeaders,
+          details.webContentsId
+        )
+      })
+    }
+  )
+}
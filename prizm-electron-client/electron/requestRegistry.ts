@@ -0,0 +1,36 @@
+import { PrizmError } from './errors'
+
+/** 进行中的可取消操作：request_id -> AbortController */
+const inflight = new Map<string, AbortController>()
+
+/**
+ * 以 requestId 登记一个可取消的操作，fn 收到的 signal 在 cancelRequest(requestId) 时中止；
+ * 未提供 requestId 时不登记，直接执行
+ */
+export async function runCancellable<T>(
+  requestId: string | undefined,
+  fn: (signal?: AbortSignal) => Promise<T>
+): Promise<T> {
+  if (!requestId) return fn()
+  if (inflight.has(requestId)) {
+    throw PrizmError.invalidInput(`Request id already in use: ${requestId}`)
+  }
+  const controller = new AbortController()
+  inflight.set(requestId, controller)
+  try {
+    return await fn(controller.signal)
+  } finally {
+    if (inflight.get(requestId) === controller) inflight.delete(requestId)
+  }
+}
+
+/**
+ * 中止进行中的操作，返回是否找到该请求
+ */
+export function cancelRequest(requestId: string): boolean {
+  const controller = inflight.get(requestId)
+  if (!controller) return false
+  inflight.delete(requestId)
+  controller.abort(PrizmError.cancelled(`Request ${requestId} was cancelled`))
+  return true
+}
@@ -0,0 +1,70 @@
+import * as crypto from 'crypto'
+
+/**
+ * 请求签名（network.sign_requests）：注册后不再发送原始 API Key，
+ * 改为用由 API Key 派生的签名密钥对请求做 HMAC-SHA256。签名密钥与服务端 clients.json 中的
+ * Key 哈希无关，服务端另行加密保存。
+ * 派生方式与签名串格式须与服务端 prizm/src/auth/requestSigning.ts 保持一致
+ */
+export const SIGNATURE_HEADER = 'X-Prizm-Signature'
+export const TIMESTAMP_HEADER = 'X-Prizm-Timestamp'
+export const NONCE_HEADER = 'X-Prizm-Nonce'
+
+export interface SignableRequest {
+  method: string
+  /** 服务端看到的路径与查询串（不含 server.base_path 前缀） */
+  path: string
+  /** 已序列化的请求体，无请求体时为空串 */
+  body: string
+  timestamp: number
+  nonce: string
+}
+
+const SIGNING_SECRET_INFO = 'prizm-request-signing'
+
+function sha256Hex(value: string): string {
+  return crypto.createHash('sha256').update(value, 'utf8').digest('hex')
+}
+
+/**
+ * 签名串：METHOD \n path \n timestamp \n nonce \n sha256(body)
+ */
+export function canonicalRequest(req: SignableRequest): string {
+  return [req.method.toUpperCase(), req.path, req.timestamp, req.nonce, sha256Hex(req.body)].join(
+    '\n'
+  )
+}
+
+/**
+ * 签名密钥（hex）：HKDF-SHA256(ikm = apiKey, salt = clientId)
+ */
+export function deriveSigningSecret(apiKey: string, clientId: string): string {
+  const secret = crypto.hkdfSync('sha256', apiKey, clientId, SIGNING_SECRET_INFO, 32)
+  return Buffer.from(secret).toString('hex')
+}
+
+/**
+ * 以签名密钥计算签名（hex）
+ */
+export function signRequest(signingSecret: string, req: SignableRequest): string {
+  return crypto
+    .createHmac('sha256', signingSecret)
+    .update(canonicalRequest(req), 'utf8')
+    .digest('hex')
+}
+
+/**
+ * 生成签名请求头
+ */
+export function signatureHeaders(
+  signingSecret: string,
+  req: Omit<SignableRequest, 'timestamp' | 'nonce'>,
+  now: number = Date.now()
+): Record<string, string> {
+  const signable: SignableRequest = { ...req, timestamp: now, nonce: crypto.randomUUID() }
+  return {
+    [TIMESTAMP_HEADER]: String(signable.timestamp),
+    [NONCE_HEADER]: signable.nonce,
+    [SIGNATURE_HEADER]: signRequest(signingSecret, signable)
+  }
+}
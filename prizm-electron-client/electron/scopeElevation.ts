@@ -0,0 +1,104 @@
+import log from 'electron-log/main'
+import { loadConfigFromDisk, sharedState, updateConfig } from './config'
+import { requestConsent } from './consent'
+import { PrizmError } from './errors'
+import { sleep } from './httpClient'
+import { upsertIdentity } from './identities'
+import { PrizmApi } from './prizmApi'
+import type { ScopeRequestStatus } from './prizmApi'
+import { upsertActiveProfile } from './profiles'
+import { serverConfigToUrl } from './serverUrl'
+
+/** 轮询申请状态的间隔 */
+const POLL_INTERVAL_MS = 3_000
+/** 服务端未返回过期时间时的等待上限 */
+const DEFAULT_WAIT_MS = 10 * 60 * 1000
+
+/** 推送给渲染进程的申请进度 */
+export interface ScopeRequestEvent {
+  requestId?: string
+  status: ScopeRequestStatus
+  scopes: string[]
+}
+
+function emitScopeRequest(event: ScopeRequestEvent): void {
+  const win = sharedState.mainWindow
+  if (win && !win.isDestroyed()) {
+    win.webContents.send('auth://scope-request', event)
+  }
+}
+
+async function saveGrantedScopes(requested: string[], granted: string[]): Promise<void> {
+  await updateConfig((config) => {
+    config.client.granted_scopes = [...granted]
+    config.client.requested_scopes = [...new Set([...config.client.requested_scopes, ...requested])]
+    if (config.active_identity) upsertIdentity(config, config.active_identity)
+    if (config.active_profile) upsertActiveProfile(config)
+  })
+}
+
+/**
+ * 为当前客户端申请追加 scope：提交申请后推送 auth://scope-request 并轮询，
+ * 管理员批准后更新 client.granted_scopes 并返回全部 scope；被拒或过期时抛出 auth 错误。可通过 signal 取消
+ */
+export async function requestAdditionalScopes(
+  scopes: string[],
+  options: { requestId?: string; signal?: AbortSignal } = {}
+): Promise<string[]> {
+  const { requestId, signal } = options
+  const requested = [
+    ...new Set(scopes.filter((s) => typeof s === 'string').map((s) => s.trim()))
+  ].filter(Boolean)
+  if (requested.length === 0) {
+    throw PrizmError.invalidInput('No scopes requested')
+  }
+  const config = await loadConfigFromDisk()
+  const serverUrl = serverConfigToUrl(config.server)
+  const api = new PrizmApi(serverUrl)
+
+  let response = await api.requestScopes(requested, signal)
+  const deadline = response.expiresAt ?? Date.now() + DEFAULT_WAIT_MS
+  emitScopeRequest({ requestId, status: response.status, scopes: requested })
+  if (response.status === 'pending' && response.requestId) {
+    log.info(`[Auth] Waiting for approval of scopes ${requested.join(', ')} on ${serverUrl}`)
+  }
+
+  while (response.status === 'pending') {
+    if (!response.requestId || Date.now() >= deadline) {
+      response = { ...response, status: 'expired' }
+      emitScopeRequest({ requestId, status: 'expired', scopes: requested })
+      break
+    }
+    await sleep(POLL_INTERVAL_MS, signal)
+    const next = await api.getScopeRequest(response.requestId, signal)
+    if (next.status !== 'pending') {
+      emitScopeRequest({ requestId, status: next.status, scopes: requested })
+    }
+    response = { ...next, requestId: next.requestId ?? response.requestId }
+  }
+
+  if (response.status === 'denied') {
+    throw PrizmError.auth('Scope request was denied')
+  }
+  if (response.status === 'expired') {
+    throw PrizmError.auth('Scope request expired before it was approved')
+  }
+  const granted =
+    response.grantedScopes ?? [...new Set([...config.client.granted_scopes, ...requested])]
+  // 服务端授予了未申请的敏感 scope 时先征得用户同意
+  const allowed = await requestConsent(
+    {
+      serverUrl,
+      reason: 'scope_change',
+      granted,
+      previous: [...config.client.granted_scopes, ...requested]
+    },
+    signal
+  )
+  if (!allowed) {
+    throw PrizmError.auth('Granting sensitive scopes was not allowed')
+  }
+  await saveGrantedScopes(requested, granted)
+  log.info(`[Auth] Scopes granted on ${serverUrl}: ${granted.join(', ')}`)
+  return granted
+}
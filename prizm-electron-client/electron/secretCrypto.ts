@@ -0,0 +1,86 @@
+import * as crypto from 'crypto'
+import * as os from 'os'
+import * as fs from 'fs'
+import * as path from 'path'
+
+/** 密文前缀，用于区分明文与加密值 */
+const ENCRYPTED_PREFIX = 'enc:v1:'
+const IV_LENGTH = 12
+const TAG_LENGTH = 16
+
+let cachedKey: Buffer | null = null
+let keyFilePath: string | null = null
+
+/**
+ * 改用文件中的随机密钥（不存在则生成），用于需要随数据目录迁移的便携模式
+ */
+export function useSecretKeyFile(filePath: string): void {
+  if (keyFilePath === filePath) return
+  keyFilePath = filePath
+  cachedKey = null
+}
+
+function loadOrCreateKeyFile(filePath: string): Buffer {
+  try {
+    const key = Buffer.from(fs.readFileSync(filePath, 'utf-8').trim(), 'base64')
+    if (key.length === 32) return key
+  } catch {
+    // 不存在时生成
+  }
+  const key = crypto.randomBytes(32)
+  fs.mkdirSync(path.dirname(filePath), { recursive: true })
+  fs.writeFileSync(filePath, key.toString('base64'), { encoding: 'utf-8', mode: 0o600 })
+  return key
+}
+
+/**
+ * 机器绑定密钥：由主机名、用户名与用户目录派生，配置文件拷贝到其他机器后无法解密。
+ * 调用过 useSecretKeyFile 时改用密钥文件。
+ */
+function getMachineKey(): Buffer {
+  if (!cachedKey && keyFilePath) {
+    cachedKey = loadOrCreateKeyFile(keyFilePath)
+  }
+  if (!cachedKey) {
+    const { username, homedir } = os.userInfo()
+    const material = [os.hostname(), username, homedir, os.platform()].join('|')
+    cachedKey = crypto.scryptSync(material, 'prizm-client/api-key', 32)
+  }
+  return cachedKey
+}
+
+/**
+ * 判断值是否为本模块生成的密文
+ */
+export function isEncryptedSecret(value: string): boolean {
+  return value.startsWith(ENCRYPTED_PREFIX)
+}
+
+/**
+ * AES-256-GCM 加密，输出 enc:v1:<base64(iv | tag | ciphertext)>
+ */
+export function encryptSecret(plain: string): string {
+  if (!plain || isEncryptedSecret(plain)) return plain
+  const iv = crypto.randomBytes(IV_LENGTH)
+  const cipher = crypto.createCipheriv('aes-256-gcm', getMachineKey(), iv)
+  const ciphertext = Buffer.concat([cipher.update(plain, 'utf8'), cipher.final()])
+  const tag = cipher.getAuthTag()
+  return ENCRYPTED_PREFIX + Buffer.concat([iv, tag, ciphertext]).toString('base64')
+}
+
+/**
+ * 解密 encryptSecret 的输出；明文原样返回，密钥不匹配或数据损坏时抛出
+ */
+export function decryptSecret(value: string): string {
+  if (!value || !isEncryptedSecret(value)) return value
+  const data = Buffer.from(value.slice(ENCRYPTED_PREFIX.length), 'base64')
+  if (data.length < IV_LENGTH + TAG_LENGTH) {
+    throw new Error('Encrypted secret is truncated')
+  }
+  const iv = data.subarray(0, IV_LENGTH)
+  const tag = data.subarray(IV_LENGTH, IV_LENGTH + TAG_LENGTH)
+  const ciphertext = data.subarray(IV_LENGTH + TAG_LENGTH)
+  const decipher = crypto.createDecipheriv('aes-256-gcm', getMachineKey(), iv)
+  decipher.setAuthTag(tag)
+  return Buffer.concat([decipher.update(ciphertext), decipher.final()]).toString('utf8')
+}
@@ -0,0 +1,74 @@
+import { sharedState } from './config'
+import type { RealtimeConnection, ServerMessage } from './realtime'
+
+/** 表示数据增删改的事件名后缀（如 document:updated、clipboard:itemAdded） */
+const DATA_CHANGE_ACTIONS = [
+  'created',
+  'updated',
+  'deleted',
+  'moved',
+  'registered',
+  'changed',
+  'itemAdded',
+  'itemDeleted',
+  'rolledBack'
+]
+
+interface ServerEventBase {
+  /** 服务端事件名，与 @prizm/shared 的 EventType 一致 */
+  eventType: string
+  payload: unknown
+  scope?: string
+  timestamp: number
+}
+
+/**
+ * 服务端推送的事件，按用途分为三类并转发到对应频道：
+ * notification → server://notification，status（任务、工作流、终端等运行状态）→ server://status，
+ * data_changed（数据增删改）→ server://data-changed
+ */
+export type ServerEvent =
+  | (ServerEventBase & { kind: 'notification' })
+  | (ServerEventBase & { kind: 'status' })
+  | (ServerEventBase & { kind: 'data_changed'; entity: string; action: string })
+
+export const SERVER_EVENT_CHANNELS: Record<ServerEvent['kind'], string> = {
+  notification: 'server://notification',
+  status: 'server://status',
+  data_changed: 'server://data-changed'
+}
+
+/** 把服务端 event 消息转换为 ServerEvent；其他消息（connected、error 等）返回 null */
+export function toServerEvent(message: ServerMessage): ServerEvent | null {
+  if (message.type !== 'event' || typeof message.eventType !== 'string') return null
+  const base: ServerEventBase = {
+    eventType: message.eventType,
+    payload: message.payload,
+    scope: typeof message.scope === 'string' ? message.scope : undefined,
+    timestamp: typeof message.timestamp === 'number' ? message.timestamp : Date.now()
+  }
+  if (base.eventType === 'notification') return { ...base, kind: 'notification' }
+  const separator = base.eventType.indexOf(':')
+  if (separator > 0 && DATA_CHANGE_ACTIONS.some((a) => base.eventType.endsWith(a))) {
+    return {
+      ...base,
+      kind: 'data_changed',
+      entity: base.eventType.slice(0, separator),
+      action: base.eventType.slice(separator + 1)
+    }
+  }
+  return { ...base, kind: 'status' }
+}
+
+/**
+ * 把实时连接收到的事件转发给渲染进程，组件按频道订阅即可，无需各自维护连接。返回取消函数
+ */
+export function bridgeServerEvents(connection: RealtimeConnection): () => void {
+  return connection.onMessage((message) => {
+    const event = toServerEvent(message)
+    const win = sharedState.mainWindow
+    if (event && win && !win.isDestroyed()) {
+      win.webContents.send(SERVER_EVENT_CHANNELS[event.kind], event)
+    }
+  })
+}
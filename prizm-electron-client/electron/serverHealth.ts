@@ -0,0 +1,141 @@
+import log from 'electron-log/main'
+import { loadConfigFromDisk, updateConfig } from './config'
+import type { PrizmConfig, ServerConfig } from './config'
+import { toPrizmError } from './errors'
+import type { PrizmErrorPayload } from './errors'
+import { httpClient } from './httpClient'
+import { PrizmApi } from './prizmApi'
+import type { HealthResponse } from './prizmApi'
+import { serverConfigToUrl } from './serverUrl'
+
+/** 同时进行的健康检查数量上限 */
+export const HEALTH_CHECK_CONCURRENCY = 4
+
+export interface ServerHealthTarget {
+  /** 档案名；未保存为档案的当前服务器为 null */
+  name: string | null
+  url: string
+  /** 该服务器上次健康检查成功的路径 */
+  health_path?: string
+}
+
+export interface ServerHealthResult extends ServerHealthTarget {
+  ok: boolean
+  latency_ms: number
+  error?: PrizmErrorPayload
+}
+
+/**
+ * 以不超过 limit 的并发执行 fn，结果顺序与 items 一致
+ */
+export async function mapWithConcurrency<T, R>(
+  items: T[],
+  limit: number,
+  fn: (item: T) => Promise<R>
+): Promise<R[]> {
+  const results = new Array<R>(items.length)
+  let next = 0
+  const worker = async (): Promise<void> => {
+    while (next < items.length) {
+      const index = next++
+      results[index] = await fn(items[index])
+    }
+  }
+  await Promise.all(Array.from({ length: Math.min(Math.max(1, limit), items.length) }, worker))
+  return results
+}
+
+/**
+ * 需要检查的服务器：全部档案，加上未保存为档案的当前服务器（按 URL 去重）
+ */
+export function healthTargets(config: PrizmConfig): ServerHealthTarget[] {
+  const targets: ServerHealthTarget[] = Object.entries(config.profiles ?? {}).map(
+    ([name, profile]) => ({
+      name,
+      url: serverConfigToUrl(profile.server),
+      health_path: profile.server.health_path
+    })
+  )
+  const current = serverConfigToUrl(config.server)
+  if (!targets.some((t) => t.url === current)) {
+    targets.unshift({ name: null, url: current, health_path: config.server.health_path })
+  }
+  return targets
+}
+
+/** 配置中地址为 url 的服务器（当前服务器与各档案） */
+function serversAt(config: PrizmConfig, url: string): ServerConfig[] {
+  const target = url.replace(/\/+$/, '')
+  return [config.server, ...Object.values(config.profiles ?? {}).map((p) => p.server)].filter(
+    (server) => serverConfigToUrl(server) === target
+  )
+}
+
+/**
+ * 把健康检查成功的路径记到地址为 url 的当前服务器与档案上；没有匹配或未变化时不写配置
+ */
+export async function rememberHealthPath(url: string, healthPath: string): Promise<void> {
+  const config = await loadConfigFromDisk()
+  if (serversAt(config, url).every((server) => server.health_path === healthPath)) return
+  await updateConfig((current) => {
+    for (const server of serversAt(current, url)) server.health_path = healthPath
+  })
+  log.info(`[Health] ${url} answers health checks at ${healthPath}`)
+}
+
+/**
+ * 健康检查：优先使用配置中记住的路径，成功后记住实际可用的路径
+ */
+export async function probeHealth(url: string, signal?: AbortSignal): Promise<HealthResponse> {
+  const config = await loadConfigFromDisk()
+  const known = serversAt(config, url).find((server) => server.health_path)?.health_path
+  const api = new PrizmApi(url, httpClient, known)
+  const health = await api.health(signal)
+  if (api.healthPath && api.healthPath !== known) {
+    await rememberHealthPath(url, api.healthPath).catch((err) => {
+      log.warn('[Health] Failed to remember health path:', err)
+    })
+  }
+  return health
+}
+
+/** probeHealth 且 status 为 ok 时返回 true；除被 signal 取消外不抛错 */
+export async function isServerHealthy(url: string, signal?: AbortSignal): Promise<boolean> {
+  try {
+    return (await probeHealth(url, signal)).status === 'ok'
+  } catch {
+    if (signal?.aborted) throw toPrizmError(signal.reason)
+    return false
+  }
+}
+
+async function checkServer(target: ServerHealthTarget): Promise<ServerHealthResult> {
+  const started = Date.now()
+  try {
+    const api = new PrizmApi(target.url, httpClient, target.health_path)
+    const health = await api.health()
+    if (api.healthPath && api.healthPath !== target.health_path) {
+      await rememberHealthPath(target.url, api.healthPath).catch((err) => {
+        log.warn('[Health] Failed to remember health path:', err)
+      })
+    }
+    return { ...target, ok: health.status === 'ok', latency_ms: Date.now() - started }
+  } catch (err) {
+    return {
+      ...target,
+      ok: false,
+      latency_ms: Date.now() - started,
+      error: toPrizmError(err).toJSON()
+    }
+  }
+}
+
+/**
+ * 并发检查多个服务器的 /health，单个失败不影响其他结果
+ */
+export function checkAllServers(
+  targets: ServerHealthTarget[],
+  concurrency = HEALTH_CHECK_CONCURRENCY
+): Promise<ServerHealthResult[]> {
+  return mapWithConcurrency(targets, concurrency, checkServer)
+}
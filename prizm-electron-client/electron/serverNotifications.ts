@@ -0,0 +1,225 @@
+import { Notification } from 'electron'
+import log from 'electron-log/main'
+import { sharedState } from './config'
+import type { NotificationCategory, NotificationSeverity } from './config'
+import { resolveRegistrationRequest } from './devices'
+import { notificationHistory } from './notificationHistory'
+import type { NotificationSource } from './notificationHistory'
+import { playNotificationSound, usesCustomSound } from './notificationSounds'
+import {
+  isDoNotDisturb,
+  isNotificationAllowed,
+  notificationCategoryOf,
+  toNotificationSeverity
+} from './notificationPolicy'
+import type { RealtimeConnection } from './realtime'
+import { toServerEvent } from './serverEvents'
+import type { ServerEvent } from './serverEvents'
+
+/** 系统通知的标题、正文长度上限，超出部分截断 */
+const MAX_TITLE_LENGTH = 120
+const MAX_BODY_LENGTH = 400
+
+/** 渲染进程中的页面位置，点击通知时经 app://navigate 推送 */
+export interface AppRoute {
+  page: 'settings'
+  section: 'connection' | 'devices'
+}
+
+/** 系统通知内容，服务端通知由 notification 事件映射而来 */
+export interface NativeNotificationContent {
+  title: string
+  body: string
+  /** 相同 updateId 的通知替换上一条而不是叠加 */
+  updateId?: string
+  category: NotificationCategory
+  severity: NotificationSeverity
+  /** 点击通知时跳转的页面：连接类通知到设置·连接，注册申请到设置·设备 */
+  route?: AppRoute
+  /** 等待批准的注册申请，通知上提供“批准”“拒绝”按钮 */
+  registrationRequestId?: string
+  /** 来源服务端事件，点击通知时随 notification://clicked 转发给渲染进程 */
+  event?: ServerEvent
+}
+
+/** 本机产生的系统通知（如注册完成） */
+export type LocalNotificationContent = Pick<
+  NativeNotificationContent,
+  'title' | 'body' | 'updateId' | 'category' | 'severity'
+>
+
+function text(value: unknown, max: number): string {
+  if (typeof value !== 'string') return ''
+  const trimmed = value.trim()
+  return trimmed.length > max ? `${trimmed.slice(0, max - 1)}…` : trimmed
+}
+
+function registrationRequestOf(action: unknown): string | undefined {
+  const a = action as { type?: unknown; requestId?: unknown } | null | undefined
+  if (a?.type !== 'registration_request' || typeof a.requestId !== 'string') return undefined
+  return a.requestId.trim() || undefined
+}
+
+function routeOf(
+  category: NotificationCategory,
+  registrationRequestId?: string
+): AppRoute | undefined {
+  if (registrationRequestId) return { page: 'settings', section: 'devices' }
+  if (category === 'connection') return { page: 'settings', section: 'connection' }
+  return undefined
+}
+
+/**
+ * 把服务端事件映射为系统通知；非 notification 事件或本机操作触发的通知（sourceClientId
+ * 为当前 clientId，界面内已有提示）返回 null
+ */
+export function toNativeNotification(
+  event: ServerEvent,
+  ownClientId: string | null
+): NativeNotificationContent | null {
+  if (event.kind !== 'notification') return null
+  const payload = (event.payload ?? {}) as Record<string, unknown>
+  if (ownClientId && payload.sourceClientId === ownClientId) return null
+  const updateId = text(payload.updateId, MAX_TITLE_LENGTH)
+  const category = notificationCategoryOf(event.eventType, payload.category)
+  const registrationRequestId = registrationRequestOf(payload.action)
+  return {
+    title: text(payload.title, MAX_TITLE_LENGTH) || '通知',
+    body: text(payload.body, MAX_BODY_LENGTH),
+    updateId: updateId || undefined,
+    category,
+    severity: toNotificationSeverity(payload.severity),
+    route: routeOf(category, registrationRequestId),
+    registrationRequestId,
+    event
+  }
+}
+
+/** 显示中的通知：持有引用避免被回收后点击回调失效，按 updateId 替换 */
+const active = new Map<string, Notification>()
+let sequence = 0
+
+function focusMainWindow(): void {
+  const win = sharedState.mainWindow
+  if (!win || win.isDestroyed()) return
+  if (win.isMinimized()) win.restore()
+  win.show()
+  win.focus()
+}
+
+/** 注册申请通知上的按钮，顺序与 action 事件的 index 对应 */
+const REGISTRATION_ACTIONS = [
+  { type: 'button' as const, text: '批准' },
+  { type: 'button' as const, text: '拒绝' }
+]
+
+/** 聚焦主窗口并跳转到指定页面 */
+export function navigateMainWindow(route: AppRoute): void {
+  focusMainWindow()
+  const win = sharedState.mainWindow
+  if (win && !win.isDestroyed()) win.webContents.send('app://navigate', route)
+}
+
+async function resolveFromNotification(requestId: string, action: 'approve' | 'deny') {
+  try {
+    await resolveRegistrationRequest(requestId, action)
+  } catch (err) {
+    log.warn(`[Notify] Failed to ${action} registration request ${requestId}:`, err)
+    if (!Notification.isSupported()) return
+    new Notification({
+      title: action === 'approve' ? '批准注册申请失败' : '拒绝注册申请失败',
+      body: err instanceof Error ? err.message : String(err)
+    }).show()
+  }
+}
+
+function showNative(content: NativeNotificationContent): void {
+  const key = content.updateId ?? `#${++sequence}`
+  active.get(key)?.close()
+  const { registrationRequestId, route } = content
+  // 静音或使用自定义提示音时关闭系统提示音
+  const notification = new Notification({
+    title: content.title,
+    body: content.body,
+    silent: usesCustomSound(content.category),
+    ...(registrationRequestId && { actions: REGISTRATION_ACTIONS })
+  })
+  notification.on('click', () => {
+    if (route) navigateMainWindow(route)
+    else focusMainWindow()
+    const win = sharedState.mainWindow
+    if (content.event && win && !win.isDestroyed()) {
+      win.webContents.send('notification://clicked', content.event)
+    }
+  })
+  if (registrationRequestId) {
+    // 不支持通知按钮的平台上只能点击通知，跳转到设置·设备处理
+    notification.on('action', (_event, index) => {
+      void resolveFromNotification(registrationRequestId, index === 0 ? 'approve' : 'deny')
+    })
+  }
+  notification.on('close', () => {
+    if (active.get(key) === notification) active.delete(key)
+  })
+  active.set(key, notification)
+  notification.show()
+  playNotificationSound(content.category)
+}
+
+/** 临时暂停或恢复弹出系统通知（托盘“暂停通知”，即立即进入免打扰） */
+export function setNotificationsPaused(paused: boolean): boolean {
+  if (sharedState.notificationsPaused !== paused) {
+    log.info(`[Notify] System notifications ${paused ? 'paused' : 'resumed'}`)
+  }
+  sharedState.notificationsPaused = paused
+  return paused
+}
+
+/**
+ * 按 tray.show_notification 与 notifications 偏好弹出系统通知并记入通知历史；
+ * 免打扰（暂停通知或免打扰时段）期间只记入历史。返回是否已弹出
+ */
+function deliver(content: NativeNotificationContent, source: NotificationSource): boolean {
+  if (!sharedState.showNotification) return false
+  if (!isNotificationAllowed(content.category, content.severity)) return false
+  const silent = isDoNotDisturb()
+  if (!silent) {
+    if (!Notification.isSupported()) {
+      log.warn(`[Notify] System notifications are not supported, dropping ${source} notification`)
+      return false
+    }
+    showNative(content)
+  }
+  notificationHistory.record({
+    title: content.title,
+    body: content.body,
+    source,
+    eventType: content.event?.eventType,
+    updateId: content.updateId,
+    category: content.category,
+    severity: content.severity,
+    silent
+  })
+  return !silent
+}
+
+/**
+ * 弹出本机产生的系统通知，与服务端通知同样受偏好与免打扰控制；连接类通知点击后打开设置·连接
+ */
+export function showLocalNotification(content: LocalNotificationContent): boolean {
+  return deliver({ ...content, route: routeOf(content.category) }, 'local')
+}
+
+/**
+ * 实时连接收到服务端通知时弹出系统通知并记入通知历史（见 deliver）。
+ * 点击通知聚焦主窗口并推送 notification://clicked，有 route 时同时推送 app://navigate；
+ * 注册申请通知上的“批准”“拒绝”按钮直接调用对应的管理接口。返回取消函数
+ */
+export function bridgeServerNotifications(connection: RealtimeConnection): () => void {
+  return connection.onMessage((message) => {
+    if (!sharedState.showNotification) return
+    const event = toServerEvent(message)
+    const content = event && toNativeNotification(event, connection.status().clientId)
+    if (content) deliver(content, 'server')
+  })
+}
@@ -0,0 +1,144 @@
+import type { ServerConfig } from './config'
+import { PrizmError } from './errors'
+
+export type ServerScheme = 'http' | 'https' | 'ws' | 'wss'
+
+export const SERVER_SCHEMES: readonly ServerScheme[] = ['http', 'https', 'ws', 'wss']
+
+/** Prizm 服务端默认端口，地址未写协议与端口时使用 */
+export const DEFAULT_SERVER_PORT = 4127
+
+const SCHEME_DEFAULT_PORTS: Record<ServerScheme, number> = {
+  http: 80,
+  https: 443,
+  ws: 80,
+  wss: 443
+}
+
+/** 是否为 TLS 协议 */
+export function isSecureScheme(scheme: ServerScheme): boolean {
+  return scheme === 'https' || scheme === 'wss'
+}
+
+/**
+ * 规范化 base_path：保证以 / 开头、不以 / 结尾，根路径返回空串
+ */
+export function normalizeBasePath(basePath: string | undefined): string {
+  const trimmed = (basePath ?? '').trim().replace(/^\/+|\/+$/g, '')
+  return trimmed ? `/${trimmed}` : ''
+}
+
+/**
+ * URL 中的主机部分：IPv6 字面量加方括号，zone ID 中的 % 按 RFC 6874 编码为 %25
+ */
+export function formatHostForUrl(host: string): string {
+  if (!host.includes(':')) return host
+  const bare = host.replace(/^\[|\]$/g, '')
+  const zoneIdx = bare.indexOf('%')
+  if (zoneIdx === -1) return `[${bare}]`
+  const zone = bare.slice(zoneIdx + 1).replace(/^25/, '')
+  return `[${bare.slice(0, zoneIdx)}%25${zone}]`
+}
+
+/** 带 zone ID 的 IPv6 字面量（如 [fe80::1%eth0] 或 [fe80::1%25eth0]），WHATWG URL 不支持，需先取出 */
+const IPV6_ZONE = /\[([0-9A-Fa-f:.]+)%(?:25)?([^\]]+)\]/
+/** 不带方括号与端口的 IPv6 字面量 */
+const BARE_IPV6 = /^[0-9A-Fa-f]*:[0-9A-Fa-f:.]*:[0-9A-Fa-f:.]*(%[^\s/]+)?$/
+
+/**
+ * 由 ServerConfig 构建基础 URL（不含结尾斜杠）。
+ * kind 为 ws 时把 http/https 映射为 ws/wss，反之亦然，便于 HTTP 与 WebSocket 共用同一份配置。
+ */
+export function serverConfigToUrl(server: ServerConfig, kind: 'http' | 'ws' = 'http'): string {
+  const scheme = server.scheme ?? 'http'
+  const secure = isSecureScheme(scheme)
+  const effective = kind === 'ws' ? (secure ? 'wss' : 'ws') : secure ? 'https' : 'http'
+  return `${effective}://${formatHostForUrl(server.host)}:${server.port}${normalizeBasePath(
+    server.base_path
+  )}`
+}
+
+/** parseServerUrl 的结果，可直接写入 ServerConfig */
+export interface ParsedServerUrl {
+  host: string
+  port: number
+  scheme: 'http' | 'https'
+  base_path: string
+}
+
+/**
+ * 解析用户输入的服务器地址。
+ * - 未写协议时按 http 处理，未写端口时使用 Prizm 默认端口 4127
+ * - IPv6 可写作 [::1]:4127、::1（无端口）或带 zone ID 的 [fe80::1%eth0]
+ * - 写了协议但未写端口时使用该协议的默认端口（http/ws 80，https/wss 443）
+ * - ws/wss 归一为 http/https；路径作为 base_path，末尾斜杠忽略
+ * - 含用户信息、查询串或片段的地址视为无效
+ */
+export function parseServerUrl(raw: string): ParsedServerUrl {
+  let input = raw.trim()
+  if (!input) {
+    throw PrizmError.invalidInput('Server url is empty')
+  }
+  if (BARE_IPV6.test(input)) {
+    input = `[${input}]`
+  }
+  let zone = ''
+  const zoneMatch = IPV6_ZONE.exec(input)
+  if (zoneMatch) {
+    zone = zoneMatch[2]
+    input = input.replace(zoneMatch[0], `[${zoneMatch[1]}]`)
+  }
+  const hasScheme = /^[A-Za-z][A-Za-z0-9+.-]*:\/\//.test(input)
+  let url: URL
+  try {
+    url = new URL(hasScheme ? input : `http://${input}`)
+  } catch {
+    throw PrizmError.invalidInput(`Invalid server url: ${input}`)
+  }
+  const scheme = url.protocol.slice(0, -1) as ServerScheme
+  if (!SERVER_SCHEMES.includes(scheme)) {
+    throw PrizmError.invalidInput(`Unsupported server url scheme: ${url.protocol}`)
+  }
+  if (url.username || url.password) {
+    throw PrizmError.invalidInput('Server url must not contain credentials')
+  }
+  if (url.search || url.hash) {
+    throw PrizmError.invalidInput('Server url must not contain a query or fragment')
+  }
+  const address = url.hostname.replace(/^\[|\]$/g, '')
+  const host = zone ? `${address}%${zone}` : address
+  if (!address) {
+    throw PrizmError.invalidInput(`Server url has no host: ${input}`)
+  }
+  // URL 会把与协议默认值相同的端口规范化为空串，因此先看是否显式写了端口
+  const port = url.port
+    ? Number(url.port)
+    : hasScheme
+      ? SCHEME_DEFAULT_PORTS[scheme]
+      : DEFAULT_SERVER_PORT
+  return {
+    host,
+    port,
+    scheme: isSecureScheme(scheme) ? 'https' : 'http',
+    base_path: normalizeBasePath(decodeURIComponent(url.pathname))
+  }
+}
+
+/**
+ * network.require_tls 开启时使用：http/ws 升级为 https/wss（主机与显式端口不变），
+ * 其他无法保证加密的协议直接报错
+ */
+export function upgradeToTls(rawUrl: string): string {
+  let url: URL
+  try {
+    url = new URL(rawUrl)
+  } catch {
+    throw PrizmError.invalidInput(`Invalid url: ${rawUrl}`)
+  }
+  if (url.protocol === 'https:' || url.protocol === 'wss:') return rawUrl
+  if (url.protocol !== 'http:' && url.protocol !== 'ws:') {
+    throw PrizmError.config(`require_tls refuses ${url.protocol} urls`)
+  }
+  url.protocol = url.protocol === 'http:' ? 'https:' : 'wss:'
+  return url.toString()
+}
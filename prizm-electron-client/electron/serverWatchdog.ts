@@ -0,0 +1,101 @@
+import log from 'electron-log/main'
+import { sharedState } from './config'
+import { realtimeConnection } from './realtime'
+import type { RealtimeConnection, RealtimeStatus } from './realtime'
+import { showLocalNotification } from './serverNotifications'
+import type { LocalNotificationContent } from './serverNotifications'
+
+/** 断线与恢复通知共用，恢复通知替换断线通知 */
+const WATCHDOG_UPDATE_ID = 'server-watchdog'
+
+export type WatchdogNotifier = (content: LocalNotificationContent) => unknown
+
+/** 时长的中文描述，如 45 秒、3 分钟、2 小时 */
+export function formatDowntime(ms: number): string {
+  const seconds = Math.max(1, Math.round(ms / 1000))
+  if (seconds < 60) return `${seconds} 秒`
+  const minutes = Math.round(seconds / 60)
+  if (minutes < 60) return `${minutes} 分钟`
+  return `${Math.round(minutes / 60)} 小时`
+}
+
+/**
+ * 服务器断线看门狗：实时连接意外断开（重连中或重连失败）超过 notifications.server_down_grace_ms
+ * 后弹出一次“服务器无法连接”，同一次断线不重复提示；连接恢复时再弹出恢复通知。
+ * 主动断开（如注销、切换服务器）不提示
+ */
+export class ServerWatchdog {
+  private timer: NodeJS.Timeout | undefined
+  private unsubscribe: (() => void) | null = null
+  /** 本次断线的开始时间，连接正常时为 null */
+  private downSince: number | null = null
+  /** 本次断线已提示过 */
+  private alerted = false
+
+  constructor(
+    private readonly connection: RealtimeConnection = realtimeConnection,
+    private readonly notify: WatchdogNotifier = showLocalNotification,
+    private readonly graceMs: () => number = () =>
+      sharedState.notificationPreferences.server_down_grace_ms,
+    private readonly now: () => number = Date.now
+  ) {}
+
+  start(): void {
+    if (this.unsubscribe) return
+    this.unsubscribe = this.connection.onStatusChange((status) => this.update(status))
+    this.update(this.connection.status())
+  }
+
+  stop(): void {
+    this.unsubscribe?.()
+    this.unsubscribe = null
+    this.reset()
+  }
+
+  update(status: RealtimeStatus): void {
+    if (status.state === 'connected') {
+      if (this.alerted && this.downSince !== null) {
+        const downtime = formatDowntime(this.now() - this.downSince)
+        log.info(`[Watchdog] Server reachable again after ${downtime}`)
+        this.notify({
+          title: '服务器已恢复连接',
+          body: `连接中断了约 ${downtime}`,
+          updateId: WATCHDOG_UPDATE_ID,
+          category: 'connection',
+          severity: 'info'
+        })
+      }
+      this.reset()
+    } else if (status.state === 'disconnected' && !status.lastError) {
+      this.reset()
+    } else if (status.state === 'reconnecting' && this.downSince === null) {
+      this.downSince = this.now()
+      this.timer = setTimeout(() => this.raise(), this.graceMs())
+      this.timer.unref?.()
+    }
+  }
+
+  private raise(): void {
+    this.timer = undefined
+    if (this.downSince === null || this.alerted) return
+    this.alerted = true
+    const downtime = formatDowntime(this.now() - this.downSince)
+    log.warn(`[Watchdog] Server unreachable for ${downtime}`)
+    this.notify({
+      title: '服务器无法连接',
+      body: `与服务器的连接已中断 ${downtime}，正在自动重连`,
+      updateId: WATCHDOG_UPDATE_ID,
+      category: 'connection',
+      severity: 'error'
+    })
+  }
+
+  private reset(): void {
+    clearTimeout(this.timer)
+    this.timer = undefined
+    this.downSince = null
+    this.alerted = false
+  }
+}
+
+export const serverWatchdog = new ServerWatchdog()
@@ -1,26 +1,84 @@
 import { globalShortcut, clipboard, screen } from 'electron'
 import log from 'electron-log/main'
-import { sharedState } from './config'
-import { createMainWindow, createQuickPanelWindow } from './windowManager'
+import { DEFAULT_GLOBAL_HOTKEY, sharedState, updateConfig } from './config'
+import { PrizmError } from './errors'
+import { createMainWindow, createQuickPanelWindow, showMainWindow } from './windowManager'
+
+/** 当前已注册的显示/隐藏主窗口快捷键 */
+let toggleHotkey: string | null = null
+
+function toggleMainWindow(): void {
+  const win = createMainWindow()
+  if (!win) return
+  if (win.isVisible() && !win.isMinimized()) {
+    win.hide()
+  } else {
+    showMainWindow()
+  }
+}
+
+function tryRegister(accelerator: string): boolean {
+  try {
+    return globalShortcut.register(accelerator, toggleMainWindow)
+  } catch (err) {
+    // 格式不合法的 accelerator 会直接抛错
+    log.warn('[Electron] Invalid global shortcut:', accelerator, err)
+    return false
+  }
+}
+
+/** 当前生效的显示/隐藏主窗口快捷键，未注册时为 null */
+export function getGlobalHotkey(): string | null {
+  return toggleHotkey
+}
 
 /**
- * 注册全局快捷键
+ * 把显示/隐藏主窗口的快捷键切换为 accelerator（空字符串表示取消）。
+ * 与当前相同时不重复注册；注册失败（格式错误或被其他程序占用）时返回 false，旧快捷键已注销
  */
-export function registerGlobalShortcuts(): void {
-  const accelerator = process.platform === 'darwin' ? 'Command+Shift+P' : 'Control+Shift+P'
-  const ok = globalShortcut.register(accelerator, () => {
-    const win = createMainWindow()
-    if (!win) return
-    if (win.isVisible()) {
-      win.hide()
-    } else {
-      win.show()
-      win.focus()
-    }
-  })
-  if (!ok) {
+export function applyGlobalHotkey(accelerator: string): boolean {
+  if (accelerator === toggleHotkey) return true
+  if (toggleHotkey) globalShortcut.unregister(toggleHotkey)
+  toggleHotkey = null
+  if (!accelerator) return true
+  if (!tryRegister(accelerator)) {
     log.warn('[Electron] Failed to register global shortcut:', accelerator)
+    return false
+  }
+  toggleHotkey = accelerator
+  return true
+}
+
+/**
+ * 注册全局快捷键
+ */
+export function registerGlobalShortcuts(accelerator: string = DEFAULT_GLOBAL_HOTKEY): void {
+  applyGlobalHotkey(accelerator)
+}
+
+/**
+ * 修改显示/隐藏主窗口的快捷键并写入 tray.global_hotkey；新快捷键注册失败时恢复原快捷键并报错
+ */
+export async function setGlobalHotkey(accelerator: string): Promise<string> {
+  if (typeof accelerator !== 'string') {
+    throw PrizmError.invalidInput('Shortcut must be a string')
+  }
+  const next = accelerator.trim()
+  const previous = toggleHotkey
+  if (!applyGlobalHotkey(next)) {
+    if (previous) applyGlobalHotkey(previous)
+    throw PrizmError.invalidInput(`快捷键 ${next} 无效或已被其他程序占用`)
+  }
+  try {
+    await updateConfig((config) => {
+      config.tray.global_hotkey = next
+    })
+  } catch (err) {
+    applyGlobalHotkey(previous ?? '')
+    throw err
   }
+  log.info('[Electron] Global shortcut set to:', next || '(none)')
+  return next
 }
 
 /** 快捷面板：双击 Ctrl 触发 */
@@ -0,0 +1,63 @@
+import { app } from 'electron'
+import log from 'electron-log/main'
+import { showMainWindow } from './windowManager'
+
+/** 深链接协议前缀 */
+export const DEEP_LINK_PREFIX = 'prizm://'
+
+/** 随单实例锁传给已运行实例的启动信息 */
+interface SecondInstanceData {
+  argv: string[]
+}
+
+export type SecondInstanceListener = (argv: string[], deepLink: string | null) => void
+
+const listeners = new Set<SecondInstanceListener>()
+
+/** 启动参数中的深链接（prizm://…），没有时返回 null */
+export function extractDeepLink(argv: string[]): string | null {
+  return argv.find((arg) => arg.toLowerCase().startsWith(DEEP_LINK_PREFIX)) ?? null
+}
+
+/**
+ * 订阅再次启动时转发过来的启动参数与深链接。返回取消函数
+ */
+export function onSecondInstance(listener: SecondInstanceListener): () => void {
+  listeners.add(listener)
+  return () => listeners.delete(listener)
+}
+
+/**
+ * 处理再次启动：通知订阅者，并显示、聚焦已有主窗口（带 --minimized 时留在托盘）
+ */
+export function handleSecondInstance(argv: string[]): void {
+  const deepLink = extractDeepLink(argv)
+  log.info('[Electron] Second instance launched', deepLink ? `with ${deepLink}` : '')
+  for (const listener of listeners) {
+    try {
+      listener(argv, deepLink)
+    } catch (err) {
+      log.error('[Electron] second-instance listener failed:', err)
+    }
+  }
+  if (!argv.includes('--minimized')) showMainWindow()
+}
+
+function argvOf(commandLine: string[], data: unknown): string[] {
+  const argv = (data as Partial<SecondInstanceData> | null | undefined)?.argv
+  return Array.isArray(argv) && argv.every((arg) => typeof arg === 'string') ? argv : commandLine
+}
+
+/**
+ * 获取单实例锁（按 userData 目录区分，--config-dir 指定的隔离实例互不影响）。
+ * 已有实例运行时把本次启动参数转交给它并返回 false，调用方应立即退出
+ */
+export function acquireSingleInstanceLock(): boolean {
+  const data: SecondInstanceData = { argv: process.argv }
+  if (!app.requestSingleInstanceLock(data)) return false
+  app.on('second-instance', (_event, commandLine, _workingDirectory, additionalData) => {
+    const argv = argvOf(commandLine, additionalData)
+    void app.whenReady().then(() => handleSecondInstance(argv))
+  })
+  return true
+}
@@ -0,0 +1,93 @@
+import * as http from 'http'
+import * as zlib from 'zlib'
+import { Readable } from 'stream'
+import { PrizmError } from './errors'
+
+export interface SocketRequestInit {
+  method: string
+  headers: Record<string, string>
+  body?: string
+  signal?: AbortSignal
+  /** 复用连接的 Agent（见 connectionPool.createSocketAgent） */
+  agent?: http.Agent
+}
+
+/**
+ * Windows 命名管道路径（\\.\pipe\name 或 //./pipe/name）
+ */
+export function isNamedPipePath(socketPath: string): boolean {
+  return /^[\\/]{2}[.?][\\/]pipe[\\/]/i.test(socketPath)
+}
+
+/** 与 Chromium 网络栈一致的可接受编码 */
+const ACCEPT_ENCODING = 'gzip, deflate, br'
+
+/**
+ * 按 Content-Encoding 解压响应体；未知编码原样返回
+ */
+function decodeBody(res: http.IncomingMessage): Readable | null {
+  switch (String(res.headers['content-encoding'] ?? '').toLowerCase()) {
+    case 'gzip':
+    case 'x-gzip':
+      return res.pipe(zlib.createGunzip())
+    case 'deflate':
+      return res.pipe(zlib.createInflate())
+    case 'br':
+      return res.pipe(zlib.createBrotliDecompress())
+    default:
+      return null
+  }
+}
+
+/**
+ * 通过 Unix 域套接字 / Windows 命名管道发送 HTTP 请求，返回与 fetch 一致的 Response。
+ * Chromium 网络栈不支持本地套接字，因此这里走 Node http 模块；不经过代理与证书校验。
+ * 与 session.fetch 一样声明 gzip / deflate / br 并透明解压。
+ */
+export function socketFetch(
+  socketPath: string,
+  url: string,
+  init: SocketRequestInit
+): Promise<Response> {
+  const target = new URL(url)
+  return new Promise((resolve, reject) => {
+    const req = http.request(
+      {
+        socketPath,
+        agent: init.agent,
+        method: init.method,
+        path: `${target.pathname}${target.search}`,
+        headers: { Host: target.host, 'Accept-Encoding': ACCEPT_ENCODING, ...init.headers },
+        signal: init.signal
+      },
+      (res) => {
+        const status = res.statusCode ?? 502
+        // 204 / 304 等无响应体状态不能携带 body
+        const hasBody = status !== 204 && status !== 304 && init.method !== 'HEAD'
+        const decoded = hasBody ? decodeBody(res) : null
+        const headers = new Headers()
+        for (let i = 0; i + 1 < res.rawHeaders.length; i += 2) {
+          const name = res.rawHeaders[i].toLowerCase()
+          // 解压后原始编码与长度不再适用
+          if (decoded && (name === 'content-encoding' || name === 'content-length')) continue
+          headers.append(res.rawHeaders[i], res.rawHeaders[i + 1])
+        }
+        const stream = decoded ?? res
+        const body = hasBody ? (Readable.toWeb(stream) as ReadableStream<Uint8Array>) : null
+        if (!hasBody) res.resume()
+        resolve(new Response(body, { status, statusText: res.statusMessage, headers }))
+      }
+    )
+    req.on('error', (err: NodeJS.ErrnoException) => {
+      if (err.name === 'AbortError') {
+        reject(init.signal?.reason ?? err)
+      } else if (err.code === 'ENOENT' || err.code === 'ECONNREFUSED' || err.code === 'EACCES') {
+        reject(PrizmError.network(`Cannot connect to local socket ${socketPath}: ${err.code}`))
+      } else {
+        reject(err)
+      }
+    })
+    if (init.body !== undefined) req.write(init.body)
+    req.end()
+  })
+}
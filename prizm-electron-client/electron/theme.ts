@@ -0,0 +1,101 @@
+import { BrowserWindow, nativeTheme } from 'electron'
+import log from 'electron-log/main'
+import { normalizeThemeMode, saveThemeMode, sharedState } from './config'
+import type { ThemeMode } from './config'
+
+/** 实际生效的外观 */
+export type EffectiveTheme = 'light' | 'dark'
+
+/** 推送给渲染进程的主题状态（theme://changed） */
+export interface ThemeState {
+  /** 用户选择：auto 跟随系统 */
+  mode: ThemeMode
+  effective: EffectiveTheme
+  highContrast: boolean
+}
+
+export type ThemeListener = (state: ThemeState) => void
+
+const listeners = new Set<ThemeListener>()
+let currentMode: ThemeMode = 'auto'
+let lastState: ThemeState | null = null
+
+export function getThemeState(): ThemeState {
+  return {
+    mode: currentMode,
+    effective: nativeTheme.shouldUseDarkColors ? 'dark' : 'light',
+    highContrast: nativeTheme.shouldUseHighContrastColors
+  }
+}
+
+/**
+ * 订阅外观变化（系统切换深浅色或用户修改主题），如托盘图标随之切换。返回取消函数
+ */
+export function onThemeChange(listener: ThemeListener): () => void {
+  listeners.add(listener)
+  return () => listeners.delete(listener)
+}
+
+/** Windows 标题栏控件颜色随主题切换 */
+function updateTitleBarOverlay(state: ThemeState): void {
+  const win = sharedState.mainWindow
+  if (process.platform !== 'win32' || !win || win.isDestroyed()) return
+  win.setTitleBarOverlay({
+    color: '#00000000',
+    symbolColor: state.effective === 'dark' ? '#CCCCCC' : '#333333'
+  })
+}
+
+function emitIfChanged(): void {
+  const state = getThemeState()
+  if (
+    lastState &&
+    lastState.mode === state.mode &&
+    lastState.effective === state.effective &&
+    lastState.highContrast === state.highContrast
+  ) {
+    return
+  }
+  lastState = state
+  updateTitleBarOverlay(state)
+  for (const win of BrowserWindow.getAllWindows()) {
+    if (!win.isDestroyed()) win.webContents.send('theme://changed', state)
+  }
+  for (const listener of listeners) {
+    try {
+      listener(state)
+    } catch (err) {
+      log.error('[Theme] listener failed:', err)
+    }
+  }
+}
+
+/**
+ * 应用主题模式到 nativeTheme.themeSource（不持久化）；启动时在创建窗口前调用
+ */
+export function applyThemeMode(mode: unknown): ThemeMode {
+  currentMode = normalizeThemeMode(mode)
+  nativeTheme.themeSource = currentMode === 'auto' ? 'system' : currentMode
+  log.info('[Electron] nativeTheme.themeSource set to:', nativeTheme.themeSource)
+  emitIfChanged()
+  return currentMode
+}
+
+/**
+ * 修改并持久化主题模式（light / dark / auto，system 视为 auto）
+ */
+export async function setThemeMode(mode: unknown): Promise<ThemeState> {
+  await saveThemeMode(applyThemeMode(mode))
+  return getThemeState()
+}
+
+/**
+ * 监听系统外观变化，向所有窗口推送 theme://changed。返回取消函数
+ */
+export function watchSystemTheme(): () => void {
+  lastState = getThemeState()
+  nativeTheme.on('updated', emitIfChanged)
+  return () => {
+    nativeTheme.removeListener('updated', emitIfChanged)
+  }
+}
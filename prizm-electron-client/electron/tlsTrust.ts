@@ -0,0 +1,127 @@
+import * as fs from 'fs'
+import * as net from 'net'
+import * as path from 'path'
+import { X509Certificate } from 'crypto'
+import type { Certificate, Session } from 'electron'
+import log from 'electron-log/main'
+
+/** Chromium 证书校验回调的返回值：0 接受，-3 沿用 Chromium 的校验结果 */
+const CERT_ACCEPT = 0
+const CERT_USE_CHROMIUM = -3
+
+const PEM_BLOCK = /-----BEGIN CERTIFICATE-----[\s\S]+?-----END CERTIFICATE-----/g
+
+/**
+ * 读取 PEM 文件中的全部证书；相对路径相对配置目录解析，读取失败的文件记录日志后跳过
+ */
+export function loadCaCertificates(files: string[], baseDir: string): X509Certificate[] {
+  const certs: X509Certificate[] = []
+  for (const file of files) {
+    const filePath = path.resolve(baseDir, file)
+    try {
+      const blocks = fs.readFileSync(filePath, 'utf-8').match(PEM_BLOCK) ?? []
+      if (blocks.length === 0) {
+        log.warn('[TLS] No certificate found in', filePath)
+      }
+      for (const block of blocks) {
+        certs.push(new X509Certificate(block))
+      }
+    } catch (err) {
+      log.warn('[TLS] Failed to load CA file', filePath, err)
+    }
+  }
+  return certs
+}
+
+function isValidNow(cert: X509Certificate, now: number): boolean {
+  return Date.parse(cert.validFrom) <= now && now <= Date.parse(cert.validTo)
+}
+
+function matchesHost(leaf: X509Certificate, hostname: string): boolean {
+  const host = hostname.replace(/^\[|\]$/g, '')
+  return net.isIP(host) ? leaf.checkIP(host) !== undefined : leaf.checkHost(host) !== undefined
+}
+
+/**
+ * 判断服务器证书链是否由额外信任的 CA 签发：链上每一环签名有效、均在有效期内、叶证书匹配主机名，
+ * 且每个签发者都是 CA（basicConstraints CA:TRUE）。否则受信任 CA 签发的任意叶证书
+ * 都能再签出其他主机的证书
+ */
+export function isTrustedByExtraCa(
+  chain: X509Certificate[],
+  hostname: string,
+  cas: X509Certificate[],
+  now = Date.now()
+): boolean {
+  if (chain.length === 0 || cas.length === 0 || !matchesHost(chain[0], hostname)) {
+    return false
+  }
+  for (let i = 0; i < chain.length; i++) {
+    const cert = chain[i]
+    if (!isValidNow(cert, now)) return false
+    // 叶证书之后的每一环都签发了前一张证书
+    if (i > 0 && !cert.ca) return false
+    const anchor = cas.find(
+      (ca) =>
+        ca.fingerprint256 === cert.fingerprint256 ||
+        (ca.ca && cert.checkIssued(ca) && cert.verify(ca.publicKey))
+    )
+    if (anchor) return isValidNow(anchor, now)
+    const issuer = chain[i + 1]
+    if (!issuer || !cert.checkIssued(issuer) || !cert.verify(issuer.publicKey)) return false
+  }
+  return false
+}
+
+/** 主机名比较用的形式：小写、去掉 IPv6 方括号 */
+export function normalizeTlsHost(hostname: string): string {
+  return hostname.replace(/^\[|\]$/g, '').toLowerCase()
+}
+
+function toChain(certificate: Certificate): X509Certificate[] {
+  const chain: X509Certificate[] = []
+  let current: Certificate | undefined = certificate
+  while (current && chain.length < 10) {
+    chain.push(new X509Certificate(current.data))
+    current = current.issuerCert
+  }
+  return chain
+}
+
+/**
+ * 为 session 安装证书校验：Chromium 校验失败时再用额外 CA 校验，通过则放行；
+ * insecureHosts 中的主机（server.verify_tls 为 false 的服务器）不校验证书。
+ * 两者都为空时恢复默认校验。
+ */
+export function applyExtraCaCertificates(
+  target: Session,
+  cas: X509Certificate[],
+  insecureHosts: string[] = []
+): void {
+  if (cas.length === 0 && insecureHosts.length === 0) {
+    target.setCertificateVerifyProc(null)
+    return
+  }
+  const skipped = new Set(insecureHosts.map(normalizeTlsHost))
+  target.setCertificateVerifyProc((request, callback) => {
+    if (request.verificationResult === 'net::OK') {
+      callback(CERT_USE_CHROMIUM)
+      return
+    }
+    if (skipped.has(normalizeTlsHost(request.hostname))) {
+      callback(CERT_ACCEPT)
+      return
+    }
+    try {
+      if (isTrustedByExtraCa(toChain(request.certificate), request.hostname, cas)) {
+        callback(CERT_ACCEPT)
+        return
+      }
+    } catch (err) {
+      log.warn('[TLS] Failed to verify certificate for', request.hostname, err)
+    }
+    callback(CERT_USE_CHROMIUM)
+  })
+  if (cas.length > 0) log.info(`[TLS] Trusting ${cas.length} extra CA certificate(s)`)
+  for (const host of skipped) log.warn(`[TLS] Certificate verification disabled for ${host}`)
+}
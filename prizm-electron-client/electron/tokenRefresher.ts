@@ -0,0 +1,134 @@
+import log from 'electron-log/main'
+import { loadConfigFromDisk, sharedState, updateConfig } from './config'
+import type { PrizmConfig } from './config'
+import { toPrizmError } from './errors'
+import { upsertIdentity } from './identities'
+import { PrizmApi } from './prizmApi'
+import { upsertActiveProfile } from './profiles'
+import { serverConfigToUrl } from './serverUrl'
+
+/** 在过期前多久续期；Key 寿命很短时改为剩余寿命的一半 */
+export const REFRESH_MARGIN_MS = 60_000
+/** 续期因网络等暂时性错误失败后的重试间隔 */
+export const REFRESH_RETRY_MS = 30_000
+
+/** 服务端签发的过期信息（注册、设备码授权、配对或续期的结果） */
+export interface TokenExpiry {
+  expiresAt?: number
+  refreshToken?: string
+}
+
+export interface ReauthRequiredEvent {
+  serverUrl: string
+  reason: 'missing_refresh_token' | 'refresh_rejected'
+}
+
+/**
+ * 把过期信息写入配置：没有过期时间时视为永久 Key，清除旧的过期时间与刷新令牌；
+ * 续期结果未返回新刷新令牌时沿用旧令牌
+ */
+export function applyTokenExpiry(config: PrizmConfig, expiry: TokenExpiry): void {
+  if (!expiry.expiresAt) {
+    delete config.api_key_expires_at
+    delete config.refresh_token
+    return
+  }
+  config.api_key_expires_at = expiry.expiresAt
+  if (expiry.refreshToken) config.refresh_token = expiry.refreshToken
+}
+
+/**
+ * 距下次续期的等待时间：过期前 REFRESH_MARGIN_MS，且不早于剩余寿命的一半；已过期时为 0
+ */
+export function refreshDelay(expiresAt: number, now: number): number {
+  const remaining = expiresAt - now
+  if (remaining <= 0) return 0
+  return remaining - Math.min(REFRESH_MARGIN_MS, remaining / 2)
+}
+
+function emitReauthRequired(event: ReauthRequiredEvent): void {
+  log.warn(`[Auth] Re-authentication required for ${event.serverUrl} (${event.reason})`)
+  const win = sharedState.mainWindow
+  if (win && !win.isDestroyed()) {
+    win.webContents.send('auth://reauth-required', event)
+  }
+}
+
+/**
+ * 后台续期：按配置中的 api_key_expires_at 在过期前用 refresh_token 换取新 Key 并写回配置。
+ * 没有刷新令牌或刷新令牌被拒时发出 auth://reauth-required，由用户重新注册
+ */
+export class TokenRefresher {
+  private timer: NodeJS.Timeout | undefined
+  private inFlight: Promise<string | null> | null = null
+
+  constructor(private readonly now: () => number = Date.now) {}
+
+  /**
+   * 按配置重新安排续期（配置变更后调用）；没有过期时间时取消
+   */
+  schedule(config: PrizmConfig): void {
+    this.stop()
+    if (!config.api_key || !config.api_key_expires_at) return
+    this.arm(refreshDelay(config.api_key_expires_at, this.now()))
+  }
+
+  stop(): void {
+    clearTimeout(this.timer)
+    this.timer = undefined
+  }
+
+  private arm(delay: number): void {
+    this.timer = setTimeout(() => {
+      this.timer = undefined
+      void this.refresh()
+    }, delay)
+    this.timer.unref?.()
+  }
+
+  /**
+   * 立即续期，返回新的 API Key；无需续期或续期失败时返回 null。并发调用共用同一次请求
+   */
+  refresh(): Promise<string | null> {
+    if (!this.inFlight) {
+      this.inFlight = this.doRefresh().finally(() => {
+        this.inFlight = null
+      })
+    }
+    return this.inFlight
+  }
+
+  private async doRefresh(): Promise<string | null> {
+    const config = await loadConfigFromDisk()
+    if (!config.api_key || !config.api_key_expires_at) return null
+    const serverUrl = serverConfigToUrl(config.server)
+    if (!config.refresh_token) {
+      emitReauthRequired({ serverUrl, reason: 'missing_refresh_token' })
+      return null
+    }
+    try {
+      const refreshed = await new PrizmApi(serverUrl).refreshToken(config.refresh_token)
+      // 写回配置后由 onConfigUpdated 按新的过期时间重新安排
+      await updateConfig((current) => {
+        current.api_key = refreshed.apiKey
+        applyTokenExpiry(current, refreshed)
+        if (current.active_identity) upsertIdentity(current, current.active_identity)
+        if (current.active_profile) upsertActiveProfile(current)
+      })
+      log.info(`[Auth] API key refreshed for ${serverUrl}`)
+      return refreshed.apiKey
+    } catch (err) {
+      const error = toPrizmError(err)
+      if (error.kind === 'auth') {
+        emitReauthRequired({ serverUrl, reason: 'refresh_rejected' })
+      } else {
+        log.warn(`[Auth] Token refresh failed (${error.kind}), retrying:`, error.message)
+        this.stop()
+        this.arm(REFRESH_RETRY_MS)
+      }
+      return null
+    }
+  }
+}
+
+export const tokenRefresher = new TokenRefresher()
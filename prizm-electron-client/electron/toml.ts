@@ -0,0 +1,228 @@
+/**
+ * 配置文件用的 TOML 子集读写：表 / 点分表名、字符串、整数、浮点、布尔、单行标量数组、注释。
+ * 只覆盖 PrizmConfig 需要的语法，不支持内联表、多行字符串与日期。
+ */
+
+type TomlValue = string | number | boolean | TomlValue[] | TomlTable
+interface TomlTable {
+  [key: string]: TomlValue
+}
+
+const BARE_KEY = /^[A-Za-z0-9_-]+$/
+
+function formatKey(key: string): string {
+  return BARE_KEY.test(key) ? key : JSON.stringify(key)
+}
+
+function formatScalar(value: unknown): string | null {
+  if (typeof value === 'string') return JSON.stringify(value)
+  if (typeof value === 'number' && Number.isFinite(value)) return String(value)
+  if (typeof value === 'boolean') return value ? 'true' : 'false'
+  if (Array.isArray(value)) {
+    const items = value.map(formatScalar).filter((v): v is string => v !== null)
+    return `[${items.join(', ')}]`
+  }
+  return null
+}
+
+function isTable(value: unknown): value is Record<string, unknown> {
+  return !!value && typeof value === 'object' && !Array.isArray(value)
+}
+
+function writeTable(obj: Record<string, unknown>, prefix: string[], out: string[]): void {
+  const scalars: string[] = []
+  const tables: Array<[string, Record<string, unknown>]> = []
+  for (const [key, value] of Object.entries(obj)) {
+    if (value === undefined || value === null) continue
+    if (isTable(value)) {
+      tables.push([key, value])
+      continue
+    }
+    const formatted = formatScalar(value)
+    if (formatted !== null) scalars.push(`${formatKey(key)} = ${formatted}`)
+  }
+  if (prefix.length > 0 && (scalars.length > 0 || tables.length === 0)) {
+    if (out.length > 0) out.push('')
+    out.push(`[${prefix.map(formatKey).join('.')}]`)
+  }
+  out.push(...scalars)
+  for (const [key, value] of tables) {
+    writeTable(value, [...prefix, key], out)
+  }
+}
+
+/**
+ * 序列化为 TOML 文本
+ */
+export function stringifyToml(obj: Record<string, unknown>): string {
+  const out: string[] = []
+  writeTable(obj, [], out)
+  return out.join('\n') + '\n'
+}
+
+class TomlParseError extends Error {
+  constructor(message: string, line: number) {
+    super(`TOML parse error at line ${line}: ${message}`)
+    this.name = 'TomlParseError'
+  }
+}
+
+/** 逐字符读取一行中的 key / value */
+class LineReader {
+  pos = 0
+  constructor(
+    private readonly text: string,
+    private readonly line: number
+  ) {}
+
+  fail(message: string): never {
+    throw new TomlParseError(message, this.line)
+  }
+
+  skipWs(): void {
+    while (this.pos < this.text.length && /[ \t]/.test(this.text[this.pos])) this.pos++
+  }
+
+  peek(): string {
+    return this.text[this.pos] ?? ''
+  }
+
+  atEnd(): boolean {
+    this.skipWs()
+    return this.pos >= this.text.length || this.peek() === '#'
+  }
+
+  readString(): string {
+    const quote = this.text[this.pos++]
+    let result = ''
+    while (this.pos < this.text.length) {
+      const ch = this.text[this.pos++]
+      if (ch === quote) return result
+      if (quote === '"' && ch === '\\') {
+        const esc = this.text[this.pos++]
+        const map: Record<string, string> = {
+          n: '\n',
+          t: '\t',
+          r: '\r',
+          '"': '"',
+          '\\': '\\',
+          b: '\b',
+          f: '\f'
+        }
+        if (esc === 'u' || esc === 'U') {
+          const len = esc === 'u' ? 4 : 8
+          const hex = this.text.slice(this.pos, this.pos + len)
+          if (!/^[0-9A-Fa-f]+$/.test(hex) || hex.length !== len) this.fail('invalid unicode escape')
+          result += String.fromCodePoint(parseInt(hex, 16))
+          this.pos += len
+        } else if (esc in map) {
+          result += map[esc]
+        } else {
+          this.fail(`invalid escape \\${esc}`)
+        }
+        continue
+      }
+      result += ch
+    }
+    return this.fail('unterminated string')
+  }
+
+  readKey(): string {
+    this.skipWs()
+    const ch = this.peek()
+    if (ch === '"' || ch === "'") return this.readString()
+    const start = this.pos
+    while (this.pos < this.text.length && /[A-Za-z0-9_-]/.test(this.peek())) this.pos++
+    if (start === this.pos) this.fail('expected key')
+    return this.text.slice(start, this.pos)
+  }
+
+  readDottedKey(terminator: string): string[] {
+    const parts = [this.readKey()]
+    this.skipWs()
+    while (this.peek() === '.') {
+      this.pos++
+      parts.push(this.readKey())
+      this.skipWs()
+    }
+    if (this.peek() !== terminator) this.fail(`expected '${terminator}'`)
+    this.pos++
+    return parts
+  }
+
+  readValue(): TomlValue {
+    this.skipWs()
+    const ch = this.peek()
+    if (ch === '"' || ch === "'") return this.readString()
+    if (ch === '[') {
+      this.pos++
+      const items: TomlValue[] = []
+      this.skipWs()
+      while (this.peek() !== ']') {
+        items.push(this.readValue())
+        this.skipWs()
+        if (this.peek() === ',') {
+          this.pos++
+          this.skipWs()
+        } else if (this.peek() !== ']') {
+          this.fail("expected ',' or ']'")
+        }
+      }
+      this.pos++
+      return items
+    }
+    const match = /^[^\s,\]#]+/.exec(this.text.slice(this.pos))
+    if (!match) return this.fail('expected value')
+    this.pos += match[0].length
+    const raw = match[0]
+    if (raw === 'true') return true
+    if (raw === 'false') return false
+    const num = Number(raw.replace(/_/g, ''))
+    if (!Number.isNaN(num)) return num
+    return this.fail(`invalid value '${raw}'`)
+  }
+}
+
+function descend(root: TomlTable, path: string[], line: number): TomlTable {
+  let table = root
+  for (const key of path) {
+    const next = table[key]
+    if (next === undefined) {
+      const created: TomlTable = {}
+      table[key] = created
+      table = created
+    } else if (isTable(next)) {
+      table = next as TomlTable
+    } else {
+      throw new TomlParseError(`key '${key}' is not a table`, line)
+    }
+  }
+  return table
+}
+
+/**
+ * 解析 TOML 文本
+ */
+export function parseToml(text: string): Record<string, unknown> {
+  const root: TomlTable = {}
+  let current = root
+  const lines = text.split(/\r?\n/)
+  for (let i = 0; i < lines.length; i++) {
+    const lineNo = i + 1
+    const reader = new LineReader(lines[i], lineNo)
+    if (reader.atEnd()) continue
+    if (reader.peek() === '[') {
+      reader.pos++
+      current = descend(root, reader.readDottedKey(']'), lineNo)
+    } else {
+      const path = reader.readDottedKey('=')
+      const value = reader.readValue()
+      const key = path.pop() as string
+      const table = descend(current, path, lineNo)
+      if (key in table) throw new TomlParseError(`duplicate key '${key}'`, lineNo)
+      table[key] = value
+    }
+    if (!reader.atEnd()) reader.fail('unexpected trailing characters')
+  }
+  return root
+}
@@ -1,49 +1,345 @@
-import { Tray, Menu, nativeImage, app } from 'electron'
-import { sharedState } from './config'
-import { createMainWindow } from './windowManager'
+import { Tray, Menu, nativeImage, app, clipboard } from 'electron'
+import type { BrowserWindow, MenuItemConstructorOptions, NativeImage } from 'electron'
+import log from 'electron-log/main'
+import { DEFAULT_TRAY_CLICK_BINDINGS, normalizeTrayClickBindings, sharedState } from './config'
+import type { PrizmConfig, TrayClickAction, TrayClickBindings } from './config'
+import { CONNECTION_STATE_LABELS, connectionState } from './connectionState'
+import type { ConnectionState } from './connectionState'
+import { notificationHistory } from './notificationHistory'
+import { realtimeConnection } from './realtime'
+import { setNotificationsPaused } from './serverNotifications'
+import { serverConfigToUrl } from './serverUrl'
+import { trayStatusMonitor } from './trayStatus'
+import type { TrayHealthStatus } from './trayStatus'
+import { openDashboard, showMainWindow } from './windowManager'
+
+/** 各连接状态的托盘图标颜色：已连接绿色，连接中灰色，连接不稳定黄色，未连接红色 */
+export const TRAY_ICON_COLORS: Record<ConnectionState, string> = {
+  connected: '#10b981',
+  connecting: '#a1a1aa',
+  degraded: '#f59e0b',
+  disconnected: '#ef4444'
+}
+
+export interface TrayMenuState {
+  /** 主窗口当前可见 */
+  windowVisible: boolean
+  connection: ConnectionState
+  /** 服务器基础地址，未配置服务器时为 null */
+  serverUrl: string | null
+  notificationsPaused: boolean
+}
+
+export interface TrayMenuActions {
+  toggleWindow(): void
+  openDashboard(url: string): void
+  reconnect(): void
+  setNotificationsPaused(paused: boolean): void
+  copyServerUrl(url: string): void
+  quit(): void
+}
 
 /**
- * 创建系统托盘
+ * 托盘菜单模板：显示/隐藏主窗口、打开管理面板、连接状态（只读）与重新连接、
+ * 暂停通知、复制服务器地址、退出
  */
-export function createTray(): void {
-  if (!sharedState.trayEnabled || sharedState.tray) {
-    return
+export function buildTrayMenuTemplate(
+  state: TrayMenuState,
+  actions: TrayMenuActions
+): MenuItemConstructorOptions[] {
+  const { serverUrl } = state
+  return [
+    {
+      label: state.windowVisible ? '隐藏窗口' : '显示窗口',
+      click: () => actions.toggleWindow()
+    },
+    {
+      label: '打开管理面板',
+      enabled: !!serverUrl,
+      click: () => serverUrl && actions.openDashboard(`${serverUrl}/dashboard/`)
+    },
+    { type: 'separator' },
+    { label: `连接状态：${CONNECTION_STATE_LABELS[state.connection]}`, enabled: false },
+    { label: '重新连接', enabled: !!serverUrl, click: () => actions.reconnect() },
+    { type: 'separator' },
+    {
+      label: '暂停通知',
+      type: 'checkbox',
+      checked: state.notificationsPaused,
+      click: () => actions.setNotificationsPaused(!state.notificationsPaused)
+    },
+    {
+      label: '复制服务器地址',
+      enabled: !!serverUrl,
+      click: () => serverUrl && actions.copyServerUrl(serverUrl)
+    },
+    { type: 'separator' },
+    { label: '退出', click: () => actions.quit() }
+  ]
+}
+
+/** 单击后等待双击的时间 */
+const DOUBLE_CLICK_DELAY_MS = 250
+
+export interface TrayClickHandlers {
+  click(): void
+  rightClick(): void
+  doubleClick(): void
+}
+
+/**
+ * 把托盘的单击、右键、双击映射为绑定的动作。绑定了双击时单击延迟执行、双击到来时取消，
+ * 避免双击前先触发单击；未绑定双击时单击立即响应
+ */
+export function createTrayClickHandlers(
+  bindings: () => TrayClickBindings,
+  run: (action: TrayClickAction) => void,
+  delay = DOUBLE_CLICK_DELAY_MS
+): TrayClickHandlers {
+  let timer: NodeJS.Timeout | undefined
+  return {
+    click() {
+      const { left_click, double_click } = bindings()
+      clearTimeout(timer)
+      if (double_click === 'none') {
+        run(left_click)
+        return
+      }
+      timer = setTimeout(() => run(left_click), delay)
+    },
+    rightClick() {
+      run(bindings().right_click)
+    },
+    doubleClick() {
+      clearTimeout(timer)
+      timer = undefined
+      run(bindings().double_click)
+    }
   }
+}
 
-  const icon = nativeImage.createEmpty()
-  sharedState.tray = new Tray(icon)
-  sharedState.tray.setToolTip('Prizm Electron Client')
+export interface TrayTooltipInfo extends TrayHealthStatus {
+  connection: ConnectionState
+  serverUrl: string | null
+  unread: number
+}
 
-  const contextMenu = Menu.buildFromTemplate([
-    {
-      label: '打开 Prizm',
-      click: () => {
-        const win = createMainWindow()
-        if (win) {
-          win.show()
-          win.focus()
-        }
+/** Windows 托盘提示文字最多 127 个字符 */
+const MAX_TOOLTIP_LENGTH = 127
+
+function clockTime(ms: number): string {
+  const date = new Date(ms)
+  return [date.getHours(), date.getMinutes(), date.getSeconds()]
+    .map((n) => String(n).padStart(2, '0'))
+    .join(':')
+}
+
+/**
+ * 托盘提示文字，悬停即可查看简要状态：连接状态、服务器地址、最近一次健康检查的时间与延迟、未读通知数
+ */
+export function trayTooltip(info: TrayTooltipInfo): string {
+  const lines = [`Prizm · ${CONNECTION_STATE_LABELS[info.connection]}`]
+  lines.push(info.serverUrl ?? '未配置服务器')
+  if (info.lastHealthyAt !== null) {
+    const time = clockTime(info.lastHealthyAt)
+    lines.push(
+      info.latencyMs !== null
+        ? `健康检查 ${time} · ${info.latencyMs} ms`
+        : `上次正常 ${time}，最近一次检查失败`
+    )
+  }
+  if (info.unread > 0) lines.push(`${info.unread} 条未读通知`)
+  const text = lines.join('\n')
+  return text.length > MAX_TOOLTIP_LENGTH ? `${text.slice(0, MAX_TOOLTIP_LENGTH - 1)}…` : text
+}
+
+/**
+ * 生成圆点图标（含 @2x），不依赖打包的图片资源
+ */
+export function createDotIcon(color: string): NativeImage {
+  const [r, g, b] = [1, 3, 5].map((i) => parseInt(color.slice(i, i + 2), 16))
+  const image = nativeImage.createEmpty()
+  for (const scaleFactor of [1, 2]) {
+    const size = 16 * scaleFactor
+    const bitmap = Buffer.alloc(size * size * 4)
+    const center = (size - 1) / 2
+    const radius = size * 0.375
+    for (let y = 0; y < size; y++) {
+      for (let x = 0; x < size; x++) {
+        const distance = Math.hypot(x - center, y - center)
+        // 边缘 1 像素做抗锯齿
+        const alpha = Math.max(0, Math.min(1, radius - distance + 0.5))
+        const offset = (y * size + x) * 4
+        // createFromBitmap 使用 BGRA 顺序
+        bitmap[offset] = b
+        bitmap[offset + 1] = g
+        bitmap[offset + 2] = r
+        bitmap[offset + 3] = Math.round(alpha * 255)
       }
+    }
+    image.addRepresentation({ scaleFactor, width: size, height: size, buffer: bitmap })
+  }
+  return image
+}
+
+const iconCache = new Map<ConnectionState, NativeImage>()
+/** 托盘当前显示的图标对应的状态，避免每次刷新都替换图标 */
+let iconState: ConnectionState | null = null
+
+function trayIcon(state: ConnectionState): NativeImage {
+  let icon = iconCache.get(state)
+  if (!icon) {
+    icon = createDotIcon(TRAY_ICON_COLORS[state])
+    iconCache.set(state, icon)
+  }
+  return icon
+}
+
+let serverUrl: string | null = null
+let watchingWindows = false
+let clickBindings: TrayClickBindings = DEFAULT_TRAY_CLICK_BINDINGS
+/** 最近一次构建的托盘菜单，供 menu 动作弹出 */
+let trayMenu: Menu | null = null
+
+/** 主窗口显示或隐藏时更新菜单中的“显示/隐藏窗口” */
+function watchMainWindow(): void {
+  if (watchingWindows) return
+  watchingWindows = true
+  const watch = (win: BrowserWindow) => {
+    win.on('show', refreshTray)
+    win.on('hide', refreshTray)
+  }
+  if (sharedState.mainWindow) watch(sharedState.mainWindow)
+  app.on('browser-window-created', (_event, win) => {
+    // 主窗口在 createMainWindow 返回后才写入 sharedState，下一轮再判断
+    setImmediate(() => {
+      if (win === sharedState.mainWindow) watch(win)
+    })
+  })
+}
+
+function toggleMainWindow(): void {
+  const win = sharedState.mainWindow
+  if (win && !win.isDestroyed() && win.isVisible() && !win.isMinimized()) {
+    win.hide()
+  } else {
+    showMainWindow()
+  }
+}
+
+function openDashboardFromTray(url: string): void {
+  openDashboard(url).catch((err) => {
+    log.error('[Electron] Tray open dashboard failed:', err)
+  })
+}
+
+function runTrayAction(action: TrayClickAction): void {
+  const tray = sharedState.tray
+  if (!tray || tray.isDestroyed()) return
+  if (action === 'toggle_window') {
+    toggleMainWindow()
+  } else if (action === 'menu') {
+    if (trayMenu) tray.popUpContextMenu(trayMenu)
+  } else if (action === 'dashboard') {
+    if (serverUrl) openDashboardFromTray(`${serverUrl}/dashboard/`)
+  }
+}
+
+/**
+ * 按当前窗口、连接状态、健康检查结果与未读数重建托盘菜单、提示文字和图标
+ */
+export function refreshTray(): void {
+  const tray = sharedState.tray
+  if (!tray || tray.isDestroyed()) return
+  const win = sharedState.mainWindow
+  const state = connectionState.get().state
+  const template = buildTrayMenuTemplate(
+    {
+      windowVisible: !!win && !win.isDestroyed() && win.isVisible(),
+      connection: state,
+      serverUrl,
+      notificationsPaused: sharedState.notificationsPaused
     },
-    { type: 'separator' },
     {
-      label: '退出',
-      click: () => {
+      toggleWindow: toggleMainWindow,
+      openDashboard: openDashboardFromTray,
+      reconnect: () => {
+        realtimeConnection.restart().catch((err) => {
+          log.warn('[Electron] Tray reconnect failed:', err)
+        })
+      },
+      setNotificationsPaused: (paused) => {
+        setNotificationsPaused(paused)
+        refreshTray()
+      },
+      copyServerUrl: (url) => clipboard.writeText(url),
+      quit: () => {
         sharedState.isQuitting = true
         app.quit()
       }
     }
-  ])
-
-  sharedState.tray.setContextMenu(contextMenu)
-  sharedState.tray.on('click', () => {
-    const win = createMainWindow()
-    if (!win) return
-    if (win.isVisible()) {
-      win.hide()
-    } else {
-      win.show()
-      win.focus()
-    }
-  })
+  )
+  trayMenu = Menu.buildFromTemplate(template)
+  // Linux 托盘（AppIndicator）不上报点击，只能挂上菜单；其他平台由点击绑定决定何时弹出
+  if (process.platform === 'linux') tray.setContextMenu(trayMenu)
+  tray.setToolTip(
+    trayTooltip({
+      connection: state,
+      serverUrl,
+      unread: notificationHistory.unreadCount(),
+      ...trayStatusMonitor.get()
+    })
+  )
+  if (iconState !== state) {
+    tray.setImage(trayIcon(state))
+    iconState = state
+  }
+}
+
+/**
+ * 创建系统托盘
+ */
+export function createTray(): void {
+  if (!sharedState.trayEnabled || sharedState.tray) {
+    return
+  }
+
+  iconState = connectionState.get().state
+  sharedState.tray = new Tray(trayIcon(iconState))
+  const handlers = createTrayClickHandlers(() => clickBindings, runTrayAction)
+  sharedState.tray.on('click', handlers.click)
+  sharedState.tray.on('right-click', handlers.rightClick)
+  sharedState.tray.on('double-click', handlers.doubleClick)
+  watchMainWindow()
+  refreshTray()
+  log.info('[Electron] Tray created')
+}
+
+/**
+ * 移除系统托盘
+ */
+export function destroyTray(): void {
+  if (!sharedState.tray) return
+  if (!sharedState.tray.isDestroyed()) sharedState.tray.destroy()
+  sharedState.tray = null
+  trayMenu = null
+  trayStatusMonitor.stop()
+  log.info('[Electron] Tray destroyed')
+}
+
+/**
+ * 按配置创建或移除托盘（启动及配置变更后调用）
+ */
+export function applyTrayConfig(config: PrizmConfig): void {
+  sharedState.trayEnabled = config.tray.enabled !== false
+  sharedState.minimizeToTray = config.tray.minimize_to_tray !== false
+  serverUrl = config.server.host ? serverConfigToUrl(config.server) : null
+  clickBindings = normalizeTrayClickBindings(config.tray)
+  if (sharedState.trayEnabled) {
+    createTray()
+    trayStatusMonitor.start(serverUrl, refreshTray)
+    refreshTray()
+  } else {
+    destroyTray()
+  }
 }
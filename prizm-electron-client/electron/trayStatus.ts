@@ -0,0 +1,76 @@
+import log from 'electron-log/main'
+import { isServerHealthy } from './serverHealth'
+
+/** 托盘存在期间健康检查的间隔 */
+export const TRAY_STATUS_INTERVAL_MS = 30_000
+
+/** 托盘提示文字中的健康检查结果 */
+export interface TrayHealthStatus {
+  /** 最近一次健康检查成功的时间，尚未成功过时为 null */
+  lastHealthyAt: number | null
+  /** 最近一次健康检查的往返耗时，失败时为 null */
+  latencyMs: number | null
+}
+
+export type HealthProbe = (url: string) => Promise<boolean>
+
+/**
+ * 定期检查当前服务器的 /health 并记录耗时，托盘提示文字据此显示最近检查时间与延迟。
+ * 服务器地址变化时清空旧结果并立即检查一次
+ */
+export class TrayStatusMonitor {
+  private timer: NodeJS.Timeout | undefined
+  private url: string | null = null
+  private status: TrayHealthStatus = { lastHealthyAt: null, latencyMs: null }
+  private onUpdate: () => void = () => undefined
+
+  constructor(
+    private readonly probe: HealthProbe = isServerHealthy,
+    private readonly now: () => number = Date.now,
+    private readonly intervalMs = TRAY_STATUS_INTERVAL_MS
+  ) {}
+
+  get(): TrayHealthStatus {
+    return { ...this.status }
+  }
+
+  /** 开始（或按新地址重新开始）定期检查，每次检查后调用 onUpdate */
+  start(url: string | null, onUpdate: () => void): void {
+    this.onUpdate = onUpdate
+    if (url === this.url && this.timer) return
+    this.stop()
+    this.url = url
+    if (!url) return
+    this.timer = setInterval(() => void this.check(), this.intervalMs)
+    this.timer.unref?.()
+    void this.check()
+  }
+
+  stop(): void {
+    clearInterval(this.timer)
+    this.timer = undefined
+    this.url = null
+    this.status = { lastHealthyAt: null, latencyMs: null }
+  }
+
+  async check(): Promise<void> {
+    const url = this.url
+    if (!url) return
+    const started = this.now()
+    let healthy = false
+    try {
+      healthy = await this.probe(url)
+    } catch (err) {
+      log.warn('[Tray] Health check failed:', err)
+    }
+    // 检查期间地址已变更或已停止，丢弃结果
+    if (url !== this.url) return
+    const finished = this.now()
+    this.status = healthy
+      ? { lastHealthyAt: finished, latencyMs: finished - started }
+      : { lastHealthyAt: this.status.lastHealthyAt, latencyMs: null }
+    this.onUpdate()
+  }
+}
+
+export const trayStatusMonitor = new TrayStatusMonitor()
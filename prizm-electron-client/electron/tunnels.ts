@@ -0,0 +1,225 @@
+import http from 'node:http'
+import log from 'electron-log/main'
+import type { PrizmConfig, TunnelConfig } from './config'
+import { updateConfig } from './config'
+import { realtimeConnection } from './realtime'
+import type { RealtimeConnection, ServerMessage } from './realtime'
+
+/** 请求体与响应体的大小上限，与服务端一致 */
+export const TUNNEL_MAX_BODY_BYTES = 10 * 1024 * 1024
+/** 本机服务的应答上限，需短于服务端的 30 秒 */
+const LOCAL_TIMEOUT_MS = 25_000
+
+/** 经隧道转发的请求，与 @prizm/shared 的 TunnelRequestMessage 一致 */
+export interface TunnelRequest {
+  requestId: string
+  name: string
+  method: string
+  path: string
+  headers: Record<string, string>
+  /** base64 */
+  body: string
+}
+
+export interface LocalResponse {
+  status: number
+  headers: Record<string, string>
+  body: Buffer
+}
+
+/** 向本机服务发出请求，便于测试替换 */
+export type LocalRequester = (
+  tunnel: TunnelConfig,
+  method: string,
+  path: string,
+  headers: Record<string, string>,
+  body: Buffer
+) => Promise<LocalResponse>
+
+interface TunnelServerState {
+  /** 服务端已接受 */
+  open: boolean
+  /** 服务端上的访问路径，未开放时为 null */
+  path: string | null
+  /** 服务端拒绝的原因 */
+  error: string | null
+}
+
+export type TunnelStatus = TunnelConfig & TunnelServerState
+
+function requestLocal(
+  tunnel: TunnelConfig,
+  method: string,
+  path: string,
+  headers: Record<string, string>,
+  body: Buffer
+): Promise<LocalResponse> {
+  return new Promise((resolve, reject) => {
+    const req = http.request(
+      {
+        host: tunnel.local_host,
+        port: tunnel.local_port,
+        method,
+        path,
+        headers: { ...headers, host: `${tunnel.local_host}:${tunnel.local_port}` },
+        timeout: LOCAL_TIMEOUT_MS
+      },
+      (res) => {
+        const chunks: Buffer[] = []
+        let size = 0
+        res.on('data', (chunk: Buffer) => {
+          size += chunk.length
+          if (size > TUNNEL_MAX_BODY_BYTES) {
+            res.destroy(new Error('Local response too large'))
+            return
+          }
+          chunks.push(chunk)
+        })
+        res.on('error', reject)
+        res.on('end', () => {
+          const responseHeaders: Record<string, string> = {}
+          for (const [key, value] of Object.entries(res.headers)) {
+            if (value !== undefined) {
+              responseHeaders[key] = Array.isArray(value) ? value.join(', ') : value
+            }
+          }
+          resolve({
+            status: res.statusCode ?? 502,
+            headers: responseHeaders,
+            body: Buffer.concat(chunks)
+          })
+        })
+      }
+    )
+    req.on('timeout', () => req.destroy(new Error('Local service timed out')))
+    req.on('error', reject)
+    req.end(body)
+  })
+}
+
+/**
+ * 反向隧道客户端：连接建立后向服务端开放已启用的隧道，
+ * 把服务端转发来的 tunnel_request 代理到本机服务并回送 tunnel_response。
+ * 只代理配置中已启用的隧道，其余请求一律 404
+ */
+export class TunnelClient {
+  private tunnels: TunnelConfig[] = []
+  private readonly status = new Map<string, TunnelServerState>()
+  private unsubscribe: (() => void) | null = null
+
+  constructor(
+    private readonly connection: RealtimeConnection = realtimeConnection,
+    private readonly requester: LocalRequester = requestLocal
+  ) {}
+
+  start(): void {
+    if (this.unsubscribe) return
+    this.unsubscribe = this.connection.onMessage((message) => this.handleMessage(message))
+  }
+
+  stop(): void {
+    this.unsubscribe?.()
+    this.unsubscribe = null
+  }
+
+  /** 按配置开放或关闭隧道（配置变更后调用） */
+  apply(config: PrizmConfig): void {
+    const previous = this.tunnels
+    this.tunnels = config.tunnels ?? []
+    for (const tunnel of previous) {
+      const next = this.find(tunnel.name)
+      if (tunnel.enabled && (!next || !next.enabled)) {
+        this.connection.sendMessage({ type: 'tunnel_close', name: tunnel.name })
+        this.status.delete(tunnel.name)
+      }
+    }
+    for (const tunnel of this.tunnels) {
+      const before = previous.find((t) => t.name === tunnel.name)
+      if (tunnel.enabled && (!before?.enabled || before.scope !== tunnel.scope)) {
+        this.announce(tunnel)
+      }
+    }
+  }
+
+  list(): TunnelStatus[] {
+    return this.tunnels.map((tunnel) => ({
+      ...tunnel,
+      ...(this.status.get(tunnel.name) ?? { open: false, path: null, error: null })
+    }))
+  }
+
+  /** 开启或关闭某个已配置的隧道；返回更新后的列表 */
+  async setEnabled(name: string, enabled: boolean): Promise<TunnelStatus[]> {
+    if (!this.find(name)) throw new Error(`Tunnel ${name} is not configured`)
+    const config = await updateConfig((current) => {
+      current.tunnels = (current.tunnels ?? []).map((tunnel) =>
+        tunnel.name === name ? { ...tunnel, enabled } : tunnel
+      )
+    })
+    this.apply(config)
+    return this.list()
+  }
+
+  private find(name: string): TunnelConfig | undefined {
+    return this.tunnels.find((tunnel) => tunnel.name === name)
+  }
+
+  private announce(tunnel: TunnelConfig): void {
+    this.connection.sendMessage({ type: 'tunnel_open', name: tunnel.name, scope: tunnel.scope })
+  }
+
+  private handleMessage(message: ServerMessage): void {
+    if (message.type === 'connected') {
+      // 服务端在断线时已关闭全部隧道，重连后重新开放
+      this.status.clear()
+      for (const tunnel of this.tunnels) {
+        if (tunnel.enabled) this.announce(tunnel)
+      }
+    } else if (message.type === 'tunnel_status') {
+      const name = String(message.name)
+      if (!this.find(name)) return
+      const error = typeof message.error === 'string' ? message.error : null
+      if (error) log.warn('[Tunnels] Server rejected tunnel', name, ':', error)
+      this.status.set(name, {
+        open: message.open === true,
+        path: typeof message.path === 'string' ? message.path : null,
+        error
+      })
+    } else if (message.type === 'tunnel_request') {
+      void this.forward(message as unknown as TunnelRequest)
+    }
+  }
+
+  private async forward(request: TunnelRequest): Promise<void> {
+    const tunnel = this.find(request.name)
+    let response: LocalResponse
+    if (!tunnel?.enabled) {
+      response = {
+        status: 404,
+        headers: { 'content-type': 'application/json' },
+        body: Buffer.from(JSON.stringify({ error: 'Tunnel not found' }))
+      }
+    } else {
+      try {
+        const body = Buffer.from(request.body ?? '', 'base64')
+        response = await this.requester(tunnel, request.method, request.path, request.headers, body)
+      } catch (err) {
+        log.warn('[Tunnels] Local request for', request.name, 'failed:', err)
+        response = {
+          status: 502,
+          headers: { 'content-type': 'application/json' },
+          body: Buffer.from(JSON.stringify({ error: 'Local service unavailable' }))
+        }
+      }
+    }
+    this.connection.sendMessage({
+      type: 'tunnel_response',
+      requestId: request.requestId,
+      status: response.status,
+      headers: response.headers,
+      body: response.body.toString('base64')
+    })
+  }
+}
+
+export const tunnelClient = new TunnelClient()
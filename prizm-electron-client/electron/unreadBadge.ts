@@ -0,0 +1,59 @@
+import { app } from 'electron'
+import type { BrowserWindow, NativeImage } from 'electron'
+import { sharedState } from './config'
+import { notificationHistory } from './notificationHistory'
+import { createDotIcon, refreshTray } from './trayManager'
+
+/** Windows 任务栏覆盖图标的颜色 */
+const OVERLAY_COLOR = '#ef4444'
+
+let overlayIcon: NativeImage | null = null
+
+/** 角标文字：无未读时为空，超过 99 显示 99+ */
+export function badgeText(count: number): string {
+  if (count <= 0) return ''
+  return count > 99 ? '99+' : String(count)
+}
+
+/**
+ * 按未读数更新角标：macOS 为 Dock 角标与托盘标题，Windows 为任务栏覆盖图标，
+ * Linux 为启动器计数（桌面环境支持时）；托盘提示文字同时更新
+ */
+export function applyUnreadBadge(count: number): void {
+  if (process.platform === 'darwin') {
+    app.dock?.setBadge(badgeText(count))
+    const tray = sharedState.tray
+    if (tray && !tray.isDestroyed()) tray.setTitle(badgeText(count))
+  } else if (process.platform === 'win32') {
+    const win = sharedState.mainWindow
+    if (win && !win.isDestroyed()) {
+      if (count > 0) {
+        overlayIcon ??= createDotIcon(OVERLAY_COLOR)
+        win.setOverlayIcon(overlayIcon, `${count} 条未读通知`)
+      } else {
+        win.setOverlayIcon(null, '')
+      }
+    }
+  } else {
+    app.setBadgeCount(count)
+  }
+  refreshTray()
+}
+
+/**
+ * 角标跟随通知中心的未读数；主窗口获得焦点时全部标为已读，角标随之清除。返回取消函数
+ */
+export function watchUnreadBadge(): () => void {
+  const onFocus = (_event: unknown, win: BrowserWindow) => {
+    if (win === sharedState.mainWindow && notificationHistory.unreadCount() > 0) {
+      notificationHistory.markRead()
+    }
+  }
+  app.on('browser-window-focus', onFocus)
+  const unsubscribe = notificationHistory.onChange(applyUnreadBadge)
+  applyUnreadBadge(notificationHistory.unreadCount())
+  return () => {
+    unsubscribe()
+    app.removeListener('browser-window-focus', onFocus)
+  }
+}
@@ -0,0 +1,126 @@
+import { screen } from 'electron'
+import type { BrowserWindow, Rectangle } from 'electron'
+import log from 'electron-log/main'
+import { updateConfig } from './config'
+import type { PrizmConfig, WindowConfig, WindowGeometry } from './config'
+
+/** 移动、缩放结束后延迟保存，拖动过程中不反复写配置 */
+const SAVE_DELAY_MS = 1_000
+/** 最多记住的显示器布局数，超出时丢弃最早记录的 */
+const MAX_LAYOUTS = 8
+/** 窗口在某个显示器工作区内至少可见这么多像素才恢复，否则回到默认位置 */
+const MIN_VISIBLE_PX = 100
+
+/** 记住位置与大小的窗口及其在配置中的字段 */
+const WINDOW_CONFIG_KEYS = {
+  main: 'window',
+  dashboard: 'dashboard_window'
+} as const
+
+export type ManagedWindow = keyof typeof WINDOW_CONFIG_KEYS
+
+export interface DisplayInfo {
+  bounds: Rectangle
+  workArea: Rectangle
+  scaleFactor: number
+}
+
+const windowConfigs: Partial<Record<ManagedWindow, WindowConfig>> = {}
+
+/**
+ * 显示器布局标识：各显示器的位置、尺寸与缩放，与显示器顺序无关
+ */
+export function displayLayoutKey(displays: DisplayInfo[]): string {
+  return displays
+    .map(({ bounds: b, scaleFactor }) => `${b.x},${b.y},${b.width}x${b.height}@${scaleFactor}`)
+    .sort()
+    .join(';')
+}
+
+/** 窗口是否有足够部分落在某个显示器的工作区内 */
+export function isVisibleOn(bounds: Rectangle, displays: DisplayInfo[]): boolean {
+  return displays.some(({ workArea: area }) => {
+    const width =
+      Math.min(bounds.x + bounds.width, area.x + area.width) - Math.max(bounds.x, area.x)
+    const height =
+      Math.min(bounds.y + bounds.height, area.y + area.height) - Math.max(bounds.y, area.y)
+    return width >= MIN_VISIBLE_PX && height >= MIN_VISIBLE_PX
+  })
+}
+
+/** 同步配置中的窗口记录（启动时与配置变更时调用） */
+export function applyWindowConfig(config: PrizmConfig): void {
+  for (const [name, key] of Object.entries(WINDOW_CONFIG_KEYS)) {
+    windowConfigs[name as ManagedWindow] = config[key]
+  }
+}
+
+/**
+ * 当前显示器布局下记住的窗口状态；没有记录或记录的位置已不可见时返回 null
+ */
+export function savedWindowGeometry(
+  name: ManagedWindow = 'main',
+  displays: DisplayInfo[] = screen.getAllDisplays()
+): WindowGeometry | null {
+  const geometry = windowConfigs[name]?.layouts[displayLayoutKey(displays)]
+  if (!geometry || !isVisibleOn(geometry, displays)) return null
+  return geometry
+}
+
+function sameGeometry(a: WindowGeometry | undefined, b: WindowGeometry): boolean {
+  return (
+    !!a &&
+    a.x === b.x &&
+    a.y === b.y &&
+    a.width === b.width &&
+    a.height === b.height &&
+    a.maximized === b.maximized
+  )
+}
+
+/**
+ * 保存当前显示器布局下的窗口状态；与已有记录相同时不写配置
+ */
+export async function saveWindowGeometry(
+  geometry: WindowGeometry,
+  name: ManagedWindow = 'main',
+  key: string = displayLayoutKey(screen.getAllDisplays())
+): Promise<void> {
+  if (sameGeometry(windowConfigs[name]?.layouts[key], geometry)) return
+  const configKey = WINDOW_CONFIG_KEYS[name]
+  const config = await updateConfig((config) => {
+    const layouts = { ...config[configKey]?.layouts }
+    // 重新插入到末尾，按插入顺序淘汰最久未用的布局
+    delete layouts[key]
+    layouts[key] = geometry
+    for (const stale of Object.keys(layouts).slice(0, -MAX_LAYOUTS)) delete layouts[stale]
+    config[configKey] = { layouts }
+  })
+  applyWindowConfig(config)
+}
+
+/**
+ * 跟踪窗口的位置、大小与最大化状态：移动、缩放后防抖保存，关闭时立即保存。
+ * 最大化时保存的是还原后的尺寸，恢复时先按该尺寸创建再最大化
+ */
+export function trackWindowGeometry(win: BrowserWindow, name: ManagedWindow = 'main'): void {
+  let timer: NodeJS.Timeout | undefined
+  const save = () => {
+    clearTimeout(timer)
+    timer = undefined
+    if (win.isDestroyed() || win.isMinimized() || !win.isVisible()) return
+    const { x, y, width, height } = win.getNormalBounds()
+    const geometry = { x, y, width, height, maximized: win.isMaximized() }
+    void saveWindowGeometry(geometry, name).catch((err) =>
+      log.warn(`[Electron] Failed to save ${name} window geometry:`, err)
+    )
+  }
+  const schedule = () => {
+    clearTimeout(timer)
+    timer = setTimeout(save, SAVE_DELAY_MS)
+  }
+  for (const event of ['resize', 'move', 'maximize', 'unmaximize'] as const) {
+    win.on(event, schedule)
+  }
+  win.on('close', save)
+}
@@ -1,7 +1,12 @@
 import { app, BrowserWindow, ipcMain, shell, screen, nativeTheme } from 'electron'
 import * as path from 'path'
 import log from 'electron-log/main'
-import { sharedState } from './config'
+import { MIN_WINDOW_HEIGHT, MIN_WINDOW_WIDTH, sharedState } from './config'
+import { httpClient } from './httpClient'
+import { upgradeToTls } from './serverUrl'
+import { CONNECTION_STATE_LABELS, connectionState } from './connectionState'
+import type { ConnectionState } from './connectionState'
+import { savedWindowGeometry, trackWindowGeometry } from './windowGeometry'
 
 const DEBUG_NOTIFY = true
 function logNotify(...args: unknown[]) {
@@ -9,9 +14,134 @@ function logNotify(...args: unknown[]) {
 }
 
 /**
- * 创建主窗口
+ * 按连接状态更新主窗口标题（任务栏、窗口切换器中可见）
  */
-export function createMainWindow(): BrowserWindow {
+export function updateMainWindowTitle(state: ConnectionState): void {
+  const win = sharedState.mainWindow
+  if (!win || win.isDestroyed()) return
+  win.setTitle(state === 'connected' ? 'Prizm' : `Prizm（${CONNECTION_STATE_LABELS[state]}）`)
+}
+
+/**
+ * 托盘存在且开启 tray.minimize_to_tray 时，关闭与最小化主窗口改为隐藏到托盘
+ */
+function hidesToTray(): boolean {
+  return !!sharedState.tray && sharedState.minimizeToTray
+}
+
+/**
+ * 显示并聚焦主窗口（不存在时创建）；最小化后隐藏到托盘的窗口先还原
+ */
+export function showMainWindow(): BrowserWindow {
+  const win = createMainWindow()
+  if (win.isMinimized()) win.restore()
+  win.show()
+  win.focus()
+  return win
+}
+
+/**
+ * 在浏览器中打开服务端管理面板；开启 network.require_tls 时升级为 https
+ */
+export async function openDashboard(dashboardUrl: string): Promise<void> {
+  await shell.openExternal(
+    httpClient.getNetworkConfig().require_tls ? upgradeToTls(dashboardUrl) : dashboardUrl
+  )
+}
+
+/** 内嵌管理面板所在的源，面板内只允许在该源内导航 */
+let dashboardOrigin: string | null = null
+
+function isDashboardUrl(url: string): boolean {
+  try {
+    return new URL(url).origin === dashboardOrigin
+  } catch {
+    return false
+  }
+}
+
+function loadDashboard(win: BrowserWindow, url: string): void {
+  win.loadURL(url).catch((err: Error) => {
+    log.warn('[Electron] Dashboard failed to load:', err.message)
+  })
+}
+
+/**
+ * 在应用内窗口中打开服务端管理面板，已打开时切换地址并聚焦；开启 network.require_tls 时升级为 https。
+ * 面板使用独立的持久化会话（登录状态与主窗口隔离），位置与大小单独记住
+ */
+export function openDashboardWindow(dashboardUrl: string): BrowserWindow {
+  const url = httpClient.getNetworkConfig().require_tls ? upgradeToTls(dashboardUrl) : dashboardUrl
+  dashboardOrigin = new URL(url).origin
+
+  const existing = sharedState.dashboardWindow
+  if (existing && !existing.isDestroyed()) {
+    if (existing.webContents.getURL() !== url) loadDashboard(existing, url)
+    if (existing.isMinimized()) existing.restore()
+    existing.show()
+    existing.focus()
+    return existing
+  }
+
+  const geometry = savedWindowGeometry('dashboard')
+  const win = new BrowserWindow({
+    width: geometry?.width ?? 1200,
+    height: geometry?.height ?? 800,
+    ...(geometry && { x: geometry.x, y: geometry.y }),
+    minWidth: MIN_WINDOW_WIDTH,
+    minHeight: MIN_WINDOW_HEIGHT,
+    title: 'Prizm 管理面板',
+    show: false,
+    backgroundColor: nativeTheme.shouldUseDarkColors ? '#000000' : '#ffffff',
+    webPreferences: {
+      partition: 'persist:dashboard',
+      contextIsolation: true,
+      nodeIntegration: false,
+      sandbox: true
+    }
+  })
+  sharedState.dashboardWindow = win
+
+  const show = () => {
+    if (win.isDestroyed() || win.isVisible()) return
+    if (geometry?.maximized) win.maximize()
+    else win.show()
+  }
+  win.once('ready-to-show', show)
+  // 服务器不可达时也显示窗口，让用户看到加载失败
+  win.webContents.once('did-fail-load', show)
+  trackWindowGeometry(win, 'dashboard')
+
+  win.on('closed', () => {
+    sharedState.dashboardWindow = null
+  })
+
+  win.webContents.setWindowOpenHandler(({ url: target }) => {
+    if (isDashboardUrl(target)) {
+      loadDashboard(win, target)
+    } else {
+      shell.openExternal(target)
+    }
+    return { action: 'deny' }
+  })
+  win.webContents.on('will-navigate', (event, target) => {
+    if (!isDashboardUrl(target)) {
+      event.preventDefault()
+      shell.openExternal(target)
+    }
+  })
+
+  loadDashboard(win, url)
+  return win
+}
+
+/**
+ * 创建主窗口；showOnReady 为 false 时加载完成后保持隐藏（启动到托盘）。
+ * 按当前显示器布局恢复上次的位置、大小与最大化状态，显示前即就位，避免闪动
+ */
+export function createMainWindow({
+  showOnReady = true
+}: { showOnReady?: boolean } = {}): BrowserWindow {
   const isDev = !app.isPackaged
 
   if (sharedState.mainWindow) {
@@ -20,12 +150,14 @@ export function createMainWindow(): BrowserWindow {
 
   const isDark = nativeTheme.shouldUseDarkColors
   const bgColor = isDark ? '#000000' : '#ffffff'
+  const geometry = savedWindowGeometry('main')
 
   sharedState.mainWindow = new BrowserWindow({
-    width: 980,
-    height: 640,
-    minWidth: 400,
-    minHeight: 500,
+    width: geometry?.width ?? 980,
+    height: geometry?.height ?? 640,
+    ...(geometry && { x: geometry.x, y: geometry.y }),
+    minWidth: MIN_WINDOW_WIDTH,
+    minHeight: MIN_WINDOW_HEIGHT,
     resizable: true,
     show: false,
     backgroundColor: bgColor,
@@ -46,9 +178,20 @@ export function createMainWindow(): BrowserWindow {
 
   const mainWindow = sharedState.mainWindow
 
-  mainWindow.once('ready-to-show', () => {
-    mainWindow?.show()
-  })
+  // maximize() 会同时显示窗口，因此在原本显示的时机调用
+  if (showOnReady) {
+    mainWindow.once('ready-to-show', () => {
+      if (geometry?.maximized) mainWindow.maximize()
+      else mainWindow.show()
+    })
+  } else if (geometry?.maximized) {
+    mainWindow.once('show', () => mainWindow.maximize())
+  }
+  trackWindowGeometry(mainWindow)
+
+  // 标题由主进程按连接状态维护，不使用页面的 <title>
+  mainWindow.on('page-title-updated', (event) => event.preventDefault())
+  updateMainWindowTitle(connectionState.get().state)
 
   if (isDev) {
     mainWindow.loadURL('http://localhost:5183')
@@ -61,12 +204,16 @@ export function createMainWindow(): BrowserWindow {
     if (sharedState.isQuitting) {
       return
     }
-    if (sharedState.trayEnabled && sharedState.minimizeToTray) {
+    if (hidesToTray()) {
       event.preventDefault()
-      sharedState.mainWindow!.hide()
+      mainWindow.hide()
     }
   })
 
+  mainWindow.on('minimize', () => {
+    if (hidesToTray()) mainWindow.hide()
+  })
+
   mainWindow.on('closed', () => {
     sharedState.mainWindow = null
   })
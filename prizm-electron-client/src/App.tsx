@@ -1,7 +1,7 @@
 import { ActionIcon, Icon } from '@lobehub/ui'
 import { Segmented } from './components/ui/Segmented'
 import { App as AntdApp, Modal } from 'antd'
-import { buildServerUrl, type NotificationPayload } from '@prizm/client-core'
+import type { NotificationPayload } from '@prizm/client-core'
 import type { LucideIcon } from 'lucide-react'
 import {
   FileText,
@@ -33,6 +33,7 @@ import { LogsDrawer } from './components/LogsDrawer'
 import { CommandPalette } from './components/CommandPalette'
 import { useHashRoute } from './hooks/useHashRoute'
 import { useScopeDataBinding } from './hooks/useScopeDataBinding'
+import { useConsentPrompts } from './hooks/useConsentPrompts'
 import { QuickActionHandler } from './components/QuickActionHandler'
 import CollaborationPage from './views/CollaborationPage'
 import DocumentEditorPage from './views/DocumentEditorPage'
@@ -86,6 +87,7 @@ function AppContent() {
 
   useHashRoute(activePage, setActivePage)
   useScopeDataBinding()
+  useConsentPrompts(addLog)
 
   /**
    * Stable: 带离开保护的页面切换 — 通过 ref 读取当前页，回调引用永久稳定。
@@ -112,6 +114,19 @@ function AppContent() {
   const navigateToAgent = useCallback(() => setActivePageSafe('agent'), [setActivePageSafe])
   const navigateToWorkflow = useCallback(() => setActivePageSafe('workflow'), [setActivePageSafe])
 
+  /** 主进程要求打开的设置分类；每次跳转都是新对象，重复跳转到同一分类也会生效 */
+  const [settingsFocus, setSettingsFocus] = useState<{ section: AppRoute['section'] } | null>(
+    null
+  )
+  useEffect(
+    () =>
+      window.prizm.onNavigate((route) => {
+        setActivePageSafe(route.page)
+        setSettingsFocus({ section: route.section })
+      }),
+    [setActivePageSafe]
+  )
+
   /** 懒挂载 + 空闲预加载：首屏只挂载 home，之后空闲时预挂载高频页面 */
   const mountedPagesRef = useRef(new Set<PageKey>(['home']))
   mountedPagesRef.current.add(activePage)
@@ -144,6 +159,25 @@ function AppContent() {
     const unsubscribeClipboard = window.prizm.onClipboardItemAdded(() => {
       setLastSyncEvent('clipboard:itemAdded')
     })
+    const unsubscribeDeregistered = window.prizm.onDeregistered(() => {
+      localStorage.removeItem('prizm.onboardingCompleted')
+      disconnect()
+      addLog('客户端已注销', 'info')
+      setActivePage('settings')
+    })
+    const unsubscribeAutoRegister = window.prizm.onAutoRegister(async (event) => {
+      if (event.status === 'failed') {
+        addLog(`自动注册失败，请到设置页手动注册: ${event.message}`, 'warning')
+        return
+      }
+      const cfg = await loadConfig()
+      if (!cfg?.api_key) return
+      addLog('已自动注册并连接', 'success')
+      await initializePrizm(cfg, {
+        onLog: addLog,
+        onNotify: (p: NotificationPayload) => addLog(`通知: ${p.title}`, 'info')
+      })
+    })
 
     async function init() {
       try {
@@ -154,28 +188,9 @@ function AppContent() {
           return
         }
         if (!cfg.api_key?.length) {
-          if (cfg.client?.auto_register === 'true' && cfg.server?.host && cfg.server?.port) {
-            try {
-              const serverUrl = buildServerUrl(cfg.server.host, cfg.server.port)
-              const apiKey = await window.prizm.registerClient(
-                serverUrl,
-                cfg.client.name || 'Prizm Electron Client',
-                cfg.client.requested_scopes ?? ['default', 'online']
-              )
-              if (apiKey) {
-                const newCfg = await loadConfig()
-                if (newCfg?.api_key) {
-                  addLog('已自动注册并连接', 'success')
-                  await initializePrizm(newCfg, {
-                    onLog: addLog,
-                    onNotify: (p: NotificationPayload) => addLog(`通知: ${p.title}`, 'info')
-                  })
-                  return
-                }
-              }
-            } catch {
-              addLog('自动注册失败，请到设置页手动注册', 'warning')
-            }
+          // 开启 auto_register 时主进程已在后台注册，结果见 onAutoRegister
+          if (cfg.client?.auto_register === true && cfg.server?.host) {
+            addLog('正在后台自动注册客户端…', 'info')
           } else {
             addLog('需要注册客户端获取 API Key', 'warning')
           }
@@ -195,6 +210,8 @@ function AppContent() {
 
     return () => {
       unsubscribeClipboard?.()
+      unsubscribeDeregistered()
+      unsubscribeAutoRegister()
       disconnect()
     }
   }, [addLog, loadConfig, initializePrizm, disconnect])
@@ -367,7 +384,7 @@ function AppContent() {
                       activePage !== 'settings' ? ' page-keep-alive--hidden' : ''
                     }`}
                   >
-                    <SettingsPage />
+                    <SettingsPage focus={settingsFocus} />
                   </div>
                 )}
                 {mounted.has('test') && (
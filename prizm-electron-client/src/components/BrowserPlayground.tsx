@@ -12,7 +12,8 @@ import {
 
 export interface BrowserPlaygroundProps {
   baseUrl: string
-  apiKey: string
+  /** 是否已注册；请求的 Authorization 由主进程附加 */
+  authenticated: boolean
   isNodeRunning?: boolean
 }
 
@@ -34,7 +35,11 @@ interface TestResult {
 
 const { Text } = Typography
 
-export function BrowserPlayground({ baseUrl, apiKey, isNodeRunning }: BrowserPlaygroundProps) {
+export function BrowserPlayground({
+  baseUrl,
+  authenticated,
+  isNodeRunning
+}: BrowserPlaygroundProps) {
   const [testingRelay, setTestingRelay] = useState(false)
   const [relayStatus, setRelayStatus] = useState<boolean | null>(null)
   const [runningAction, setRunningAction] = useState<TestAction | null>(null)
@@ -47,16 +52,11 @@ export function BrowserPlayground({ baseUrl, apiKey, isNodeRunning }: BrowserPla
   const [selectRef, setSelectRef] = useState(0)
   const [selectValue, setSelectValue] = useState('')
 
-  const headers: Record<string, string> = {}
-  if (apiKey) {
-    headers['X-Prizm-Api-Key'] = apiKey
-  }
-
   const testRelayConnection = async () => {
     setTestingRelay(true)
     setRelayStatus(null)
     try {
-      const res = await fetch(`${baseUrl}/api/v1/browser/relay/status`, { headers })
+      const res = await fetch(`${baseUrl}/api/v1/browser/relay/status`)
       const data = await res.json().catch(() => ({}))
       if (!res.ok) {
         message.error(data?.error ?? `请求失败: ${res.status}`)
@@ -83,7 +83,7 @@ export function BrowserPlayground({ baseUrl, apiKey, isNodeRunning }: BrowserPla
       try {
         const res = await fetch(`${baseUrl}/api/v1/browser/test`, {
           method: 'POST',
-          headers: { 'Content-Type': 'application/json', ...headers },
+          headers: { 'Content-Type': 'application/json' },
           body: JSON.stringify({ action, ...payload })
         })
         const data = await res.json().catch(() => ({}))
@@ -111,10 +111,10 @@ export function BrowserPlayground({ baseUrl, apiKey, isNodeRunning }: BrowserPla
         setRunningAction(null)
       }
     },
-    [baseUrl, headers]
+    [baseUrl]
   )
 
-  const disabled = !apiKey || !isNodeRunning
+  const disabled = !authenticated || !isNodeRunning
 
   const testItems = [
     {
@@ -333,7 +333,7 @@ export function BrowserPlayground({ baseUrl, apiKey, isNodeRunning }: BrowserPla
             size="small"
             danger
             loading={runningAction === 'close'}
-            disabled={!apiKey}
+            disabled={!authenticated}
             onClick={() => runTest('close', {})}
           >
             关闭会话
@@ -357,7 +357,7 @@ export function BrowserPlayground({ baseUrl, apiKey, isNodeRunning }: BrowserPla
           icon={<Wifi size={14} />}
           loading={testingRelay}
           onClick={testRelayConnection}
-          disabled={!apiKey}
+          disabled={!authenticated}
         >
           检测 Relay 连接
         </Button>
@@ -0,0 +1,105 @@
+/**
+ * 设备管理 - 列出注册在服务器上的客户端，吊销不再使用的设备（需管理员权限）
+ */
+import { Button } from '@lobehub/ui'
+import type { ListItemProps } from '@lobehub/ui'
+import { AccentList } from './ui/AccentList'
+import { useState, useCallback, useEffect } from 'react'
+
+interface DevicesSettingsProps {
+  onLog: (msg: string, type: 'info' | 'success' | 'error' | 'warning') => void
+}
+
+function formatTime(ts: number | null): string {
+  return ts ? new Date(ts).toLocaleString('zh-CN') : '从未使用'
+}
+
+export function DevicesSettings({ onLog }: DevicesSettingsProps) {
+  const [clients, setClients] = useState<ClientInfo[]>([])
+  const [loading, setLoading] = useState(false)
+  const [error, setError] = useState('')
+  const [revoking, setRevoking] = useState<string | null>(null)
+
+  const refresh = useCallback(async () => {
+    setLoading(true)
+    setError('')
+    try {
+      setClients(await window.prizm.listClients())
+    } catch (e) {
+      setError(String(e))
+    } finally {
+      setLoading(false)
+    }
+  }, [])
+
+  useEffect(() => {
+    void refresh()
+  }, [refresh])
+
+  async function handleRevoke(client: ClientInfo) {
+    const hint = client.current
+      ? '这是当前设备，吊销后需要重新注册。'
+      : '该设备将无法再访问服务器。'
+    if (!window.confirm(`确定要吊销「${client.name}」吗？${hint}`)) return
+    setRevoking(client.id)
+    try {
+      await window.prizm.revokeClient(client.id)
+      onLog(`已吊销设备: ${client.name}`, 'success')
+      if (!client.current) await refresh()
+    } catch (e) {
+      onLog(`吊销设备失败: ${String(e)}`, 'error')
+    } finally {
+      setRevoking(null)
+    }
+  }
+
+  return (
+    <div className="settings-section">
+      <div className="settings-section-header">
+        <h2>设备</h2>
+        <p className="form-hint">
+          注册在当前服务器上的全部客户端。需要管理员 Key 或拥有全部权限的客户端
+        </p>
+      </div>
+      <div className="scope-list">
+        {loading ? (
+          <div className="scope-list-placeholder">加载中...</div>
+        ) : error ? (
+          <div className="scope-list-placeholder">无法获取设备列表：{error}</div>
+        ) : clients.length === 0 ? (
+          <div className="scope-list-placeholder">暂无设备</div>
+        ) : (
+          <AccentList
+            items={clients.map((c) => {
+              const item: ListItemProps = {
+                key: c.id,
+                title: c.current ? `${c.name}（当前设备）` : c.name,
+                description: `最近使用 ${formatTime(c.lastSeen)} · 注册于 ${formatTime(
+                  c.createdAt
+                )} · ${c.scopes.join(', ')}`,
+                actions: (
+                  <Button
+                    size="small"
+                    type="text"
+                    danger
+                    loading={revoking === c.id}
+                    onClick={() => handleRevoke(c)}
+                  >
+                    吊销
+                  </Button>
+                ),
+                showAction: true
+              }
+              return item
+            })}
+          />
+        )}
+      </div>
+      <div className="config-actions" style={{ marginTop: 8 }}>
+        <Button onClick={() => void refresh()} loading={loading}>
+          刷新
+        </Button>
+      </div>
+    </div>
+  )
+}
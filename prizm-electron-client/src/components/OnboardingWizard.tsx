@@ -5,6 +5,7 @@ import { useState, useEffect } from 'react'
 import { CheckCircle, Globe, Key, Rocket, User, Zap } from 'lucide-react'
 import { buildServerUrl } from '@prizm/client-core'
 import type { PrizmConfig } from '@prizm/client-core'
+import { PORT_INPUT_ERROR, parsePortInput, portInputError } from '../utils/serverPort'
 
 export interface UserProfilePatch {
   displayName?: string
@@ -14,7 +15,7 @@ export interface UserProfilePatch {
 interface OnboardingWizardProps {
   onComplete: () => void
   testConnection: (serverUrl: string) => Promise<boolean>
-  registerClient: (serverUrl: string, name: string, scopes: string[]) => Promise<string | null>
+  registerClient: (serverUrl: string, name: string, scopes: string[]) => Promise<boolean>
   saveConfig: (cfg: PrizmConfig) => Promise<boolean>
   loadConfig: () => Promise<PrizmConfig | null>
   setConfig: (cfg: PrizmConfig) => void
@@ -42,6 +43,7 @@ export function OnboardingWizard({
   }, [initialStep])
   const [host, setHost] = useState('127.0.0.1')
   const [port, setPort] = useState('4127')
+  const portError = portInputError(port)
   const [clientName, setClientName] = useState('Prizm Electron Client')
   const [scopes, setScopes] = useState('default, online')
   const [testing, setTesting] = useState(false)
@@ -55,7 +57,7 @@ export function OnboardingWizard({
 
   // 第一步挂载时自动检测当前地址是否已有服务端
   useEffect(() => {
-    if (step !== 0 || !host?.trim() || !port?.trim()) {
+    if (step !== 0 || !host?.trim() || parsePortInput(port) === null) {
       setAutoDetecting(false)
       return
     }
@@ -70,7 +72,7 @@ export function OnboardingWizard({
   }, [step, host, port, testConnection])
 
   async function handleTestConnection() {
-    if (!host.trim() || !port.trim()) {
+    if (!host.trim() || parsePortInput(port) === null) {
       toast.error('请填写服务器地址和端口')
       return
     }
@@ -89,6 +91,11 @@ export function OnboardingWizard({
   }
 
   async function handleRegister(): Promise<boolean> {
+    const portNumber = parsePortInput(port)
+    if (portNumber === null) {
+      toast.error(PORT_INPUT_ERROR)
+      return false
+    }
     if (!clientName.trim()) {
       toast.error('请填写客户端名称')
       return false
@@ -105,31 +112,30 @@ export function OnboardingWizard({
     const serverUrl = buildServerUrl(host.trim(), port.trim())
 
     const baseCfg: PrizmConfig = {
-      server: { host: host.trim(), port: port.trim() },
+      server: { host: host.trim(), port: portNumber },
       client: {
         name: clientName.trim(),
-        auto_register: 'true',
+        auto_register: true,
         requested_scopes: scopeList
       },
       api_key: '',
       tray: {
-        enabled: 'true',
-        minimize_to_tray: 'true',
-        show_notification: 'true'
+        enabled: true,
+        minimize_to_tray: true,
+        show_notification: true
       },
       notify_events: ['notification', 'todo_list:updated']
     }
     await saveConfig(baseCfg)
 
-    const apiKey = await registerClient(serverUrl, clientName.trim(), scopeList)
+    const registered = await registerClient(serverUrl, clientName.trim(), scopeList)
     setRegistering(false)
-    if (apiKey) {
+    if (registered) {
       setRegistered(true)
       toast.success('注册成功')
-      const updatedCfg = { ...baseCfg, api_key: apiKey }
-      await saveConfig(updatedCfg)
+      // 凭据已由主进程保存，重新读取配置即可
       const loaded = await loadConfig()
-      setConfig(loaded ?? updatedCfg)
+      setConfig(loaded ?? baseCfg)
       setStep(2)
       return true
     }
@@ -140,7 +146,7 @@ export function OnboardingWizard({
   /** 一键：测试连接 → 注册 → 进入「认识你」步骤（检测到服务端时可用） */
   async function handleOneClick() {
     const serverUrl = buildServerUrl(host.trim(), port.trim())
-    if (!host.trim() || !port.trim()) {
+    if (!host.trim() || parsePortInput(port) === null) {
       toast.error('请填写服务器地址和端口')
       return
     }
@@ -223,7 +229,11 @@ export function OnboardingWizard({
                   placeholder="127.0.0.1"
                 />
               </Form.Item>
-              <Form.Item label="端口">
+              <Form.Item
+                label="端口"
+                validateStatus={portError ? 'error' : undefined}
+                help={portError}
+              >
                 <Input
                   variant={inputVariant}
                   value={port}
@@ -128,7 +128,11 @@ export function UserOverviewCard() {
   }, [connectedSince])
 
   const isConnected = !!manager
-  const displayName = profile?.displayName?.trim() || config?.client?.name || 'Prizm Client'
+  const displayName =
+    profile?.displayName?.trim() ||
+    config?.client?.registration_name ||
+    config?.client?.name ||
+    'Prizm Client'
 
   return (
     <div className={styles.hero}>
@@ -214,7 +214,11 @@ export function HomeHero({
   }, [navEl])
 
   const isConnected = !!manager
-  const displayName = profile?.displayName?.trim() || config?.client?.name || 'Prizm Client'
+  const displayName =
+    profile?.displayName?.trim() ||
+    config?.client?.registration_name ||
+    config?.client?.name ||
+    'Prizm Client'
 
   return (
     <>
@@ -19,6 +19,7 @@ import {
 import type { PrizmConfig, NotificationPayload } from '@prizm/client-core'
 import { toast } from '@lobehub/ui'
 import { setLastSyncEvent, subscribeSyncEventStore } from '../events/syncEventStore'
+import { describeIpcError, parseIpcError } from '../utils/ipcError'
 
 const log = createClientLogger('PrizmContext')
 
@@ -51,11 +52,8 @@ export interface PrizmContextValue {
   loadConfig: () => Promise<PrizmConfig | null>
   saveConfig: (cfg: PrizmConfig) => Promise<boolean>
   testConnection: (serverUrl: string) => Promise<boolean>
-  registerClient: (
-    serverUrl: string,
-    clientName: string,
-    scopes: string[]
-  ) => Promise<string | null>
+  /** 注册并保存凭据（API Key 只保存在主进程），返回是否成功 */
+  registerClient: (serverUrl: string, clientName: string, scopes: string[]) => Promise<boolean>
   initializePrizm: (
     cfg: PrizmConfig,
     opt: {
@@ -107,16 +105,18 @@ export function PrizmProvider({ children }: { children: ReactNode }) {
   }, [])
 
   const registerClient = useCallback(
-    async (serverUrl: string, clientName: string, scopes: string[]): Promise<string | null> => {
+    async (serverUrl: string, clientName: string, scopes: string[]): Promise<boolean> => {
       try {
         setStatus('connecting')
-        const apiKey = await window.prizm.registerClient(serverUrl, clientName, scopes)
-        if (apiKey) return apiKey
+        const registered = await window.prizm.registerClient(serverUrl, clientName, scopes)
+        if (registered) return true
         throw new Error('注册失败')
       } catch (err) {
-        log.error('Client registration failed:', err)
+        const error = parseIpcError(err)
+        log.error('Client registration failed:', error)
+        toast.error(describeIpcError(error))
         setStatus('error')
-        return null
+        return false
       }
     },
     []
@@ -137,8 +137,9 @@ export function PrizmProvider({ children }: { children: ReactNode }) {
           managerRef.current.disconnect()
         }
 
+        // 渲染进程不持有 Key：发往服务端的 HTTP / WebSocket 请求由主进程附加 Authorization
         const m = new PrizmClientManager({
-          config: cfg,
+          config: { ...cfg, api_key: '' },
           subscribeEvents: 'all',
           notifyEvents: cfg.notify_events ?? [
             'notification',
@@ -155,7 +156,7 @@ export function PrizmProvider({ children }: { children: ReactNode }) {
                 ? `${payload.title ?? '通知'}: ${payload.body}`
                 : payload.title ?? '通知'
               toast.success(msg)
-            } else if (cfg.tray?.show_notification === 'true') {
+            } else if (cfg.tray?.show_notification === true) {
               const raw = (payload as { rawEvent?: { eventType: string; payload: unknown } })
                 .rawEvent
               void window.prizm.showNotification(
@@ -182,7 +183,6 @@ export function PrizmProvider({ children }: { children: ReactNode }) {
             opt.onLog(`WebSocket 已连接 - Client ID: ${msg.clientId}`, 'success')
             void window.prizm.startClipboardSync({
               serverUrl: buildServerUrl(cfg.server.host, cfg.server.port),
-              apiKey: cfg.api_key,
               scope: ONLINE_SCOPE
             })
           },
@@ -221,6 +221,12 @@ export function PrizmProvider({ children }: { children: ReactNode }) {
     setConfigState(c)
   }, [])
 
+  /** 主进程检测到 config.json 被外部修改时同步到状态 */
+  useEffect(() => window.prizm.onConfigChanged((c) => setConfigState(c)), [])
+
+  /** 主进程内部修改配置（注册、切换档案等）时同步到状态 */
+  useEffect(() => window.prizm.onConfigUpdated(({ config: c }) => setConfigState(c)), [])
+
   const prizmValue = useMemo<PrizmContextValue>(
     () => ({
       status,
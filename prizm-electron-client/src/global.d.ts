@@ -1,25 +1,499 @@
 import type { PrizmConfig } from '@prizm/client-core'
 
+/** 离线请求队列状态（见 electron/offlineQueue.ts） */
+interface OfflineQueueStatus {
+  pending: number
+  replaying: boolean
+  replayed: number
+  dropped: number
+}
+
+/** 服务器熔断状态（见 electron/circuitBreaker.ts），retry_at 为 open 状态下可重试的时间戳 */
+interface CircuitStatus {
+  origin: string
+  state: 'closed' | 'open' | 'half_open'
+  failures: number
+  retry_at: number | null
+}
+
+/** HTTP 流量统计（见 electron/networkStats.ts），字节数为近似值 */
+interface NetworkStatsSnapshot {
+  since: number
+  totals: { requests: number; errors: number; bytes_sent: number; bytes_received: number }
+  endpoints: Array<{
+    endpoint: string
+    requests: number
+    errors: number
+    bytes_sent: number
+    bytes_received: number
+  }>
+}
+
+/** 下载进度（见 electron/download.ts） */
+interface DownloadProgress {
+  requestId?: string
+  dest: string
+  url: string
+  bytes: number
+  total: number | null
+  rate: number
+  done: boolean
+}
+
+/** 已保存凭据的健康状况（见 electron/credentialHealth.ts） */
+interface CredentialStatus {
+  serverUrl: string
+  valid: boolean
+  reason?: 'missing' | 'rejected'
+  clientId?: string
+  name?: string
+  scopes: string[]
+  lastUsed: number | null
+  expiresAt: number | null
+}
+
+/** 主进程 WebSocket 连接状态（见 electron/realtime.ts） */
+interface RealtimeStatus {
+  state: 'disconnected' | 'connecting' | 'connected' | 'reconnecting'
+  /** 连接地址（不含 API Key） */
+  url: string | null
+  clientId: string | null
+  connectedAt: number | null
+  /** 最近一次非主动断开的原因 */
+  lastError?: string
+  /** 当前是第几次重连，未在重连时为 0 */
+  attempt: number
+  /** reconnecting 时下次重连的时间 */
+  nextRetryAt: number | null
+  /** 重连后服务端开始重放时错过的事件数，重放完成后归零 */
+  missedEvents: number
+}
+
+/**
+ * 与服务器的总体连接状态（见 electron/connectionState.ts）：实时连接正常但 HTTP 熔断、
+ * 或实时连接断线重连中为 degraded
+ */
+interface ConnectionSnapshot {
+  state: 'disconnected' | 'connecting' | 'connected' | 'degraded'
+  realtime: RealtimeStatus['state']
+  /** HTTP 熔断中的服务器 origin */
+  trippedOrigins: string[]
+  changedAt: number
+}
+
+/** 反向隧道及其在服务端的状态（见 electron/tunnels.ts） */
+interface TunnelStatus {
+  name: string
+  local_port: number
+  local_host: string
+  scope: string
+  enabled: boolean
+  /** 服务端已接受 */
+  open: boolean
+  /** 服务端上的访问路径，如 /tunnels/client-1/grafana */
+  path: string | null
+  /** 服务端拒绝的原因，如服务端未开启隧道 */
+  error: string | null
+}
+
+/** 主进程要求跳转到的页面位置 */
+interface AppRoute {
+  page: 'settings'
+  section: 'connection' | 'devices'
+}
+
+/** 主题状态（见 electron/theme.ts），mode 为 auto 时跟随系统 */
+interface ThemeState {
+  mode: 'auto' | 'light' | 'dark'
+  effective: 'light' | 'dark'
+  highContrast: boolean
+}
+
+/** 通知中心的一条记录（见 electron/notificationHistory.ts） */
+interface NotificationEntry {
+  id: string
+  title: string
+  body: string
+  /** server：服务端推送；local：本机界面发出 */
+  source: 'server' | 'local'
+  eventType?: string
+  updateId?: string
+  createdAt: number
+  read: boolean
+}
+
+/** 主进程转发的服务端事件（见 electron/serverEvents.ts） */
+interface ServerEventBase {
+  /** 服务端事件名，如 document:updated、task:completed */
+  eventType: string
+  payload: unknown
+  scope?: string
+  timestamp: number
+}
+
+type ServerEvent =
+  | (ServerEventBase & { kind: 'notification' })
+  | (ServerEventBase & { kind: 'status' })
+  | (ServerEventBase & { kind: 'data_changed'; entity: string; action: string })
+
+/** 服务器上注册的客户端（见 electron/devices.ts） */
+interface ClientInfo {
+  id: string
+  name: string
+  scopes: string[]
+  /** 最近一次使用时间，服务端未记录时为 null */
+  lastSeen: number | null
+  createdAt: number
+  /** 是否为本客户端 */
+  current: boolean
+}
+
+/** 注册成功后返回的客户端信息（见 electron/registration.ts），不含 API Key */
+interface RegisteredClient {
+  clientId: string
+  grantedScopes: string[]
+  expiresAt?: number
+}
+
+/** 未完成的注册进度（见 electron/registration.ts），不含 API Key */
+interface PendingRegistration {
+  step: 'requested' | 'awaiting_approval' | 'created' | 'verified' | 'saved'
+  serverUrl: string
+  name: string
+  requestedScopes: string[]
+  profileName?: string
+  /** 启动时自动发起的注册，授予敏感 scope 前需确认 */
+  unattended?: boolean
+  /** awaiting_approval 时审批申请的过期时间 */
+  approvalExpiresAt?: number
+  clientId?: string
+  updatedAt: number
+}
+
+/** 授予敏感 scope 前的确认请求（见 electron/consent.ts） */
+interface ConsentRequest {
+  id: string
+  serverUrl: string
+  /** auto_register：无人值守注册；scope_change：服务端授予了未申请的 scope */
+  reason: 'auto_register' | 'scope_change'
+  scopes: string[]
+  expiresAt: number
+}
+
+/** 设备码授权的用户码与验证地址（见 electron/deviceAuth.ts） */
+interface DeviceCodeEvent {
+  requestId?: string
+  userCode: string
+  verificationUri: string
+  verificationUriComplete?: string
+  expiresAt: number
+}
+
 declare global {
   interface Window {
     prizm: {
+      /** 配置中的 api_key / refresh_token / admin_key 以占位符代替，原样存回时保持不变 */
       loadConfig(): Promise<PrizmConfig | null>
       saveConfig(config: PrizmConfig): Promise<boolean>
-      testConnection(serverUrl: string): Promise<boolean>
+      /**
+       * 经系统验证（Touch ID / Windows Hello / polkit）后返回 API Key 原文；
+       * 用户取消、验证失败或系统不支持时抛错
+       */
+      revealApiKey(): Promise<string>
+      /** 深度合并部分配置并保存（null 删除字段），返回合并后的完整配置 */
+      updateConfig(patch: Record<string, unknown>): Promise<PrizmConfig>
+      /** 恢复默认配置；section 为 server / client / tray 等时只重置该段 */
+      resetConfig(section?: string): Promise<PrizmConfig>
+      /** 列出与默认值不同的字段，api_key、admin_key 与 refresh_token 以 *** 代替 */
+      diffConfig(): Promise<Array<{ path: string; current: unknown; default: unknown }>>
+      listConfigBackups(): Promise<Array<{ index: number; path: string; modifiedAt: number }>>
+      /** 从第 index 份历史备份（1 为最新）恢复配置 */
+      restoreConfigBackup(index: number): Promise<PrizmConfig>
+      /** 检查配置问题；不传 config 时校验磁盘上的配置 */
+      validateConfig(
+        config?: PrizmConfig,
+        knownScopes?: string[]
+      ): Promise<
+        Array<{
+          path: string
+          severity: 'error' | 'warning' | 'info'
+          code: string
+          message: string
+        }>
+      >
+      /** 导出配置，path 缺省时弹出保存对话框；返回实际路径，取消时为 null */
+      exportConfig(path?: string, includeSecrets?: boolean): Promise<string | null>
+      /** 导入配置，path 缺省时弹出打开对话框；取消时为 null */
+      importConfig(path?: string): Promise<{
+        imported: boolean
+        diagnostics: Array<{
+          path: string
+          severity: 'error' | 'warning' | 'info'
+          code: string
+          message: string
+        }>
+      } | null>
+      /** requestId 可用于 cancelRequest 中止本次检查，取消时抛出 cancelled 错误 */
+      testConnection(serverUrl: string, requestId?: string): Promise<boolean>
+      /** 中止带 requestId 发起的注册 / 连接测试，返回是否找到该请求 */
+      cancelRequest(requestId: string): Promise<boolean>
+      /** 流式下载到 dest（绝对路径），可用 requestId 取消；返回写入的字节数 */
+      downloadFile(
+        url: string,
+        dest: string,
+        options?: { auth?: boolean; requestId?: string }
+      ): Promise<{ path: string; bytes: number }>
+      /** 下载进度（约每 200ms 一次，完成时 done 为 true），rate 为字节 / 秒 */
+      onDownloadProgress(callback: (progress: DownloadProgress) => void): () => void
+      /** 用给定代理设置请求服务器 /health，不改变当前生效的代理 */
+      testProxy(
+        proxy: { mode: 'system' | 'manual' | 'none'; url: string; bypass: string },
+        serverUrl: string
+      ): Promise<{
+        ok: boolean
+        status?: number
+        latency_ms: number
+        resolved: string
+        error?: { kind: string; message: string }
+      }>
+      /** 并发检查所有档案服务器的 /health，name 为 null 表示未保存为档案的当前服务器 */
+      checkAllServers(): Promise<
+        Array<{
+          name: string | null
+          url: string
+          ok: boolean
+          latency_ms: number
+          error?: { kind: string; message: string }
+        }>
+      >
+      /**
+       * 多次 /health 往返统计延迟（samples 默认 5，最多 20），并直连测量一次 DNS / TCP / TLS 耗时；
+       * 走本地套接字的服务器 phases 为 null
+       */
+      measureLatency(
+        serverUrl: string,
+        samples?: number
+      ): Promise<{
+        url: string
+        samples: number[]
+        failures: number
+        min_ms: number | null
+        avg_ms: number | null
+        p95_ms: number | null
+        phases: { address: string; dns_ms: number; tcp_ms: number; tls_ms: number | null } | null
+        phase_error?: { kind: string; message: string }
+      }>
+      /** 主进程 HTTP 流量统计（启动或上次重置以来），按收发字节数降序 */
+      getNetworkStats(): Promise<NetworkStatsSnapshot>
+      resetNetworkStats(): Promise<NetworkStatsSnapshot>
+      /** 处于熔断（open / half_open）状态的服务器 */
+      getCircuitStates(): Promise<CircuitStatus[]>
+      /** 熔断状态变化，可用于提示“服务器暂时不可用”并暂停轮询 */
+      onCircuitStateChanged(callback: (status: CircuitStatus) => void): () => void
+      /** 总体连接状态，托盘图标与窗口标题使用同一状态 */
+      getConnectionState(): Promise<ConnectionSnapshot>
+      onConnectionStateChanged(callback: (snapshot: ConnectionSnapshot) => void): () => void
+      /** 发送非幂等请求，服务器不可达时进入离线队列，连接恢复后按顺序重放 */
+      sendOrQueue(entry: {
+        method: string
+        url: string
+        body?: unknown
+        /** 附带 API Key，默认不附带 */
+        auth?: boolean
+        description?: string
+      }): Promise<
+        { queued: false; status: number; body: string } | { queued: true; id: string }
+      >
+      getOfflineQueue(): Promise<
+        OfflineQueueStatus & {
+          items: Array<{
+            id: string
+            method: string
+            url: string
+            description?: string
+            createdAt: number
+          }>
+        }
+      >
+      replayOfflineQueue(): Promise<OfflineQueueStatus>
+      clearOfflineQueue(): Promise<OfflineQueueStatus>
+      /** 离线队列变化（入队、重放进度、清空），可用于显示“N 项待同步” */
+      onOfflineQueueUpdated(callback: (status: OfflineQueueStatus) => void): () => void
+      /** 开关网络请求日志（运行时生效，密钥与鉴权头会被隐去） */
+      setNetworkLogging(enabled: boolean): Promise<boolean>
+      getNetworkLogging(): Promise<boolean>
       registerClient(
         serverUrl: string,
         clientName: string,
-        scopes: string[]
-      ): Promise<string | null>
+        scopes: string[],
+        profileName?: string,
+        requestId?: string
+      ): Promise<RegisteredClient | null>
+      /**
+       * 设备码授权（需服务端支持 /auth/device/*）：等待用户在服务端确认后完成注册，
+       * 期间通过 onDeviceCode 推送验证地址与用户码；可用 cancelRequest(requestId) 取消
+       */
+      registerWithDeviceCode(
+        serverUrl: string,
+        clientName: string,
+        scopes: string[],
+        profileName?: string,
+        requestId?: string
+      ): Promise<RegisteredClient | null>
+      onDeviceCode(callback: (event: DeviceCodeEvent) => void): () => void
+      /**
+       * 申请一次性配对令牌（默认转授本客户端的全部 scope），返回二维码 PNG 的 base64
+       * 与编码文本 prizm://pair?server=…&token=…
+       */
+      generatePairingQr(
+        scopes?: string[]
+      ): Promise<{ payload: string; png: string; expiresAt: number }>
+      /** 用扫码得到的配对内容注册并保存凭据 */
+      pairFromQr(
+        payload: string,
+        clientName?: string,
+        profileName?: string,
+        requestId?: string
+      ): Promise<RegisteredClient | null>
+      /** 用服务端 Dashboard 展示的 6 位配对码注册到当前服务器并保存凭据 */
+      pairWithCode(
+        code: string,
+        clientName?: string,
+        profileName?: string,
+        requestId?: string
+      ): Promise<RegisteredClient | null>
+      /**
+       * 从上次中断的步骤继续注册（如服务端已创建客户端但配置未保存），
+       * 没有未完成的注册时返回 null
+       */
+      resumeRegistration(requestId?: string): Promise<RegisteredClient | null>
+      /** 未完成的注册进度，无则为 null */
+      getPendingRegistration(): Promise<PendingRegistration | null>
+      /** 服务端要求人工批准新客户端时的审批进度：pending（等待批准）、approved、denied、expired */
+      onRegistrationStatus(
+        callback: (event: {
+          serverUrl: string
+          name: string
+          status: 'pending' | 'approved' | 'denied' | 'expired'
+          expiresAt?: number
+        }) => void
+      ): () => void
+      /** 注册完成（含后台自动注册），主进程同时弹出系统通知 */
+      onRegistrationComplete(
+        callback: (event: { clientId: string; serverUrl: string; name: string }) => void
+      ): () => void
+      /** 注册时服务端实际授予的 scope，未注册时为空数组 */
+      getGrantedScopes(): Promise<string[]>
+      /**
+       * 申请追加 scope，等待管理员在 Dashboard 批准后返回全部 scope；
+       * 被拒绝或过期时抛错，无需注销重新注册
+       */
+      requestAdditionalScopes(scopes: string[], requestId?: string): Promise<string[]>
+      onScopeRequest(
+        callback: (event: {
+          requestId?: string
+          status: 'pending' | 'approved' | 'denied' | 'expired'
+          scopes: string[]
+        }) => void
+      ): () => void
+      /**
+       * 在服务端吊销本客户端并清除 api_key / client.name；服务器不可达时抛错，
+       * force 为 true 时仍清除本地配置（revoked 为 false）
+       */
+      deregisterClient(force?: boolean): Promise<{ revoked: boolean; serverUrl: string }>
+      /**
+       * 检查已保存的 API Key 是否仍有效、拥有哪些 scope、上次何时使用；
+       * 只发一次无副作用的请求，不触发重新注册。服务器不可达时抛错
+       */
+      verifyCredentials(requestId?: string): Promise<CredentialStatus>
+      /** 将授予敏感 scope（如 clipboard、filesystem）时的确认请求，超时未答视为拒绝 */
+      onConsentRequest(callback: (event: ConsentRequest) => void): () => void
+      /** 尚未作答的确认请求（界面加载晚于事件时补齐） */
+      getPendingConsents(): Promise<ConsentRequest[]>
+      /** 回答确认请求，请求已作答或已超时时抛错 */
+      respondConsent(id: string, allow: boolean): Promise<boolean>
+      /** 服务器上注册的全部客户端（管理操作，需管理员 Key 或拥有 '*' scope），无权限时抛错 */
+      listClients(requestId?: string): Promise<ClientInfo[]>
+      /** 吊销服务器上的客户端（管理操作）；吊销本客户端时等同 deregisterClient */
+      revokeClient(clientId: string): Promise<boolean>
+      /** 批准、拒绝其他客户端的注册申请（管理操作），申请已处理或无权限时抛错 */
+      approveRegistrationRequest(requestId: string): Promise<boolean>
+      denyRegistrationRequest(requestId: string): Promise<boolean>
+      /** 保存当前服务器的管理员 Key，与客户端 api_key 分开存放（系统凭据存储或加密写入配置） */
+      setAdminKey(adminKey: string): Promise<boolean>
+      clearAdminKey(): Promise<boolean>
+      hasAdminKey(): Promise<boolean>
+      /** 主进程发起的页面跳转，如点击连接类或注册申请通知 */
+      onNavigate(callback: (route: AppRoute) => void): () => void
+      /** 客户端已注销，界面应回到引导流程 */
+      onDeregistered(
+        callback: (result: { revoked: boolean; serverUrl: string }) => void
+      ): () => void
+      /** 启动时按 client.auto_register 后台注册的结果，registered 后可用新配置连接 */
+      onAutoRegister(
+        callback: (
+          event:
+            | { status: 'registered'; clientId: string; serverUrl: string }
+            | { status: 'failed'; serverUrl: string; message: string }
+        ) => void
+      ): () => void
+      /** API Key 失效后已自动重新注册（client.auto_register），可提示用户 */
+      onReregistered(
+        callback: (event: { clientId: string; serverUrl: string }) => void
+      ): () => void
+      /** api_key 过期且无法续期（无刷新令牌或刷新令牌被拒），应引导用户重新注册 */
+      onReauthRequired(
+        callback: (event: {
+          serverUrl: string
+          reason: 'missing_refresh_token' | 'refresh_rejected'
+        }) => void
+      ): () => void
+      /** 使用配置中的 scope 预设注册 */
+      registerWithPreset(
+        serverUrl: string,
+        clientName: string,
+        preset: string,
+        profileName?: string,
+        requestId?: string
+      ): Promise<RegisteredClient | null>
+      listProfiles(): Promise<
+        Array<{ name: string; host: string; port: number; hasApiKey: boolean; active: boolean }>
+      >
+      switchProfile(name: string): Promise<PrizmConfig>
+      deleteProfile(name: string): Promise<boolean>
+      /** 在当前服务器上以另一名称 / scope 组合注册（如“管理模式”与“只读模式”），保存为身份 */
+      registerIdentity(
+        identity: string,
+        clientName: string,
+        scopes: string[],
+        requestId?: string
+      ): Promise<RegisteredClient | null>
+      /** 当前服务器上的身份 */
+      listIdentities(): Promise<
+        Array<{
+          name: string
+          clientName: string
+          grantedScopes: string[]
+          hasApiKey: boolean
+          active: boolean
+        }>
+      >
+      /** 切换到指定身份，替换 client.name / api_key / scope */
+      useIdentity(name: string): Promise<PrizmConfig>
+      deleteIdentity(name: string): Promise<boolean>
+      /** 手动编辑 config.json 后主进程推送的新配置 */
+      onConfigChanged(callback: (config: PrizmConfig) => void): () => void
+      /** 主进程修改配置后推送，sections 为发生变化的顶层配置段 */
+      onConfigUpdated(
+        callback: (event: { sections: string[]; config: PrizmConfig }) => void
+      ): () => void
       getAppVersion(): Promise<string>
       openDashboard(serverUrl: string): Promise<boolean>
+      /** 在应用内窗口中打开管理面板，已打开时聚焦 */
+      openDashboardWindow(serverUrl: string): Promise<boolean>
       readClipboard(): Promise<string>
       writeClipboard(text: string): Promise<boolean>
-      startClipboardSync(config: {
-        serverUrl: string
-        apiKey: string
-        scope?: string
-      }): Promise<boolean>
+      startClipboardSync(config: { serverUrl: string; scope?: string }): Promise<boolean>
       stopClipboardSync(): Promise<boolean>
       onClipboardItemAdded(callback: () => void): () => void
       showNotification(
@@ -27,6 +501,19 @@ declare global {
           | import('@prizm/client-core').NotifyWindowPayload
           | { title: string; body?: string; source?: string }
       ): Promise<boolean>
+      /** 通知中心：弹出过的通知，按时间倒序 */
+      listNotifications(options?: {
+        unreadOnly?: boolean
+        limit?: number
+      }): Promise<NotificationEntry[]>
+      /** 标为已读，不传 ids 时全部标为已读；返回变化的条数 */
+      markNotificationsRead(ids?: string[]): Promise<number>
+      clearNotifications(): Promise<number>
+      /** 通知历史变化（新通知、已读、清空），附带未读数 */
+      onNotificationsChanged(callback: (change: { unread: number }) => void): () => void
+      /** 托盘“暂停通知”（立即免打扰）：通知只记入通知中心不弹出，重启后恢复 */
+      getNotificationsPaused(): Promise<boolean>
+      setNotificationsPaused(paused: boolean): Promise<boolean>
       onLogFromMain(
         callback: (entry: {
           level: string
@@ -71,6 +558,14 @@ declare global {
       }): Promise<boolean>
       /** 设置原生主题模式，同步到主进程 nativeTheme 并持久化 */
       setNativeTheme(mode: 'auto' | 'light' | 'dark'): Promise<boolean>
+      /** 当前主题：用户选择的模式与实际生效的深浅色 */
+      getSystemTheme(): Promise<ThemeState>
+      /** 系统外观或主题设置变化 */
+      onThemeChanged(callback: (state: ThemeState) => void): () => void
+      /** 当前生效的显示/隐藏主窗口全局快捷键，未注册时为 null */
+      getGlobalHotkey(): Promise<string | null>
+      /** 修改显示/隐藏主窗口的全局快捷键并持久化，空字符串表示取消；注册失败时保留原快捷键 */
+      setGlobalHotkey(accelerator: string): Promise<string>
       /** 在系统资源管理器中打开目录 */
       openInExplorer(dirPath: string): Promise<boolean>
       browserNode: {
@@ -82,6 +577,43 @@ declare global {
           wsEndpoint: string | null
         }>
       }
+      /** 主进程持有的服务端 WebSocket 连接，用 API Key 鉴权 */
+      realtime: {
+        /** 建立连接并等待服务端确认；没有 API Key 或被拒时抛错 */
+        connect(): Promise<RealtimeStatus>
+        disconnect(): Promise<RealtimeStatus>
+        getStatus(): Promise<RealtimeStatus>
+        /** 断开后立即重新连接，重置重连计数 */
+        reconnect(): Promise<RealtimeStatus>
+        /** 订阅服务端事件（如 document:updated），断开重连后保持；返回当前订阅列表 */
+        subscribe(topic: string): Promise<string[]>
+        unsubscribe(topic: string): Promise<string[]>
+        getSubscriptions(): Promise<string[]>
+        /** 重连后仍在重放的错过事件数，大于 0 时可显示“正在同步” */
+        getMissedEventCount(): Promise<number>
+        /** 连接状态的每次变化（ws://state），含断线后的重连进度 */
+        onState(callback: (status: RealtimeStatus) => void): () => void
+        /** 服务端推送的通知 */
+        onNotification(
+          callback: (event: Extract<ServerEvent, { kind: 'notification' }>) => void
+        ): () => void
+        /** 点击了服务端通知弹出的系统通知（tray.show_notification 开启时弹出），主窗口已聚焦 */
+        onNotificationClick(
+          callback: (event: Extract<ServerEvent, { kind: 'notification' }>) => void
+        ): () => void
+        /** 任务、工作流、终端等运行状态变化 */
+        onStatus(callback: (event: Extract<ServerEvent, { kind: 'status' }>) => void): () => void
+        /** 数据增删改，entity 为资源类型（如 document），action 为动作（如 updated） */
+        onDataChanged(
+          callback: (event: Extract<ServerEvent, { kind: 'data_changed' }>) => void
+        ): () => void
+      }
+      /** 反向隧道：只能开关 config.tunnels 中已配置的隧道 */
+      tunnels: {
+        list(): Promise<TunnelStatus[]>
+        enable(name: string): Promise<TunnelStatus[]>
+        disable(name: string): Promise<TunnelStatus[]>
+      }
     }
     quickPanelApi?: {
       onShow(callback: (data: { clipboardText: string }) => void): () => void
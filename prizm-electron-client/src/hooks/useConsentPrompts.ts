@@ -0,0 +1,40 @@
+/**
+ * 敏感 scope 确认：主进程在自动注册或服务端变更将授予敏感 scope 前发出确认请求，
+ * 这里逐个弹窗并通过 respondConsent 作答。挂载时补齐加载前已发出的请求。
+ */
+import { useEffect } from 'react'
+import { Modal } from 'antd'
+
+const REASON_TEXT: Record<ConsentRequest['reason'], string> = {
+  auto_register: '自动注册',
+  scope_change: '服务器权限变更'
+}
+
+export function useConsentPrompts(onLog?: (msg: string, type: 'info' | 'warning') => void) {
+  useEffect(() => {
+    const shown = new Set<string>()
+
+    function prompt(request: ConsentRequest) {
+      if (shown.has(request.id)) return
+      shown.add(request.id)
+      const answer = (allow: boolean) =>
+        window.prizm.respondConsent(request.id, allow).catch(() => {
+          onLog?.('确认请求已超时，授权未保存', 'warning')
+        })
+      const scopes = request.scopes.join(', ')
+      const reason = REASON_TEXT[request.reason]
+      Modal.confirm({
+        title: '授予敏感权限',
+        content: `${reason}将授予 ${request.serverUrl} 以下权限：${scopes}。是否允许？`,
+        okText: '允许',
+        cancelText: '拒绝',
+        onOk: () => answer(true),
+        onCancel: () => answer(false)
+      })
+    }
+
+    const unsubscribe = window.prizm.onConsentRequest(prompt)
+    void window.prizm.getPendingConsents().then((list) => list.forEach(prompt))
+    return unsubscribe
+  }, [onLog])
+}
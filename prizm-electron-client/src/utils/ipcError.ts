@@ -0,0 +1,71 @@
+/**
+ * 解析主进程 IPC 抛出的结构化错误（见 electron/errors.ts）
+ */
+export type PrizmErrorKind =
+  | 'network'
+  | 'timeout'
+  | 'bad_status'
+  | 'parse'
+  | 'config'
+  | 'auth'
+  | 'invalid_input'
+  | 'cancelled'
+  | 'internal'
+
+export interface PrizmErrorPayload {
+  kind: PrizmErrorKind
+  message: string
+  status?: number
+  body?: string
+  /** 服务端错误码（如 VALIDATION） */
+  code?: string
+  /** 服务端返回的错误消息 */
+  serverMessage?: string
+}
+
+const PRIZM_ERROR_MARKER = '__PRIZM_ERROR__'
+
+/**
+ * 从 ipcRenderer.invoke 的异常中还原 PrizmErrorPayload；非结构化错误归为 internal
+ */
+export function parseIpcError(err: unknown): PrizmErrorPayload {
+  const message = err instanceof Error ? err.message : String(err)
+  const idx = message.indexOf(PRIZM_ERROR_MARKER)
+  if (idx !== -1) {
+    try {
+      return JSON.parse(message.slice(idx + PRIZM_ERROR_MARKER.length)) as PrizmErrorPayload
+    } catch {
+      // 落到下方兜底
+    }
+  }
+  return { kind: 'internal', message }
+}
+
+/**
+ * 面向用户的错误描述，区分“服务器不可达”与“API Key 无效”等情况以提示正确的处理方式
+ */
+export function describeIpcError(error: PrizmErrorPayload): string {
+  switch (error.kind) {
+    case 'network':
+      return '无法连接到服务器，请确认地址正确且服务端已启动'
+    case 'timeout':
+      return '连接服务器超时'
+    case 'auth':
+      return 'API Key 无效或权限不足，请重新注册客户端'
+    case 'bad_status': {
+      const status = error.status ? ` (${error.status})` : ''
+      return error.serverMessage
+        ? `服务器返回错误${status}: ${error.serverMessage}`
+        : `服务器返回错误${status}`
+    }
+    case 'parse':
+      return '服务器响应格式异常'
+    case 'cancelled':
+      return '操作已取消'
+    case 'config':
+    case 'invalid_input':
+      return error.message
+    default:
+      return error.message || '未知错误'
+  }
+}
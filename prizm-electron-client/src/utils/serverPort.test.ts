@@ -0,0 +1,21 @@
+/**
+ * 端口输入校验的单元测试
+ */
+import { describe, it, expect } from 'vitest'
+import { parsePortInput, portInputError } from './serverPort'
+
+describe('parsePortInput', () => {
+  it('accepts integers from 1 to 65535', () => {
+    expect(parsePortInput('4127')).toBe(4127)
+    expect(parsePortInput(' 1 ')).toBe(1)
+    expect(parsePortInput('65535')).toBe(65535)
+  })
+
+  it('rejects empty, non-numeric and out-of-range input', () => {
+    for (const value of ['', '  ', 'abc', '41a', '4127.5', '-1', '0', '65536']) {
+      expect(parsePortInput(value)).toBeNull()
+      expect(portInputError(value)).toBeDefined()
+    }
+    expect(portInputError('4127')).toBeUndefined()
+  })
+})
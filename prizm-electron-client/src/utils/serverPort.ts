@@ -0,0 +1,18 @@
+/**
+ * 服务器端口输入的校验（设置页与首次启动向导共用）
+ */
+
+export const PORT_INPUT_ERROR = '端口须为 1–65535 的整数'
+
+/** 解析端口输入：1–65535 的整数，否则返回 null */
+export function parsePortInput(value: string): number | null {
+  const text = value.trim()
+  if (!/^\d+$/.test(text)) return null
+  const port = Number(text)
+  return port >= 1 && port <= 65535 ? port : null
+}
+
+/** 端口输入的错误提示；合法时为 undefined */
+export function portInputError(value: string): string | undefined {
+  return parsePortInput(value) === null ? PORT_INPUT_ERROR : undefined
+}
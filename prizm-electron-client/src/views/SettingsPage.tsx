@@ -22,6 +22,7 @@ import { LLMConfigSettings } from '../components/LLMConfigSettings'
 import { CommandsSettings } from '../components/CommandsSettings'
 import { AgentRulesSettings } from '../components/AgentRulesSettings'
 import { ScopeManagement } from '../components/ScopeManagement'
+import { DevicesSettings } from '../components/DevicesSettings'
 import { EmbeddingStatus } from '../components/EmbeddingStatus'
 import { useClientSettings } from '../context/ClientSettingsContext'
 import { EVENT_TYPES, buildServerUrl, getEventLabel } from '@prizm/client-core'
@@ -48,17 +49,20 @@ import {
   Zap,
   Palette,
   AppWindow,
+  MonitorSmartphone,
   User
 } from 'lucide-react'
 import { OnboardingWizard } from '../components/OnboardingWizard'
 import { BrowserPlayground } from '../components/BrowserPlayground'
 import { useUserProfile } from '../hooks/useUserProfile'
+import { parsePortInput, portInputError } from '../utils/serverPort'
 type SettingsCategory =
   | 'connection'
   | 'appearance'
   | 'profile'
   | 'input'
   | 'scope'
+  | 'devices'
   | 'server'
   | 'llm'
   | 'agent'
@@ -82,6 +86,7 @@ const CATEGORIES: CategoryItem[] = [
   { key: 'profile', label: '用户画像', icon: <User size={16} />, requiresAuth: true },
   { key: 'input', label: '输入', icon: <Keyboard size={16} /> },
   { key: 'scope', label: '工作区', icon: <FolderOpen size={16} />, requiresAuth: true },
+  { key: 'devices', label: '设备', icon: <MonitorSmartphone size={16} />, requiresAuth: true },
   { key: 'server', label: '服务端/运维', icon: <Server size={16} />, requiresAuth: true },
   { key: 'llm', label: 'LLM 配置', icon: <Key size={16} />, requiresAuth: true },
   { key: 'agent', label: 'Agent', icon: <Bot size={16} />, requiresAuth: true },
@@ -93,7 +98,12 @@ const CATEGORIES: CategoryItem[] = [
   { key: 'actions', label: '快捷操作', icon: <Zap size={16} /> }
 ]
 
-function SettingsPage() {
+interface SettingsPageProps {
+  /** 打开指定分类，如点击连接类通知时打开“连接” */
+  focus?: { section: SettingsCategory } | null
+}
+
+function SettingsPage({ focus }: SettingsPageProps) {
   const {
     config,
     manager,
@@ -118,6 +128,9 @@ function SettingsPage() {
   } = useClientSettings()
 
   const [activeCategory, setActiveCategory] = useState<SettingsCategory>('connection')
+  useEffect(() => {
+    if (focus) setActiveCategory(focus.section)
+  }, [focus])
   const [profileSaving, setProfileSaving] = useState(false)
   const [profileForm, setProfileForm] = useState({ displayName: '', preferredTone: '' })
   const [testing, setTesting] = useState(false)
@@ -130,6 +143,7 @@ function SettingsPage() {
     scopesText: 'default, online',
     notifyEvents: ['notification', 'todo_list:updated'] as string[]
   })
+  const portError = portInputError(form.port)
 
   const [browserState, setBrowserState] = useState<{
     isRunning: boolean
@@ -204,8 +218,8 @@ function SettingsPage() {
     if (config) {
       setForm({
         host: config.server.host,
-        port: config.server.port,
-        clientName: config.client.name,
+        port: String(config.server.port),
+        clientName: config.client.registration_name || config.client.name,
         scopesText: config.client.requested_scopes.join(', '),
         notifyEvents: [...(config.notify_events ?? ['notification', 'todo_list:updated'])]
       })
@@ -222,6 +236,11 @@ function SettingsPage() {
   }, [profile, profileLoading])
 
   async function saveConfig() {
+    const port = parsePortInput(form.port)
+    if (port === null) {
+      // 错误已显示在端口输入框下方
+      return
+    }
     const scopes = form.scopesText
       ? form.scopesText
           .split(',')
@@ -229,22 +248,22 @@ function SettingsPage() {
           .filter(Boolean)
       : ['default', 'online']
     const base = config ?? {
-      server: { host: '', port: '', is_dev: 'true' },
+      server: { host: '', port: 4127 },
       client: {
         name: '',
-        auto_register: 'true',
+        auto_register: true,
         requested_scopes: ['default', 'online']
       },
       api_key: '',
       tray: {
-        enabled: 'true',
-        minimize_to_tray: 'true',
-        show_notification: 'true'
+        enabled: true,
+        minimize_to_tray: true,
+        show_notification: true
       },
       notify_events: ['notification', 'todo_list:updated']
     }
     const cfg: PrizmConfig = { ...base }
-    cfg.server = { ...cfg.server, host: form.host, port: form.port }
+    cfg.server = { ...cfg.server, host: form.host, port }
     cfg.client = {
       ...cfg.client,
       name: form.clientName,
@@ -261,7 +280,7 @@ function SettingsPage() {
 
   async function testConnection() {
     const serverUrl = buildServerUrl(form.host.trim(), form.port.trim())
-    if (!form.host.trim() || !form.port.trim()) {
+    if (!form.host.trim() || parsePortInput(form.port) === null) {
       toast.error('请填写服务器地址和端口')
       addLog('请填写服务器地址和端口', 'error')
       return
@@ -280,7 +299,7 @@ function SettingsPage() {
 
   async function registerClient() {
     const serverUrl = buildServerUrl(form.host.trim(), form.port.trim())
-    if (!form.host.trim() || !form.port.trim()) {
+    if (!form.host.trim() || parsePortInput(form.port) === null) {
       addLog('请填写服务器地址和端口', 'error')
       return
     }
@@ -295,9 +314,9 @@ function SettingsPage() {
           .filter(Boolean)
       : ['default', 'online']
     setRegistering(true)
-    const apiKey = await registerClientApi(serverUrl, form.clientName.trim(), scopes)
+    const registered = await registerClientApi(serverUrl, form.clientName.trim(), scopes)
     setRegistering(false)
-    if (apiKey) {
+    if (registered) {
       const cfg = await loadConfig()
       if (cfg) {
         setConfig(cfg)
@@ -325,14 +344,19 @@ function SettingsPage() {
     }
   }
 
-  async function openDashboard() {
+  async function openDashboard(inBrowser = false) {
     const c = config
     if (!c) {
       addLog('没有配置可用的服务器', 'error')
       return
     }
     try {
-      await window.prizm.openDashboard(buildServerUrl(c.server.host, c.server.port))
+      const serverUrl = buildServerUrl(c.server.host, c.server.port)
+      if (inBrowser) {
+        await window.prizm.openDashboard(serverUrl)
+      } else {
+        await window.prizm.openDashboardWindow(serverUrl)
+      }
       addLog('已打开仪表板', 'success')
     } catch (e) {
       addLog(`打开仪表板失败: ${String(e)}`, 'error')
@@ -355,15 +379,34 @@ function SettingsPage() {
     () => !config?.api_key || localStorage.getItem('prizm.onboardingCompleted') !== 'true'
   )
 
+  const configLoaded = !!config
   useEffect(() => {
-    if (config?.api_key && localStorage.getItem('prizm.onboardingCompleted') === 'true') {
+    if (configLoaded && !config?.api_key) {
+      // 注销后回到引导流程
+      setShowOnboarding(true)
+    } else if (config?.api_key && localStorage.getItem('prizm.onboardingCompleted') === 'true') {
       setShowOnboarding(false)
     }
-  }, [config?.api_key])
+  }, [configLoaded, config?.api_key])
 
   const inputVariant = 'filled' as const
   const hasAuth = !!config?.api_key
 
+  /** 经系统验证后显示的 API Key 原文，切换页面后不保留 */
+  const [revealedApiKey, setRevealedApiKey] = useState('')
+
+  useEffect(() => {
+    setRevealedApiKey('')
+  }, [activeCategory])
+
+  async function revealApiKey() {
+    try {
+      setRevealedApiKey(await window.prizm.revealApiKey())
+    } catch (e) {
+      toast.error(`无法显示 API Key：${String(e)}`)
+    }
+  }
+
   const primarySwatches = useMemo(
     () =>
       primaryColorsSwatches.map((c) => ({ color: c, title: findCustomThemeName('primary', c) })),
@@ -416,7 +459,12 @@ function SettingsPage() {
                     placeholder="127.0.0.1"
                   />
                 </Form.Item>
-                <Form.Item label="端口" extra="默认端口: 4127">
+                <Form.Item
+                  label="端口"
+                  extra="默认端口: 4127"
+                  validateStatus={portError ? 'error' : undefined}
+                  help={portError}
+                >
                   <Input
                     variant={inputVariant}
                     value={form.port}
@@ -446,6 +494,18 @@ function SettingsPage() {
                     placeholder="default, online"
                   />
                 </Form.Item>
+                {hasAuth && (
+                  <Form.Item
+                    label="API Key"
+                    extra="查看前需通过系统验证（Touch ID / Windows Hello）"
+                  >
+                    {revealedApiKey ? (
+                      <Input variant={inputVariant} value={revealedApiKey} readOnly />
+                    ) : (
+                      <Button onClick={() => void revealApiKey()}>显示 API Key</Button>
+                    )}
+                  </Form.Item>
+                )}
                 <Form.Item
                   label="接收通知的事件"
                   extra="勾选后，对应事件发生时将弹出应用内通知。含 TODO 列表更新、便签、文档、剪贴板等。"
@@ -654,6 +714,9 @@ function SettingsPage() {
       case 'scope':
         return <ScopeManagement http={manager?.getHttpClient() ?? null} onLog={addLog} />
 
+      case 'devices':
+        return <DevicesSettings onLog={addLog} />
+
       case 'server':
         return <ServerConfigSettings http={manager?.getHttpClient() ?? null} onLog={addLog} />
 
@@ -687,7 +750,8 @@ function SettingsPage() {
                 <Button onClick={reconnect} disabled={reconnecting}>
                   {reconnecting ? '重新连接中...' : '重新连接'}
                 </Button>
-                <Button onClick={openDashboard}>打开仪表板</Button>
+                <Button onClick={() => openDashboard()}>打开仪表板</Button>
+                <Button onClick={() => openDashboard(true)}>在浏览器中打开</Button>
               </div>
             </div>
           </div>
@@ -804,7 +868,7 @@ function SettingsPage() {
               {config?.server && (
                 <BrowserPlayground
                   baseUrl={buildServerUrl(config.server.host, config.server.port)}
-                  apiKey={config.api_key ?? ''}
+                  authenticated={hasAuth}
                   isNodeRunning={browserState.isRunning}
                 />
               )}
@@ -2,11 +2,35 @@
  * Auth / 权限相关类型
  */
 
+import type { PresenceState } from './websocket'
+
+/** 客户端注册时声明的能力，服务端据此决定推送内容 */
+export interface ClientCapabilities {
+  /** 运行平台，如 win32-x64 */
+  platform?: string
+  appVersion?: string
+  /** 支持的功能，如 notifications、ws、file-transfer */
+  features: string[]
+}
+
 export interface ClientInfo {
   clientId: string
   name: string
   allowedScopes: string[]
   createdAt: number
+  /** 最近一次通过鉴权的时间（从未使用过时不返回） */
+  lastSeenAt?: number
+  /** 最近一次注册时声明的能力（旧版客户端不声明） */
+  capabilities?: ClientCapabilities
+  /** 最近一次通过 WebSocket 上报的在线状态（未连接或未上报时不返回） */
+  presence?: ClientPresence
+}
+
+/** 客户端在线状态，由 WebSocket presence 消息上报 */
+export interface ClientPresence {
+  state: PresenceState
+  idleSeconds: number
+  updatedAt: number
 }
 
 export type { ScopeDescription } from './scopes'
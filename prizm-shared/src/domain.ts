@@ -1433,8 +1433,23 @@ export interface NotificationPayload {
   sourceClientId?: string
   /** 用于更新同一条通知而非新建，如 todo_list:{scope}:{id} 使 TODO 列表多次更新合并为一条 */
   updateId?: string
+  /** 通知级别，缺省为 info；客户端可按最低级别过滤 */
+  severity?: NotificationSeverity
+  /** 可直接在通知上处理的操作，客户端据此提供按钮 */
+  action?: NotificationAction
 }
 
+/** 通知附带的操作：registration_request 为等待批准的注册申请，可批准或拒绝（需管理权限） */
+export interface NotificationAction {
+  type: 'registration_request'
+  requestId: string
+}
+
+/** 通知级别 */
+export type NotificationSeverity = 'info' | 'warning' | 'error'
+
+export const NOTIFICATION_SEVERITIES: readonly NotificationSeverity[] = ['info', 'warning', 'error']
+
 // ============ 反馈系统 ============
 
 /** 反馈评分 */
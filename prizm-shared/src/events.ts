@@ -63,7 +63,8 @@ export const EVENT_TYPES = [
   'workflow:def.registered',
   'workflow:def.deleted',
   'command:changed',
-  'feedback:submitted'
+  'feedback:submitted',
+  'client:presence'
 ] as const
 
 export type EventType = (typeof EVENT_TYPES)[number]
@@ -121,7 +122,8 @@ export const EVENT_TYPES_OBJ = {
   WORKFLOW_DEF_REGISTERED: 'workflow:def.registered',
   WORKFLOW_DEF_DELETED: 'workflow:def.deleted',
   COMMAND_CHANGED: 'command:changed',
-  FEEDBACK_SUBMITTED: 'feedback:submitted'
+  FEEDBACK_SUBMITTED: 'feedback:submitted',
+  CLIENT_PRESENCE: 'client:presence'
 } as const satisfies Record<string, EventType>
 
 /** 服务端全部事件类型（用于 subscribeEvents: "all"） */
@@ -173,7 +175,8 @@ export const DATA_SYNC_EVENTS = [
   'workflow:def.registered',
   'workflow:def.deleted',
   'command:changed',
-  'feedback:submitted'
+  'feedback:submitted',
+  'client:presence'
 ] as const
 
 export type DataSyncEventType = (typeof DATA_SYNC_EVENTS)[number]
@@ -395,6 +398,13 @@ export interface FeedbackSubmittedPayload extends EventPayloadBase {
   sessionId?: string
 }
 
+/** 客户端在线状态变化事件载荷；连接断开时 state 为 offline */
+export interface ClientPresencePayload extends EventPayloadBase {
+  clientId: string
+  state: import('./websocket').PresenceState | 'offline'
+  idleSeconds?: number
+}
+
 /** 各事件类型对应的 payload 类型 */
 export interface EventPayloadMap {
   notification: NotificationPayload
@@ -447,6 +457,7 @@ export interface EventPayloadMap {
   'workflow:failed': WorkflowFailedPayload
   'command:changed': CommandChangedPayload
   'feedback:submitted': FeedbackSubmittedPayload
+  'client:presence': ClientPresencePayload
 }
 
 /** 类型安全的 EventPushMessage，payload 与 eventType 对应 */
@@ -27,11 +27,105 @@ export interface PingMessage {
   type: 'ping'
 }
 
+/** 设备在线状态：online 正在使用，idle / away 按系统空闲时长区分 */
+export type PresenceState = 'online' | 'idle' | 'away'
+
+/** 客户端定期上报的在线状态 */
+export interface PresenceMessage {
+  type: 'presence'
+  state: PresenceState
+  /** 系统空闲秒数 */
+  idleSeconds?: number
+}
+
+/** 重连后请求重放 lastEventId 之后错过的事件 */
+export interface ResumeMessage {
+  type: 'resume'
+  lastEventId: number
+}
+
+/**
+ * 通过 WebSocket 调用 HTTP API，省去建立 HTTP 连接的开销。服务端以该连接的 API Key
+ * 转发到同名路由，id 由客户端生成，用于匹配 response
+ */
+export interface RpcRequestMessage {
+  type: 'request'
+  id: string
+  method: 'GET' | 'POST' | 'PUT' | 'PATCH' | 'DELETE'
+  /** 路由路径（不含 base path），如 /auth/scope-requests */
+  path: string
+  body?: unknown
+}
+
+/**
+ * 单个连接上的逻辑通道：各自排队、各自流控，大批数据同步不会阻塞通知。
+ * control 为连接控制消息（不受流控），binary 帧归入 data-sync
+ */
+export type RealtimeChannel = 'control' | 'notifications' | 'data-sync' | 'logs'
+
+export const REALTIME_CHANNELS: readonly RealtimeChannel[] = [
+  'control',
+  'notifications',
+  'data-sync',
+  'logs'
+]
+
+/** 开启流控后各通道的默认初始窗口（字节） */
+export const CHANNEL_DEFAULT_WINDOW = 1024 * 1024
+
+/**
+ * 开启按通道流控：此后除 control 外每个通道最多有 window 字节未被确认，
+ * 客户端处理完消息后以 channel_window 归还额度。未发送时服务端不限流
+ */
+export interface FlowControlMessage {
+  type: 'flow_control'
+  window: number
+}
+
+/** 为某个通道补充可发送的字节数 */
+export interface ChannelWindowMessage {
+  type: 'channel_window'
+  channel: RealtimeChannel
+  credit: number
+}
+
+/**
+ * 开放反向隧道：服务端把 /tunnels/{clientId}/{name}/ 下的请求经实时连接转发给客户端，
+ * 由客户端代理到本机端口。只有持有 scope 的调用方可以访问，scope 须在客户端的授权范围内
+ */
+export interface TunnelOpenMessage {
+  type: 'tunnel_open'
+  name: string
+  scope: string
+}
+
+export interface TunnelCloseMessage {
+  type: 'tunnel_close'
+  name: string
+}
+
+/** 客户端对 tunnel_request 的应答，body 为 base64 */
+export interface TunnelResponseMessage {
+  type: 'tunnel_response'
+  requestId: string
+  status: number
+  headers: Record<string, string>
+  body: string
+}
+
 export type ClientToServerMessage =
   | AuthMessage
   | RegisterEventMessage
   | UnregisterEventMessage
   | PingMessage
+  | PresenceMessage
+  | ResumeMessage
+  | RpcRequestMessage
+  | FlowControlMessage
+  | ChannelWindowMessage
+  | TunnelOpenMessage
+  | TunnelCloseMessage
+  | TunnelResponseMessage
 
 // ============ 服务器 -> 客户端的消息 ============
 
@@ -39,6 +133,12 @@ export interface ConnectedMessage {
   type: 'connected'
   clientId: string
   serverTime: number
+  /** 事件流 id，服务端重启后变化，此前的事件序号随之失效 */
+  streamId?: string
+  /** 连接时最新的事件序号，客户端以此作为初始重放游标 */
+  lastEventId?: number
+  /** 支持的逻辑通道；存在时客户端可发送 flow_control 开启流控 */
+  channels?: RealtimeChannel[]
 }
 
 export interface AuthenticatedMessage {
@@ -59,10 +159,52 @@ export interface UnregisteredMessage {
 
 export interface EventPushMessage<T = unknown> {
   type: 'event'
+  /** 事件序号，同一 streamId 内递增，用于断线后重放 */
+  id?: number
   eventType: EventType | string
   payload: T
   scope?: string
   timestamp: number
+  /** 所属逻辑通道，客户端据此归还流控额度 */
+  channel?: RealtimeChannel
+}
+
+/** 对 resume 的应答，随后依次推送 count 条错过的事件（序号不超过 untilId） */
+export interface ReplayMessage {
+  type: 'replay'
+  count: number
+  untilId: number
+  /** 游标早于服务端保留的最旧事件，部分事件已无法重放 */
+  truncated: boolean
+}
+
+/** 对 request 的应答；body 为 JSON 响应体，非 JSON 时为文本 */
+export interface RpcResponseMessage {
+  type: 'response'
+  id: string
+  status: number
+  body: unknown
+}
+
+/** 经隧道转发给客户端的 HTTP 请求，path 不含隧道前缀，body 为 base64 */
+export interface TunnelRequestMessage {
+  type: 'tunnel_request'
+  requestId: string
+  name: string
+  method: string
+  path: string
+  headers: Record<string, string>
+  body: string
+}
+
+/** 对 tunnel_open / tunnel_close 的应答；open 为 false 且有 error 时表示被拒绝 */
+export interface TunnelStatusMessage {
+  type: 'tunnel_status'
+  name: string
+  open: boolean
+  /** 隧道在服务端的访问路径，如 /tunnels/client-1/grafana */
+  path?: string
+  error?: string
 }
 
 export interface ErrorMessage {
@@ -77,6 +219,10 @@ export type ServerToClientMessage =
   | RegisteredMessage
   | UnregisteredMessage
   | EventPushMessage
+  | ReplayMessage
+  | RpcResponseMessage
+  | TunnelRequestMessage
+  | TunnelStatusMessage
   | ErrorMessage
   | { type: 'pong' }
 
@@ -174,6 +320,68 @@ export type TerminalServerMessage =
 /** 所有终端 WebSocket 消息 */
 export type TerminalWebSocketMessage = TerminalClientMessage | TerminalServerMessage
 
+// ============ 二进制帧（主 /ws 通道） ============
+
+/**
+ * 二进制帧头：version(1) | flags(1) | channel(uint32 BE) | index(uint32 BE)，其后为数据。
+ * 大数据（文件分块、截图等）按 channel 分块传输，避免 base64 膨胀
+ */
+export const BINARY_FRAME_VERSION = 1
+export const BINARY_FRAME_HEADER_SIZE = 10
+/** flags 位：该帧是 channel 的最后一块 */
+export const BINARY_FRAME_FLAG_LAST = 0x01
+/** 默认分块大小 */
+export const BINARY_FRAME_CHUNK_SIZE = 64 * 1024
+
+export interface BinaryFrame {
+  /** 逻辑流 id，由收发双方约定 */
+  channel: number
+  /** 块序号，从 0 开始 */
+  index: number
+  last: boolean
+  data: Uint8Array
+}
+
+export function encodeBinaryFrame(frame: BinaryFrame): Uint8Array {
+  const bytes = new Uint8Array(BINARY_FRAME_HEADER_SIZE + frame.data.byteLength)
+  const view = new DataView(bytes.buffer)
+  view.setUint8(0, BINARY_FRAME_VERSION)
+  view.setUint8(1, frame.last ? BINARY_FRAME_FLAG_LAST : 0)
+  view.setUint32(2, frame.channel)
+  view.setUint32(6, frame.index)
+  bytes.set(frame.data, BINARY_FRAME_HEADER_SIZE)
+  return bytes
+}
+
+/** 把数据按 chunkSize 拆成同一 channel 的连续帧；空数据也产生一个 last 帧 */
+export function splitBinaryFrames(
+  channel: number,
+  data: Uint8Array,
+  chunkSize: number
+): BinaryFrame[] {
+  const size = Math.max(1, Math.floor(chunkSize))
+  const count = Math.max(1, Math.ceil(data.byteLength / size))
+  return Array.from({ length: count }, (_, index) => ({
+    channel,
+    index,
+    last: index === count - 1,
+    data: data.subarray(index * size, (index + 1) * size)
+  }))
+}
+
+/** 解析二进制帧；长度不足或版本不符时返回 null */
+export function decodeBinaryFrame(bytes: Uint8Array): BinaryFrame | null {
+  if (bytes.byteLength < BINARY_FRAME_HEADER_SIZE) return null
+  const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength)
+  if (view.getUint8(0) !== BINARY_FRAME_VERSION) return null
+  return {
+    channel: view.getUint32(2),
+    index: view.getUint32(6),
+    last: (view.getUint8(1) & BINARY_FRAME_FLAG_LAST) !== 0,
+    data: bytes.subarray(BINARY_FRAME_HEADER_SIZE)
+  }
+}
+
 // ============ 通用 ============
 
 export type WebSocketMessage = ClientToServerMessage | ServerToClientMessage
@@ -184,7 +392,20 @@ export function isClientMessage(message: unknown): message is ClientToServerMess
   if (typeof message !== 'object' || message === null) return false
   const msg = message as Record<string, unknown>
   const type = msg.type as string
-  return type === 'auth' || type === 'register' || type === 'unregister' || type === 'ping'
+  return (
+    type === 'auth' ||
+    type === 'register' ||
+    type === 'unregister' ||
+    type === 'ping' ||
+    type === 'presence' ||
+    type === 'resume' ||
+    type === 'request' ||
+    type === 'flow_control' ||
+    type === 'channel_window' ||
+    type === 'tunnel_open' ||
+    type === 'tunnel_close' ||
+    type === 'tunnel_response'
+  )
 }
 
 export function isServerMessage(message: unknown): message is ServerToClientMessage {
@@ -197,7 +418,24 @@ export function isServerMessage(message: unknown): message is ServerToClientMess
     type === 'registered' ||
     type === 'unregistered' ||
     type === 'event' ||
+    type === 'replay' ||
+    type === 'response' ||
+    type === 'tunnel_request' ||
+    type === 'tunnel_status' ||
     type === 'error' ||
     type === 'pong'
   )
 }
+
+/**
+ * 服务端消息所属的逻辑通道：通知类事件走 notifications，log: 事件走 logs，
+ * 其余事件与隧道请求走 data-sync
+ */
+export function channelOfMessage(message: ServerToClientMessage): RealtimeChannel {
+  if (message.type === 'tunnel_request') return 'data-sync'
+  if (message.type !== 'event') return 'control'
+  if (message.eventType === 'notification' || message.eventType === 'schedule:reminded') {
+    return 'notifications'
+  }
+  return message.eventType.startsWith('log:') ? 'logs' : 'data-sync'
+}
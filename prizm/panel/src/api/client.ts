@@ -16,6 +16,7 @@ import type {
   AgentSession,
   AgentMessage,
   ClientInfo,
+  ClientPresence,
   ScopeDescription,
   TokenUsageRecord,
   TokenUsageCategory
@@ -34,6 +35,7 @@ export type {
   AgentSession,
   AgentMessage,
   ClientInfo,
+  ClientPresence,
   ScopeDescription,
   TokenUsageRecord,
   TokenUsageCategory
@@ -50,11 +52,25 @@ export interface RequestOptions extends RequestInit {
   scope?: string
 }
 
+/** 管理员 Key（拥有 * 权限的 API Key）的本地存储键；审批、配对码等管理操作需要它 */
+const ADMIN_KEY_STORAGE = 'prizm.adminKey'
+
+export function getAdminKey(): string {
+  return localStorage.getItem(ADMIN_KEY_STORAGE) ?? ''
+}
+
+export function setAdminKey(key: string): void {
+  if (key) localStorage.setItem(ADMIN_KEY_STORAGE, key)
+  else localStorage.removeItem(ADMIN_KEY_STORAGE)
+}
+
 async function request<T>(path: string, options?: RequestOptions): Promise<T> {
   const { scope, ...rest } = options ?? {}
+  const adminKey = getAdminKey()
   const headers: Record<string, string> = {
     'Content-Type': 'application/json',
     'X-Prizm-Panel': 'true',
+    ...(adminKey ? { Authorization: `Bearer ${adminKey}` } : {}),
     ...(rest.headers as Record<string, string>)
   }
   let url = `${getBaseUrl()}${path}`
@@ -114,6 +130,44 @@ export const registerClient = (name: string, requestedScopes?: string[]) =>
       requestedScopes: requestedScopes ?? ['default']
     })
   })
+export const createPairingCode = (scopes?: string[]) =>
+  request<{ code: string; scopes: string[]; expiresAt: number }>('/auth/pairing', {
+    method: 'POST',
+    body: JSON.stringify({ kind: 'code', scopes })
+  })
+export interface ScopeRequestInfo {
+  requestId: string
+  clientId: string
+  clientName: string
+  scopes: string[]
+  status: 'pending' | 'approved' | 'denied' | 'expired'
+  createdAt: number
+  expiresAt: number
+}
+export const getScopeRequests = () =>
+  request<{ requests: ScopeRequestInfo[] }>('/auth/scope-requests')
+export const resolveScopeRequest = (requestId: string, approve: boolean) =>
+  request<ScopeRequestInfo>(
+    `/auth/scope-requests/${encodeURIComponent(requestId)}/${approve ? 'approve' : 'deny'}`,
+    { method: 'POST' }
+  )
+
+// 注册审批（服务端开启 requireClientApproval 时）
+export interface RegistrationRequestInfo {
+  requestId: string
+  name: string
+  scopes: string[]
+  status: 'pending' | 'approved' | 'denied' | 'expired'
+  createdAt: number
+  expiresAt: number
+}
+export const getRegistrationRequests = () =>
+  request<{ requests: RegistrationRequestInfo[] }>('/auth/registration-requests')
+export const resolveRegistrationRequest = (requestId: string, approve: boolean) =>
+  request<RegistrationRequestInfo>(
+    `/auth/registration-requests/${encodeURIComponent(requestId)}/${approve ? 'approve' : 'deny'}`,
+    { method: 'POST' }
+  )
 
 // Notes（支持 scope）
 export const getNotes = (scope?: string) => request<{ notes: StickyNote[] }>('/notes', { scope })
@@ -595,6 +649,9 @@ export interface ServerConfigResponse {
     port?: number
     host?: string
     authDisabled?: boolean
+    requireClientApproval?: boolean
+    websocketCompression?: boolean
+    tunnelsEnabled?: boolean
     logLevel?: string
     mcpScope?: string
   }
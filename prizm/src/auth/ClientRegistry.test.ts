@@ -0,0 +1,116 @@
+/**
+ * ClientRegistry 单元测试：客户端声明的能力与请求签名
+ */
+
+import fs from 'fs'
+import os from 'os'
+import path from 'path'
+import { afterEach, beforeEach, describe, it, expect } from 'vitest'
+import { ClientRegistry, parseCapabilities } from './ClientRegistry'
+import { computeSignature, deriveSigningSecret } from './requestSigning'
+
+describe('parseCapabilities', () => {
+  it('keeps well-formed fields and drops the rest', () => {
+    expect(
+      parseCapabilities({
+        platform: ' win32-x64 ',
+        appVersion: 42,
+        features: ['ws', 'ws', '', 'notifications', 7]
+      })
+    ).toEqual({ platform: 'win32-x64', appVersion: undefined, features: ['ws', 'notifications'] })
+  })
+
+  it('ignores non-object values', () => {
+    expect(parseCapabilities(undefined)).toBeUndefined()
+    expect(parseCapabilities(['ws'])).toBeUndefined()
+    expect(parseCapabilities('ws')).toBeUndefined()
+  })
+})
+
+describe('ClientRegistry capabilities', () => {
+  let tmpDir: string
+
+  beforeEach(() => {
+    tmpDir = fs.mkdtempSync(path.join(os.tmpdir(), 'prizm-clients-test-'))
+  })
+
+  afterEach(() => {
+    fs.rmSync(tmpDir, { recursive: true, force: true })
+  })
+
+  it('records capabilities and keeps them across reloads', () => {
+    const registry = new ClientRegistry(tmpDir)
+    const { clientId } = registry.register('desktop', ['default'], {
+      platform: 'darwin-arm64',
+      appVersion: '1.2.0',
+      features: ['ws']
+    })
+    expect(registry.supportsFeature(clientId, 'ws')).toBe(true)
+    expect(registry.supportsFeature(clientId, 'file-transfer')).toBe(false)
+    expect(new ClientRegistry(tmpDir).get(clientId)?.capabilities?.platform).toBe('darwin-arm64')
+  })
+
+  it('treats clients without declared capabilities as supporting everything', () => {
+    const registry = new ClientRegistry(tmpDir)
+    const { clientId } = registry.register('legacy', ['default'])
+    expect(registry.supportsFeature(clientId, 'notifications')).toBe(true)
+  })
+
+  it('keeps earlier capabilities when re-registering without them', () => {
+    const registry = new ClientRegistry(tmpDir)
+    const { clientId } = registry.register('desktop', ['default'], { features: ['ws'] })
+    registry.register('desktop', ['default'])
+    expect(registry.get(clientId)?.capabilities).toEqual({ features: ['ws'] })
+  })
+})
+
+describe('ClientRegistry request signing', () => {
+  let tmpDir: string
+  const request = { method: 'GET', path: '/notes', body: '', timestamp: Date.now(), nonce: 'n' }
+
+  beforeEach(() => {
+    tmpDir = fs.mkdtempSync(path.join(os.tmpdir(), 'prizm-clients-test-'))
+  })
+
+  afterEach(() => {
+    fs.rmSync(tmpDir, { recursive: true, force: true })
+  })
+
+  it('accepts signatures made with the secret derived from the api key', () => {
+    const registry = new ClientRegistry(tmpDir)
+    const { clientId, apiKey } = registry.register('desktop', ['default'])
+    const signature = computeSignature(deriveSigningSecret(apiKey, clientId), request)
+    expect(registry.validateSignature(clientId, request, signature)?.clientId).toBe(clientId)
+    expect(
+      new ClientRegistry(tmpDir).validateSignature(clientId, request, signature)?.clientId
+    ).toBe(clientId)
+  })
+
+  it('does not let the stored key hash sign requests', () => {
+    const registry = new ClientRegistry(tmpDir)
+    const { clientId } = registry.register('desktop', ['default'])
+    const [stored] = JSON.parse(fs.readFileSync(path.join(tmpDir, 'clients.json'), 'utf-8'))
+    const forged = computeSignature(stored.apiKeyHash, request)
+    expect(registry.validateSignature(clientId, request, forged)).toBeNull()
+    expect(registry.get(clientId)).not.toHaveProperty('signingSecret')
+  })
+
+  it('rejects replays before recording the request', () => {
+    const registry = new ClientRegistry(tmpDir)
+    const { clientId, apiKey } = registry.register('desktop', ['default'])
+    const signature = computeSignature(deriveSigningSecret(apiKey, clientId), request)
+    expect(registry.validateSignature(clientId, request, signature, () => false)).toBeNull()
+    expect(registry.get(clientId)?.lastSeenAt).toBeUndefined()
+  })
+
+  it('rebuilds the signing secret on the next plain api key request', () => {
+    const registry = new ClientRegistry(tmpDir)
+    const { clientId, apiKey } = registry.register('desktop', ['default'])
+    fs.rmSync(path.join(tmpDir, 'request-signing.key'))
+    const reloaded = new ClientRegistry(tmpDir)
+    const signature = computeSignature(deriveSigningSecret(apiKey, clientId), request)
+    expect(reloaded.validateSignature(clientId, request, signature)).toBeNull()
+    expect(reloaded.validate(apiKey)?.clientId).toBe(clientId)
+    expect(reloaded.validateSignature(clientId, request, signature)?.clientId).toBe(clientId)
+  })
+})
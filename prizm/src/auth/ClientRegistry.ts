@@ -5,14 +5,25 @@
 import crypto from 'crypto'
 import fs from 'fs'
 import path from 'path'
-import type { ClientRecord } from '../types'
+import type { ClientCapabilities, ClientRecord } from '../types'
 import { createLogger } from '../logger'
 import { getConfig } from '../config'
 import { ONLINE_SCOPE } from '../core/ScopeStore'
-import { getClientsPath, getDataDir } from '../core/PathProviderCore'
+import { getClientsPath, getDataDir, getSigningKeyPath } from '../core/PathProviderCore'
+import {
+  computeSignature,
+  deriveSigningSecret,
+  openSigningSecret,
+  sealSigningSecret,
+  signaturesMatch
+} from './requestSigning'
+import type { SignedRequest } from './requestSigning'
 
 const log = createLogger('ClientRegistry')
 
+/** lastSeenAt 的更新精度：同一客户端在此间隔内的请求不重复写盘 */
+export const LAST_SEEN_RESOLUTION_MS = 60 * 1000
+
 function hashApiKey(apiKey: string): string {
   return crypto.createHash('sha256').update(apiKey, 'utf8').digest('hex')
 }
@@ -25,18 +36,71 @@ function generateApiKey(): string {
   return `prizm_${crypto.randomBytes(32).toString('hex')}`
 }
 
+/** 读取服务端签名密钥，不存在或损坏时生成新的（已保存的签名密钥随之失效，下次明文鉴权时重建） */
+function loadSigningKey(keyPath: string): Buffer {
+  try {
+    const key = Buffer.from(fs.readFileSync(keyPath, 'utf-8').trim(), 'hex')
+    if (key.length === 32) return key
+    log.warn('Invalid signing key file, generating a new one')
+  } catch (e) {
+    if ((e as NodeJS.ErrnoException).code !== 'ENOENT') log.warn('Failed to read signing key:', e)
+  }
+  const key = crypto.randomBytes(32)
+  fs.writeFileSync(keyPath, key.toString('hex'), { encoding: 'utf-8', mode: 0o600 })
+  return key
+}
+
+/** 对外返回的客户端记录：不含 Key 哈希与签名密钥 */
+export type PublicClientRecord = Omit<ClientRecord, 'apiKeyHash' | 'signingSecret'>
+
+function toPublic({ apiKeyHash: _, signingSecret: __, ...rest }: ClientRecord): PublicClientRecord {
+  return rest
+}
+
+/** 客户端声明能力的长度上限，防止注册请求写入过大的记录 */
+const MAX_CAPABILITY_LENGTH = 64
+const MAX_CAPABILITY_FEATURES = 32
+
+function capabilityString(value: unknown): string | undefined {
+  return typeof value === 'string' && value.trim()
+    ? value.trim().slice(0, MAX_CAPABILITY_LENGTH)
+    : undefined
+}
+
+/**
+ * 校验注册请求中的 capabilities：非对象时返回 undefined，
+ * 字段类型不符的忽略，功能列表去重并限制数量与长度
+ */
+export function parseCapabilities(raw: unknown): ClientCapabilities | undefined {
+  if (!raw || typeof raw !== 'object' || Array.isArray(raw)) return undefined
+  const { platform, appVersion, features } = raw as Record<string, unknown>
+  const list = Array.isArray(features)
+    ? features.map(capabilityString).filter((f): f is string => !!f)
+    : []
+  return {
+    platform: capabilityString(platform),
+    appVersion: capabilityString(appVersion),
+    features: [...new Set(list)].slice(0, MAX_CAPABILITY_FEATURES)
+  }
+}
+
 export interface RegisterResult {
   clientId: string
   apiKey: string
+  /** 实际授予的 scope */
+  grantedScopes: string[]
 }
 
 export interface ValidateResult {
   clientId: string
   allowedScopes: string[]
+  /** 本次请求之前最近一次使用时间 */
+  lastSeenAt?: number
 }
 
 export class ClientRegistry {
   private dataPath: string
+  private signingKey: Buffer
   private clients = new Map<string, ClientRecord>()
   /** Hash of API key -> record, for O(1) validate */
   private hashToRecord = new Map<string, ClientRecord>()
@@ -45,6 +109,9 @@ export class ClientRegistry {
     const dir = dataDir ? path.resolve(dataDir) : getDataDir()
     this.dataPath = dataDir ? path.join(dir, 'clients.json') : getClientsPath()
     this.ensureDataDir(dir)
+    this.signingKey = loadSigningKey(
+      dataDir ? path.join(dir, 'request-signing.key') : getSigningKeyPath()
+    )
     this.load()
   }
 
@@ -102,47 +169,68 @@ export class ClientRegistry {
    * 如果同名客户端已存在，则复用其 clientId（重新生成 apiKey、更新 scopes），
    * 避免因 clientId 变化导致记忆等用户数据丢失。
    */
-  register(name: string, requestedScopes: string[]): RegisterResult {
+  register(
+    name: string,
+    requestedScopes: string[],
+    capabilities?: ClientCapabilities
+  ): RegisterResult {
     const scopes = requestedScopes.length > 0 ? requestedScopes : ['default']
 
     // 检查同名客户端是否已存在
     const existing = this.findByName(name)
     if (existing) {
       // 复用已有 clientId，只重新生成 apiKey 并更新 scopes
-      const apiKey = generateApiKey()
-      const newHash = hashApiKey(apiKey)
-      this.hashToRecord.delete(existing.apiKeyHash)
-      existing.apiKeyHash = newHash
+      const apiKey = this.issueApiKey(existing)
       existing.allowedScopes = scopes
-      this.hashToRecord.set(newHash, existing)
+      if (capabilities) existing.capabilities = capabilities
       this.save()
       log.info(`Re-registered existing client "${name}" (clientId=${existing.clientId}), apiKey refreshed`)
-      return { clientId: existing.clientId, apiKey }
+      return { clientId: existing.clientId, apiKey, grantedScopes: [...scopes] }
     }
 
     // 新客户端
     const clientId = generateId()
-    const apiKey = generateApiKey()
-    const apiKeyHash = hashApiKey(apiKey)
-
     const record: ClientRecord = {
       clientId,
-      apiKeyHash,
+      apiKeyHash: '',
       name,
       allowedScopes: scopes,
-      createdAt: Date.now()
+      createdAt: Date.now(),
+      capabilities
     }
+    const apiKey = this.issueApiKey(record)
     this.clients.set(clientId, record)
-    this.hashToRecord.set(apiKeyHash, record)
     this.save()
-    return { clientId, apiKey }
+    return { clientId, apiKey, grantedScopes: [...scopes] }
+  }
+
+  /** 生成新 Key 并替换记录中的哈希与签名密钥（旧 Key 立即失效），不写盘 */
+  private issueApiKey(record: ClientRecord): string {
+    const apiKey = generateApiKey()
+    this.hashToRecord.delete(record.apiKeyHash)
+    record.apiKeyHash = hashApiKey(apiKey)
+    record.signingSecret = this.sealFor(record, apiKey)
+    this.hashToRecord.set(record.apiKeyHash, record)
+    return apiKey
+  }
+
+  private sealFor(record: ClientRecord, apiKey: string): string {
+    return sealSigningSecret(this.signingKey, deriveSigningSecret(apiKey, record.clientId))
   }
 
   /**
-   * 列出所有客户端（不含 apiKeyHash）
+   * 列出所有客户端（不含 apiKeyHash 与签名密钥）
    */
-  list(): Omit<ClientRecord, 'apiKeyHash'>[] {
-    return Array.from(this.clients.values()).map(({ apiKeyHash: _, ...rest }) => rest)
+  list(): PublicClientRecord[] {
+    return Array.from(this.clients.values()).map(toPublic)
+  }
+
+  /**
+   * 客户端是否声明支持某项功能；未声明能力的旧版客户端视为全部支持
+   */
+  supportsFeature(clientId: string, feature: string): boolean {
+    const capabilities = this.clients.get(clientId)?.capabilities
+    return !capabilities || capabilities.features.includes(feature)
   }
 
   /**
@@ -151,15 +239,25 @@ export class ClientRegistry {
   regenerateApiKey(clientId: string): string | null {
     const record = this.clients.get(clientId)
     if (!record) return null
-    const apiKey = generateApiKey()
-    const newHash = hashApiKey(apiKey)
-    this.hashToRecord.delete(record.apiKeyHash)
-    record.apiKeyHash = newHash
-    this.hashToRecord.set(newHash, record)
+    const apiKey = this.issueApiKey(record)
     this.save()
     return apiKey
   }
 
+  /**
+   * 为客户端追加 scope（批准 scope 申请后调用），返回更新后的全部 scope；客户端不存在时返回 null
+   */
+  addScopes(clientId: string, scopes: string[]): string[] | null {
+    const record = this.clients.get(clientId)
+    if (!record) return null
+    const merged = [...new Set([...record.allowedScopes, ...scopes])]
+    if (merged.length !== record.allowedScopes.length) {
+      record.allowedScopes = merged
+      this.save()
+    }
+    return [...merged]
+  }
+
   /**
    * 吊销客户端
    */
@@ -179,10 +277,47 @@ export class ClientRegistry {
     const hash = hashApiKey(apiKey)
     const record = this.hashToRecord.get(hash)
     if (!record) return null
-    return {
-      clientId: record.clientId,
-      allowedScopes: record.allowedScopes
+    // 旧记录（或服务端密钥重建后）没有可用的签名密钥：拿到 Key 原文时补上
+    if (!record.signingSecret || !openSigningSecret(this.signingKey, record.signingSecret)) {
+      record.signingSecret = this.sealFor(record, apiKey)
+      this.save()
     }
+    return this.accept(record)
+  }
+
+  /** 鉴权通过：记录最近使用时间（按 LAST_SEEN_RESOLUTION_MS 节流写盘），返回此前的使用时间 */
+  private accept(record: ClientRecord): ValidateResult {
+    const lastSeenAt = record.lastSeenAt
+    const now = Date.now()
+    if (!lastSeenAt || now - lastSeenAt >= LAST_SEEN_RESOLUTION_MS) {
+      record.lastSeenAt = now
+      this.save()
+    }
+    return { clientId: record.clientId, allowedScopes: record.allowedScopes, lastSeenAt }
+  }
+
+  /** 查询单个客户端（不含 Key 哈希与签名密钥） */
+  get(clientId: string): PublicClientRecord | null {
+    const record = this.clients.get(clientId)
+    return record ? toPublic(record) : null
+  }
+
+  /**
+   * 校验签名请求：以该客户端的签名密钥重新计算签名并比较。
+   * 签名有效后先调用 checkReplay（登记 nonce），通过后才记录使用时间，重放请求不写盘
+   */
+  validateSignature(
+    clientId: string,
+    req: SignedRequest,
+    signature: string,
+    checkReplay: () => boolean = () => true
+  ): ValidateResult | null {
+    const record = this.clients.get(clientId)
+    const secret = record?.signingSecret && openSigningSecret(this.signingKey, record.signingSecret)
+    if (!record || !secret) return null
+    if (!signaturesMatch(computeSignature(secret, req), signature)) return null
+    if (!checkReplay()) return null
+    return this.accept(record)
   }
 }
 
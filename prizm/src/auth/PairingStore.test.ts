@@ -0,0 +1,42 @@
+/**
+ * PairingStore 单元测试：一次性兑换、过期与短码防穷举
+ */
+
+import { describe, it, expect } from 'vitest'
+import { MAX_CODE_ATTEMPTS, PairingStore } from './PairingStore'
+
+describe('PairingStore', () => {
+  it('redeems a token only once', () => {
+    const store = new PairingStore()
+    const ticket = store.create(['default'])
+    expect(store.redeem(ticket.token)).toMatchObject({ scopes: ['default'] })
+    expect(store.redeem(ticket.token)).toBeNull()
+  })
+
+  it('rejects expired tokens', () => {
+    let now = 1000
+    const store = new PairingStore(60_000, () => now)
+    const ticket = store.create(['default'])
+    expect(ticket.expiresAt).toBe(61_000)
+    now = 61_000
+    expect(store.redeem(ticket.token)).toBeNull()
+  })
+
+  it('issues six-digit codes that redeem once', () => {
+    const store = new PairingStore()
+    const entry = store.createCode(['default'])
+    expect(entry.code).toMatch(/^\d{6}$/)
+    expect(store.redeemCode(entry.code)).toMatchObject({ scopes: ['default'] })
+    expect(store.redeemCode(entry.code)).toBeNull()
+  })
+
+  it('drops outstanding codes after too many failed attempts', () => {
+    const store = new PairingStore()
+    const entry = store.createCode(['default'])
+    const wrong = entry.code === '000000' ? '000001' : '000000'
+    for (let i = 0; i < MAX_CODE_ATTEMPTS; i++) {
+      expect(store.redeemCode(wrong)).toBeNull()
+    }
+    expect(store.redeemCode(entry.code)).toBeNull()
+  })
+})
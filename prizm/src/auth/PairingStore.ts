@@ -0,0 +1,98 @@
+/**
+ * Prizm PairingStore - 一次性配对凭证
+ *
+ * 已注册的客户端（或 Dashboard）签发短期配对令牌，新客户端用令牌注册，
+ * 获得与签发方相同（或更少）的 scope，无需手动输入 API Key。
+ * 二维码使用长令牌；Dashboard 展示的 6 位短码便于手动输入，连续输错过多次后全部短码作废。
+ */
+
+import crypto from 'crypto'
+
+/** 配对令牌默认有效期 */
+export const PAIRING_TTL_MS = 10 * 60 * 1000
+
+/** 短码连续兑换失败的上限，超过后作废所有未使用的短码，防止穷举 */
+export const MAX_CODE_ATTEMPTS = 5
+
+export interface PairingTicket {
+  token: string
+  scopes: string[]
+  expiresAt: number
+}
+
+export interface PairingCode {
+  code: string
+  scopes: string[]
+  expiresAt: number
+}
+
+export class PairingStore {
+  private tickets = new Map<string, PairingTicket>()
+  private codes = new Map<string, PairingCode>()
+  private failedCodeAttempts = 0
+
+  constructor(
+    private readonly ttlMs = PAIRING_TTL_MS,
+    private readonly now: () => number = Date.now
+  ) {}
+
+  /** 签发配对令牌 */
+  create(scopes: string[]): PairingTicket {
+    this.prune()
+    const ticket: PairingTicket = {
+      token: crypto.randomBytes(24).toString('base64url'),
+      scopes: [...scopes],
+      expiresAt: this.now() + this.ttlMs
+    }
+    this.tickets.set(ticket.token, ticket)
+    return ticket
+  }
+
+  /** 兑换配对令牌（一次性），无效或已过期时返回 null */
+  redeem(token: string): PairingTicket | null {
+    this.prune()
+    const ticket = this.tickets.get(token)
+    if (!ticket) return null
+    this.tickets.delete(token)
+    return ticket
+  }
+
+  /** 签发 6 位数字短码，供 Dashboard 展示 */
+  createCode(scopes: string[]): PairingCode {
+    this.prune()
+    let code: string
+    do {
+      code = crypto.randomInt(0, 1_000_000).toString().padStart(6, '0')
+    } while (this.codes.has(code))
+    const entry: PairingCode = { code, scopes: [...scopes], expiresAt: this.now() + this.ttlMs }
+    this.codes.set(code, entry)
+    return entry
+  }
+
+  /** 兑换短码（一次性），无效或已过期时返回 null */
+  redeemCode(code: string): PairingCode | null {
+    this.prune()
+    const entry = this.codes.get(code)
+    if (!entry) {
+      this.failedCodeAttempts += 1
+      if (this.failedCodeAttempts >= MAX_CODE_ATTEMPTS) {
+        this.codes.clear()
+        this.failedCodeAttempts = 0
+      }
+      return null
+    }
+    this.codes.delete(code)
+    this.failedCodeAttempts = 0
+    return entry
+  }
+
+  private prune(): void {
+    const now = this.now()
+    for (const [token, ticket] of this.tickets) {
+      if (ticket.expiresAt <= now) this.tickets.delete(token)
+    }
+    for (const [code, entry] of this.codes) {
+      if (entry.expiresAt <= now) this.codes.delete(code)
+    }
+  }
+}
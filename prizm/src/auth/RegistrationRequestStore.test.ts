@@ -0,0 +1,41 @@
+/**
+ * RegistrationRequestStore 单元测试：审批、一次性领取与过期
+ */
+
+import { describe, it, expect } from 'vitest'
+import { RegistrationRequestStore } from './RegistrationRequestStore'
+
+const result = { clientId: 'c-1', apiKey: 'key-1', grantedScopes: ['default', 'online'] }
+
+describe('RegistrationRequestStore', () => {
+  it('hands out the approved key exactly once', () => {
+    const store = new RegistrationRequestStore()
+    const request = store.create('desktop', ['default', 'online'])
+    expect(store.claim(request.requestId)).toMatchObject({ status: 'pending' })
+    expect(store.claim(request.requestId)?.result).toBeUndefined()
+
+    expect(store.approve(request.requestId, result)).toMatchObject({ status: 'approved' })
+    expect(store.listPending()).toHaveLength(0)
+    expect(store.claim(request.requestId)).toMatchObject({ status: 'approved', result })
+    expect(store.claim(request.requestId)).toBeNull()
+  })
+
+  it('resolves a request only once', () => {
+    const store = new RegistrationRequestStore()
+    const request = store.create('desktop', ['default'])
+    expect(store.deny(request.requestId)).toMatchObject({ status: 'denied' })
+    expect(store.approve(request.requestId, result)).toBeNull()
+    expect(store.claim(request.requestId)).toMatchObject({ status: 'denied' })
+  })
+
+  it('expires requests that were never answered', () => {
+    let now = 0
+    const store = new RegistrationRequestStore(1_000, () => now)
+    const request = store.create('desktop', ['default'])
+    now = 1_000
+    expect(store.get(request.requestId)?.status).toBe('expired')
+    expect(store.approve(request.requestId, result)).toBeNull()
+    now = 2_000
+    expect(store.get(request.requestId)).toBeNull()
+  })
+})
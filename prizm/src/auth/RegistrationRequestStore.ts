@@ -0,0 +1,115 @@
+/**
+ * Prizm RegistrationRequestStore - 需人工审批的客户端注册申请
+ *
+ * 开启 requireClientApproval 后，POST /auth/register 不再直接签发 API Key，
+ * 而是创建注册申请；管理员在 Dashboard 批准后才注册客户端。
+ * 客户端凭申请 ID 轮询结果，批准后的 API Key 只能领取一次。
+ */
+
+import crypto from 'crypto'
+import type { ClientCapabilities } from '../types'
+import type { RegisterResult } from './ClientRegistry'
+
+/** 申请有效期，过期未处理视为拒绝 */
+export const REGISTRATION_REQUEST_TTL_MS = 30 * 60 * 1000
+
+export type RegistrationRequestStatus = 'pending' | 'approved' | 'denied' | 'expired'
+
+export interface RegistrationRequest {
+  requestId: string
+  name: string
+  scopes: string[]
+  /** 申请时声明的能力，批准注册时一并记录 */
+  capabilities?: ClientCapabilities
+  status: RegistrationRequestStatus
+  createdAt: number
+  expiresAt: number
+}
+
+export class RegistrationRequestStore {
+  private requests = new Map<string, RegistrationRequest>()
+  /** 已批准、尚未被客户端领取的注册结果 */
+  private results = new Map<string, RegisterResult>()
+
+  constructor(
+    private readonly ttlMs = REGISTRATION_REQUEST_TTL_MS,
+    private readonly now: () => number = Date.now
+  ) {}
+
+  create(
+    name: string,
+    scopes: string[],
+    capabilities?: ClientCapabilities
+  ): RegistrationRequest {
+    this.prune()
+    const createdAt = this.now()
+    const request: RegistrationRequest = {
+      requestId: crypto.randomBytes(16).toString('hex'),
+      name,
+      scopes: [...scopes],
+      capabilities,
+      status: 'pending',
+      createdAt,
+      expiresAt: createdAt + this.ttlMs
+    }
+    this.requests.set(request.requestId, request)
+    return request
+  }
+
+  /** 查询申请（不含注册结果），过期的待处理申请标记为 expired */
+  get(requestId: string): RegistrationRequest | null {
+    this.prune()
+    return this.requests.get(requestId) ?? null
+  }
+
+  /** 待处理的申请，按创建时间排序 */
+  listPending(): RegistrationRequest[] {
+    this.prune()
+    return [...this.requests.values()]
+      .filter((r) => r.status === 'pending')
+      .sort((a, b) => a.createdAt - b.createdAt)
+  }
+
+  /** 批准并保存注册结果；申请不存在或已处理时返回 null */
+  approve(requestId: string, result: RegisterResult): RegistrationRequest | null {
+    const request = this.get(requestId)
+    if (!request || request.status !== 'pending') return null
+    request.status = 'approved'
+    this.results.set(requestId, result)
+    return request
+  }
+
+  /** 拒绝；申请不存在或已处理时返回 null */
+  deny(requestId: string): RegistrationRequest | null {
+    const request = this.get(requestId)
+    if (!request || request.status !== 'pending') return null
+    request.status = 'denied'
+    return request
+  }
+
+  /**
+   * 客户端查询申请：已批准时附带注册结果并移除申请，API Key 只交付一次
+   */
+  claim(requestId: string): (RegistrationRequest & { result?: RegisterResult }) | null {
+    const request = this.get(requestId)
+    if (!request) return null
+    const result = this.results.get(requestId)
+    if (request.status === 'approved') {
+      this.requests.delete(requestId)
+      this.results.delete(requestId)
+    }
+    return result ? { ...request, result } : request
+  }
+
+  /** 待处理申请到期后标记为 expired；申请在到期后再保留一个有效期，供客户端查询结果 */
+  private prune(): void {
+    const now = this.now()
+    for (const [id, request] of this.requests) {
+      if (request.status === 'pending' && request.expiresAt <= now) request.status = 'expired'
+      if (request.expiresAt + this.ttlMs <= now) {
+        this.requests.delete(id)
+        this.results.delete(id)
+      }
+    }
+  }
+}
@@ -0,0 +1,34 @@
+/**
+ * ScopeRequestStore 单元测试：审批与过期
+ */
+
+import { describe, it, expect } from 'vitest'
+import { ScopeRequestStore } from './ScopeRequestStore'
+
+describe('ScopeRequestStore', () => {
+  it('resolves a pending request once', () => {
+    const store = new ScopeRequestStore()
+    const request = store.create('c-1', 'desktop', ['notes'])
+    expect(store.listPending()).toHaveLength(1)
+    expect(store.resolve(request.requestId, true)).toMatchObject({ status: 'approved' })
+    expect(store.resolve(request.requestId, false)).toBeNull()
+    expect(store.listPending()).toHaveLength(0)
+  })
+
+  it('replaces an earlier pending request from the same client', () => {
+    const store = new ScopeRequestStore()
+    const first = store.create('c-1', 'desktop', ['notes'])
+    store.create('c-1', 'desktop', ['notes', 'todo'])
+    expect(store.get(first.requestId)).toBeNull()
+    expect(store.listPending().map((r) => r.scopes)).toEqual([['notes', 'todo']])
+  })
+
+  it('expires requests that were never answered', () => {
+    let now = 0
+    const store = new ScopeRequestStore(1_000, () => now)
+    const request = store.create('c-1', 'desktop', ['notes'])
+    now = 1_000
+    expect(store.get(request.requestId)?.status).toBe('expired')
+    expect(store.resolve(request.requestId, true)).toBeNull()
+  })
+})
@@ -0,0 +1,83 @@
+/**
+ * Prizm ScopeRequestStore - 客户端追加 scope 的申请
+ *
+ * 已注册的客户端申请额外 scope，管理员在 Dashboard 批准或拒绝；
+ * 客户端轮询申请状态，批准后即可使用新 scope，无需注销重新注册。
+ */
+
+import crypto from 'crypto'
+
+/** 申请有效期，过期未处理视为拒绝 */
+export const SCOPE_REQUEST_TTL_MS = 10 * 60 * 1000
+
+export type ScopeRequestStatus = 'pending' | 'approved' | 'denied' | 'expired'
+
+export interface ScopeRequest {
+  requestId: string
+  clientId: string
+  clientName: string
+  scopes: string[]
+  status: ScopeRequestStatus
+  createdAt: number
+  expiresAt: number
+}
+
+export class ScopeRequestStore {
+  private requests = new Map<string, ScopeRequest>()
+
+  constructor(
+    private readonly ttlMs = SCOPE_REQUEST_TTL_MS,
+    private readonly now: () => number = Date.now
+  ) {}
+
+  /** 创建申请；同一客户端未处理的旧申请会被替换 */
+  create(clientId: string, clientName: string, scopes: string[]): ScopeRequest {
+    this.prune()
+    for (const [id, existing] of this.requests) {
+      if (existing.clientId === clientId && existing.status === 'pending') this.requests.delete(id)
+    }
+    const createdAt = this.now()
+    const request: ScopeRequest = {
+      requestId: crypto.randomBytes(16).toString('hex'),
+      clientId,
+      clientName,
+      scopes: [...scopes],
+      status: 'pending',
+      createdAt,
+      expiresAt: createdAt + this.ttlMs
+    }
+    this.requests.set(request.requestId, request)
+    return request
+  }
+
+  /** 查询申请，过期的待处理申请标记为 expired */
+  get(requestId: string): ScopeRequest | null {
+    this.prune()
+    return this.requests.get(requestId) ?? null
+  }
+
+  /** 待处理的申请，按创建时间排序 */
+  listPending(): ScopeRequest[] {
+    this.prune()
+    return [...this.requests.values()]
+      .filter((r) => r.status === 'pending')
+      .sort((a, b) => a.createdAt - b.createdAt)
+  }
+
+  /** 批准或拒绝；申请不存在或已处理时返回 null */
+  resolve(requestId: string, approved: boolean): ScopeRequest | null {
+    const request = this.get(requestId)
+    if (!request || request.status !== 'pending') return null
+    request.status = approved ? 'approved' : 'denied'
+    return request
+  }
+
+  /** 待处理申请到期后标记为 expired；申请在到期后再保留一个有效期，供客户端查询结果 */
+  private prune(): void {
+    const now = this.now()
+    for (const [id, request] of this.requests) {
+      if (request.status === 'pending' && request.expiresAt <= now) request.status = 'expired'
+      if (request.expiresAt + this.ttlMs <= now) this.requests.delete(id)
+    }
+  }
+}
@@ -2,12 +2,22 @@
  * Prizm Auth Middleware - 鉴权中间件
  */
 
+import crypto from 'crypto'
 import type { Request, Response, NextFunction } from 'express'
-import type { ClientRegistry } from './ClientRegistry'
+import type { ClientRegistry, ValidateResult } from './ClientRegistry'
+import {
+  CLIENT_ID_HEADER,
+  NONCE_HEADER,
+  NonceCache,
+  SIGNATURE_HEADER,
+  TIMESTAMP_HEADER
+} from './requestSigning'
 
 export interface PrizmAuthContext {
   clientId?: string
   allowedScopes: string[]
+  /** 本次请求之前该客户端最近一次使用时间 */
+  lastSeenAt?: number
 }
 
 // 扩展 Express Request 类型，添加 WebSocket 服务器访问
@@ -15,6 +25,8 @@ declare global {
   namespace Express {
     interface Request {
       prizmClient?: PrizmAuthContext
+      /** 原始请求体，用于校验请求签名（由 express.json 的 verify 回调写入） */
+      rawBody?: Buffer
       /** @deprecated scope 已改为请求参数，不再使用全局 prizmScope */
       prizmScope?: string
       // prizmServer 将由服务器在中间件中设置
@@ -32,11 +44,16 @@ function isExemptPath(pathname: string): boolean {
 
 /**
  * Auth routes that remain exempt (no API key required).
- * Only POST /auth/register and GET /auth/scopes (read-only) are exempt;
- * GET /auth/clients, DELETE /auth/clients/:id, POST .../regenerate-key require auth.
+ * Only POST /auth/register, GET /auth/register/:requestId (polling a pending
+ * registration; the random request id is the credential), POST /auth/pair
+ * (one-time pairing token) and GET /auth/scopes (read-only) are exempt;
+ * GET /auth/clients, DELETE /auth/clients/:id, POST .../regenerate-key require auth
+ * (and, in the auth routes, the admin key or a key holding the '*' scope).
  */
 function isAuthExemptPath(method: string, pathname: string): boolean {
   if (pathname === '/auth/register' && method === 'POST') return true
+  if (/^\/auth\/register\/[^/]+$/.test(pathname) && method === 'GET') return true
+  if (pathname === '/auth/pair' && method === 'POST') return true
   if (pathname === '/auth/scopes' && method === 'GET') return true
   return false
 }
@@ -57,13 +74,59 @@ function extractApiKey(req: Request): string | null {
   return null
 }
 
+function headerValue(req: Request, name: string): string | null {
+  const value = req.headers[name]
+  return typeof value === 'string' && value.trim() ? value.trim() : null
+}
+
+/**
+ * 校验签名请求（X-Prizm-Signature）；返回 null 表示签名无效、过期或重放
+ */
+function validateSignedRequest(
+  req: Request,
+  signature: string,
+  clientRegistry: ClientRegistry,
+  nonces: NonceCache
+): ValidateResult | null {
+  const clientId = headerValue(req, CLIENT_ID_HEADER)
+  const nonce = headerValue(req, NONCE_HEADER)
+  const timestamp = Number(headerValue(req, TIMESTAMP_HEADER))
+  // 过期请求不必计算签名；nonce 在签名有效后、记录使用时间前登记，
+  // 伪造请求占用不了 nonce，重放请求也不会触发写盘
+  if (!clientId || !nonce || !nonces.isFresh(timestamp)) return null
+  return clientRegistry.validateSignature(
+    clientId,
+    {
+      method: req.method,
+      path: req.originalUrl,
+      body: req.rawBody?.toString('utf8') ?? '',
+      timestamp,
+      nonce
+    },
+    signature,
+    () => nonces.accept(nonce, timestamp)
+  )
+}
+
+/**
+ * 比较 API Key 与配置的管理员 Key（先取摘要，避免长度差异与逐字节比较泄露信息）
+ */
+function isAdminKey(apiKey: string, adminKey: string | undefined): boolean {
+  if (!adminKey) return false
+  const digest = (value: string) => crypto.createHash('sha256').update(value).digest()
+  return crypto.timingSafeEqual(digest(apiKey), digest(adminKey))
+}
+
 export interface CreateAuthMiddlewareOptions {
   clientRegistry: ClientRegistry
   authEnabled?: boolean
+  /** 管理员 Key（PRIZM_ADMIN_KEY），以它鉴权的请求拥有全部权限（*），不对应任何客户端 */
+  adminKey?: string
 }
 
 export function createAuthMiddleware(options: CreateAuthMiddlewareOptions) {
-  const { clientRegistry, authEnabled = true } = options
+  const { clientRegistry, authEnabled = true, adminKey } = options
+  const nonces = new NonceCache()
 
   return (req: Request, res: Response, next: NextFunction): void => {
     if (!authEnabled) {
@@ -76,8 +139,26 @@ export function createAuthMiddleware(options: CreateAuthMiddlewareOptions) {
       return
     }
 
+    // Dashboard 请求免鉴权但没有客户端上下文；附带了 API Key 时照常校验，
+    // 管理操作（auth 路由）需要管理员 Key 或拥有 * 权限的客户端 Key
     const isPanelRequest = req.headers['x-prizm-panel'] === 'true'
-    if (isPanelRequest) {
+    if (isPanelRequest && !headerValue(req, SIGNATURE_HEADER) && !extractApiKey(req)) {
+      next()
+      return
+    }
+
+    const signature = headerValue(req, SIGNATURE_HEADER)
+    if (signature) {
+      const signed = validateSignedRequest(req, signature, clientRegistry, nonces)
+      if (!signed) {
+        res.status(401).json({ error: 'Invalid or expired request signature' })
+        return
+      }
+      req.prizmClient = {
+        clientId: signed.clientId,
+        allowedScopes: signed.allowedScopes,
+        lastSeenAt: signed.lastSeenAt
+      }
       next()
       return
     }
@@ -90,6 +171,12 @@ export function createAuthMiddleware(options: CreateAuthMiddlewareOptions) {
       return
     }
 
+    if (isAdminKey(apiKey, adminKey)) {
+      req.prizmClient = { allowedScopes: ['*'] }
+      next()
+      return
+    }
+
     const result = clientRegistry.validate(apiKey)
     if (!result) {
       res.status(401).json({ error: 'Invalid API key' })
@@ -98,7 +185,8 @@ export function createAuthMiddleware(options: CreateAuthMiddlewareOptions) {
 
     req.prizmClient = {
       clientId: result.clientId,
-      allowedScopes: result.allowedScopes
+      allowedScopes: result.allowedScopes,
+      lastSeenAt: result.lastSeenAt
     }
     next()
   }
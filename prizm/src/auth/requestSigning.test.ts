@@ -0,0 +1,79 @@
+/**
+ * 请求签名单元测试：签名计算与防重放
+ */
+
+import crypto from 'crypto'
+import { describe, it, expect } from 'vitest'
+import {
+  NonceCache,
+  canonicalRequest,
+  computeSignature,
+  deriveSigningSecret,
+  openSigningSecret,
+  sealSigningSecret,
+  signaturesMatch
+} from './requestSigning'
+
+const request = {
+  method: 'post',
+  path: '/notes?scope=default',
+  body: '{"content":"hi"}',
+  timestamp: 1_700_000_000_000,
+  nonce: 'n-1'
+}
+
+describe('computeSignature', () => {
+  it('signs the canonical request with the signing secret', () => {
+    const secret = deriveSigningSecret('prizm_key', 'client-1')
+    const expected = crypto
+      .createHmac('sha256', secret)
+      .update(canonicalRequest(request))
+      .digest('hex')
+    expect(canonicalRequest(request).split('\n').slice(0, 4)).toEqual([
+      'POST',
+      '/notes?scope=default',
+      '1700000000000',
+      'n-1'
+    ])
+    expect(signaturesMatch(expected, computeSignature(secret, request))).toBe(true)
+    expect(signaturesMatch(expected, computeSignature(secret, { ...request, body: '{}' }))).toBe(
+      false
+    )
+  })
+})
+
+describe('deriveSigningSecret', () => {
+  it('is HKDF of the api key salted with the client id', () => {
+    const expected = Buffer.from(
+      crypto.hkdfSync('sha256', 'prizm_key', 'client-1', 'prizm-request-signing', 32)
+    ).toString('hex')
+    expect(deriveSigningSecret('prizm_key', 'client-1')).toBe(expected)
+    expect(deriveSigningSecret('prizm_key', 'client-2')).not.toBe(expected)
+    expect(expected).not.toBe(crypto.createHash('sha256').update('prizm_key').digest('hex'))
+  })
+})
+
+describe('sealSigningSecret', () => {
+  it('round-trips with the server key and fails with any other', () => {
+    const key = crypto.randomBytes(32)
+    const sealed = sealSigningSecret(key, 'secret')
+    expect(sealed).not.toContain('secret')
+    expect(openSigningSecret(key, sealed)).toBe('secret')
+    expect(openSigningSecret(crypto.randomBytes(32), sealed)).toBeNull()
+    expect(openSigningSecret(key, 'garbage')).toBeNull()
+  })
+})
+
+describe('NonceCache', () => {
+  it('rejects replays and stale timestamps', () => {
+    let now = 10_000_000
+    const cache = new NonceCache(60_000, () => now)
+    expect(cache.accept('a', now)).toBe(true)
+    expect(cache.accept('a', now)).toBe(false)
+    expect(cache.accept('b', now - 120_000)).toBe(false)
+    now += 61_000
+    expect(cache.accept('c', now)).toBe(true)
+    expect(cache.isFresh(now - 30_000)).toBe(true)
+    expect(cache.isFresh(now - 120_000)).toBe(false)
+  })
+})
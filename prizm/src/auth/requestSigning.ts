@@ -0,0 +1,121 @@
+/**
+ * Prizm 请求签名校验
+ *
+ * 客户端开启签名后不再发送原始 API Key，而是以签名密钥对请求做 HMAC-SHA256。
+ * 签名密钥由 API Key 经 HKDF 派生（以 clientId 为 salt），与 clients.json 中的 apiKeyHash 无关；
+ * 服务端在拿到 Key 原文时（签发或明文鉴权）派生并以服务端密钥加密保存，
+ * 仅读取 clients.json 无法伪造签名。派生与签名串格式须与
+ * prizm-electron-client/electron/requestSigning.ts 保持一致。
+ */
+
+import crypto from 'crypto'
+
+export const SIGNATURE_HEADER = 'x-prizm-signature'
+export const TIMESTAMP_HEADER = 'x-prizm-timestamp'
+export const NONCE_HEADER = 'x-prizm-nonce'
+export const CLIENT_ID_HEADER = 'x-prizm-client-id'
+
+/** 允许的时钟偏差，超出视为过期请求 */
+export const SIGNATURE_WINDOW_MS = 5 * 60 * 1000
+
+const SIGNING_SECRET_INFO = 'prizm-request-signing'
+const SEAL_ALGORITHM = 'aes-256-gcm'
+
+export interface SignedRequest {
+  method: string
+  /** 请求路径与查询串（req.originalUrl） */
+  path: string
+  body: string
+  timestamp: number
+  nonce: string
+}
+
+function sha256Hex(value: string): string {
+  return crypto.createHash('sha256').update(value, 'utf8').digest('hex')
+}
+
+/**
+ * 签名串：METHOD \n path \n timestamp \n nonce \n sha256(body)
+ */
+export function canonicalRequest(req: SignedRequest): string {
+  return [req.method.toUpperCase(), req.path, req.timestamp, req.nonce, sha256Hex(req.body)].join(
+    '\n'
+  )
+}
+
+/**
+ * 签名密钥（hex）：HKDF-SHA256(ikm = apiKey, salt = clientId)
+ */
+export function deriveSigningSecret(apiKey: string, clientId: string): string {
+  const secret = crypto.hkdfSync('sha256', apiKey, clientId, SIGNING_SECRET_INFO, 32)
+  return Buffer.from(secret).toString('hex')
+}
+
+/**
+ * 以签名密钥计算签名（hex）
+ */
+export function computeSignature(signingSecret: string, req: SignedRequest): string {
+  return crypto
+    .createHmac('sha256', signingSecret)
+    .update(canonicalRequest(req), 'utf8')
+    .digest('hex')
+}
+
+/**
+ * 用服务端密钥（32 字节）加密签名密钥，格式 iv.tag.ciphertext（base64）
+ */
+export function sealSigningSecret(serverKey: Buffer, secret: string): string {
+  const iv = crypto.randomBytes(12)
+  const cipher = crypto.createCipheriv(SEAL_ALGORITHM, serverKey, iv)
+  const ciphertext = Buffer.concat([cipher.update(secret, 'utf8'), cipher.final()])
+  return [iv, cipher.getAuthTag(), ciphertext].map((part) => part.toString('base64')).join('.')
+}
+
+/** 解密 sealSigningSecret 的结果；格式不符或服务端密钥不匹配时返回 null */
+export function openSigningSecret(serverKey: Buffer, sealed: string): string | null {
+  const [iv, tag, ciphertext] = sealed.split('.').map((part) => Buffer.from(part, 'base64'))
+  if (!iv || !tag || !ciphertext) return null
+  try {
+    const decipher = crypto.createDecipheriv(SEAL_ALGORITHM, serverKey, iv)
+    decipher.setAuthTag(tag)
+    return Buffer.concat([decipher.update(ciphertext), decipher.final()]).toString('utf8')
+  } catch {
+    return null
+  }
+}
+
+/** 常量时间比较两个 hex 签名 */
+export function signaturesMatch(expected: string, actual: string): boolean {
+  const a = Buffer.from(expected, 'utf8')
+  const b = Buffer.from(actual, 'utf8')
+  return a.length === b.length && crypto.timingSafeEqual(a, b)
+}
+
+/**
+ * 防重放：时间戳须在窗口内，且窗口内同一 nonce 只接受一次
+ */
+export class NonceCache {
+  private seen = new Map<string, number>()
+
+  constructor(
+    private readonly windowMs = SIGNATURE_WINDOW_MS,
+    private readonly now: () => number = Date.now
+  ) {}
+
+  /** 时间戳是否在窗口内 */
+  isFresh(timestamp: number): boolean {
+    return Number.isFinite(timestamp) && Math.abs(this.now() - timestamp) <= this.windowMs
+  }
+
+  /** 接受该请求时返回 true，过期或重复时返回 false */
+  accept(nonce: string, timestamp: number): boolean {
+    const now = this.now()
+    if (!this.isFresh(timestamp)) return false
+    for (const [key, expiresAt] of this.seen) {
+      if (expiresAt <= now) this.seen.delete(key)
+    }
+    if (this.seen.has(nonce)) return false
+    this.seen.set(nonce, timestamp + this.windowMs)
+    return true
+  }
+}
@@ -43,6 +43,27 @@ describe("config", () => {
 		expect(cfg.authEnabled).toBe(false);
 	});
 
+	it("PRIZM_REQUIRE_CLIENT_APPROVAL=1 开启注册审批", () => {
+		expect(getConfig().requireClientApproval).toBe(false);
+		resetConfig();
+		process.env.PRIZM_REQUIRE_CLIENT_APPROVAL = "1";
+		expect(getConfig().requireClientApproval).toBe(true);
+	});
+
+	it("PRIZM_WEBSOCKET_COMPRESSION=0 关闭 WebSocket 压缩", () => {
+		expect(getConfig().websocketCompression).toBe(true);
+		resetConfig();
+		process.env.PRIZM_WEBSOCKET_COMPRESSION = "0";
+		expect(getConfig().websocketCompression).toBe(false);
+	});
+
+	it("PRIZM_TUNNELS_ENABLED=1 开启反向隧道", () => {
+		expect(getConfig().enableTunnels).toBe(false);
+		resetConfig();
+		process.env.PRIZM_TUNNELS_ENABLED = "1";
+		expect(getConfig().enableTunnels).toBe(true);
+	});
+
 	it("PRIZM_LOG_LEVEL 支持 warn/error", () => {
 		process.env.PRIZM_LOG_LEVEL = "warn";
 		const cfg = getConfig();
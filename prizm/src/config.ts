@@ -16,12 +16,20 @@ export interface PrizmConfig {
   dataDir: string
   /** 是否启用鉴权 */
   authEnabled: boolean
+  /** 新客户端注册是否需要管理员批准。环境变量 PRIZM_REQUIRE_CLIENT_APPROVAL */
+  requireClientApproval: boolean
+  /** 管理员 Key，拥有全部权限（*），供 Dashboard 等管理操作使用（仅来自 env）。环境变量 PRIZM_ADMIN_KEY */
+  adminKey?: string
   /** 是否启用 CORS */
   enableCors: boolean
   /** 是否启用 WebSocket */
   enableWebSocket: boolean
   /** WebSocket 路径 */
   websocketPath: string
+  /** 允许客户端协商 permessage-deflate 压缩。环境变量 PRIZM_WEBSOCKET_COMPRESSION */
+  websocketCompression: boolean
+  /** 允许客户端经实时连接开放反向隧道（默认关闭）。环境变量 PRIZM_TUNNELS_ENABLED */
+  enableTunnels: boolean
   /** 日志级别 */
   logLevel: 'info' | 'warn' | 'error'
   /** MCP 默认 scope，连接时未传 ?scope= 时使用。环境变量 PRIZM_MCP_SCOPE */
@@ -76,6 +84,11 @@ export function getConfig(): PrizmConfig {
         : s?.authDisabled === true
         ? false
         : true,
+    requireClientApproval:
+      env.PRIZM_REQUIRE_CLIENT_APPROVAL !== undefined
+        ? parseBool(env.PRIZM_REQUIRE_CLIENT_APPROVAL, false)
+        : s?.requireClientApproval ?? false,
+    adminKey: env.PRIZM_ADMIN_KEY?.trim() || undefined,
     enableCors:
       env.PRIZM_CORS_ENABLED !== undefined
         ? parseBool(env.PRIZM_CORS_ENABLED, true)
@@ -85,6 +98,14 @@ export function getConfig(): PrizmConfig {
         ? parseBool(env.PRIZM_WEBSOCKET_ENABLED, true)
         : s?.websocketEnabled ?? true,
     websocketPath: (env.PRIZM_WEBSOCKET_PATH?.trim() || s?.websocketPath) ?? '/ws',
+    websocketCompression:
+      env.PRIZM_WEBSOCKET_COMPRESSION !== undefined
+        ? parseBool(env.PRIZM_WEBSOCKET_COMPRESSION, true)
+        : s?.websocketCompression ?? true,
+    enableTunnels:
+      env.PRIZM_TUNNELS_ENABLED !== undefined
+        ? parseBool(env.PRIZM_TUNNELS_ENABLED, false)
+        : s?.tunnelsEnabled ?? false,
     logLevel:
       env.PRIZM_LOG_LEVEL === 'warn' || env.PRIZM_LOG_LEVEL === 'error'
         ? env.PRIZM_LOG_LEVEL
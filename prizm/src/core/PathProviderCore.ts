@@ -16,6 +16,7 @@ const MEMORY_DIR = 'memory'
 
 const SCOPE_REGISTRY_FILE = 'scope-registry.json'
 const CLIENTS_FILE = 'clients.json'
+const SIGNING_KEY_FILE = 'request-signing.key'
 const AGENT_TOOLS_FILE = 'agent-tools.json'
 const MCP_SERVERS_FILE = 'mcp-servers.json'
 
@@ -97,6 +98,11 @@ export function getClientsPath(): string {
   return path.join(getDataDir(), CLIENTS_FILE)
 }
 
+/** 加密签名密钥用的服务端密钥，与 clients.json 分开保存 */
+export function getSigningKeyPath(): string {
+  return path.join(getDataDir(), SIGNING_KEY_FILE)
+}
+
 export function getAgentToolsPath(): string {
   return path.join(getDataDir(), AGENT_TOOLS_FILE)
 }
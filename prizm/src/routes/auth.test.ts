@@ -0,0 +1,158 @@
+/**
+ * Auth Routes 单元测试：管理操作只接受管理员 Key 或拥有 * 权限的 API Key，免鉴权注册不能申请 *
+ *
+ * 使用 express + supertest + 真实的鉴权中间件与 ClientRegistry
+ */
+
+import fs from 'fs'
+import os from 'os'
+import path from 'path'
+import { afterEach, beforeEach, describe, it, expect, vi } from 'vitest'
+import express from 'express'
+import request from 'supertest'
+import { ClientRegistry } from '../auth/ClientRegistry'
+import { createAuthMiddleware } from '../auth/authMiddleware'
+import { createAuthRoutes } from './auth'
+
+const config = vi.hoisted(() => ({ requireClientApproval: true }))
+
+vi.mock('../config', () => ({ getConfig: () => config }))
+
+vi.mock('../core/ScopeStore', () => ({
+  scopeStore: { getAllScopes: () => ['default', 'notes', 'online'] }
+}))
+vi.mock('../core/ScopeRegistry', () => ({ scopeRegistry: { list: () => [] } }))
+
+describe('Auth Routes admin access', () => {
+  let tmpDir: string
+  let registry: ClientRegistry
+  let app: express.Express
+
+  beforeEach(() => {
+    tmpDir = fs.mkdtempSync(path.join(os.tmpdir(), 'prizm-auth-routes-test-'))
+    registry = new ClientRegistry(tmpDir)
+    app = express()
+    app.use(express.json())
+    app.use(createAuthMiddleware({ clientRegistry: registry, adminKey: 'admin-secret' }))
+    const router = express.Router()
+    createAuthRoutes(router, registry)
+    app.use('/auth', router)
+  })
+
+  afterEach(() => {
+    config.requireClientApproval = true
+    fs.rmSync(tmpDir, { recursive: true, force: true })
+  })
+
+  async function pendingRegistration(): Promise<string> {
+    const res = await request(app).post('/auth/register').send({ name: 'laptop' }).expect(202)
+    return res.body.requestId
+  }
+
+  it('does not treat a panel request without a key as admin', async () => {
+    const requestId = await pendingRegistration()
+    const panel = { 'X-Prizm-Panel': 'true' }
+    await request(app).get('/auth/registration-requests').set(panel).expect(403)
+    await request(app)
+      .post(`/auth/registration-requests/${requestId}/approve`)
+      .set(panel)
+      .expect(403)
+    await request(app).get('/auth/scope-requests').set(panel).expect(403)
+    await request(app).post('/auth/pairing').set(panel).send({ kind: 'code' }).expect(403)
+    expect(registry.list()).toHaveLength(0)
+  })
+
+  it('rejects clients without the * scope', async () => {
+    const requestId = await pendingRegistration()
+    const { apiKey } = registry.register('phone', ['default'])
+    await request(app)
+      .post(`/auth/registration-requests/${requestId}/approve`)
+      .set('Authorization', `Bearer ${apiKey}`)
+      .expect(403)
+  })
+
+  it('accepts a * key, also on panel requests', async () => {
+    const requestId = await pendingRegistration()
+    const { apiKey } = registry.register('admin', ['*'])
+    await request(app)
+      .post(`/auth/registration-requests/${requestId}/approve`)
+      .set({ 'X-Prizm-Panel': 'true', Authorization: `Bearer ${apiKey}` })
+      .expect(200)
+    expect(registry.list().map((c) => c.name)).toContain('laptop')
+  })
+
+  it('still validates keys sent with the panel header', async () => {
+    await request(app)
+      .get('/auth/registration-requests')
+      .set({ 'X-Prizm-Panel': 'true', Authorization: 'Bearer wrong' })
+      .expect(401)
+  })
+
+  it('lets clients mint pairing codes only for their own scopes', async () => {
+    const { apiKey } = registry.register('phone', ['default', 'notes'])
+    const res = await request(app)
+      .post('/auth/pairing')
+      .set('Authorization', `Bearer ${apiKey}`)
+      .send({ kind: 'code', scopes: ['notes', 'secret'] })
+      .expect(201)
+    expect(res.body.scopes).toEqual(['notes'])
+  })
+
+  it('accepts the configured admin key', async () => {
+    await request(app)
+      .get('/auth/clients')
+      .set({ 'X-Prizm-Panel': 'true', Authorization: 'Bearer admin-secret' })
+      .expect(200)
+  })
+
+  it('rejects * and unknown scopes in unauthenticated registration', async () => {
+    config.requireClientApproval = false
+    await request(app)
+      .post('/auth/register')
+      .send({ name: 'x', requestedScopes: ['*'] })
+      .expect(400)
+    await request(app)
+      .post('/auth/register')
+      .send({ name: 'x', requestedScopes: ['missing'] })
+      .expect(400)
+    expect(registry.list()).toHaveLength(0)
+    await request(app)
+      .post('/auth/register')
+      .send({ name: 'x', requestedScopes: ['notes'] })
+      .expect(201)
+  })
+
+  it('lets * registrations through only for admin approval', async () => {
+    await request(app)
+      .post('/auth/register')
+      .send({ name: 'x', requestedScopes: ['*'] })
+      .expect(202)
+    expect(registry.list()).toHaveLength(0)
+  })
+
+  it('rejects unknown scopes when minting pairing codes', async () => {
+    await request(app)
+      .post('/auth/pairing')
+      .set('Authorization', 'Bearer admin-secret')
+      .send({ kind: 'code', scopes: ['missing'] })
+      .expect(400)
+  })
+
+  it('keeps a scope request pending when the client is gone', async () => {
+    const { clientId, apiKey } = registry.register('phone', ['default'])
+    const created = await request(app)
+      .post('/auth/scope-requests')
+      .set('Authorization', `Bearer ${apiKey}`)
+      .send({ scopes: ['notes'] })
+      .expect(202)
+    registry.revoke(clientId)
+
+    const admin = { Authorization: 'Bearer admin-secret' }
+    await request(app)
+      .post(`/auth/scope-requests/${created.body.requestId}/approve`)
+      .set(admin)
+      .expect(404)
+    const pending = await request(app).get('/auth/scope-requests').set(admin).expect(200)
+    expect(pending.body.requests).toMatchObject([{ requestId: created.body.requestId }])
+  })
+})
@@ -3,18 +3,43 @@
  */
 
 import type { Router, Request, Response } from 'express'
+import { parseCapabilities } from '../auth/ClientRegistry'
 import type { ClientRegistry } from '../auth/ClientRegistry'
+import { PairingStore } from '../auth/PairingStore'
+import { RegistrationRequestStore } from '../auth/RegistrationRequestStore'
+import { ScopeRequestStore } from '../auth/ScopeRequestStore'
 import { scopeStore } from '../core/ScopeStore'
 import { scopeRegistry } from '../core/ScopeRegistry'
 import { ensureStringParam } from '../scopeUtils'
 import { getScopeInfos } from '../scopes'
 import { DEFAULT_SCOPE, ONLINE_SCOPE, BUILTIN_SCOPES } from '@prizm/shared'
+import type { NotificationPayload } from '@prizm/shared'
+import { getConfig } from '../config'
 import { toErrorResponse } from '../errors'
 import { createLogger } from '../logger'
+import { EVENT_TYPES } from '../websocket/types'
 
 const log = createLogger('Auth')
 
+/**
+ * 管理操作：仅限以管理员 Key 或拥有全部权限（*）的客户端 Key 鉴权的请求。
+ * 没有客户端上下文（如只带 X-Prizm-Panel 头的请求）不视为管理员，该请求头可被任意调用方伪造
+ */
+function isAdminRequest(req: Request): boolean {
+  return req.prizmClient?.allowedScopes.includes('*') === true
+}
+
+/** 请求中不存在的 scope；* 不算已知 scope，需由调用方单独决定是否允许 */
+function unknownScopes(scopes: string[]): string[] {
+  const known = new Set(scopeStore.getAllScopes())
+  return scopes.filter((s) => !known.has(s))
+}
+
 export function createAuthRoutes(router: Router, clientRegistry: ClientRegistry): void {
+  const pairingStore = new PairingStore()
+  const scopeRequests = new ScopeRequestStore()
+  const registrationRequests = new RegistrationRequestStore()
+
   // GET /auth/scopes - 列出所有 scope 及说明（含 path、label、builtin）
   router.get('/scopes', (_req: Request, res: Response) => {
     try {
@@ -112,10 +137,16 @@ export function createAuthRoutes(router: Router, clientRegistry: ClientRegistry)
       res.status(status).json(body)
     }
   })
-  // GET /auth/clients - 列出客户端（Dashboard 专用）
-  router.get('/clients', (_req: Request, res: Response) => {
+  // GET /auth/clients - 列出客户端（管理操作）
+  router.get('/clients', (req: Request, res: Response) => {
     try {
-      const clients = clientRegistry.list()
+      if (!isAdminRequest(req)) {
+        return res.status(403).json({ error: 'Admin access required' })
+      }
+      const clients = clientRegistry.list().map((client) => ({
+        ...client,
+        presence: req.prizmServer?.getPresence(client.clientId)
+      }))
       res.json({ clients })
     } catch (error) {
       log.error('list clients error:', error)
@@ -124,9 +155,28 @@ export function createAuthRoutes(router: Router, clientRegistry: ClientRegistry)
     }
   })
 
-  // POST /auth/clients/:clientId/regenerate-key - 重新生成 API Key
+  // GET /auth/me - 当前凭据对应的客户端（无副作用，用于检查 Key 是否仍有效）
+  router.get('/me', (req: Request, res: Response) => {
+    try {
+      const clientId = req.prizmClient?.clientId
+      const client = clientId ? clientRegistry.get(clientId) : null
+      if (!client) {
+        return res.status(400).json({ error: 'Request must come from a registered client' })
+      }
+      res.json({ ...client, lastSeenAt: req.prizmClient?.lastSeenAt })
+    } catch (error) {
+      log.error('get current client error:', error)
+      const { status, body } = toErrorResponse(error)
+      res.status(status).json(body)
+    }
+  })
+
+  // POST /auth/clients/:clientId/regenerate-key - 重新生成 API Key（管理操作）
   router.post('/clients/:clientId/regenerate-key', (req: Request, res: Response) => {
     try {
+      if (!isAdminRequest(req)) {
+        return res.status(403).json({ error: 'Admin access required' })
+      }
       const clientId = ensureStringParam(req.params.clientId)
       const apiKey = clientRegistry.regenerateApiKey(clientId)
       if (!apiKey) {
@@ -140,10 +190,13 @@ export function createAuthRoutes(router: Router, clientRegistry: ClientRegistry)
     }
   })
 
-  // DELETE /auth/clients/:clientId - 吊销客户端
+  // DELETE /auth/clients/:clientId - 吊销客户端（管理操作；客户端可吊销自己，用于注销）
   router.delete('/clients/:clientId', (req: Request, res: Response) => {
     try {
       const clientId = ensureStringParam(req.params.clientId)
+      if (!isAdminRequest(req) && req.prizmClient?.clientId !== clientId) {
+        return res.status(403).json({ error: 'Admin access required' })
+      }
       const ok = clientRegistry.revoke(clientId)
       if (!ok) {
         return res.status(404).json({ error: 'Client not found' })
@@ -160,6 +213,7 @@ export function createAuthRoutes(router: Router, clientRegistry: ClientRegistry)
   router.post('/register', async (req: Request, res: Response) => {
     try {
       const { name, requestedScopes } = req.body
+      const capabilities = parseCapabilities(req.body.capabilities)
       if (!name || typeof name !== 'string') {
         return res.status(400).json({ error: 'name is required and must be a string' })
       }
@@ -171,7 +225,31 @@ export function createAuthRoutes(router: Router, clientRegistry: ClientRegistry)
       // online 是公共 scope，始终包含
       if (!scopes.includes(ONLINE_SCOPE)) scopes.push(ONLINE_SCOPE)
 
-      const result = clientRegistry.register(name.trim(), scopes)
+      // 免鉴权注册只能申请已有的 scope；* 仅在需要管理员批准时可以申请
+      const requireApproval = getConfig().requireClientApproval
+      const invalid = unknownScopes(scopes).filter((s) => s !== '*' || !requireApproval)
+      if (invalid.length > 0) {
+        return res.status(400).json({ error: `Scopes not allowed: ${invalid.join(', ')}` })
+      }
+
+      if (requireApproval) {
+        // 需管理员在 Dashboard 批准，客户端凭 requestId 轮询 GET /auth/register/:requestId
+        const request = registrationRequests.create(name.trim(), scopes, capabilities)
+        log.info(`Client "${request.name}" awaits approval (requestId=${request.requestId})`)
+        // 通知有管理权限的客户端，可在系统通知上直接批准或拒绝；requestId 是领取 Key 的凭据，
+        // 只发给 * 客户端且不进入重放历史
+        const payload: NotificationPayload = {
+          title: '新客户端等待批准',
+          body: `"${request.name}" 申请访问 ${scopes.join(', ')}`,
+          updateId: `registration_request:${request.requestId}`,
+          severity: 'warning',
+          action: { type: 'registration_request', requestId: request.requestId }
+        }
+        req.prizmServer?.broadcastToAdmins(EVENT_TYPES.NOTIFICATION, payload)
+        return res.status(202).json(request)
+      }
+
+      const result = clientRegistry.register(name.trim(), scopes, capabilities)
       res.status(201).json(result)
     } catch (error) {
       log.error('register error:', error)
@@ -179,4 +257,203 @@ export function createAuthRoutes(router: Router, clientRegistry: ClientRegistry)
       res.status(status).json(body)
     }
   })
+
+  // GET /auth/register/:requestId - 查询注册申请（免鉴权，requestId 即凭据），批准后一次性返回 API Key
+  router.get('/register/:requestId', (req: Request, res: Response) => {
+    try {
+      const request = registrationRequests.claim(ensureStringParam(req.params.requestId))
+      if (!request) {
+        return res.status(404).json({ error: 'Registration request not found' })
+      }
+      res.json(request)
+    } catch (error) {
+      log.error('get registration request error:', error)
+      const { status, body } = toErrorResponse(error)
+      res.status(status).json(body)
+    }
+  })
+
+  // GET /auth/registration-requests - 待审批的注册申请（管理操作）
+  router.get('/registration-requests', (req: Request, res: Response) => {
+    if (!isAdminRequest(req)) {
+      return res.status(403).json({ error: 'Admin access required' })
+    }
+    res.json({ requests: registrationRequests.listPending() })
+  })
+
+  // POST /auth/registration-requests/:id/approve|deny - 批准（此时才注册客户端）或拒绝（管理操作）
+  for (const action of ['approve', 'deny'] as const) {
+    router.post(`/registration-requests/:id/${action}`, (req: Request, res: Response) => {
+      try {
+        if (!isAdminRequest(req)) {
+          return res.status(403).json({ error: 'Admin access required' })
+        }
+        const id = ensureStringParam(req.params.id)
+        const pending = registrationRequests.get(id)
+        if (!pending || pending.status !== 'pending') {
+          return res
+            .status(404)
+            .json({ error: 'Registration request not found or already resolved' })
+        }
+        const request =
+          action === 'approve'
+            ? registrationRequests.approve(
+                id,
+                clientRegistry.register(pending.name, pending.scopes, pending.capabilities)
+              )
+            : registrationRequests.deny(id)
+        log.info(`Registration request ${id} of "${pending.name}" ${request?.status}`)
+        res.json(request)
+      } catch (error) {
+        log.error(`${action} registration request error:`, error)
+        const { status, body } = toErrorResponse(error)
+        res.status(status).json(body)
+      }
+    })
+  }
+
+  // POST /auth/pairing - 签发一次性配对令牌（需 API Key；Dashboard 需填写管理员 Key）
+  // body.kind === 'code' 时签发 6 位短码，供 Dashboard 展示、客户端手动输入
+  router.post('/pairing', (req: Request, res: Response) => {
+    try {
+      // 必须以 API Key 鉴权，客户端只能转授自己拥有的 scope
+      const issuerScopes = req.prizmClient?.allowedScopes
+      if (!issuerScopes) {
+        return res.status(403).json({ error: 'Admin access required' })
+      }
+      const { scopes: requested, kind } = req.body ?? {}
+      let scopes = Array.isArray(requested)
+        ? requested.filter((s: unknown): s is string => typeof s === 'string')
+        : issuerScopes
+      if (!issuerScopes.includes('*')) {
+        scopes = scopes.filter((s) => issuerScopes.includes(s))
+      }
+      // 只能转授已有的 scope；* 仅限管理员签发
+      const invalid = unknownScopes(scopes).filter((s) => s !== '*')
+      if (invalid.length > 0) {
+        return res.status(400).json({ error: `Unknown scopes: ${invalid.join(', ')}` })
+      }
+      if (scopes.length === 0) {
+        return res.status(400).json({ error: 'No scopes to grant' })
+      }
+      const ticket = kind === 'code' ? pairingStore.createCode(scopes) : pairingStore.create(scopes)
+      res.status(201).json(ticket)
+    } catch (error) {
+      log.error('create pairing error:', error)
+      const { status, body } = toErrorResponse(error)
+      res.status(status).json(body)
+    }
+  })
+
+  // POST /auth/pair - 用配对令牌或短码注册客户端（免鉴权，一次有效）
+  router.post('/pair', (req: Request, res: Response) => {
+    try {
+      const { token, code, name } = req.body ?? {}
+      const hasToken = typeof token === 'string' && token.length > 0
+      const hasCode = typeof code === 'string' && code.length > 0
+      if ((!hasToken && !hasCode) || !name || typeof name !== 'string') {
+        return res.status(400).json({ error: 'token (or code) and name are required' })
+      }
+      const ticket = hasToken ? pairingStore.redeem(token) : pairingStore.redeemCode(code.trim())
+      if (!ticket) {
+        return res.status(401).json({ error: 'Invalid or expired pairing token' })
+      }
+      const scopes = [...ticket.scopes]
+      if (!scopes.includes(ONLINE_SCOPE)) scopes.push(ONLINE_SCOPE)
+      const result = clientRegistry.register(name.trim(), scopes)
+      log.info(`Client "${name.trim()}" paired (clientId=${result.clientId})`)
+      res.status(201).json(result)
+    } catch (error) {
+      log.error('pair error:', error)
+      const { status, body } = toErrorResponse(error)
+      res.status(status).json(body)
+    }
+  })
+
+  // POST /auth/scope-requests - 客户端申请追加 scope，需管理员批准
+  router.post('/scope-requests', (req: Request, res: Response) => {
+    try {
+      const clientId = req.prizmClient?.clientId
+      if (!clientId) {
+        return res.status(400).json({ error: 'Scope requests must come from a registered client' })
+      }
+      const { scopes: requested } = req.body ?? {}
+      if (!Array.isArray(requested)) {
+        return res.status(400).json({ error: 'scopes must be an array of strings' })
+      }
+      const current = req.prizmClient?.allowedScopes ?? []
+      const scopes = [
+        ...new Set(requested.filter((s: unknown): s is string => typeof s === 'string'))
+      ].filter((s) => !current.includes(s))
+      if (scopes.length === 0 || current.includes('*')) {
+        // 已拥有全部申请的 scope，无需审批
+        return res.json({ status: 'approved', grantedScopes: current })
+      }
+      const name = clientRegistry.list().find((c) => c.clientId === clientId)?.name ?? clientId
+      const request = scopeRequests.create(clientId, name, scopes)
+      log.info(`Client "${name}" requested scopes: ${scopes.join(', ')}`)
+      res.status(202).json(request)
+    } catch (error) {
+      log.error('create scope request error:', error)
+      const { status, body } = toErrorResponse(error)
+      res.status(status).json(body)
+    }
+  })
+
+  // GET /auth/scope-requests - 待审批的 scope 申请（管理操作）
+  router.get('/scope-requests', (req: Request, res: Response) => {
+    if (!isAdminRequest(req)) {
+      return res.status(403).json({ error: 'Admin access required' })
+    }
+    res.json({ requests: scopeRequests.listPending() })
+  })
+
+  // GET /auth/scope-requests/:id - 查询申请状态（申请者本人或管理员），批准后附带全部 scope
+  router.get('/scope-requests/:id', (req: Request, res: Response) => {
+    try {
+      const request = scopeRequests.get(ensureStringParam(req.params.id))
+      if (!request || (!isAdminRequest(req) && request.clientId !== req.prizmClient?.clientId)) {
+        return res.status(404).json({ error: 'Scope request not found' })
+      }
+      const grantedScopes =
+        request.status === 'approved'
+          ? clientRegistry.list().find((c) => c.clientId === request.clientId)?.allowedScopes
+          : undefined
+      res.json({ ...request, grantedScopes })
+    } catch (error) {
+      log.error('get scope request error:', error)
+      const { status, body } = toErrorResponse(error)
+      res.status(status).json(body)
+    }
+  })
+
+  // POST /auth/scope-requests/:id/approve|deny - 批准或拒绝申请（管理操作）
+  for (const action of ['approve', 'deny'] as const) {
+    router.post(`/scope-requests/:id/${action}`, (req: Request, res: Response) => {
+      try {
+        if (!isAdminRequest(req)) {
+          return res.status(403).json({ error: 'Admin access required' })
+        }
+        const id = ensureStringParam(req.params.id)
+        const pending = scopeRequests.get(id)
+        if (!pending || pending.status !== 'pending') {
+          return res.status(404).json({ error: 'Scope request not found or already resolved' })
+        }
+        // 先授予 scope，客户端已被删除时申请保持待处理
+        if (action === 'approve' && !clientRegistry.addScopes(pending.clientId, pending.scopes)) {
+          return res.status(404).json({ error: 'Client not found' })
+        }
+        const request = scopeRequests.resolve(id, action === 'approve')
+        if (!request) {
+          return res.status(404).json({ error: 'Scope request not found or already resolved' })
+        }
+        log.info(`Scope request ${request.requestId} of "${request.clientName}" ${request.status}`)
+        res.json(request)
+      } catch (error) {
+        log.error(`${action} scope request error:`, error)
+        const { status, body } = toErrorResponse(error)
+        res.status(status).json(body)
+      }
+    })
+  }
 }
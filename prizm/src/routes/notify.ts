@@ -3,6 +3,7 @@
  */
 
 import type { Router, Request, Response } from 'express'
+import { NOTIFICATION_SEVERITIES } from '@prizm/shared'
 import type { INotificationAdapter } from '../adapters/interfaces'
 import { EVENT_TYPES } from '../websocket/types'
 import { toErrorResponse } from '../errors'
@@ -22,11 +23,15 @@ export function createNotifyRoutes(router: Router, adapter?: INotificationAdapte
         return res.status(503).json({ error: 'Notification adapter not available' })
       }
 
-      const { title, body, targetClientId } = req.body
+      const { title, body, targetClientId, severity } = req.body
 
       if (!title) {
         return res.status(400).json({ error: 'title is required' })
       }
+      if (severity !== undefined && !NOTIFICATION_SEVERITIES.includes(severity)) {
+        return res.status(400).json({ error: 'severity must be info, warning or error' })
+      }
+      const payload = { title, body, severity, sourceClientId: req.prizmClient?.clientId }
 
       const wsServer = req.prizmServer
 
@@ -34,20 +39,11 @@ export function createNotifyRoutes(router: Router, adapter?: INotificationAdapte
       if (wsServer) {
         if (targetClientId) {
           // 发送到指定客户端
-          wsServer.broadcastToClient(
-            targetClientId,
-            EVENT_TYPES.NOTIFICATION,
-            { title, body, sourceClientId: req.prizmClient?.clientId },
-            undefined
-          )
+          wsServer.broadcastToClient(targetClientId, EVENT_TYPES.NOTIFICATION, payload, undefined)
           log.info('Sent notification to client', targetClientId)
         } else {
           // 广播到所有订阅者
-          const delivered = wsServer.broadcast(
-            EVENT_TYPES.NOTIFICATION,
-            { title, body, sourceClientId: req.prizmClient?.clientId },
-            undefined
-          )
+          const delivered = wsServer.broadcast(EVENT_TYPES.NOTIFICATION, payload, undefined)
           log.info('Broadcasted notification to', delivered, 'subscribers')
         }
       } else {
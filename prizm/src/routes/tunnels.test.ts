@@ -0,0 +1,83 @@
+/**
+ * Tunnel Routes 单元测试：隧道响应头的白名单与安全头
+ *
+ * 使用 express + supertest + mock WebSocketServer
+ */
+
+import { describe, it, expect, beforeEach, vi } from 'vitest'
+import express from 'express'
+import request from 'supertest'
+import { createTunnelRoutes, tunnelResponseHeaders } from './tunnels'
+
+vi.mock('../scopeUtils', () => ({
+  ensureStringParam: (v: unknown) => String(v),
+  hasScopeAccess: () => true
+}))
+
+const forwardTunnelRequest = vi.fn()
+
+function createApp() {
+  const app = express()
+  app.use((req, _res, next) => {
+    ;(req as unknown as { prizmServer: unknown }).prizmServer = {
+      getTunnel: () => ({ clientId: 'c1', name: 'web', scope: 'default' }),
+      forwardTunnelRequest
+    }
+    next()
+  })
+  const router = express.Router()
+  createTunnelRoutes(router)
+  app.use(router)
+  return app
+}
+
+describe('tunnelResponseHeaders', () => {
+  it('keeps only allowlisted headers and adds the sandbox policy', () => {
+    expect(
+      tunnelResponseHeaders({
+        'Content-Type': 'text/html',
+        ETag: '"1"',
+        'Set-Cookie': 'sid=1',
+        'Access-Control-Allow-Origin': '*',
+        'Content-Security-Policy': "default-src *; script-src 'unsafe-inline'",
+        'X-Content-Type-Options': 'off',
+        'Transfer-Encoding': 'chunked'
+      })
+    ).toEqual({
+      'Content-Type': 'text/html',
+      ETag: '"1"',
+      'Content-Security-Policy': 'sandbox',
+      'X-Content-Type-Options': 'nosniff'
+    })
+  })
+})
+
+describe('Tunnel Routes', () => {
+  beforeEach(() => {
+    forwardTunnelRequest.mockReset()
+  })
+
+  it('serves tunneled HTML sandboxed with nosniff', async () => {
+    forwardTunnelRequest.mockResolvedValue({
+      status: 200,
+      headers: {
+        'content-type': 'text/html',
+        'set-cookie': 'sid=1',
+        'access-control-allow-origin': '*',
+        'content-security-policy': 'default-src *'
+      },
+      body: Buffer.from('<script>alert(1)</script>')
+    })
+    const res = await request(createApp()).get('/tunnels/c1/web/index.html').expect(200)
+    expect(res.headers['content-security-policy']).toBe('sandbox')
+    expect(res.headers['x-content-type-options']).toBe('nosniff')
+    expect(res.headers['content-type']).toMatch(/^text\/html/)
+    expect(res.headers['set-cookie']).toBeUndefined()
+    expect(res.headers['access-control-allow-origin']).toBeUndefined()
+    expect(forwardTunnelRequest).toHaveBeenCalledWith(
+      'c1',
+      'web',
+      expect.objectContaining({ method: 'GET', path: '/index.html' })
+    )
+  })
+})
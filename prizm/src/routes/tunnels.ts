@@ -0,0 +1,141 @@
+/**
+ * 反向隧道路由
+ * 把 /tunnels/{clientId}/{name}/ 下的请求经实时连接转发给开放该隧道的客户端，
+ * 由客户端代理到其本机服务。调用方须持有隧道的 scope；API Key、Cookie 等凭据不会转发。
+ * 响应来自客户端、却以 Prizm 服务端的源返回，只回传白名单内的响应头，并以 CSP sandbox
+ * 禁止其中的页面脚本以该源运行
+ */
+
+import type { Router, Request, Response } from 'express'
+import { ensureStringParam, hasScopeAccess } from '../scopeUtils'
+import { TUNNEL_MAX_BODY_BYTES } from '../websocket/TunnelRegistry'
+import { toErrorResponse } from '../errors'
+import { createLogger } from '../logger'
+
+const log = createLogger('Tunnels')
+
+/** 不转发给本机服务的请求头：逐跳头与 Prizm 凭据 */
+const DROPPED_REQUEST_HEADERS = new Set([
+  'host',
+  'connection',
+  'keep-alive',
+  'upgrade',
+  'transfer-encoding',
+  'content-length',
+  'proxy-authorization',
+  'authorization',
+  'cookie'
+])
+
+/** 回传给调用方的响应头（白名单），其余一律丢弃 */
+const FORWARDED_RESPONSE_HEADERS = new Set([
+  'content-type',
+  'content-encoding',
+  'content-language',
+  'content-disposition',
+  'content-range',
+  'accept-ranges',
+  'cache-control',
+  'etag',
+  'expires',
+  'last-modified',
+  'location',
+  'retry-after',
+  'vary'
+])
+
+/** 每个隧道响应都附加的安全头 */
+const TUNNEL_SECURITY_HEADERS: Record<string, string> = {
+  'Content-Security-Policy': 'sandbox',
+  'X-Content-Type-Options': 'nosniff'
+}
+
+/** 过滤客户端返回的响应头并附加安全头 */
+export function tunnelResponseHeaders(headers: Record<string, string>): Record<string, string> {
+  const result: Record<string, string> = {}
+  for (const [name, value] of Object.entries(headers)) {
+    if (FORWARDED_RESPONSE_HEADERS.has(name.toLowerCase())) result[name] = value
+  }
+  return { ...result, ...TUNNEL_SECURITY_HEADERS }
+}
+
+function forwardedHeaders(req: Request): Record<string, string> {
+  const headers: Record<string, string> = {}
+  for (const [name, value] of Object.entries(req.headers)) {
+    if (value === undefined) continue
+    if (DROPPED_REQUEST_HEADERS.has(name) || name.startsWith('x-prizm-')) continue
+    headers[name] = Array.isArray(value) ? value.join(', ') : value
+  }
+  return headers
+}
+
+/** 隧道内的路径：去掉隧道前缀与 apiKey 查询参数 */
+function tunnelPath(req: Request): string {
+  const raw = (req.params as Record<string, unknown>).splat
+  const segments = Array.isArray(raw) ? raw.map(String) : raw ? [String(raw)] : []
+  const query = new URL(req.originalUrl, 'http://localhost').searchParams
+  query.delete('apiKey')
+  const search = query.toString()
+  return `/${segments.map(encodeURIComponent).join('/')}${search ? `?${search}` : ''}`
+}
+
+/** 读取请求体；express.json / urlencoded 已解析时使用保留的原始内容，超过上限返回 null */
+async function readBody(req: Request): Promise<Buffer | null> {
+  if (req.rawBody) return req.rawBody
+  const chunks: Buffer[] = []
+  let size = 0
+  for await (const chunk of req) {
+    size += (chunk as Buffer).length
+    if (size > TUNNEL_MAX_BODY_BYTES) return null
+    chunks.push(chunk as Buffer)
+  }
+  return Buffer.concat(chunks)
+}
+
+export function createTunnelRoutes(router: Router): void {
+  // GET /tunnels - 调用方有权访问的隧道
+  router.get('/tunnels', (req: Request, res: Response) => {
+    const wsServer = req.prizmServer
+    if (!wsServer) {
+      return res.status(503).json({ error: 'WebSocket server not available' })
+    }
+    res.json({
+      tunnels: wsServer.listTunnels().filter((tunnel) => hasScopeAccess(req, tunnel.scope))
+    })
+  })
+
+  // ALL /tunnels/:clientId/:name/*splat - 经隧道转发
+  router.all('/tunnels/:clientId/:name{/*splat}', async (req: Request, res: Response) => {
+    try {
+      const wsServer = req.prizmServer
+      if (!wsServer) {
+        return res.status(503).json({ error: 'WebSocket server not available' })
+      }
+      const clientId = ensureStringParam(req.params.clientId)
+      const name = ensureStringParam(req.params.name)
+      const tunnel = wsServer.getTunnel(clientId, name)
+      // 无权访问时同样返回 404，不暴露隧道是否存在
+      if (!tunnel || !hasScopeAccess(req, tunnel.scope)) {
+        return res.status(404).json({ error: 'Tunnel not found' })
+      }
+      const body = await readBody(req)
+      if (!body) {
+        return res.status(413).json({ error: 'Request body too large' })
+      }
+      const response = await wsServer.forwardTunnelRequest(clientId, name, {
+        method: req.method,
+        path: tunnelPath(req),
+        headers: forwardedHeaders(req),
+        body
+      })
+      for (const [header, value] of Object.entries(tunnelResponseHeaders(response.headers))) {
+        res.setHeader(header, value)
+      }
+      res.status(response.status).end(response.body)
+    } catch (error) {
+      log.error('tunnel forward error:', error)
+      const { status, body } = toErrorResponse(error)
+      res.status(status).json(body)
+    }
+  })
+}
@@ -20,6 +20,7 @@ import { ClientRegistry } from './auth/ClientRegistry'
 import { createAuthMiddleware } from './auth/authMiddleware'
 import { createAuthRoutes } from './routes/auth'
 import { createNotifyRoutes } from './routes/notify'
+import { createTunnelRoutes } from './routes/tunnels'
 import { createTodoListRoutes } from './routes/todoList'
 import { createClipboardRoutes } from './routes/clipboard'
 import { createDocumentsRoutes } from './routes/documents'
@@ -189,15 +190,23 @@ export function createPrizmServer(
       }
     })
   )
-  app.use(express.json())
-  app.use(express.urlencoded({ extended: true }))
+  // 保留原始请求体，供鉴权中间件校验请求签名
+  const keepRawBody = (req: express.Request, _res: unknown, buf: Buffer): void => {
+    req.rawBody = buf
+  }
+  app.use(express.json({ verify: keepRawBody }))
+  app.use(express.urlencoded({ extended: true, verify: keepRawBody }))
 
   if (enableCors) {
     app.use(cors())
   }
 
   // 鉴权中间件（/health、/panel、/auth/register 豁免）
-  const authMiddleware = createAuthMiddleware({ clientRegistry, authEnabled })
+  const authMiddleware = createAuthMiddleware({
+    clientRegistry,
+    authEnabled,
+    adminKey: getConfig().adminKey
+  })
   app.use(authMiddleware)
 
   // 设置 WebSocket 服务器引用到请求对象
@@ -249,6 +258,7 @@ export function createPrizmServer(
   createTaskRoutes(router)
   createBrowserRoutes(router, () => browserRelayServer)
   createFeedbackRoutes(router)
+  createTunnelRoutes(router)
   app.use('/', router)
 
   // MCP 端点：供 Cursor、LobeChat 等 Agent 连接（wsServer 在 start 后注入）
@@ -383,7 +393,9 @@ export function createPrizmServer(
                 const browserRelayPath = '/api/v1/browser/relay'
 
                 wsServer = new WebSocketServer(server, clientRegistry, {
-                  path: websocketPath
+                  path: websocketPath,
+                  perMessageDeflate: cfg.websocketCompression,
+                  enableTunnels: cfg.enableTunnels
                 })
                 log.info('WebSocket:', `ws://${host}:${port}${websocketPath}`)
 
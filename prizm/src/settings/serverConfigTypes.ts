@@ -7,11 +7,17 @@ export interface ServerConfigServer {
   port?: number
   host?: string
   authDisabled?: boolean
+  /** 新客户端注册需在 Dashboard 人工批准 */
+  requireClientApproval?: boolean
   logLevel?: 'info' | 'warn' | 'error'
   mcpScope?: string
   corsEnabled?: boolean
   websocketEnabled?: boolean
   websocketPath?: string
+  /** 允许 WebSocket 客户端协商 permessage-deflate 压缩，默认开启 */
+  websocketCompression?: boolean
+  /** 允许客户端开放反向隧道，默认关闭 */
+  tunnelsEnabled?: boolean
 }
 
 export interface ServerConfigEmbedding {
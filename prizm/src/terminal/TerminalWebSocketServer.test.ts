@@ -175,6 +175,18 @@ describe('TerminalWebSocketServer', () => {
       await closeWs(ws)
     })
 
+    it('should accept the API key from the Authorization header', async () => {
+      const ws = new WebSocket(`ws://127.0.0.1:${serverPort}/ws/terminal`, {
+        headers: { Authorization: 'Bearer valid-key' }
+      })
+      await new Promise<void>((resolve, reject) => {
+        ws.on('open', () => resolve())
+        ws.on('error', reject)
+      })
+      expect(mockRegistry.validate).toHaveBeenCalledWith('valid-key')
+      await closeWs(ws)
+    })
+
     it('should close connection with invalid API key', async () => {
       ;(mockRegistry.validate as Mock).mockReturnValue(null)
 
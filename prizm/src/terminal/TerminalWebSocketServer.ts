@@ -3,7 +3,7 @@
  * 专用 WebSocket 通道 — 处理终端实时 I/O
  *
  * 挂载路径: /ws/terminal
- * 认证: ?apiKey=xxx
+ * 认证: ?apiKey=xxx 或 Authorization: Bearer xxx
  * 协议: attach -> 双向流式传输 -> detach/exit
  * 支持: 同一终端多个观察者、心跳检测、重连回放
  */
@@ -77,7 +77,7 @@ export class TerminalWebSocketServer {
 
   private handleConnection(socket: WebSocket, req: http.IncomingMessage): void {
     // 认证
-    const apiKey = this.extractApiKey(req.url)
+    const apiKey = this.extractApiKey(req)
     if (!apiKey) {
       this.sendToSocket(socket, {
         type: 'terminal:error',
@@ -310,14 +310,17 @@ export class TerminalWebSocketServer {
     }
   }
 
-  private extractApiKey(url?: string): string | null {
-    if (!url) return null
+  /** ?apiKey= 或 Authorization: Bearer */
+  private extractApiKey(req: http.IncomingMessage): string | null {
     try {
-      const urlObj = new URL(url, 'http://localhost')
-      return urlObj.searchParams.get('apiKey')
+      const urlObj = new URL(req.url ?? '/', 'http://localhost')
+      const queryKey = urlObj.searchParams.get('apiKey')
+      if (queryKey) return queryKey
     } catch {
-      return null
+      // 忽略非法 URL，继续检查请求头
     }
+    const auth = req.headers.authorization
+    return auth?.startsWith('Bearer ') ? auth.slice(7).trim() || null : null
   }
 
   // ---- 心跳检测 ----
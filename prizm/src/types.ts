@@ -55,7 +55,8 @@ import type {
   CronRunLog,
   PermissionMode,
   AgentDefinition,
-  MemoryInjectPolicy
+  MemoryInjectPolicy,
+  ClientCapabilities
 } from '@prizm/shared'
 
 // 重导出，供 routes、adapters 等使用
@@ -126,6 +127,12 @@ export interface ClientRecord {
   name: string
   allowedScopes: string[]
   createdAt: number
+  /** 最近一次通过鉴权的时间，精度见 ClientRegistry 的 LAST_SEEN_RESOLUTION_MS */
+  lastSeenAt?: number
+  /** 最近一次注册时客户端声明的能力 */
+  capabilities?: ClientCapabilities
+  /** 请求签名密钥，以服务端密钥（request-signing.key）加密，见 auth/requestSigning.ts */
+  signingSecret?: string
 }
 
 // ============ Server 配置 ============
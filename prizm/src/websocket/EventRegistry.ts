@@ -3,22 +3,42 @@
  * 中央管理所有 WebSocket 客户端的事件订阅
  */
 
+import { randomUUID } from 'crypto'
 import { createLogger } from '../logger'
 import type { WebSocketContext } from './WebSocketContext'
 import type { EventType, EventPushMessage } from './types'
 
 const log = createLogger('EventRegistry')
 
+/** 保留用于断线重放的最近事件数 */
+export const REPLAY_HISTORY_SIZE = 1000
+
 export interface SubscriberInfo {
   clientId: string
   registeredEvents: EventType[]
   currentScope: string
 }
 
+/** 历史事件；clientId 有值时为定向事件，只重放给该客户端 */
+interface RecordedEvent {
+  message: EventPushMessage & { id: number }
+  clientId?: string
+}
+
+export interface ReplayResult {
+  events: EventPushMessage[]
+  untilId: number
+  truncated: boolean
+}
+
 export class EventRegistry {
+  /** 事件流 id，服务端重启后变化，客户端据此判断重放游标是否仍有效 */
+  readonly streamId = randomUUID()
   private clients = new Map<string, WebSocketContext>() // connectionId -> context
   private clientIds = new Map<string, string>() // clientId -> connectionId (for lookup)
   private eventSubscriptions = new Map<EventType, Set<string>>() // eventType -> Set<connectionId>
+  private history: RecordedEvent[] = []
+  private nextEventId = 1
 
   /**
    * 注册客户端
@@ -106,6 +126,7 @@ export class EventRegistry {
    * 广播事件到所有订阅者
    */
   broadcast(eventType: EventType, payload: unknown, scope?: string): number {
+    const message = this.record(eventType, payload, scope)
     const subscribers = this.getSubscribers(eventType)
     let delivered = 0
 
@@ -115,14 +136,6 @@ export class EventRegistry {
         continue
       }
 
-      const message: EventPushMessage = {
-        type: 'event',
-        eventType,
-        payload,
-        scope,
-        timestamp: Date.now()
-      }
-
       if (subscriber.send(message)) {
         delivered++
       }
@@ -141,6 +154,18 @@ export class EventRegistry {
     return delivered
   }
 
+  /**
+   * 只发给拥有全部权限（*）的订阅者，不记入重放历史；用于携带凭据性质数据的管理通知
+   */
+  broadcastToAdmins(eventType: EventType, payload: unknown): number {
+    const message: EventPushMessage = { type: 'event', eventType, payload, timestamp: Date.now() }
+    let delivered = 0
+    for (const subscriber of this.getSubscribers(eventType)) {
+      if (subscriber.hasScopePermission('*') && subscriber.send(message)) delivered++
+    }
+    return delivered
+  }
+
   /**
    * 向指定客户端发送事件
    */
@@ -150,6 +175,8 @@ export class EventRegistry {
     payload: unknown,
     scope?: string
   ): boolean {
+    // 客户端离线时同样记录，重连后可重放
+    const message = this.record(eventType, payload, scope, clientId)
     const context = this.getClientByClientId(clientId)
     if (!context || !context.isOpen()) {
       log.warn('Client', clientId, 'not found or not connected')
@@ -162,15 +189,55 @@ export class EventRegistry {
       return false
     }
 
-    const message: EventPushMessage = {
-      type: 'event',
+    return context.send(message)
+  }
+
+  /**
+   * 最新的事件序号（尚无事件时为 0）
+   */
+  getLastEventId(): number {
+    return this.nextEventId - 1
+  }
+
+  /**
+   * 取出 lastEventId 之后该连接错过的事件：已订阅（定向事件只给目标客户端）且有 scope 权限。
+   * 游标早于保留的最旧事件时 truncated 为 true
+   */
+  replay(contextId: string, lastEventId: number): ReplayResult | null {
+    const context = this.clients.get(contextId)
+    if (!context) return null
+    const untilId = this.getLastEventId()
+    const oldestId = this.history[0]?.message.id ?? this.nextEventId
+    const events = this.history
+      .filter(({ message, clientId }) => {
+        if (message.id <= lastEventId) return false
+        if (message.scope && !context.hasScopePermission(message.scope)) return false
+        return clientId ? clientId === context.clientId : context.hasEvent(message.eventType)
+      })
+      .map(({ message }) => message)
+    return { events, untilId, truncated: lastEventId + 1 < oldestId }
+  }
+
+  /**
+   * 为事件分配序号并记入历史，超出 REPLAY_HISTORY_SIZE 时丢弃最旧的
+   */
+  private record(
+    eventType: EventType,
+    payload: unknown,
+    scope?: string,
+    clientId?: string
+  ): EventPushMessage & { id: number } {
+    const message = {
+      type: 'event' as const,
+      id: this.nextEventId++,
       eventType,
       payload,
       scope,
       timestamp: Date.now()
     }
-
-    return context.send(message)
+    this.history.push({ message, clientId })
+    if (this.history.length > REPLAY_HISTORY_SIZE) this.history.shift()
+    return message
   }
 
   /**
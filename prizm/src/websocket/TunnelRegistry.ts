@@ -0,0 +1,193 @@
+/**
+ * Prizm 反向隧道注册表
+ * 客户端经实时连接开放本机服务（tunnel_open），服务端把 /tunnels/{clientId}/{name}/ 下的
+ * HTTP 请求以 tunnel_request 转发给该连接，按 requestId 匹配 tunnel_response
+ */
+
+import { randomUUID } from 'crypto'
+import { createLogger } from '../logger'
+import type { WebSocketContext } from './WebSocketContext'
+import type { TunnelResponseMessage } from './types'
+
+const log = createLogger('TunnelRegistry')
+
+/** 隧道名：小写字母、数字与连字符 */
+export const TUNNEL_NAME_PATTERN = /^[a-z0-9][a-z0-9-]{0,31}$/
+/** 请求体与响应体的大小上限 */
+export const TUNNEL_MAX_BODY_BYTES = 10 * 1024 * 1024
+/** 等待客户端应答的上限 */
+const TUNNEL_TIMEOUT_MS = 30_000
+
+export interface TunnelInfo {
+  clientId: string
+  name: string
+  /** 访问该隧道需要持有的 scope */
+  scope: string
+  openedAt: number
+  /** 访问路径，如 /tunnels/client-1/grafana */
+  path: string
+}
+
+export interface TunnelHttpRequest {
+  method: string
+  /** 不含隧道前缀的路径与查询串 */
+  path: string
+  headers: Record<string, string>
+  body: Buffer
+}
+
+export interface TunnelHttpResponse {
+  status: number
+  headers: Record<string, string>
+  body: Buffer
+}
+
+interface TunnelEntry extends TunnelInfo {
+  context: WebSocketContext
+}
+
+interface PendingRequest {
+  contextId: string
+  resolve: (response: TunnelHttpResponse) => void
+  timer: ReturnType<typeof setTimeout>
+}
+
+function errorResponse(status: number, error: string): TunnelHttpResponse {
+  return {
+    status,
+    headers: { 'content-type': 'application/json' },
+    body: Buffer.from(JSON.stringify({ error }))
+  }
+}
+
+export class TunnelRegistry {
+  private tunnels = new Map<string, TunnelEntry>() // clientId/name -> tunnel
+  private pending = new Map<string, PendingRequest>() // requestId -> pending
+
+  /**
+   * 开放隧道；返回拒绝原因，成功时返回 null。同一客户端重连后的同名隧道替换旧连接
+   */
+  open(context: WebSocketContext, name: string, scope: string): string | null {
+    if (typeof name !== 'string' || !TUNNEL_NAME_PATTERN.test(name)) {
+      return 'Tunnel name must be 1-32 lowercase letters, digits or dashes'
+    }
+    if (typeof scope !== 'string' || !scope.trim()) {
+      return 'Tunnel scope is required'
+    }
+    if (!context.hasScopePermission(scope)) {
+      return `Client has no access to scope ${scope}`
+    }
+    const key = `${context.clientId}/${name}`
+    this.tunnels.set(key, {
+      clientId: context.clientId,
+      name,
+      scope,
+      openedAt: Date.now(),
+      path: `/tunnels/${encodeURIComponent(context.clientId)}/${name}`,
+      context
+    })
+    log.info('Client', context.clientId, 'opened tunnel', name, 'in scope', scope)
+    return null
+  }
+
+  /**
+   * 关闭该连接开放的隧道
+   */
+  close(contextId: string, name: string): boolean {
+    for (const [key, tunnel] of this.tunnels) {
+      if (tunnel.context.id === contextId && tunnel.name === name) {
+        this.tunnels.delete(key)
+        log.info('Client', tunnel.clientId, 'closed tunnel', name)
+        return true
+      }
+    }
+    return false
+  }
+
+  /**
+   * 连接断开时关闭其全部隧道，未完成的请求以 502 结束
+   */
+  closeAll(contextId: string): void {
+    for (const [key, tunnel] of this.tunnels) {
+      if (tunnel.context.id === contextId) this.tunnels.delete(key)
+    }
+    for (const [requestId, pending] of this.pending) {
+      if (pending.contextId !== contextId) continue
+      this.finish(requestId, errorResponse(502, 'Tunnel connection closed'))
+    }
+  }
+
+  get(clientId: string, name: string): TunnelInfo | null {
+    const tunnel = this.tunnels.get(`${clientId}/${name}`)
+    return tunnel ? this.toInfo(tunnel) : null
+  }
+
+  list(): TunnelInfo[] {
+    return [...this.tunnels.values()].map((tunnel) => this.toInfo(tunnel))
+  }
+
+  /**
+   * 把请求转发给隧道所属连接；隧道不存在时 404，发送失败 502，超时 504
+   */
+  forward(
+    clientId: string,
+    name: string,
+    request: TunnelHttpRequest
+  ): Promise<TunnelHttpResponse> {
+    const tunnel = this.tunnels.get(`${clientId}/${name}`)
+    if (!tunnel || !tunnel.context.isOpen()) {
+      return Promise.resolve(errorResponse(404, 'Tunnel not found'))
+    }
+    const requestId = randomUUID()
+    return new Promise((resolve) => {
+      const timer = setTimeout(
+        () => this.finish(requestId, errorResponse(504, 'Tunnel did not respond in time')),
+        TUNNEL_TIMEOUT_MS
+      )
+      this.pending.set(requestId, { contextId: tunnel.context.id, resolve, timer })
+      const sent = tunnel.context.send({
+        type: 'tunnel_request',
+        requestId,
+        name,
+        method: request.method,
+        path: request.path,
+        headers: request.headers,
+        body: request.body.toString('base64')
+      })
+      if (!sent) this.finish(requestId, errorResponse(502, 'Failed to reach tunnel client'))
+    })
+  }
+
+  /**
+   * 处理客户端应答；只接受发往该连接的请求
+   */
+  resolve(contextId: string, message: TunnelResponseMessage): boolean {
+    const pending = this.pending.get(message.requestId)
+    if (!pending || pending.contextId !== contextId) return false
+    const body = Buffer.from(typeof message.body === 'string' ? message.body : '', 'base64')
+    const status =
+      Number.isInteger(message.status) && message.status >= 100 && message.status <= 599
+        ? message.status
+        : 502
+    this.finish(
+      message.requestId,
+      body.length > TUNNEL_MAX_BODY_BYTES
+        ? errorResponse(502, 'Tunnel response too large')
+        : { status, headers: message.headers ?? {}, body }
+    )
+    return true
+  }
+
+  private finish(requestId: string, response: TunnelHttpResponse): void {
+    const pending = this.pending.get(requestId)
+    if (!pending) return
+    this.pending.delete(requestId)
+    clearTimeout(pending.timer)
+    pending.resolve(response)
+  }
+
+  private toInfo(tunnel: TunnelEntry): TunnelInfo {
+    const { clientId, name, scope, openedAt, path } = tunnel
+    return { clientId, name, scope, openedAt, path }
+  }
+}
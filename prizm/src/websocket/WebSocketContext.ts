@@ -7,7 +7,11 @@ import WebSocket from 'ws'
 import { createLogger } from '../logger'
 
 const log = createLogger('WebSocketContext')
+import { channelOfMessage } from '@prizm/shared'
+import type { ClientPresence, RealtimeChannel } from '@prizm/shared'
 import type { ServerToClientMessage, WebSocketMessage, EventType } from './types'
+import { ChannelScheduler } from './channelScheduler'
+import type { ChannelStats } from './channelScheduler'
 
 export class WebSocketContext {
   readonly id: string
@@ -17,12 +21,15 @@ export class WebSocketContext {
 
   private registeredEvents = new Set<EventType>()
   private currentScope: string = 'default'
+  private presence: ClientPresence | null = null
+  private scheduler: ChannelScheduler
 
   constructor(id: string, clientId: string, allowedScopes: string[], socket: WebSocket) {
     this.id = id
     this.clientId = clientId
     this.allowedScopes = allowedScopes
     this.socket = socket
+    this.scheduler = new ChannelScheduler(socket)
   }
 
   /**
@@ -54,7 +61,7 @@ export class WebSocketContext {
   }
 
   /**
-   * 发送消息到客户端
+   * 发送消息到客户端，按所属逻辑通道排队（control 以外的消息附带 channel）
    */
   send(data: ServerToClientMessage): boolean {
     if (this.socket.readyState !== WebSocket.OPEN) {
@@ -62,14 +69,43 @@ export class WebSocketContext {
     }
 
     try {
-      this.socket.send(JSON.stringify(data))
-      return true
+      const channel = channelOfMessage(data)
+      const message = channel === 'control' ? data : { ...data, channel }
+      return this.scheduler.enqueue(channel, JSON.stringify(message))
     } catch (error) {
       log.error('Failed to send to client', this.clientId, ':', error)
       return false
     }
   }
 
+  /**
+   * 发送二进制帧（data-sync 通道）
+   */
+  sendBinary(data: Buffer): boolean {
+    return this.scheduler.enqueue('data-sync', data, true)
+  }
+
+  /**
+   * 开启按通道流控，返回实际使用的窗口大小
+   */
+  enableFlowControl(window: number): number {
+    return this.scheduler.enableFlowControl(window)
+  }
+
+  /**
+   * 归还通道的流控额度
+   */
+  grantChannel(channel: RealtimeChannel, credit: number): void {
+    this.scheduler.grant(channel, credit)
+  }
+
+  /**
+   * 各逻辑通道的排队数与剩余额度
+   */
+  getChannelStats(): Record<RealtimeChannel, ChannelStats> {
+    return this.scheduler.stats()
+  }
+
   /**
    * 获取当前 scope
    */
@@ -84,6 +120,20 @@ export class WebSocketContext {
     this.currentScope = scope
   }
 
+  /**
+   * 获取客户端最近上报的在线状态
+   */
+  getPresence(): ClientPresence | null {
+    return this.presence
+  }
+
+  /**
+   * 记录客户端上报的在线状态
+   */
+  setPresence(presence: ClientPresence): void {
+    this.presence = presence
+  }
+
   /**
    * 检查是否有权限访问指定 scope
    */
@@ -10,8 +10,18 @@ import { createLogger } from '../logger'
 
 const log = createLogger('WebSocket')
 import { v4 as uuidv4 } from 'uuid'
+import {
+  BINARY_FRAME_CHUNK_SIZE,
+  REALTIME_CHANNELS,
+  decodeBinaryFrame,
+  encodeBinaryFrame,
+  splitBinaryFrames
+} from '@prizm/shared'
+import type { BinaryFrame, ClientPresence, PresenceState } from '@prizm/shared'
 import type { ClientRegistry } from '../auth/ClientRegistry'
 import { EventRegistry } from './EventRegistry'
+import { TunnelRegistry } from './TunnelRegistry'
+import type { TunnelHttpRequest, TunnelHttpResponse, TunnelInfo } from './TunnelRegistry'
 import { WebSocketContext } from './WebSocketContext'
 import type {
   ClientToServerMessage,
@@ -19,20 +29,44 @@ import type {
   WebSocketMessage,
   EventType,
   EventPushMessage,
-  ErrorMessage
+  ErrorMessage,
+  PresenceMessage,
+  ResumeMessage,
+  RpcRequestMessage,
+  ChannelWindowMessage,
+  TunnelOpenMessage
 } from './types'
+import { forwardRpcRequest, validateRpcRequest } from './rpcForwarder'
+import { EVENT_TYPES } from './types'
+
+const PRESENCE_STATES: readonly PresenceState[] = ['online', 'idle', 'away']
 
 export interface WebSocketServerOptions {
   path?: string
   clientTrackingTimeout?: number // 毫秒
+  /** 允许客户端协商 permessage-deflate 压缩，是否压缩由客户端决定 */
+  perMessageDeflate?: boolean
+  /** 允许客户端开放反向隧道（见 TunnelRegistry） */
+  enableTunnels?: boolean
 }
 
+/** 小于该字节数的消息不压缩 */
+const COMPRESSION_THRESHOLD = 1024
+
+/** 收到客户端二进制帧时的处理函数 */
+export type BinaryFrameHandler = (clientId: string, frame: BinaryFrame) => void
+
 export class WebSocketServer {
   private wss: InstanceType<typeof WSServer>
   private eventRegistry: EventRegistry
   private clientRegistry: ClientRegistry
   private options: WebSocketServerOptions
   private cleanupTimer: ReturnType<typeof setInterval> | null = null
+  private binaryHandlers = new Set<BinaryFrameHandler>()
+  private httpServer: http.Server
+  /** connectionId -> 建立连接时使用的 API Key，用于转发 RPC 请求 */
+  private apiKeys = new Map<string, string>()
+  private tunnels = new TunnelRegistry()
 
   constructor(
     httpServer: http.Server,
@@ -41,6 +75,7 @@ export class WebSocketServer {
   ) {
     this.eventRegistry = this.createEventRegistry()
     this.clientRegistry = clientRegistry
+    this.httpServer = httpServer
     this.options = {
       path: '/ws',
       clientTrackingTimeout: 30000,
@@ -48,7 +83,12 @@ export class WebSocketServer {
     }
 
     // 创建 WebSocket 服务器（noServer 模式，由 server.ts 统一路由 upgrade）
-    this.wss = new WSServer({ noServer: true })
+    this.wss = new WSServer({
+      noServer: true,
+      perMessageDeflate: this.options.perMessageDeflate !== false && {
+        threshold: COMPRESSION_THRESHOLD
+      }
+    })
 
     this.setupConnectionHandlers()
     log.info('Initialized on path', this.options.path)
@@ -93,8 +133,8 @@ export class WebSocketServer {
     log.info('New connection', connectionId)
 
     try {
-      // 鉴权 - 从 URL 查询参数获取 API Key
-      const apiKey = this.extractApiKey(req.url)
+      // 鉴权 - 从 URL 查询参数或 Authorization 头获取 API Key
+      const apiKey = this.extractApiKey(req)
       if (!apiKey) {
         this.sendError(socket, 'AUTH_MISSING', 'API key is required')
         socket.close(4001, 'API key is required')
@@ -114,21 +154,34 @@ export class WebSocketServer {
       // 创建上下文
       const context = this.createWebSocketContext(connectionId, clientId, allowedScopes, socket)
       this.eventRegistry.registerClient(context)
+      this.apiKeys.set(connectionId, apiKey)
 
       // 发送连接成功消息
       this.sendMessage(socket, {
         type: 'connected',
         clientId,
-        serverTime: Date.now()
+        serverTime: Date.now(),
+        streamId: this.eventRegistry.streamId,
+        lastEventId: this.eventRegistry.getLastEventId(),
+        channels: [...REALTIME_CHANNELS]
       })
 
       // 设置消息处理器
-      socket.on('message', (data: Buffer) => {
-        this.handleMessage(context, data.toString())
+      socket.on('message', (data: Buffer, isBinary: boolean) => {
+        if (isBinary) {
+          this.handleBinary(context, data)
+        } else {
+          this.handleMessage(context, data.toString())
+        }
       })
 
       socket.on('close', () => {
         this.eventRegistry.unregisterClient(connectionId)
+        this.apiKeys.delete(connectionId)
+        this.tunnels.closeAll(connectionId)
+        if (context.getPresence()) {
+          this.broadcast(EVENT_TYPES.CLIENT_PRESENCE, { clientId, state: 'offline' })
+        }
       })
 
       socket.on('error', (error) => {
@@ -158,17 +211,19 @@ export class WebSocketServer {
   }
 
   /**
-   * 从 URL 提取 API Key
+   * 提取 API Key：优先 ?apiKey=，其次 Authorization: Bearer（Electron 客户端由主进程注入，
+   * 渲染进程不持有 Key）
    */
-  private extractApiKey(url?: string): string | null {
-    if (!url) return null
-
+  private extractApiKey(req: http.IncomingMessage): string | null {
     try {
-      const urlObj = new URL(url, 'http://localhost')
-      return urlObj.searchParams.get('apiKey')
+      const urlObj = new URL(req.url ?? '/', 'http://localhost')
+      const queryKey = urlObj.searchParams.get('apiKey')
+      if (queryKey) return queryKey
     } catch {
-      return null
+      // 忽略非法 URL，继续检查请求头
     }
+    const auth = req.headers.authorization
+    return auth?.startsWith('Bearer ') ? auth.slice(7).trim() || null : null
   }
 
   /**
@@ -184,6 +239,28 @@ export class WebSocketServer {
     }
   }
 
+  /**
+   * 处理二进制帧，交给 onBinaryFrame 注册的处理函数
+   */
+  private handleBinary(context: WebSocketContext, data: Buffer): void {
+    const frame = decodeBinaryFrame(data)
+    if (!frame) {
+      this.sendError(context.socket, 'INVALID_FRAME', 'Malformed binary frame')
+      return
+    }
+    if (this.binaryHandlers.size === 0) {
+      log.warn('No handler for binary frame on channel', frame.channel, 'from', context.clientId)
+      return
+    }
+    for (const handler of this.binaryHandlers) {
+      try {
+        handler(context.clientId, frame)
+      } catch (error) {
+        log.error('Binary frame handler failed:', error)
+      }
+    }
+  }
+
   /**
    * 处理消息
    */
@@ -201,6 +278,43 @@ export class WebSocketServer {
         this.sendMessage(context.socket, { type: 'pong' })
         break
 
+      case 'presence':
+        this.handlePresence(context, message)
+        break
+
+      case 'resume':
+        this.handleResume(context, message)
+        break
+
+      case 'request':
+        this.handleRpcRequest(context, message).catch((error) => {
+          log.error('RPC request failed for', context.id, ':', error)
+        })
+        break
+
+      case 'flow_control': {
+        const window = context.enableFlowControl(message.window)
+        log.info('Flow control enabled for', context.clientId, 'with window', window)
+        break
+      }
+
+      case 'channel_window':
+        this.handleChannelWindow(context, message)
+        break
+
+      case 'tunnel_open':
+        this.handleTunnelOpen(context, message)
+        break
+
+      case 'tunnel_close':
+        this.tunnels.close(context.id, message.name)
+        this.sendMessage(context.socket, { type: 'tunnel_status', name: message.name, open: false })
+        break
+
+      case 'tunnel_response':
+        this.tunnels.resolve(context.id, message)
+        break
+
       default:
         this.sendError(
           context.socket,
@@ -245,6 +359,106 @@ export class WebSocketServer {
     })
   }
 
+  /**
+   * 处理在线状态上报，状态变化时广播 client:presence
+   */
+  private handlePresence(context: WebSocketContext, message: PresenceMessage): void {
+    if (!PRESENCE_STATES.includes(message.state)) {
+      this.sendError(context.socket, 'INVALID_PRESENCE', `Unknown presence state: ${message.state}`)
+      return
+    }
+    const idleSeconds =
+      typeof message.idleSeconds === 'number' && Number.isFinite(message.idleSeconds)
+        ? Math.max(0, Math.floor(message.idleSeconds))
+        : 0
+    const previous = context.getPresence()
+    context.setPresence({ state: message.state, idleSeconds, updatedAt: Date.now() })
+    if (previous?.state !== message.state) {
+      this.broadcast(EVENT_TYPES.CLIENT_PRESENCE, {
+        clientId: context.clientId,
+        state: message.state,
+        idleSeconds
+      })
+    }
+  }
+
+  /**
+   * 处理重放请求：先回复 replay（错过的事件数），再按序推送这些事件。
+   * 客户端应在重新订阅之后发送，只重放已订阅的事件
+   */
+  private handleResume(context: WebSocketContext, message: ResumeMessage): void {
+    if (!Number.isInteger(message.lastEventId) || message.lastEventId < 0) {
+      this.sendError(
+        context.socket,
+        'INVALID_MESSAGE',
+        'lastEventId must be a non-negative integer'
+      )
+      return
+    }
+    const result = this.eventRegistry.replay(context.id, message.lastEventId)
+    if (!result) return
+    this.sendMessage(context.socket, {
+      type: 'replay',
+      count: result.events.length,
+      untilId: result.untilId,
+      truncated: result.truncated
+    })
+    for (const event of result.events) {
+      context.send(event)
+    }
+    log.info('Replayed', result.events.length, 'events to', context.clientId)
+  }
+
+  /**
+   * 处理通道额度归还
+   */
+  private handleChannelWindow(context: WebSocketContext, message: ChannelWindowMessage): void {
+    if (!REALTIME_CHANNELS.includes(message.channel)) {
+      this.sendError(context.socket, 'INVALID_MESSAGE', `Unknown channel: ${message.channel}`)
+      return
+    }
+    context.grantChannel(message.channel, message.credit)
+  }
+
+  /**
+   * 处理隧道开放请求；服务端未开启隧道或 scope 不在客户端授权范围内时拒绝
+   */
+  private handleTunnelOpen(context: WebSocketContext, message: TunnelOpenMessage): void {
+    const error = this.options.enableTunnels
+      ? this.tunnels.open(context, message.name, message.scope)
+      : 'Tunnels are disabled on this server'
+    const tunnel = error ? null : this.tunnels.get(context.clientId, message.name)
+    this.sendMessage(context.socket, {
+      type: 'tunnel_status',
+      name: message.name,
+      open: !!tunnel,
+      ...(tunnel && { path: tunnel.path }),
+      ...(error && { error })
+    })
+  }
+
+  /**
+   * 处理 RPC 请求：以该连接的 API Key 转发到 HTTP 路由，结果以 response 返回
+   */
+  private async handleRpcRequest(
+    context: WebSocketContext,
+    message: RpcRequestMessage
+  ): Promise<void> {
+    const invalid = validateRpcRequest(message)
+    const apiKey = this.apiKeys.get(context.id)
+    if (invalid || !apiKey) {
+      this.sendMessage(context.socket, {
+        type: 'response',
+        id: typeof message.id === 'string' ? message.id : '',
+        status: 400,
+        body: { error: invalid ?? 'Connection is not authenticated' }
+      })
+      return
+    }
+    const response = await forwardRpcRequest(this.httpServer, apiKey, message)
+    this.sendMessage(context.socket, response)
+  }
+
   /**
    * 发送消息
    */
@@ -276,6 +490,13 @@ export class WebSocketServer {
     return this.eventRegistry.broadcast(eventType, payload, scope)
   }
 
+  /**
+   * 只发给拥有全部权限（*）的客户端，不参与断线重放
+   */
+  broadcastToAdmins(eventType: EventType, payload: unknown): number {
+    return this.eventRegistry.broadcastToAdmins(eventType, payload)
+  }
+
   /**
    * 向指定客户端发送事件
    */
@@ -299,6 +520,72 @@ export class WebSocketServer {
     return this.eventRegistry.getConnectedClients()
   }
 
+  /**
+   * 注册二进制帧处理函数，返回取消函数
+   */
+  onBinaryFrame(handler: BinaryFrameHandler): () => void {
+    this.binaryHandlers.add(handler)
+    return () => {
+      this.binaryHandlers.delete(handler)
+    }
+  }
+
+  /**
+   * 以二进制帧向指定客户端发送数据（data-sync 通道），按 chunkSize 分块；
+   * 返回排入发送队列的帧数，客户端未连接时为 0
+   */
+  sendBinaryToClient(
+    clientId: string,
+    channel: number,
+    data: Uint8Array,
+    chunkSize = BINARY_FRAME_CHUNK_SIZE
+  ): number {
+    const context = this.eventRegistry.getClientByClientId(clientId)
+    if (!context?.isOpen()) return 0
+    let sent = 0
+    for (const frame of splitBinaryFrames(channel, data, chunkSize)) {
+      const bytes = encodeBinaryFrame(frame)
+      if (!context.sendBinary(Buffer.from(bytes.buffer, bytes.byteOffset, bytes.byteLength))) {
+        log.error('Failed to send binary frame to', clientId)
+        break
+      }
+      sent++
+    }
+    return sent
+  }
+
+  /**
+   * 查找隧道，不存在时返回 null
+   */
+  getTunnel(clientId: string, name: string): TunnelInfo | null {
+    return this.tunnels.get(clientId, name)
+  }
+
+  /**
+   * 所有已开放的隧道
+   */
+  listTunnels(): TunnelInfo[] {
+    return this.tunnels.list()
+  }
+
+  /**
+   * 经隧道把 HTTP 请求转发给开放它的客户端
+   */
+  forwardTunnelRequest(
+    clientId: string,
+    name: string,
+    request: TunnelHttpRequest
+  ): Promise<TunnelHttpResponse> {
+    return this.tunnels.forward(clientId, name, request)
+  }
+
+  /**
+   * 获取客户端最近上报的在线状态（未连接或未上报时返回 undefined）
+   */
+  getPresence(clientId: string): ClientPresence | undefined {
+    return this.eventRegistry.getClientByClientId(clientId)?.getPresence() ?? undefined
+  }
+
   /**
    * 处理 HTTP upgrade 请求（noServer 模式下由 server.ts 调用）
    */
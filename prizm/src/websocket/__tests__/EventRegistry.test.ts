@@ -0,0 +1,58 @@
+import { describe, it, expect, vi } from 'vitest'
+import { EventRegistry } from '../EventRegistry'
+import type { WebSocketContext } from '../WebSocketContext'
+
+function context(id: string, clientId: string, events: string[], scopes = ['*']) {
+  return {
+    id,
+    clientId,
+    send: vi.fn(() => true),
+    isOpen: () => true,
+    hasEvent: (eventType: string) => events.includes(eventType),
+    getRegisteredEvents: () => [],
+    registerEvent: () => {},
+    hasScopePermission: (scope: string) => scopes.includes('*') || scopes.includes(scope)
+  } as unknown as WebSocketContext
+}
+
+describe('EventRegistry replay', () => {
+  it('numbers events and replays only what the connection subscribed to', () => {
+    const registry = new EventRegistry()
+    registry.broadcast('document:updated', { id: 'a' })
+    registry.broadcast('notification', { title: 't' })
+    registry.broadcastToClient('other', 'notification', { title: 'private' })
+    registry.broadcastToClient('c-1', 'notification', { title: 'mine' }, 'secret')
+    expect(registry.getLastEventId()).toBe(4)
+
+    registry.registerClient(context('conn-1', 'c-1', ['notification']))
+    const result = registry.replay('conn-1', 1)
+    expect(result?.events.map((e) => e.id)).toEqual([2, 4])
+    expect(result).toMatchObject({ untilId: 4, truncated: false })
+    expect(registry.replay('conn-1', 4)?.events).toEqual([])
+  })
+
+  it('skips events outside the allowed scopes', () => {
+    const registry = new EventRegistry()
+    registry.broadcast('notification', { title: 't' }, 'private')
+    registry.registerClient(context('conn-1', 'c-1', ['notification'], ['default']))
+    expect(registry.replay('conn-1', 0)?.events).toEqual([])
+  })
+})
+
+describe('EventRegistry broadcastToAdmins', () => {
+  it('reaches only * connections and is never replayed', () => {
+    const registry = new EventRegistry()
+    const admin = context('conn-1', 'admin', ['notification'])
+    const member = context('conn-2', 'member', ['notification'], ['default'])
+    registry.registerClient(admin)
+    registry.registerClient(member)
+    registry.registerEvent('conn-1', 'notification')
+    registry.registerEvent('conn-2', 'notification')
+
+    expect(registry.broadcastToAdmins('notification', { requestId: 'secret' })).toBe(1)
+    expect(admin.send).toHaveBeenCalledTimes(1)
+    expect(member.send).not.toHaveBeenCalled()
+    expect(registry.getLastEventId()).toBe(0)
+    expect(registry.replay('conn-1', 0)?.events).toEqual([])
+  })
+})
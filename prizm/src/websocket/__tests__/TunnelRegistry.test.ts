@@ -0,0 +1,72 @@
+import { describe, it, expect, vi } from 'vitest'
+import { TunnelRegistry } from '../TunnelRegistry'
+import type { WebSocketContext } from '../WebSocketContext'
+
+function fakeContext(id: string, clientId: string, scopes: string[]) {
+  return {
+    id,
+    clientId,
+    hasScopePermission: (scope: string) => scopes.includes(scope),
+    isOpen: () => true,
+    send: vi.fn(() => true)
+  }
+}
+
+const asContext = (context: ReturnType<typeof fakeContext>) =>
+  context as unknown as WebSocketContext
+
+const request = { method: 'GET', path: '/api?x=1', headers: {}, body: Buffer.alloc(0) }
+
+describe('TunnelRegistry', () => {
+  it('opens tunnels only for valid names within the client scopes', () => {
+    const registry = new TunnelRegistry()
+    const context = fakeContext('ctx-1', 'client-1', ['default'])
+    expect(registry.open(asContext(context), 'Bad Name', 'default')).toMatch(/Tunnel name/)
+    expect(registry.open(asContext(context), 'grafana', 'online')).toMatch(/no access/)
+    expect(registry.open(asContext(context), 'grafana', 'default')).toBeNull()
+    expect(registry.get('client-1', 'grafana')).toMatchObject({
+      scope: 'default',
+      path: '/tunnels/client-1/grafana'
+    })
+
+    expect(registry.close('ctx-2', 'grafana')).toBe(false)
+    expect(registry.close('ctx-1', 'grafana')).toBe(true)
+    expect(registry.list()).toEqual([])
+  })
+
+  it('forwards requests and matches responses from the owning connection', async () => {
+    const registry = new TunnelRegistry()
+    const context = fakeContext('ctx-1', 'client-1', ['default'])
+    registry.open(asContext(context), 'grafana', 'default')
+
+    const pending = registry.forward('client-1', 'grafana', request)
+    const sent = context.send.mock.calls[0][0] as unknown as { requestId: string; path: string }
+    expect(sent).toMatchObject({ type: 'tunnel_request', name: 'grafana', path: '/api?x=1' })
+
+    const response = {
+      type: 'tunnel_response' as const,
+      requestId: sent.requestId,
+      status: 200,
+      headers: { 'content-type': 'text/plain' },
+      body: Buffer.from('ok').toString('base64')
+    }
+    expect(registry.resolve('ctx-other', response)).toBe(false)
+    expect(registry.resolve('ctx-1', response)).toBe(true)
+    const result = await pending
+    expect(result.status).toBe(200)
+    expect(result.body.toString()).toBe('ok')
+
+    expect((await registry.forward('client-1', 'missing', request)).status).toBe(404)
+  })
+
+  it('fails pending requests with 502 when the connection closes', async () => {
+    const registry = new TunnelRegistry()
+    const context = fakeContext('ctx-1', 'client-1', ['default'])
+    registry.open(asContext(context), 'grafana', 'default')
+
+    const pending = registry.forward('client-1', 'grafana', request)
+    registry.closeAll('ctx-1')
+    expect((await pending).status).toBe(502)
+    expect(registry.get('client-1', 'grafana')).toBeNull()
+  })
+})
@@ -0,0 +1,33 @@
+import { describe, it, expect } from 'vitest'
+import {
+  BINARY_FRAME_HEADER_SIZE,
+  decodeBinaryFrame,
+  encodeBinaryFrame,
+  splitBinaryFrames
+} from '@prizm/shared'
+
+describe('binary frames', () => {
+  it('round-trips channel, index and the last flag', () => {
+    const data = new Uint8Array([1, 2, 3])
+    const bytes = encodeBinaryFrame({ channel: 7, index: 2, last: true, data })
+    expect(bytes.byteLength).toBe(BINARY_FRAME_HEADER_SIZE + 3)
+    expect(decodeBinaryFrame(bytes)).toEqual({ channel: 7, index: 2, last: true, data })
+  })
+
+  it('rejects short or unknown-version frames', () => {
+    expect(decodeBinaryFrame(new Uint8Array(4))).toBeNull()
+    const bytes = encodeBinaryFrame({ channel: 1, index: 0, last: false, data: new Uint8Array() })
+    bytes[0] = 9
+    expect(decodeBinaryFrame(bytes)).toBeNull()
+  })
+
+  it('splits payloads into ordered chunks', () => {
+    const frames = splitBinaryFrames(3, new Uint8Array(10), 4)
+    expect(frames.map((f) => [f.index, f.data.byteLength, f.last])).toEqual([
+      [0, 4, false],
+      [1, 4, false],
+      [2, 2, true]
+    ])
+    expect(splitBinaryFrames(3, new Uint8Array(), 4)).toHaveLength(1)
+  })
+})
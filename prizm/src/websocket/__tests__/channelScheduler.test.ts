@@ -0,0 +1,49 @@
+import { describe, it, expect } from 'vitest'
+import { ChannelScheduler, MIN_CHANNEL_WINDOW, SEND_HIGH_WATER_MARK } from '../channelScheduler'
+
+function fakeSocket() {
+  const sent: string[] = []
+  const socket = {
+    readyState: 1,
+    bufferedAmount: 0,
+    sent,
+    send: (data: string | Buffer, _options: { binary: boolean }, callback: () => void) => {
+      sent.push(String(data).slice(0, 8))
+      callback()
+    }
+  }
+  return socket
+}
+
+describe('ChannelScheduler', () => {
+  it('sends control first and rotates through the other channels', () => {
+    const socket = fakeSocket()
+    const scheduler = new ChannelScheduler(socket)
+    socket.bufferedAmount = SEND_HIGH_WATER_MARK
+    scheduler.enqueue('data-sync', 'sync-1')
+    scheduler.enqueue('data-sync', 'sync-2')
+    scheduler.enqueue('notifications', 'notify-1')
+    scheduler.enqueue('control', 'pong')
+    expect(socket.sent).toEqual([])
+
+    socket.bufferedAmount = 0
+    scheduler.enqueue('logs', 'log-1')
+    expect(socket.sent).toEqual(['pong', 'notify-1', 'sync-1', 'log-1', 'sync-2'])
+  })
+
+  it('pauses a channel without credit while others keep flowing', () => {
+    const socket = fakeSocket()
+    const scheduler = new ChannelScheduler(socket)
+    expect(scheduler.enableFlowControl(1)).toBe(MIN_CHANNEL_WINDOW)
+
+    scheduler.enqueue('data-sync', 'sync-1'.padEnd(MIN_CHANNEL_WINDOW + 10, '.'))
+    scheduler.enqueue('data-sync', 'sync-2')
+    scheduler.enqueue('notifications', 'notify-1')
+    expect(socket.sent).toEqual(['sync-1..', 'notify-1'])
+    expect(scheduler.stats()['data-sync']).toEqual({ queued: 1, credit: -10 })
+
+    scheduler.grant('data-sync', 100)
+    expect(socket.sent).toEqual(['sync-1..', 'notify-1', 'sync-2'])
+    expect(scheduler.stats().control.credit).toBeNull()
+  })
+})
@@ -0,0 +1,89 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest'
+import http from 'http'
+import { forwardRpcRequest, validateRpcRequest } from '../rpcForwarder'
+import type { RpcRequestMessage } from '../types'
+
+describe('rpcForwarder', () => {
+  let server: http.Server
+
+  beforeEach(async () => {
+    server = http.createServer((req, res) => {
+      let body = ''
+      req.on('data', (chunk) => (body += chunk))
+      req.on('end', () => {
+        res.setHeader('Content-Type', 'application/json')
+        const auth = req.headers.authorization
+        res.end(JSON.stringify({ method: req.method, url: req.url, auth, body }))
+      })
+    })
+    await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve))
+  })
+
+  afterEach(() => {
+    server.close()
+  })
+
+  it('forwards with the connection api key and parses JSON', async () => {
+    const response = await forwardRpcRequest(server, 'key-1', {
+      type: 'request',
+      id: 'r-1',
+      method: 'POST',
+      path: '/auth/scope-requests',
+      body: { scopes: ['online'] }
+    })
+    expect(response).toEqual({
+      type: 'response',
+      id: 'r-1',
+      status: 200,
+      body: {
+        method: 'POST',
+        url: '/auth/scope-requests',
+        auth: 'Bearer key-1',
+        body: '{"scopes":["online"]}'
+      }
+    })
+  })
+
+  it('rejects malformed requests', () => {
+    const base: RpcRequestMessage = { type: 'request', id: 'r', method: 'GET', path: '/health' }
+    expect(validateRpcRequest(base)).toBeNull()
+    expect(validateRpcRequest({ ...base, path: 'health' })).toMatch(/path/)
+    expect(validateRpcRequest({ ...base, path: '//evil.example/x' })).toMatch(/not allowed/)
+    expect(validateRpcRequest({ ...base, method: 'TRACE' as 'GET' })).toMatch(/method/)
+    expect(validateRpcRequest({ ...base, id: '' })).toMatch(/id/)
+  })
+
+  it('rejects paths http.request cannot send', () => {
+    const base: RpcRequestMessage = { type: 'request', id: 'r', method: 'GET', path: '/health' }
+    expect(validateRpcRequest({ ...base, path: '/a b' })).toMatch(/invalid characters/)
+    expect(validateRpcRequest({ ...base, path: '/a\nb' })).toMatch(/invalid characters/)
+    expect(validateRpcRequest({ ...base, path: '/笔记' })).toMatch(/invalid characters/)
+  })
+
+  it('normalizes the path before applying the deny-list', () => {
+    const base: RpcRequestMessage = { type: 'request', id: 'r', method: 'GET', path: '/health' }
+    expect(validateRpcRequest({ ...base, path: '/./ws' })).toMatch(/not allowed/)
+    expect(validateRpcRequest({ ...base, path: '/notes/../ws/terminal' })).toMatch(/not allowed/)
+    expect(validateRpcRequest({ ...base, path: '/notes/./1?q=/ws' })).toBeNull()
+  })
+
+  it('answers 400 instead of throwing when http.request rejects the path', async () => {
+    const response = await forwardRpcRequest(server, 'key-1', {
+      type: 'request',
+      id: 'r-2',
+      method: 'GET',
+      path: '/a b'
+    })
+    expect(response).toMatchObject({ type: 'response', id: 'r-2', status: 400 })
+  })
+
+  it('forwards the normalized path', async () => {
+    const response = await forwardRpcRequest(server, 'key-1', {
+      type: 'request',
+      id: 'r-3',
+      method: 'GET',
+      path: '/notes/./a/../1?x=1'
+    })
+    expect(response.body).toMatchObject({ url: '/notes/1?x=1' })
+  })
+})
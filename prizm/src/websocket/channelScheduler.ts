@@ -0,0 +1,150 @@
+/**
+ * 逻辑通道发送调度
+ * 单个 WebSocket 连接上的消息按通道分别排队：control 优先，其余通道轮转发送，
+ * 发送缓冲区积压时暂停，避免大批数据同步占满缓冲区后通知被延迟。
+ * 客户端发送 flow_control 后，非 control 通道按额度发送，某个通道额度用尽只暂停该通道
+ */
+
+import { REALTIME_CHANNELS } from '@prizm/shared'
+import type { RealtimeChannel } from '@prizm/shared'
+import { createLogger } from '../logger'
+
+const log = createLogger('ChannelScheduler')
+
+/** 调度器需要的最小 socket 接口（ws 的 WebSocket 满足） */
+export interface ChannelSocket {
+  readonly readyState: number
+  readonly bufferedAmount: number
+  send(data: string | Buffer, options: { binary: boolean }, callback: (error?: Error) => void): void
+}
+
+export interface ChannelStats {
+  /** 排队中的消息数 */
+  queued: number
+  /** 剩余额度（字节），未开启流控或 control 通道为 null */
+  credit: number | null
+}
+
+interface Outgoing {
+  data: string | Buffer
+  binary: boolean
+  size: number
+}
+
+/** 发送缓冲区超过该字节数时暂停，已发出的数据写出后继续 */
+export const SEND_HIGH_WATER_MARK = 256 * 1024
+/** 每个通道最多排队的消息数，超出时丢弃最旧的 */
+export const MAX_QUEUED_PER_CHANNEL = 1000
+/** flow_control 窗口的取值范围 */
+export const MIN_CHANNEL_WINDOW = 16 * 1024
+export const MAX_CHANNEL_WINDOW = 16 * 1024 * 1024
+
+const OPEN = 1
+/** 参与轮转的通道（control 总是优先） */
+const ROTATING_CHANNELS = REALTIME_CHANNELS.filter((channel) => channel !== 'control')
+
+export class ChannelScheduler {
+  private readonly queues = new Map<RealtimeChannel, Outgoing[]>(
+    REALTIME_CHANNELS.map((channel) => [channel, []])
+  )
+  private readonly credits = new Map<RealtimeChannel, number>()
+  /** 开启流控后的窗口大小，未开启时为 0 */
+  private window = 0
+  private next = 0
+  private pumping = false
+
+  constructor(private readonly socket: ChannelSocket) {}
+
+  /**
+   * 开启流控，窗口限制在 [MIN_CHANNEL_WINDOW, MAX_CHANNEL_WINDOW]；返回实际使用的窗口。
+   * 重复调用时重置各通道额度
+   */
+  enableFlowControl(window: number): number {
+    const size = Number.isFinite(window) ? Math.floor(window) : MIN_CHANNEL_WINDOW
+    this.window = Math.min(MAX_CHANNEL_WINDOW, Math.max(MIN_CHANNEL_WINDOW, size))
+    for (const channel of ROTATING_CHANNELS) this.credits.set(channel, this.window)
+    this.pump()
+    return this.window
+  }
+
+  /** 归还通道额度（不超过窗口）；未开启流控或 control 通道时忽略 */
+  grant(channel: RealtimeChannel, credit: number): void {
+    if (!this.window || !this.credits.has(channel) || !(credit > 0)) return
+    const current = this.credits.get(channel) ?? 0
+    this.credits.set(channel, Math.min(this.window, current + Math.floor(credit)))
+    this.pump()
+  }
+
+  /**
+   * 排入通道队列并尝试发送；连接已关闭时返回 false。
+   * 队列已满时丢弃最旧的消息，只影响该通道
+   */
+  enqueue(channel: RealtimeChannel, data: string | Buffer, binary = false): boolean {
+    if (this.socket.readyState !== OPEN) return false
+    const queue = this.queues.get(channel)
+    if (!queue) return false
+    if (queue.length >= MAX_QUEUED_PER_CHANNEL) {
+      queue.shift()
+      log.warn('Channel', channel, 'queue is full, dropped the oldest message')
+    }
+    const size = typeof data === 'string' ? Buffer.byteLength(data) : data.length
+    queue.push({ data, binary, size })
+    this.pump()
+    return true
+  }
+
+  stats(): Record<RealtimeChannel, ChannelStats> {
+    const stats = {} as Record<RealtimeChannel, ChannelStats>
+    for (const channel of REALTIME_CHANNELS) {
+      stats[channel] = {
+        queued: this.queues.get(channel)?.length ?? 0,
+        credit: this.credits.get(channel) ?? null
+      }
+    }
+    return stats
+  }
+
+  private pump(): void {
+    if (this.pumping) return
+    this.pumping = true
+    try {
+      while (
+        this.socket.readyState === OPEN &&
+        this.socket.bufferedAmount < SEND_HIGH_WATER_MARK
+      ) {
+        const channel = this.pick()
+        if (!channel) break
+        const item = this.queues.get(channel)?.shift()
+        if (!item) break
+        const credit = this.credits.get(channel)
+        if (credit !== undefined) this.credits.set(channel, credit - item.size)
+        this.socket.send(item.data, { binary: item.binary }, (error) => {
+          if (error) {
+            log.error('Failed to send on channel', channel, ':', error)
+            return
+          }
+          this.pump()
+        })
+      }
+    } catch (error) {
+      log.error('Failed to send queued message:', error)
+    } finally {
+      this.pumping = false
+    }
+  }
+
+  /** 下一个可发送的通道：control 优先，其余有消息且有额度的通道轮转 */
+  private pick(): RealtimeChannel | null {
+    if (this.queues.get('control')?.length) return 'control'
+    for (let i = 0; i < ROTATING_CHANNELS.length; i++) {
+      const index = (this.next + i) % ROTATING_CHANNELS.length
+      const channel = ROTATING_CHANNELS[index]
+      if (!this.queues.get(channel)?.length) continue
+      // 额度为正即可发送，单条消息大于剩余额度时额度变为负数，待归还后继续
+      if ((this.credits.get(channel) ?? 1) <= 0) continue
+      this.next = index + 1
+      return channel
+    }
+    return null
+  }
+}
@@ -0,0 +1,132 @@
+/**
+ * WebSocket RPC 转发
+ * 把客户端经 WebSocket 发来的 request 以该连接的 API Key 转发到本机 HTTP 路由，
+ * 复用现有路由、鉴权与 scope 校验，客户端省去建立 HTTP 连接的开销
+ */
+
+import http from 'http'
+import type { AddressInfo } from 'net'
+import path from 'path'
+import type { RpcRequestMessage, RpcResponseMessage } from './types'
+
+export const RPC_METHODS: readonly RpcRequestMessage['method'][] = [
+  'GET',
+  'POST',
+  'PUT',
+  'PATCH',
+  'DELETE'
+]
+
+/** 单次转发的超时 */
+const RPC_TIMEOUT_MS = 30_000
+
+/** http.request 拒绝的字符（控制字符、空格及非 Latin-1），须提前拦截，否则会同步抛错 */
+const INVALID_PATH_CHARS = /[^\u0021-\u00ff]/
+
+/**
+ * 规范化路径部分（解析 . 与 ..），查询串原样保留
+ */
+export function normalizeRpcPath(rawPath: string): string {
+  const queryIdx = rawPath.indexOf('?')
+  const pathname = queryIdx === -1 ? rawPath : rawPath.slice(0, queryIdx)
+  const query = queryIdx === -1 ? '' : rawPath.slice(queryIdx)
+  return path.posix.normalize(pathname) + query
+}
+
+/**
+ * 校验 request 消息；返回错误说明，合法时返回 null
+ */
+export function validateRpcRequest(message: RpcRequestMessage): string | null {
+  if (typeof message.id !== 'string' || !message.id || message.id.length > 64) {
+    return 'id must be a non-empty string'
+  }
+  if (!RPC_METHODS.includes(message.method)) return `Unsupported method: ${message.method}`
+  if (typeof message.path !== 'string' || !message.path.startsWith('/')) {
+    return 'path must start with /'
+  }
+  if (INVALID_PATH_CHARS.test(message.path)) return 'path contains invalid characters'
+  // 先规范化再比对，/./ws、/a/../ws 等同样拦截
+  const normalized = normalizeRpcPath(message.path)
+  if (message.path.startsWith('//') || normalized.startsWith('/ws')) {
+    return `Path not allowed: ${message.path}`
+  }
+  return null
+}
+
+function loopbackHost(address: AddressInfo): string {
+  if (address.address === '::' || address.address === '0.0.0.0') return '127.0.0.1'
+  return address.address
+}
+
+/**
+ * 转发到 httpServer 监听的地址，返回 response 消息；网络错误或超时时 status 为 502 / 504
+ */
+export function forwardRpcRequest(
+  httpServer: http.Server,
+  apiKey: string,
+  message: RpcRequestMessage
+): Promise<RpcResponseMessage> {
+  const address = httpServer.address()
+  if (!address || typeof address === 'string') {
+    return Promise.resolve({
+      type: 'response',
+      id: message.id,
+      status: 503,
+      body: { error: 'Server address unavailable' }
+    })
+  }
+  const payload = message.body === undefined ? undefined : JSON.stringify(message.body)
+
+  return new Promise((resolve) => {
+    const respond = (status: number, body: unknown): void =>
+      resolve({ type: 'response', id: message.id, status, body })
+
+    let req: http.ClientRequest
+    try {
+      req = http.request(
+        {
+          host: loopbackHost(address),
+          port: address.port,
+          method: message.method,
+          path: normalizeRpcPath(message.path),
+          timeout: RPC_TIMEOUT_MS,
+          headers: {
+            Authorization: `Bearer ${apiKey}`,
+            Accept: 'application/json',
+            ...(payload !== undefined && {
+              'Content-Type': 'application/json',
+              'Content-Length': Buffer.byteLength(payload)
+            })
+          }
+        },
+        (res) => {
+          const chunks: Buffer[] = []
+          res.on('data', (chunk: Buffer) => chunks.push(chunk))
+          res.on('end', () => {
+            const text = Buffer.concat(chunks).toString('utf-8')
+            let body: unknown = text
+            if (/json/i.test(res.headers['content-type'] ?? '')) {
+              try {
+                body = JSON.parse(text)
+              } catch {
+                // 保留原文
+              }
+            }
+            respond(res.statusCode ?? 502, body)
+          })
+          res.on('error', (error) => respond(502, { error: error.message }))
+        }
+      )
+    } catch (error) {
+      // 路径等参数不合法时 http.request 同步抛错（如 ERR_UNESCAPED_CHARACTERS）
+      respond(400, { error: error instanceof Error ? error.message : String(error) })
+      return
+    }
+    req.on('timeout', () => {
+      req.destroy()
+      respond(504, { error: `Request timed out after ${RPC_TIMEOUT_MS}ms` })
+    })
+    req.on('error', (error) => respond(502, { error: error.message }))
+    req.end(payload)
+  })
+}
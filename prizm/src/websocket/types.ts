@@ -9,6 +9,17 @@ import {
   type RegisterEventMessage,
   type UnregisterEventMessage,
   type PingMessage,
+  type PresenceMessage,
+  type ResumeMessage,
+  type RpcRequestMessage,
+  type RpcResponseMessage,
+  type FlowControlMessage,
+  type ChannelWindowMessage,
+  type TunnelOpenMessage,
+  type TunnelCloseMessage,
+  type TunnelResponseMessage,
+  type TunnelRequestMessage,
+  type TunnelStatusMessage,
   type ClientToServerMessage,
   type ConnectedMessage,
   type AuthenticatedMessage,
@@ -33,6 +44,17 @@ export type {
   RegisterEventMessage,
   UnregisterEventMessage,
   PingMessage,
+  PresenceMessage,
+  ResumeMessage,
+  RpcRequestMessage,
+  RpcResponseMessage,
+  FlowControlMessage,
+  ChannelWindowMessage,
+  TunnelOpenMessage,
+  TunnelCloseMessage,
+  TunnelResponseMessage,
+  TunnelRequestMessage,
+  TunnelStatusMessage,
   ClientToServerMessage,
   ConnectedMessage,
   AuthenticatedMessage,